@@ -54,6 +54,13 @@ fn main() {
     // Tell Cargo that if the given file changes, to rerun this build script.
     println!("cargo:rerun-if-changed={}", PROTO_SRC);
 
+    // Expose the target triple, which selects the node binary checksum of a
+    // scheduled upgrade
+    println!(
+        "cargo:rustc-env=NAMADA_TARGET={}",
+        env::var("TARGET").expect("Cargo sets the target triple")
+    );
+
     // Tell Cargo to build when the `NAMADA_DEV` env var changes
     println!("cargo:rerun-if-env-changed=NAMADA_DEV");
     // Enable "dev" feature if `NAMADA_DEV` is trueish
@@ -219,6 +219,8 @@ pub mod cmds {
                 .subcommand(TxUpdateAccount::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
                 .subcommand(TxRevealPk::def().display_order(1))
+                .subcommand(TxInitToken::def().display_order(1))
+                .subcommand(TxMintToken::def().display_order(1))
                 // Governance transactions
                 .subcommand(TxInitProposal::def().display_order(1))
                 .subcommand(TxVoteProposal::def().display_order(1))
@@ -252,9 +254,12 @@ pub mod cmds {
                 .subcommand(QueryProposalResult::def().display_order(5))
                 .subcommand(QueryProtocolParameters::def().display_order(5))
                 .subcommand(QueryPgf::def().display_order(5))
+                .subcommand(QueryTreasury::def().display_order(5))
+                .subcommand(QueryShieldedRewards::def().display_order(5))
                 .subcommand(QueryValidatorState::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
+                .subcommand(GenIbcShieldedTransfer::def().display_order(6))
                 // Utils
                 .subcommand(Utils::def().display_order(7))
         }
@@ -272,6 +277,8 @@ pub mod cmds {
             let tx_unjail_validator =
                 Self::parse_with_ctx(matches, TxUnjailValidator);
             let tx_reveal_pk = Self::parse_with_ctx(matches, TxRevealPk);
+            let tx_init_token = Self::parse_with_ctx(matches, TxInitToken);
+            let tx_mint_token = Self::parse_with_ctx(matches, TxMintToken);
             let tx_init_proposal =
                 Self::parse_with_ctx(matches, TxInitProposal);
             let tx_vote_proposal =
@@ -308,11 +315,16 @@ pub mod cmds {
             let query_protocol_parameters =
                 Self::parse_with_ctx(matches, QueryProtocolParameters);
             let query_pgf = Self::parse_with_ctx(matches, QueryPgf);
+            let query_treasury = Self::parse_with_ctx(matches, QueryTreasury);
+            let query_shielded_rewards =
+                Self::parse_with_ctx(matches, QueryShieldedRewards);
             let query_validator_state =
                 Self::parse_with_ctx(matches, QueryValidatorState);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
+            let gen_ibc_shielded =
+                Self::parse_with_ctx(matches, GenIbcShieldedTransfer);
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
             tx_custom
                 .or(tx_transfer)
@@ -320,6 +332,8 @@ pub mod cmds {
                 .or(tx_update_account)
                 .or(tx_init_account)
                 .or(tx_reveal_pk)
+                .or(tx_init_token)
+                .or(tx_mint_token)
                 .or(tx_init_proposal)
                 .or(tx_vote_proposal)
                 .or(tx_init_validator)
@@ -347,9 +361,12 @@ pub mod cmds {
                 .or(query_proposal_result)
                 .or(query_protocol_parameters)
                 .or(query_pgf)
+                .or(query_treasury)
+                .or(query_shielded_rewards)
                 .or(query_validator_state)
                 .or(query_account)
                 .or(sign_tx)
+                .or(gen_ibc_shielded)
                 .or(utils)
         }
     }
@@ -399,6 +416,8 @@ pub mod cmds {
         TxInitProposal(TxInitProposal),
         TxVoteProposal(TxVoteProposal),
         TxRevealPk(TxRevealPk),
+        TxInitToken(TxInitToken),
+        TxMintToken(TxMintToken),
         Bond(Bond),
         Unbond(Unbond),
         Withdraw(Withdraw),
@@ -422,8 +441,11 @@ pub mod cmds {
         QueryProposalResult(QueryProposalResult),
         QueryProtocolParameters(QueryProtocolParameters),
         QueryPgf(QueryPgf),
+        QueryTreasury(QueryTreasury),
+        QueryShieldedRewards(QueryShieldedRewards),
         QueryValidatorState(QueryValidatorState),
         SignTx(SignTx),
+        GenIbcShieldedTransfer(GenIbcShieldedTransfer),
     }
 
     #[allow(clippy::large_enum_variant)]
@@ -1224,6 +1246,55 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryTreasury(pub args::QueryTreasury<args::CliTypes>);
+
+    impl SubCmd for QueryTreasury {
+        const CMD: &'static str = "query-treasury";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryTreasury(args::QueryTreasury::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query the token balances of the governance treasury.")
+                .add_args::<args::QueryTreasury<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryShieldedRewards(
+        pub args::QueryShieldedRewards<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryShieldedRewards {
+        const CMD: &'static str = "shielded-rewards";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryShieldedRewards(args::QueryShieldedRewards::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the shielded reward parameters of tokens and their \
+                     expected yield.",
+                )
+                .add_args::<args::QueryShieldedRewards<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxCustom(pub args::TxCustom<args::CliTypes>);
 
@@ -1580,6 +1651,32 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct GenIbcShieldedTransfer(
+        pub args::GenIbcShieldedTransfer<args::CliTypes>,
+    );
+
+    impl SubCmd for GenIbcShieldedTransfer {
+        const CMD: &'static str = "ibc-gen-shielded";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                GenIbcShieldedTransfer(args::GenIbcShieldedTransfer::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Generate the shielded transfer to be set in the memo of \
+                     an IBC transfer to a payment address of this chain.",
+                )
+                .add_args::<args::GenIbcShieldedTransfer<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryValidatorState(
         pub args::QueryValidatorState<args::CliTypes>,
@@ -1796,6 +1893,53 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxInitToken(pub args::InitToken<args::CliTypes>);
+
+    impl SubCmd for TxInitToken {
+        const CMD: &'static str = "init-token";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxInitToken(args::InitToken::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Register an established account as a user-defined token, \
+                     or update the settings of a registered token.",
+                )
+                .add_args::<args::InitToken<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxMintToken(pub args::MintToken<args::CliTypes>);
+
+    impl SubCmd for TxMintToken {
+        const CMD: &'static str = "mint-token";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxMintToken(args::MintToken::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Mint a user-defined token as its minter.")
+                .add_args::<args::MintToken<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxCommissionRateChange(
         pub args::CommissionRateChange<args::CliTypes>,
@@ -2545,7 +2689,9 @@ pub mod args {
     pub const TX_IBC_WASM: &str = "tx_ibc.wasm";
     pub const TX_INIT_ACCOUNT_WASM: &str = "tx_init_account.wasm";
     pub const TX_INIT_PROPOSAL: &str = "tx_init_proposal.wasm";
+    pub const TX_INIT_TOKEN_WASM: &str = "tx_init_token.wasm";
     pub const TX_INIT_VALIDATOR_WASM: &str = "tx_init_validator.wasm";
+    pub const TX_MINT_TOKEN_WASM: &str = "tx_mint_token.wasm";
     pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
     pub const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
     pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
@@ -2613,6 +2759,7 @@ pub mod args {
     pub const DATA_PATH_OPT: ArgOpt<PathBuf> = arg_opt("data-path");
     pub const DATA_PATH: Arg<PathBuf> = arg("data-path");
     pub const DECRYPT: ArgFlag = flag("decrypt");
+    pub const DENOMINATION: Arg<u8> = arg("denomination");
     pub const DISPOSABLE_SIGNING_KEY: ArgFlag = flag("disposable-gas-payer");
     pub const DONT_ARCHIVE: ArgFlag = flag("dont-archive");
     pub const DONT_PREFETCH_WASM: ArgFlag = flag("dont-prefetch-wasm");
@@ -2675,6 +2822,9 @@ pub mod args {
     pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
         arg("max-commission-rate-change");
     pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
+    pub const MAX_SUPPLY: ArgOpt<token::DenominatedAmount> =
+        arg_opt("max-supply");
+    pub const MINTER: Arg<WalletAddress> = arg("minter");
     pub const MODE: ArgOpt<String> = arg_opt("mode");
     pub const NET_ADDRESS: Arg<String> = arg("net-address");
     pub const NAMADA_START_TIME: ArgOpt<DateTimeUtc> = arg_opt("time");
@@ -2685,6 +2835,7 @@ pub mod args {
         arg_opt("output-folder-path");
     pub const OWNER: Arg<WalletAddress> = arg("owner");
     pub const OWNER_OPT: ArgOpt<WalletAddress> = OWNER.opt();
+    pub const PGF_PAYMENTS: ArgFlag = flag("payments");
     pub const PIN: ArgFlag = flag("pin");
     pub const PORT_ID: ArgDefault<PortId> = arg_default(
         "port-id",
@@ -2693,7 +2844,10 @@ pub mod args {
     pub const PROPOSAL_ETH: ArgFlag = flag("eth");
     pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
     pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
+    pub const PROPOSAL_PARAMETER_CHANGE: ArgFlag = flag("parameter-change");
     pub const PROPOSAL_OFFLINE: ArgFlag = flag("offline");
+    pub const PROPOSAL_TREASURY: ArgFlag = flag("treasury");
+    pub const PROPOSAL_UPGRADE: ArgFlag = flag("upgrade");
     pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
     pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
     pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
@@ -2722,9 +2876,13 @@ pub mod args {
     pub const SUSPEND_ACTION: ArgFlag = flag("suspend");
     pub const TIMEOUT_HEIGHT: ArgOpt<u64> = arg_opt("timeout-height");
     pub const TIMEOUT_SEC_OFFSET: ArgOpt<u64> = arg_opt("timeout-sec-offset");
+    pub const TARGET: Arg<WalletAddress> = arg("target");
     pub const TM_ADDRESS: Arg<String> = arg("tm-address");
     pub const TOKEN_OPT: ArgOpt<WalletAddress> = TOKEN.opt();
     pub const TOKEN: Arg<WalletAddress> = arg("token");
+    pub const TOKEN_NAME: Arg<String> = arg("name");
+    pub const TOKEN_STR: Arg<String> = arg("token");
+    pub const TOKEN_SYMBOL: Arg<String> = arg("symbol");
     pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
     pub const TX_HASH: Arg<String> = arg("tx-hash");
@@ -3549,7 +3707,7 @@ pub mod args {
         fn to_sdk(self, ctx: &mut Context) -> TxIbcTransfer<SdkTypes> {
             TxIbcTransfer::<SdkTypes> {
                 tx: self.tx.to_sdk(ctx),
-                source: ctx.get(&self.source),
+                source: ctx.get_cached(&self.source),
                 receiver: self.receiver,
                 token: ctx.get(&self.token),
                 amount: self.amount,
@@ -3566,7 +3724,7 @@ pub mod args {
     impl Args for TxIbcTransfer<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let tx = Tx::parse(matches);
-            let source = SOURCE.parse(matches);
+            let source = TRANSFER_SOURCE.parse(matches);
             let receiver = RECEIVER.parse(matches);
             let token = TOKEN.parse(matches);
             let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
@@ -3593,12 +3751,15 @@ pub mod args {
 
         fn def(app: App) -> App {
             app.add_args::<Tx<CliTypes>>()
-                .arg(SOURCE.def().help(
-                    "The source account address. The source's key is used to \
-                     produce the signature.",
+                .arg(TRANSFER_SOURCE.def().help(
+                    "The source account address or a spending key. The \
+                     source's key is used to produce the signature.",
                 ))
                 .arg(RECEIVER.def().help(
-                    "The receiver address on the destination chain as string.",
+                    "The receiver address on the destination chain as string. \
+                     To transfer to a payment address, set the shielded \
+                     transfer generated with `ibc-gen-shielded` on the \
+                     destination chain as the memo.",
                 ))
                 .arg(TOKEN.def().help("The transfer token."))
                 .arg(AMOUNT.def().help("The amount to transfer in decimal."))
@@ -4018,6 +4179,107 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<InitToken<SdkTypes>> for InitToken<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> InitToken<SdkTypes> {
+            InitToken::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                token: ctx.get(&self.token),
+                minter: ctx.get(&self.minter),
+                max_supply: self.max_supply,
+                denom: self.denom,
+                name: self.name,
+                symbol: self.symbol,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for InitToken<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let token = TOKEN.parse(matches);
+            let minter = MINTER.parse(matches);
+            let max_supply = MAX_SUPPLY.parse(matches);
+            let denom = DENOMINATION.parse(matches);
+            let name = TOKEN_NAME.parse(matches);
+            let symbol = TOKEN_SYMBOL.parse(matches);
+            let tx_code_path = PathBuf::from(TX_INIT_TOKEN_WASM);
+            Self {
+                tx,
+                token,
+                minter,
+                max_supply,
+                denom,
+                name,
+                symbol,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(TOKEN.def().help(
+                    "The established account of the token. It must sign the \
+                     transaction.",
+                ))
+                .arg(
+                    MINTER
+                        .def()
+                        .help("The only address allowed to mint the token."),
+                )
+                .arg(MAX_SUPPLY.def().help(
+                    "The maximum amount of the token that may ever be minted. \
+                     Unlimited if not set.",
+                ))
+                .arg(
+                    DENOMINATION
+                        .def()
+                        .help("The number of decimal places of the token."),
+                )
+                .arg(TOKEN_NAME.def().help("The name of the token."))
+                .arg(TOKEN_SYMBOL.def().help(
+                    "The ticker symbol of the token, also used as its alias \
+                     in the wallet.",
+                ))
+        }
+    }
+
+    impl CliToSdk<MintToken<SdkTypes>> for MintToken<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> MintToken<SdkTypes> {
+            MintToken::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                token: ctx.get(&self.token),
+                target: ctx.get(&self.target),
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for MintToken<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let token = TOKEN.parse(matches);
+            let target = TARGET.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_MINT_TOKEN_WASM);
+            Self {
+                tx,
+                token,
+                target,
+                amount,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(TOKEN.def().help("The token to mint."))
+                .arg(TARGET.def().help("The receiver of the minted tokens."))
+                .arg(AMOUNT.def().help("The amount to mint."))
+        }
+    }
+
     impl CliToSdk<InitProposal<SdkTypes>> for InitProposal<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> InitProposal<SdkTypes> {
             InitProposal::<SdkTypes> {
@@ -4026,6 +4288,9 @@ pub mod args {
                 is_offline: self.is_offline,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_parameter_change: self.is_parameter_change,
+                is_upgrade: self.is_upgrade,
+                is_treasury: self.is_treasury,
                 native_token: ctx.native_token.clone(),
                 tx_code_path: self.tx_code_path,
             }
@@ -4039,6 +4304,9 @@ pub mod args {
             let is_offline = PROPOSAL_OFFLINE.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_parameter_change = PROPOSAL_PARAMETER_CHANGE.parse(matches);
+            let is_upgrade = PROPOSAL_UPGRADE.parse(matches);
+            let is_treasury = PROPOSAL_TREASURY.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -4049,6 +4317,9 @@ pub mod args {
                 is_offline,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_parameter_change,
+                is_upgrade,
+                is_treasury,
             }
         }
 
@@ -4103,6 +4374,52 @@ pub mod args {
                             PROPOSAL_PGF_STEWARD.name,
                         ]),
                 )
+                .arg(
+                    PROPOSAL_PARAMETER_CHANGE
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type \
+                             parameter-change. Used to change protocol, PoS, \
+                             governance or pgf parameters.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_OFFLINE.name,
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_UPGRADE
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type upgrade. Used to \
+                             schedule a software upgrade at a block height.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_OFFLINE.name,
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_TREASURY
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type treasury. Used \
+                             to transfer tokens from the governance treasury.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_OFFLINE.name,
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                            PROPOSAL_UPGRADE.name,
+                        ]),
+                )
         }
     }
 
@@ -4155,11 +4472,13 @@ pub mod args {
                             DATA_PATH_OPT.name,
                         ]),
                 )
-                .arg(
-                    PROPOSAL_VOTE
-                        .def()
-                        .help("The vote for the proposal. Either yay or nay."),
-                )
+                .arg(PROPOSAL_VOTE.def().help(
+                    "The vote for the proposal. Either yay, nay, abstain or \
+                     veto (nay with veto). The voting power can be split \
+                     across options by weight, e.g. \
+                     \"yay=0.6,nay=0.3,abstain=0.1\". Voting again before the \
+                     end of the voting period replaces the previous vote.",
+                ))
                 .arg(
                     PROPOSAL_OFFLINE
                         .def()
@@ -4236,6 +4555,10 @@ pub mod args {
         pub offline: bool,
         /// The folder containing the proposal and votes
         pub proposal_folder: Option<PathBuf>,
+        /// The epoch at which the offline votes are tallied
+        pub epoch: Option<Epoch>,
+        /// The folder where the offline tally report is written
+        pub output_folder: Option<PathBuf>,
     }
 
     impl CliToSdk<QueryProposalResult<SdkTypes>> for QueryProposalResult<CliTypes> {
@@ -4245,6 +4568,8 @@ pub mod args {
                 proposal_id: self.proposal_id,
                 offline: self.offline,
                 proposal_folder: self.proposal_folder,
+                epoch: self.epoch,
+                output_folder: self.output_folder,
             }
         }
     }
@@ -4255,12 +4580,16 @@ pub mod args {
             let proposal_id = PROPOSAL_ID_OPT.parse(matches);
             let offline = PROPOSAL_OFFLINE.parse(matches);
             let proposal_folder = DATA_PATH_OPT.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
 
             Self {
                 query,
                 proposal_id,
                 offline,
                 proposal_folder,
+                epoch,
+                output_folder,
             }
         }
 
@@ -4295,6 +4624,24 @@ pub mod args {
                         .conflicts_with(PROPOSAL_ID.name)
                         .requires(PROPOSAL_OFFLINE.name),
                 )
+                .arg(
+                    EPOCH
+                        .def()
+                        .help(
+                            "The epoch at which the offline votes are \
+                             tallied. Defaults to the proposal tally epoch.",
+                        )
+                        .requires(PROPOSAL_OFFLINE.name),
+                )
+                .arg(
+                    OUTPUT_FOLDER_PATH
+                        .def()
+                        .help(
+                            "The folder where the offline tally report is \
+                             written. Defaults to the current directory.",
+                        )
+                        .requires(PROPOSAL_OFFLINE.name),
+                )
         }
     }
 
@@ -4307,6 +4654,7 @@ pub mod args {
         ) -> QueryProtocolParameters<SdkTypes> {
             QueryProtocolParameters::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
+                native_token: ctx.native_token.clone(),
             }
         }
     }
@@ -4315,7 +4663,10 @@ pub mod args {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
 
-            Self { query }
+            Self {
+                query,
+                native_token: (),
+            }
         }
 
         fn def(app: App) -> App {
@@ -4323,15 +4674,44 @@ pub mod args {
         }
     }
 
+    impl Args for QueryTreasury<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            Self { query, token }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(TOKEN_OPT.def().help(
+                "The token to query, all the tokens of the wallet if not \
+                 given.",
+            ))
+        }
+    }
+
+    impl CliToSdk<QueryTreasury<SdkTypes>> for QueryTreasury<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryTreasury<SdkTypes> {
+            QueryTreasury::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                token: self.token.map(|x| ctx.get(&x)),
+            }
+        }
+    }
+
     impl Args for QueryPgf<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
+            let payments = PGF_PAYMENTS.parse(matches);
 
-            Self { query }
+            Self { query, payments }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
+                .arg(PGF_PAYMENTS.def().help(
+                    "Show the funding streams with their remaining budget and \
+                     payout history.",
+                ))
         }
     }
 
@@ -4339,6 +4719,34 @@ pub mod args {
         fn to_sdk(self, ctx: &mut Context) -> QueryPgf<SdkTypes> {
             QueryPgf::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
+                payments: self.payments,
+            }
+        }
+    }
+
+    impl Args for QueryShieldedRewards<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            Self { query, token }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                TOKEN_OPT
+                    .def()
+                    .help("The token to query, all the tokens if not given."),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryShieldedRewards<SdkTypes>>
+        for QueryShieldedRewards<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryShieldedRewards<SdkTypes> {
+            QueryShieldedRewards::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                token: self.token.map(|x| ctx.get(&x)),
             }
         }
     }
@@ -4698,6 +5106,65 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<GenIbcShieldedTransfer<SdkTypes>>
+        for GenIbcShieldedTransfer<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> GenIbcShieldedTransfer<SdkTypes> {
+            GenIbcShieldedTransfer::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                output_folder: self.output_folder,
+                target: ctx.get(&self.target),
+                token: self.token,
+                amount: self.amount,
+                port_id: self.port_id,
+                channel_id: self.channel_id,
+                force: self.force,
+            }
+        }
+    }
+
+    impl Args for GenIbcShieldedTransfer<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
+            let target = TRANSFER_TARGET.parse(matches);
+            let token = TOKEN_STR.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let port_id = PORT_ID.parse(matches);
+            let channel_id = CHANNEL_ID.parse(matches);
+            let force = FORCE.parse(matches);
+            Self {
+                query,
+                output_folder,
+                target,
+                token,
+                amount,
+                port_id,
+                channel_id,
+                force,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(OUTPUT_FOLDER_PATH.def().help(
+                    "The output folder path where the artifact will be stored.",
+                ))
+                .arg(TRANSFER_TARGET.def().help("The payment address."))
+                .arg(TOKEN_STR.def().help(
+                    "The token address or the IBC denom of the token sent \
+                     from the counterparty chain.",
+                ))
+                .arg(AMOUNT.def().help("The amount to transfer in decimal."))
+                .arg(PORT_ID.def().help("The port ID of this chain."))
+                .arg(CHANNEL_ID.def().help("The channel ID of this chain."))
+                .arg(FORCE.def().help(
+                    "Generate the shielded transfer even if the token \
+                     denomination is unknown on this chain.",
+                ))
+        }
+    }
+
     impl CliToSdk<SignTx<SdkTypes>> for SignTx<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> SignTx<SdkTypes> {
             SignTx::<SdkTypes> {
@@ -4966,7 +5433,9 @@ pub mod args {
             ))
             .arg(FEE_AMOUNT_OPT.def().help(
                 "The amount being paid, per gas unit, for the inclusion of \
-                 this transaction",
+                 this transaction. The current base fee per gas unit is \
+                 burned and the rest is paid to the block proposer. Defaults \
+                 to the current base fee.",
             ))
             .arg(FEE_TOKEN.def().help("The token for paying the gas"))
             .arg(FEE_UNSHIELD_SPENDING_KEY.def().help(
@@ -181,6 +181,34 @@ impl<IO: Io> CliApi<IO> {
                         tx::submit_reveal_pk::<_, IO>(&client, &mut ctx, args)
                             .await?;
                     }
+                    Sub::TxInitToken(TxInitToken(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client
+                            .wait_until_node_is_synced::<IO>()
+                            .await
+                            .proceed_or_else(error)?;
+                        let args = args.to_sdk(&mut ctx);
+                        tx::submit_init_token::<_, IO>(&client, &mut ctx, args)
+                            .await?;
+                    }
+                    Sub::TxMintToken(TxMintToken(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client
+                            .wait_until_node_is_synced::<IO>()
+                            .await
+                            .proceed_or_else(error)?;
+                        let args = args.to_sdk(&mut ctx);
+                        tx::submit_mint_token::<_, IO>(&client, &mut ctx, args)
+                            .await?;
+                    }
                     Sub::Bond(Bond(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -634,6 +662,40 @@ impl<IO: Io> CliApi<IO> {
                         let args = args.to_sdk(&mut ctx);
                         rpc::query_pgf::<_, IO>(&client, args).await;
                     }
+                    Sub::QueryTreasury(QueryTreasury(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client
+                            .wait_until_node_is_synced::<IO>()
+                            .await
+                            .proceed_or_else(error)?;
+                        let args = args.to_sdk(&mut ctx);
+                        rpc::query_treasury::<_, IO>(
+                            &client,
+                            &ctx.wallet,
+                            args,
+                        )
+                        .await;
+                    }
+                    Sub::QueryShieldedRewards(QueryShieldedRewards(
+                        mut args,
+                    )) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client
+                            .wait_until_node_is_synced::<IO>()
+                            .await
+                            .proceed_or_else(error)?;
+                        let args = args.to_sdk(&mut ctx);
+                        rpc::query_shielded_rewards::<_, IO>(&client, args)
+                            .await;
+                    }
                     Sub::QueryAccount(QueryAccount(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -660,6 +722,24 @@ impl<IO: Io> CliApi<IO> {
                         let args = args.to_sdk(&mut ctx);
                         tx::sign_tx::<_, IO>(&client, &mut ctx, args).await?;
                     }
+                    Sub::GenIbcShieldedTransfer(GenIbcShieldedTransfer(
+                        mut args,
+                    )) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client
+                            .wait_until_node_is_synced::<IO>()
+                            .await
+                            .proceed_or_else(error)?;
+                        let args = args.to_sdk(&mut ctx);
+                        tx::gen_ibc_shielded_transfer::<_, IO>(
+                            &client, ctx, args,
+                        )
+                        .await?;
+                    }
                 }
             }
             cli::NamadaClient::WithoutContext(cmd, global_args) => match cmd {
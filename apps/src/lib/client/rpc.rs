@@ -15,13 +15,16 @@ use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::{Node, ViewingKey};
 use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada::core::ledger::governance::cli::offline::{
-    find_offline_proposal, find_offline_votes, read_offline_files,
-    OfflineSignedProposal, OfflineVote,
+    dedup_offline_votes, find_offline_proposal, find_offline_votes,
+    read_offline_files, OfflineSignedProposal, OfflineTallyReport,
+    OfflineTallyVote, OfflineVote, OfflineVoteRejection,
 };
+use namada::core::ledger::governance::cli::onchain::ProposalVote;
 use namada::core::ledger::governance::parameters::GovernanceParameters;
 use namada::core::ledger::governance::storage::keys as governance_storage;
+use namada::core::ledger::governance::storage::parameter_change::ParameterChange;
 use namada::core::ledger::governance::storage::proposal::{
-    StoragePgfFunding, StorageProposal,
+    ProposalType, StoragePgfFunding, StorageProposal,
 };
 use namada::core::ledger::governance::utils::{
     compute_proposal_result, ProposalVotes, TallyType, TallyVote, VotePower,
@@ -29,7 +32,11 @@ use namada::core::ledger::governance::utils::{
 use namada::core::ledger::pgf::parameters::PgfParameters;
 use namada::core::ledger::pgf::storage::steward::StewardDetail;
 use namada::ledger::events::Event;
-use namada::ledger::parameters::{storage as param_storage, EpochDuration};
+use namada::ledger::gas::GasCosts;
+use namada::ledger::parameters::{
+    storage as param_storage, EpochDuration, FeeMarketParams,
+    ShieldedRewardParams, ShieldedRewardState,
+};
 use namada::ledger::pos::{CommissionPair, PosParams, Slash};
 use namada::ledger::queries::RPC;
 use namada::ledger::storage::ConversionState;
@@ -46,6 +53,7 @@ use namada::sdk::rpc::{
 use namada::sdk::wallet::{AddressVpType, Wallet};
 use namada::types::address::{masp, Address};
 use namada::types::control_flow::ProceedOrElse;
+use namada::types::dec::Dec;
 use namada::types::hash::Hash;
 use namada::types::io::Io;
 use namada::types::key::*;
@@ -353,7 +361,7 @@ pub async fn query_transparent_balance<
         (Some(token), Some(owner)) => {
             let balance_key =
                 token::balance_key(&token, &owner.address().unwrap());
-            let token_alias = wallet.lookup_alias(&token);
+            let token_alias = lookup_token_alias(client, wallet, &token).await;
             match query_storage_value::<C, token::Amount>(client, &balance_key)
                 .await
             {
@@ -377,9 +385,11 @@ pub async fn query_transparent_balance<
         }
         (None, Some(owner)) => {
             let owner = owner.address().unwrap();
-            for (token_alias, token) in tokens {
+            for (_, token) in tokens {
                 let balance = get_token_balance(client, &token, &owner).await;
                 if !balance.is_zero() {
+                    let token_alias =
+                        lookup_token_alias(client, wallet, &token).await;
                     let balance = format_denominated_amount::<_, IO>(
                         client, &token, balance,
                     )
@@ -578,6 +588,25 @@ pub async fn query_pinned_balance<
     }
 }
 
+/// Look-up the alias of a token in the wallet, followed by its name if it's a
+/// user-defined token
+async fn lookup_token_alias<C: namada::ledger::queries::Client + Sync>(
+    client: &C,
+    wallet: &Wallet<CliWalletUtils>,
+    token: &Address,
+) -> String {
+    let alias = wallet.lookup_alias(token);
+    match query_storage_value::<C, token::TokenMetadata>(
+        client,
+        &token::metadata_key(token),
+    )
+    .await
+    {
+        Ok(metadata) => format!("{} ({})", alias, metadata.name),
+        Err(_) => alias,
+    }
+}
+
 async fn print_balances<C: namada::ledger::queries::Client + Sync, IO: Io>(
     client: &C,
     wallet: &Wallet<CliWalletUtils>,
@@ -628,7 +657,7 @@ async fn print_balances<C: namada::ledger::queries::Client + Sync, IO: Io>(
                 // the token has been already printed
             }
             _ => {
-                let token_alias = wallet.lookup_alias(&t);
+                let token_alias = lookup_token_alias(client, wallet, &t).await;
                 display_line!(IO, &mut w; "Token {}", token_alias).unwrap();
                 print_token = Some(t);
             }
@@ -674,6 +703,16 @@ pub async fn query_proposal<
                 "{}",
                 proposal.to_string_with_status(current_epoch)
             );
+            match &proposal.r#type {
+                ProposalType::ParameterChange(changes) => {
+                    query_and_print_parameter_changes::<_, IO>(client, changes)
+                        .await;
+                }
+                ProposalType::Upgrade(plan) => {
+                    display_line!(IO, "{}", plan);
+                }
+                _ => {}
+            }
         } else {
             edisplay_line!(IO, "No proposal found with id: {}", id);
         }
@@ -702,6 +741,49 @@ pub async fn query_proposal<
     }
 }
 
+/// Print the current and the proposed values of the parameters changed by a
+/// proposal
+pub async fn query_and_print_parameter_changes<
+    C: namada::ledger::queries::Client + Sync,
+    IO: Io,
+>(
+    client: &C,
+    changes: &[ParameterChange],
+) {
+    let protocol_parameters = namada::sdk::rpc::get_protocol_parameters(client)
+        .await
+        .unwrap();
+    let pos_parameters = query_pos_parameters(client).await;
+    let governance_parameters = query_governance_parameters(client).await;
+    let pgf_parameters = query_pgf_parameters(client).await;
+
+    display_line!(IO, "Parameter changes (current -> proposed):");
+    for change in changes {
+        let current_value = match change {
+            ParameterChange::Protocol(change) => {
+                change.current_value(&protocol_parameters)
+            }
+            ParameterChange::Pos(change) => {
+                pos_parameters.current_value(change)
+            }
+            ParameterChange::Governance(change) => {
+                change.current_value(&governance_parameters)
+            }
+            ParameterChange::Pgf(change) => {
+                change.current_value(&pgf_parameters)
+            }
+        };
+        display_line!(
+            IO,
+            "{:4}{}: {} -> {}",
+            "",
+            change.name(),
+            current_value,
+            change.value()
+        );
+    }
+}
+
 /// Query proposal by Id
 pub async fn query_proposal_by_id<C: namada::ledger::queries::Client + Sync>(
     client: &C,
@@ -1120,26 +1202,106 @@ pub async fn query_proposal_result<
                 vote
             })
             .collect::<Vec<OfflineVote>>();
+        display_line!(IO, "Parsed {} votes.", votes.len());
 
-        let proposal_votes = compute_offline_proposal_votes::<_, IO>(
-            client,
-            &proposal,
-            votes.clone(),
-        )
-        .await;
-        let total_voting_power =
-            get_total_staked_tokens(client, proposal.proposal.tally_epoch)
+        let proposal_hash = proposal.proposal.hash();
+        let tally_epoch = args.epoch.unwrap_or(proposal.proposal.tally_epoch);
+
+        // Only the votes with valid signatures are deduplicated, so that a
+        // forged vote can't get the genuine vote of an address rejected
+        let mut rejected_votes: BTreeMap<
+            Address,
+            BTreeSet<OfflineVoteRejection>,
+        > = BTreeMap::new();
+        let mut signed_votes = vec![];
+        for vote in votes {
+            let rejection = if vote.proposal_hash != proposal_hash {
+                Some(OfflineVoteRejection::WrongProposal)
+            } else if matches!(vote.vote, ProposalVote::Invalid) {
+                Some(OfflineVoteRejection::InvalidVote)
+            } else {
+                let account =
+                    match rpc::get_account_info(client, &vote.address).await {
+                        Ok(account) => account,
+                        Err(e) => {
+                            edisplay_line!(
+                                IO,
+                                "Couldn't query the account of {}, the tally \
+                                 is aborted: {}",
+                                vote.address,
+                                e
+                            );
+                            return;
+                        }
+                    };
+                match account {
+                    Some(account)
+                        if vote.check_signature(
+                            &account.public_keys_map,
+                            account.threshold,
+                        ) =>
+                    {
+                        None
+                    }
+                    _ => Some(OfflineVoteRejection::InvalidSignature),
+                }
+            };
+            match rejection {
+                Some(rejection) => {
+                    rejected_votes
+                        .entry(vote.address)
+                        .or_default()
+                        .insert(rejection);
+                }
+                None => signed_votes.push(vote),
+            }
+        }
+        let (unique_votes, conflicting) = dedup_offline_votes(signed_votes);
+        for address in conflicting {
+            rejected_votes
+                .entry(address)
+                .or_default()
+                .insert(OfflineVoteRejection::ConflictingVotes);
+        }
+
+        let (proposal_votes, counted_votes, no_power) =
+            compute_offline_proposal_votes(client, tally_epoch, unique_votes)
                 .await;
+        for address in no_power {
+            rejected_votes
+                .entry(address)
+                .or_default()
+                .insert(OfflineVoteRejection::NoVotingPower);
+        }
+        let total_voting_power =
+            get_total_staked_tokens(client, tally_epoch).await;
 
         let proposal_result = compute_proposal_result(
             proposal_votes,
             total_voting_power,
-            TallyType::TwoThird,
+            TallyType::TwoThirdWithVeto,
+        );
+        let report = OfflineTallyReport::new(
+            proposal_hash,
+            tally_epoch,
+            &proposal_result,
+            counted_votes,
+            rejected_votes,
         );
 
-        display_line!(IO, "Proposal offline: {}", proposal.proposal.hash());
-        display_line!(IO, "Parsed {} votes.", votes.len());
-        display_line!(IO, "{:4}{}", "", proposal_result);
+        display_line!(IO, "{}", report);
+        match report.serialize(args.output_folder) {
+            Ok(path) => {
+                display_line!(
+                    IO,
+                    "Tally report written to {}.",
+                    path.to_string_lossy()
+                )
+            }
+            Err(e) => {
+                edisplay_line!(IO, "Couldn't write the tally report: {}", e)
+            }
+        }
     }
 }
 
@@ -1165,8 +1327,13 @@ pub async fn query_account<
 
 pub async fn query_pgf<C: namada::ledger::queries::Client + Sync, IO: Io>(
     client: &C,
-    _args: args::QueryPgf,
+    args: args::QueryPgf,
 ) {
+    if args.payments {
+        query_pgf_payments::<_, IO>(client).await;
+        return;
+    }
+
     let stewards = query_pgf_stewards(client).await;
     let fundings = query_pgf_fundings(client).await;
 
@@ -1211,12 +1378,184 @@ pub async fn query_pgf<C: namada::ledger::queries::Client + Sync, IO: Io>(
     }
 }
 
+/// Query the pgf funding streams with their remaining budget and payouts
+async fn query_pgf_payments<
+    C: namada::ledger::queries::Client + Sync,
+    IO: Io,
+>(
+    client: &C,
+) {
+    let fundings = query_pgf_fundings(client).await;
+
+    if fundings.is_empty() {
+        display_line!(IO, "Pgf fundings: no fundings are currently set.");
+        return;
+    }
+
+    display_line!(IO, "Pgf fundings:");
+    for funding in fundings {
+        let stream = &funding.detail;
+        display_line!(
+            IO,
+            "{:4}- {} (proposal {})",
+            "",
+            stream.target,
+            funding.id
+        );
+        display_line!(
+            IO,
+            "{:4}  Per epoch: {} from epoch {} to {}",
+            "",
+            stream.amount.to_string_native(),
+            stream.start_epoch,
+            stream.end_epoch
+        );
+        display_line!(
+            IO,
+            "{:4}  Budget: {}, paid: {}, remaining: {}",
+            "",
+            stream.budget.to_string_native(),
+            funding.paid.to_string_native(),
+            funding.remaining_budget().to_string_native()
+        );
+        let payouts = query_pgf_payouts(client, &stream.target).await;
+        if payouts.is_empty() {
+            display_line!(IO, "{:4}  Payouts: none", "");
+        } else {
+            display_line!(IO, "{:4}  Payouts:", "");
+            for (epoch, amount) in payouts {
+                display_line!(
+                    IO,
+                    "{:6}- epoch {}: {}",
+                    "",
+                    epoch,
+                    amount.to_string_native()
+                );
+            }
+        }
+    }
+}
+
+/// Query the token balances of the governance treasury
+pub async fn query_treasury<
+    C: namada::ledger::queries::Client + Sync,
+    IO: Io,
+>(
+    client: &C,
+    wallet: &Wallet<CliWalletUtils>,
+    args: args::QueryTreasury,
+) {
+    let tokens = match &args.token {
+        Some(token) => vec![token.clone()],
+        None => wallet.tokens_with_aliases().into_values().collect(),
+    };
+
+    display_line!(IO, "Treasury balances:");
+    let mut found = false;
+    for token in tokens {
+        let balance = query_treasury_balance(client, &token).await;
+        if balance.is_zero() && args.token.is_none() {
+            continue;
+        }
+        found = true;
+        let token_alias = lookup_token_alias(client, wallet, &token).await;
+        let balance =
+            format_denominated_amount::<_, IO>(client, &token, balance).await;
+        display_line!(IO, "{:4}{}: {}", "", token_alias, balance);
+    }
+    if !found {
+        display_line!(IO, "{:4}The treasury holds no tokens.", "");
+    }
+}
+
+/// Query the shielded reward parameters of the rewarded tokens, together with
+/// the state of their rewards controller and the expected yield
+pub async fn query_shielded_rewards<
+    C: namada::ledger::queries::Client + Sync,
+    IO: Io,
+>(
+    client: &C,
+    args: args::QueryShieldedRewards,
+) {
+    let shielded_rewards =
+        query_storage_value::<C, BTreeMap<Address, ShieldedRewardParams>>(
+            client,
+            &param_storage::get_shielded_rewards_key(),
+        )
+        .await
+        .unwrap_or_default();
+    let epochs_per_year = query_storage_value::<C, u64>(
+        client,
+        &param_storage::get_epochs_per_year_key(),
+    )
+    .await
+    .expect("Parameter should be defined.");
+
+    let mut found = false;
+    for (token, params) in shielded_rewards {
+        if matches!(&args.token, Some(filter) if filter != &token) {
+            continue;
+        }
+        found = true;
+        let state = query_storage_value::<C, ShieldedRewardState>(
+            client,
+            &param_storage::get_shielded_rewards_state_key(&token),
+        )
+        .await
+        .unwrap_or_default();
+        let (num, den) = state.last_reward_rate;
+        let epoch_rate = Dec::from(num as u64) / Dec::from(den as u64);
+
+        display_line!(IO, "{}:", token);
+        display_line!(
+            IO,
+            "{:4}Max. reward rate: {}",
+            "",
+            params.max_reward_rate
+        );
+        display_line!(
+            IO,
+            "{:4}Target locked ratio: {}",
+            "",
+            params.locked_ratio_target
+        );
+        display_line!(IO, "{:4}Kp gain: {}", "", params.kp_gain_nom);
+        display_line!(IO, "{:4}Kd gain: {}", "", params.kd_gain_nom);
+        display_line!(
+            IO,
+            "{:4}Last locked ratio: {}",
+            "",
+            state.last_locked_ratio
+        );
+        display_line!(
+            IO,
+            "{:4}Reward per held unit in the last epoch: {}",
+            "",
+            epoch_rate
+        );
+        display_line!(
+            IO,
+            "{:4}Expected yearly reward per held unit: {}",
+            "",
+            epoch_rate * epochs_per_year
+        );
+    }
+    if !found {
+        match args.token {
+            Some(token) => {
+                display_line!(IO, "No shielded rewards for token {}", token)
+            }
+            None => display_line!(IO, "No token has shielded rewards"),
+        }
+    }
+}
+
 pub async fn query_protocol_parameters<
     C: namada::ledger::queries::Client + Sync,
     IO: Io,
 >(
     client: &C,
-    _args: args::QueryProtocolParameters,
+    args: args::QueryProtocolParameters,
 ) {
     let governance_parameters = query_governance_parameters(client).await;
     display_line!(IO, "Governance Parameters\n");
@@ -1238,6 +1577,12 @@ pub async fn query_protocol_parameters<
         "",
         governance_parameters.min_proposal_voting_period
     );
+    display_line!(
+        IO,
+        "{:4}Min. expedited proposal voting period: {}",
+        "",
+        governance_parameters.min_expedited_proposal_voting_period
+    );
     display_line!(
         IO,
         "{:4}Max. proposal period: {}",
@@ -1256,6 +1601,14 @@ pub async fn query_protocol_parameters<
         "",
         governance_parameters.min_proposal_grace_epochs
     );
+    let treasury_balance =
+        query_treasury_balance(client, &args.native_token).await;
+    display_line!(
+        IO,
+        "{:4}Treasury balance: {}",
+        "",
+        treasury_balance.to_string_native()
+    );
 
     let pgf_parameters = query_pgf_parameters(client).await;
     display_line!(IO, "Public Goods Funding Parameters\n");
@@ -1296,6 +1649,12 @@ pub async fn query_protocol_parameters<
         .expect("Parameter should be defined.");
     display_line!(IO, "{:4}Max. block duration: {}", "", max_block_duration);
 
+    let key = param_storage::get_max_tx_expiration_key();
+    let max_tx_expiration = query_storage_value::<C, Option<u64>>(client, &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(IO, "{:4}Max. tx expiration: {:?}", "", max_tx_expiration);
+
     let key = param_storage::get_tx_whitelist_storage_key();
     let vp_whitelist = query_storage_value::<C, Vec<String>>(client, &key)
         .await
@@ -1345,10 +1704,30 @@ pub async fn query_protocol_parameters<
     .await
     .expect("Parameter should be defined.");
     display_line!(IO, "{:4}Gas cost table:", "");
-    for (token, gas_cost) in gas_cost_table {
+    for (token, gas_cost) in &gas_cost_table {
         display_line!(IO, "{:8}{}: {:?}", "", token, gas_cost);
     }
 
+    let key = param_storage::get_gas_costs_key();
+    let gas_costs = query_storage_value::<C, GasCosts>(client, &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(IO, "{:4}Gas costs: {:?}", "", gas_costs);
+
+    let key = param_storage::get_fee_market_key();
+    let fee_market = query_storage_value::<C, FeeMarketParams>(client, &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(IO, "{:4}Fee market: {:?}", "", fee_market);
+    display_line!(IO, "{:4}Current base fees:", "");
+    for token in gas_cost_table.keys() {
+        if let Ok(Some(base_fee)) =
+            namada::sdk::rpc::query_base_fee(client, token).await
+        {
+            display_line!(IO, "{:8}{}: {:?}", "", token, base_fee);
+        }
+    }
+
     display_line!(IO, "PoS parameters");
     let pos_params = query_pos_parameters(client).await;
     display_line!(
@@ -1437,6 +1816,24 @@ pub async fn query_pgf_fundings<C: namada::ledger::queries::Client + Sync>(
     unwrap_client_response::<C, _>(RPC.vp().pgf().funding(client).await)
 }
 
+pub async fn query_pgf_payouts<C: namada::ledger::queries::Client + Sync>(
+    client: &C,
+    target: &Address,
+) -> BTreeMap<Epoch, token::Amount> {
+    unwrap_client_response::<C, _>(RPC.vp().pgf().payouts(client, target).await)
+}
+
+pub async fn query_treasury_balance<
+    C: namada::ledger::queries::Client + Sync,
+>(
+    client: &C,
+    token: &Address,
+) -> token::Amount {
+    unwrap_client_response::<C, _>(
+        RPC.vp().gov().treasury_balance(client, token).await,
+    )
+}
+
 pub async fn query_pgf_parameters<C: namada::ledger::queries::Client + Sync>(
     client: &C,
 ) -> PgfParameters {
@@ -2427,14 +2824,20 @@ fn unwrap_client_response<C: namada::ledger::queries::Client, T>(
     })
 }
 
+/// Compute the voting power of deduplicated offline votes with the stake at
+/// the tally epoch. Returns the votes to tally, the counted votes with their
+/// voting power and the voters without any stake.
 pub async fn compute_offline_proposal_votes<
     C: namada::ledger::queries::Client + Sync,
-    IO: Io,
 >(
     client: &C,
-    proposal: &OfflineSignedProposal,
-    votes: Vec<OfflineVote>,
-) -> ProposalVotes {
+    tally_epoch: Epoch,
+    votes: BTreeMap<Address, OfflineVote>,
+) -> (
+    ProposalVotes,
+    BTreeMap<Address, OfflineTallyVote>,
+    BTreeSet<Address>,
+) {
     let mut validators_vote: HashMap<Address, TallyVote> = HashMap::default();
     let mut validator_voting_power: HashMap<Address, VotePower> =
         HashMap::default();
@@ -2443,54 +2846,60 @@ pub async fn compute_offline_proposal_votes<
         Address,
         HashMap<Address, VotePower>,
     > = HashMap::default();
-    for vote in votes {
-        let is_validator = is_validator(client, &vote.address).await;
-        let is_delegator = is_delegator(client, &vote.address).await;
-        if is_validator {
-            let validator_stake = get_validator_stake(
-                client,
-                proposal.proposal.tally_epoch,
-                &vote.address,
-            )
-            .await
-            .unwrap_or_default();
-            validators_vote.insert(vote.address.clone(), vote.clone().into());
-            validator_voting_power
-                .insert(vote.address.clone(), validator_stake);
-        } else if is_delegator {
-            let validators = get_delegators_delegation_at(
-                client,
-                &vote.address.clone(),
-                proposal.proposal.tally_epoch,
-            )
-            .await;
+    let mut counted_votes = BTreeMap::new();
+    let mut no_power = BTreeSet::new();
 
-            for validator in vote.delegations.clone() {
-                let delegator_stake =
-                    validators.get(&validator).cloned().unwrap_or_default();
+    for (address, vote) in votes {
+        if let Some(validator_stake) =
+            get_validator_stake(client, tally_epoch, &address).await
+        {
+            counted_votes.insert(
+                address.clone(),
+                OfflineTallyVote {
+                    vote: vote.vote.clone(),
+                    voting_power: validator_stake,
+                },
+            );
+            validators_vote.insert(address.clone(), vote.into());
+            validator_voting_power.insert(address, validator_stake);
+            continue;
+        }
 
-                delegators_vote
-                    .insert(vote.address.clone(), vote.clone().into());
-                delegator_voting_power
-                    .entry(vote.address.clone())
-                    .or_default()
-                    .insert(validator, delegator_stake);
+        let mut delegations = HashMap::new();
+        for validator in vote.delegations.iter().collect::<BTreeSet<_>>() {
+            let delegator_stake =
+                get_bond_amount_at(client, &address, validator, tally_epoch)
+                    .await
+                    .unwrap_or_default();
+            if !delegator_stake.is_zero() {
+                delegations.insert(validator.clone(), delegator_stake);
             }
-        } else {
-            display_line!(
-                IO,
-                "Skipping vote, not a validator/delegator at epoch {}.",
-                proposal.proposal.tally_epoch
-            );
         }
+        if delegations.is_empty() {
+            no_power.insert(address);
+            continue;
+        }
+        counted_votes.insert(
+            address.clone(),
+            OfflineTallyVote {
+                vote: vote.vote.clone(),
+                voting_power: delegations.values().cloned().sum(),
+            },
+        );
+        delegators_vote.insert(address.clone(), vote.into());
+        delegator_voting_power.insert(address, delegations);
     }
 
-    ProposalVotes {
-        validators_vote,
-        validator_voting_power,
-        delegators_vote,
-        delegator_voting_power,
-    }
+    (
+        ProposalVotes {
+            validators_vote,
+            validator_voting_power,
+            delegators_vote,
+            delegator_voting_power,
+        },
+        counted_votes,
+        no_power,
+    )
 }
 
 pub async fn compute_proposal_votes<
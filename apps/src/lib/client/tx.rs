@@ -10,19 +10,22 @@ use namada::core::ledger::governance::cli::offline::{
     OfflineProposal, OfflineSignedProposal, OfflineVote,
 };
 use namada::core::ledger::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal, ProposalVote,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, ProposalVote, TreasuryProposal, UpgradeProposal,
 };
+use namada::ibc::applications::transfer::Memo;
 use namada::ledger::pos;
 use namada::proof_of_stake::parameters::PosParams;
 use namada::proto::Tx;
 use namada::sdk::rpc::{TxBroadcastData, TxResponse};
-use namada::sdk::wallet::{Wallet, WalletUtils};
+use namada::sdk::wallet::{AddressVpType, Wallet, WalletUtils};
 use namada::sdk::{error, masp, signing, tx};
 use namada::tendermint_rpc::HttpClient;
 use namada::types::address::{Address, ImplicitAddress};
 use namada::types::dec::Dec;
 use namada::types::io::Io;
 use namada::types::key::{self, *};
+use namada::types::token;
 use namada::types::transaction::pos::InitValidator;
 use namada::{display_line, edisplay_line};
 
@@ -795,17 +798,18 @@ where
     C: namada::ledger::queries::Client + Sync,
     C::Error: std::fmt::Display,
 {
-    let default_signer = Some(args.source.clone());
+    let source = args.source.effective_address();
+    let default_signer = Some(source.clone());
     let signing_data = aux_signing_data::<_, IO>(
         client,
         &mut ctx.wallet,
         &args.tx,
-        Some(args.source.clone()),
+        Some(source.clone()),
         default_signer,
     )
     .await?;
 
-    submit_reveal_aux::<_, IO>(client, &mut ctx, args.tx.clone(), &args.source)
+    submit_reveal_aux::<_, IO>(client, &mut ctx, args.tx.clone(), &source)
         .await?;
 
     let (mut tx, _epoch) = tx::build_ibc_transfer::<_, _, _, IO>(
@@ -830,6 +834,44 @@ where
     Ok(())
 }
 
+pub async fn gen_ibc_shielded_transfer<C, IO: Io>(
+    client: &C,
+    mut ctx: Context,
+    args: args::GenIbcShieldedTransfer,
+) -> Result<(), error::Error>
+where
+    C: namada::ledger::queries::Client + Sync,
+    C::Error: std::fmt::Display,
+{
+    if let Some(shielded_transfer) = tx::gen_ibc_shielded_transfer::<_, _, IO>(
+        client,
+        &mut ctx.shielded,
+        args.clone(),
+    )
+    .await?
+    {
+        let tx_id = shielded_transfer.masp_tx.txid().to_string();
+        let filename = format!("ibc_shielded_transfer_{}.memo", tx_id);
+        let output_path = match &args.output_folder {
+            Some(path) => path.join(filename),
+            None => filename.into(),
+        };
+        let mut out = File::create(&output_path)
+            .expect("Should be able to create the out file.");
+        out.write_all(Memo::from(shielded_transfer).as_ref().as_bytes())
+            .expect("IBC memo should be deserializable.");
+        display_line!(
+            IO,
+            "Output IBC shielded transfer for {tx_id} to {}",
+            output_path.to_string_lossy()
+        );
+    } else {
+        edisplay_line!(IO, "No shielded transfer for this IBC transfer.");
+        safe_exit(1);
+    }
+    Ok(())
+}
+
 pub async fn submit_init_proposal<C, IO: Io>(
     client: &C,
     mut ctx: Context,
@@ -971,6 +1013,163 @@ where
             .await?,
             signing_data,
         )
+    } else if args.is_parameter_change {
+        let proposal =
+            ParameterChangeProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                    error::TxError::FailedGovernaneProposalDeserialize(
+                        e.to_string(),
+                    )
+                })?;
+        let author_balance = rpc::get_token_balance(
+            client,
+            &ctx.native_token,
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| error::TxError::InvalidProposal(e.to_string()))?;
+        rpc::query_and_print_parameter_changes::<_, IO>(client, &proposal.data)
+            .await;
+
+        let default_signer = Some(proposal.proposal.author.clone());
+        let signing_data = aux_signing_data::<_, IO>(
+            client,
+            &mut ctx.wallet,
+            &args.tx,
+            Some(proposal.proposal.author.clone()),
+            default_signer,
+        )
+        .await?;
+
+        submit_reveal_aux::<_, IO>(
+            client,
+            &mut ctx,
+            args.tx.clone(),
+            &proposal.proposal.author,
+        )
+        .await?;
+
+        (
+            tx::build_parameter_change_proposal::<_, _, _, IO>(
+                client,
+                &mut ctx.wallet,
+                &mut ctx.shielded,
+                args.clone(),
+                proposal,
+                signing_data.fee_payer.clone(),
+            )
+            .await?,
+            signing_data,
+        )
+    } else if args.is_upgrade {
+        let proposal = UpgradeProposal::try_from(args.proposal_data.as_ref())
+            .map_err(|e| {
+            error::TxError::FailedGovernaneProposalDeserialize(e.to_string())
+        })?;
+        let author_balance = rpc::get_token_balance(
+            client,
+            &ctx.native_token,
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| error::TxError::InvalidProposal(e.to_string()))?;
+
+        let default_signer = Some(proposal.proposal.author.clone());
+        let signing_data = aux_signing_data::<_, IO>(
+            client,
+            &mut ctx.wallet,
+            &args.tx,
+            Some(proposal.proposal.author.clone()),
+            default_signer,
+        )
+        .await?;
+
+        submit_reveal_aux::<_, IO>(
+            client,
+            &mut ctx,
+            args.tx.clone(),
+            &proposal.proposal.author,
+        )
+        .await?;
+
+        (
+            tx::build_upgrade_proposal::<_, _, _, IO>(
+                client,
+                &mut ctx.wallet,
+                &mut ctx.shielded,
+                args.clone(),
+                proposal,
+                signing_data.fee_payer.clone(),
+            )
+            .await?,
+            signing_data,
+        )
+    } else if args.is_treasury {
+        let proposal = TreasuryProposal::try_from(args.proposal_data.as_ref())
+            .map_err(|e| {
+                error::TxError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+        let author_balance = rpc::get_token_balance(
+            client,
+            &ctx.native_token,
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| error::TxError::InvalidProposal(e.to_string()))?;
+
+        let default_signer = Some(proposal.proposal.author.clone());
+        let signing_data = aux_signing_data::<_, IO>(
+            client,
+            &mut ctx.wallet,
+            &args.tx,
+            Some(proposal.proposal.author.clone()),
+            default_signer,
+        )
+        .await?;
+
+        submit_reveal_aux::<_, IO>(
+            client,
+            &mut ctx,
+            args.tx.clone(),
+            &proposal.proposal.author,
+        )
+        .await?;
+
+        (
+            tx::build_treasury_proposal::<_, _, _, IO>(
+                client,
+                &mut ctx.wallet,
+                &mut ctx.shielded,
+                args.clone(),
+                proposal,
+                signing_data.fee_payer.clone(),
+            )
+            .await?,
+            signing_data,
+        )
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1571,6 +1770,115 @@ where
     Ok(())
 }
 
+pub async fn submit_init_token<C, IO: Io>(
+    client: &C,
+    ctx: &mut Context,
+    args: args::InitToken,
+) -> Result<(), error::Error>
+where
+    C: namada::ledger::queries::Client + Sync,
+    C::Error: std::fmt::Display,
+{
+    let default_signer = Some(args.token.clone());
+    let signing_data = signing::aux_signing_data::<_, _, IO>(
+        client,
+        &mut ctx.wallet,
+        &args.tx,
+        Some(args.token.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let (mut tx, _fee_unshield_epoch) = tx::build_init_token::<_, _, _, IO>(
+        client,
+        &mut ctx.wallet,
+        &mut ctx.shielded,
+        args.clone(),
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    signing::generate_test_vector::<_, _, IO>(client, &mut ctx.wallet, &tx)
+        .await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx::<IO>(&args.tx, tx);
+    } else {
+        signing::sign_tx(&mut ctx.wallet, &args.tx, &mut tx, signing_data)?;
+        let result =
+            tx::process_tx::<_, _, IO>(client, &mut ctx.wallet, &args.tx, tx)
+                .await?;
+        let applied = matches!(
+            result,
+            ProcessTxResponse::Applied(ref resp) if resp.code == 0.to_string()
+        );
+        if applied {
+            // Make the token known to the wallet, so that its balances are
+            // displayed under its symbol
+            if ctx.wallet.find_address(&args.symbol).is_none() {
+                ctx.wallet.add_address(
+                    args.symbol.clone(),
+                    args.token.clone(),
+                    false,
+                );
+            }
+            ctx.wallet
+                .add_vp_type_to_address(AddressVpType::Token, args.token);
+            crate::wallet::save(&ctx.wallet)
+                .unwrap_or_else(|err| edisplay_line!(IO, "{}", err));
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn submit_mint_token<C, IO: Io>(
+    client: &C,
+    ctx: &mut Context,
+    args: args::MintToken,
+) -> Result<(), error::Error>
+where
+    C: namada::ledger::queries::Client + Sync,
+    C::Error: std::fmt::Display,
+{
+    let minter = rpc::query_storage_value::<C, Address>(
+        client,
+        &token::minter_key(&args.token),
+    )
+    .await
+    .ok();
+    let signing_data = signing::aux_signing_data::<_, _, IO>(
+        client,
+        &mut ctx.wallet,
+        &args.tx,
+        minter.clone(),
+        minter,
+    )
+    .await?;
+
+    let (mut tx, _fee_unshield_epoch) = tx::build_mint_token::<_, _, _, IO>(
+        client,
+        &mut ctx.wallet,
+        &mut ctx.shielded,
+        args.clone(),
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    signing::generate_test_vector::<_, _, IO>(client, &mut ctx.wallet, &tx)
+        .await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx::<IO>(&args.tx, tx);
+    } else {
+        signing::sign_tx(&mut ctx.wallet, &args.tx, &mut tx, signing_data)?;
+        tx::process_tx::<_, _, IO>(client, &mut ctx.wallet, &args.tx, tx)
+            .await?;
+    }
+
+    Ok(())
+}
+
 /// Save accounts initialized from a tx into the wallet, if any.
 pub async fn save_initialized_accounts<U: WalletUtils, IO: Io>(
     wallet: &mut Wallet<U>,
@@ -7,7 +7,10 @@ use derivative::Derivative;
 use namada::core::ledger::governance::parameters::GovernanceParameters;
 use namada::core::ledger::pgf::parameters::PgfParameters;
 use namada::ledger::eth_bridge::EthereumBridgeConfig;
-use namada::ledger::parameters::EpochDuration;
+use namada::ledger::gas::GasCosts;
+use namada::ledger::parameters::{
+    EpochDuration, FeeMarketParams, ShieldedRewardParams,
+};
 use namada::ledger::pos::{Dec, GenesisValidator, PosParams};
 use namada::types::address::Address;
 use namada::types::chain::ProposalBytes;
@@ -30,7 +33,10 @@ pub mod genesis_config {
     use eyre::Context;
     use namada::core::ledger::governance::parameters::GovernanceParameters;
     use namada::core::ledger::pgf::parameters::PgfParameters;
-    use namada::ledger::parameters::EpochDuration;
+    use namada::ledger::gas::GasCosts;
+    use namada::ledger::parameters::{
+        EpochDuration, FeeMarketParams, ShieldedRewardParams,
+    };
     use namada::ledger::pos::{Dec, GenesisValidator, PosParams};
     use namada::types::address::Address;
     use namada::types::chain::ProposalBytes;
@@ -141,6 +147,8 @@ pub mod genesis_config {
         pub max_proposal_code_size: u64,
         // Minimum proposal period length in epochs
         pub min_proposal_voting_period: u64,
+        // Minimum expedited proposal period length in epochs
+        pub min_expedited_proposal_voting_period: u64,
         // Maximum proposal period length in epochs
         pub max_proposal_period: u64,
         // Maximum number of characters in the proposal content
@@ -213,6 +221,9 @@ pub mod genesis_config {
         pub vp: Option<String>,
         // Initial balances held by accounts defined elsewhere.
         pub balances: Option<HashMap<String, token::Amount>>,
+        // Parameters of the rewards for holding the token in the MASP.
+        // (default: the default shielded rewards parameters)
+        pub parameters: Option<ShieldedRewardParams>,
     }
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -255,6 +266,10 @@ pub mod genesis_config {
         /// Maximum duration per block (in seconds).
         // TODO: this is i64 because datetime wants it
         pub max_expected_time_per_block: i64,
+        /// Max duration between a block's time and the expiration of a
+        /// wrapper tx included in it (in seconds). `None` value allows
+        /// wrapper txs without an expiration.
+        pub max_tx_expiration: Option<i64>,
         /// Hashes of whitelisted vps array. `None` value or an empty array
         /// disables whitelisting.
         pub vp_whitelist: Option<Vec<String>>,
@@ -278,6 +293,11 @@ pub mod genesis_config {
         /// Map of the cost per gas unit for every token allowed for fee
         /// payment
         pub minimum_gas_price: BTreeMap<Address, token::Amount>,
+        /// The gas costs table. `None` value uses the default costs.
+        pub gas_costs: Option<GasCosts>,
+        /// The base fee adjustment parameters. `None` value uses the default
+        /// parameters.
+        pub fee_market: Option<FeeMarketParams>,
     }
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -574,6 +594,15 @@ pub mod genesis_config {
                 )
             })
             .collect();
+        let shielded_rewards = token
+            .values()
+            .map(|cfg| {
+                (
+                    Address::decode(cfg.address.as_ref().unwrap()).unwrap(),
+                    cfg.parameters.clone().unwrap_or_default(),
+                )
+            })
+            .collect();
 
         let implicit_vp_config = wasm.get(&parameters.implicit_vp).unwrap();
         let implicit_vp_code_path = implicit_vp_config.filename.to_owned();
@@ -603,6 +632,9 @@ pub mod genesis_config {
                     parameters.max_expected_time_per_block,
                 )
                 .into(),
+            max_tx_expiration: parameters.max_tx_expiration.map(|secs| {
+                namada::types::time::Duration::seconds(secs).into()
+            }),
             max_proposal_bytes: parameters.max_proposal_bytes,
             max_block_gas: parameters.max_block_gas,
             vp_whitelist: parameters.vp_whitelist.unwrap_or_default(),
@@ -617,6 +649,9 @@ pub mod genesis_config {
             staked_ratio: Dec::zero(),
             pos_inflation_amount: token::Amount::zero(),
             minimum_gas_price: parameters.minimum_gas_price,
+            gas_costs: parameters.gas_costs.unwrap_or_default(),
+            fee_market: parameters.fee_market.unwrap_or_default(),
+            shielded_rewards,
             fee_unshielding_gas_limit: parameters.fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit: parameters
                 .fee_unshielding_descriptions_limit,
@@ -626,6 +661,7 @@ pub mod genesis_config {
             min_proposal_fund,
             max_proposal_code_size,
             min_proposal_voting_period,
+            min_expedited_proposal_voting_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
             max_proposal_period,
@@ -634,6 +670,7 @@ pub mod genesis_config {
             min_proposal_fund: token::Amount::native_whole(min_proposal_fund),
             max_proposal_code_size,
             min_proposal_voting_period,
+            min_expedited_proposal_voting_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
             max_proposal_period,
@@ -860,6 +897,9 @@ pub struct Parameters {
     pub epoch_duration: EpochDuration,
     /// Maximum expected time per block
     pub max_expected_time_per_block: DurationSecs,
+    /// Max duration between a block's time and the expiration of a wrapper
+    /// tx included in it
+    pub max_tx_expiration: Option<DurationSecs>,
     /// Whitelisted validity predicate hashes
     pub vp_whitelist: Vec<String>,
     /// Whitelisted tx hashes
@@ -886,6 +926,13 @@ pub struct Parameters {
     pub fee_unshielding_descriptions_limit: u64,
     /// Map of the cost per gas unit for every token allowed for fee payment
     pub minimum_gas_price: BTreeMap<Address, token::Amount>,
+    /// The gas costs table
+    pub gas_costs: GasCosts,
+    /// The base fee adjustment parameters
+    pub fee_market: FeeMarketParams,
+    /// Map of the shielded rewards parameters for every token rewarded for
+    /// being held in the MASP
+    pub shielded_rewards: BTreeMap<Address, ShieldedRewardParams>,
 }
 
 #[cfg(not(any(test, feature = "dev")))]
@@ -994,6 +1041,7 @@ pub fn genesis(num_validators: u64) -> Genesis {
             min_duration: namada::types::time::Duration::seconds(600).into(),
         },
         max_expected_time_per_block: namada::types::time::DurationSecs(30),
+        max_tx_expiration: None,
         max_proposal_bytes: Default::default(),
         max_block_gas: 20_000_000,
         vp_whitelist: vec![],
@@ -1010,6 +1058,12 @@ pub fn genesis(num_validators: u64) -> Genesis {
         minimum_gas_price: [(nam(), token::Amount::from(1))]
             .into_iter()
             .collect(),
+        gas_costs: GasCosts::default(),
+        fee_market: FeeMarketParams::default(),
+        shielded_rewards: tokens()
+            .into_keys()
+            .map(|address| (address, ShieldedRewardParams::default()))
+            .collect(),
         fee_unshielding_gas_limit: 20_000,
         fee_unshielding_descriptions_limit: 15,
     };
@@ -115,6 +115,15 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// When set, will limit how many block heights in the past are the
+    /// events kept in the event index. Otherwise, the events are never
+    /// pruned.
+    pub event_index_retention: Option<u64>,
+    /// When set, the decrypted transactions of a block are executed
+    /// speculatively in parallel and only the conflicting ones are executed
+    /// again in order.
+    #[serde(default)]
+    pub parallel_tx_execution: bool,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -147,6 +156,9 @@ impl Ledger {
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                // Default corresponds to 1 week of past blocks at 1 block/sec
+                event_index_retention: Some(604_800),
+                parallel_tx_execution: false,
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
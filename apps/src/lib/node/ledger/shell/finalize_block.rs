@@ -4,13 +4,16 @@ use std::collections::HashMap;
 
 use data_encoding::HEXUPPER;
 use namada::core::ledger::pgf::ADDRESS as pgf_address;
-use namada::ledger::events::EventType;
+use namada::ledger::events::{EventLevel, EventType};
 use namada::ledger::gas::{GasMetering, TxGasMeter};
 use namada::ledger::parameters::storage as params_storage;
+use namada::ledger::protocol::parallel::{SpeculativeTxInput, SpeculativeTxs};
 use namada::ledger::pos::{namada_proof_of_stake, staking_token_address};
 use namada::ledger::storage::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada::ledger::storage_api::token::credit_tokens;
-use namada::ledger::storage_api::{pgf, StorageRead, StorageWrite};
+use namada::ledger::storage_api::{
+    governance as gov_api, pgf, StorageRead, StorageWrite,
+};
 use namada::ledger::{inflation, protocol, replay_protection};
 use namada::proof_of_stake::{
     delegator_rewards_products_handle, find_validator_by_raw_hash,
@@ -21,6 +24,7 @@ use namada::proof_of_stake::{
 };
 use namada::types::address::Address;
 use namada::types::dec::Dec;
+use namada::types::event::{IndexedEvent, TxEvent, NEW_EPOCH_EVENT};
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::types::token::Amount;
@@ -68,8 +72,16 @@ where
         let mut response = shim::response::FinalizeBlock::default();
 
         // Begin the new block and check if a new epoch has begun
+        let block_time = req.header.time;
         let (height, new_epoch) =
             self.update_state(req.header, req.hash, req.byzantine_validators);
+        self.apply_scheduled_upgrade(height)?;
+        // Track the deposits of the proposals submitted before the locked
+        // deposits were, so that they can be settled
+        gov_api::init_locked_deposits(
+            &mut self.wl_storage,
+            self.wl_storage.storage.last_epoch,
+        )?;
 
         let (current_epoch, _gas) = self.wl_storage.storage.get_current_epoch();
         let update_for_tendermint = matches!(
@@ -88,11 +100,22 @@ where
         );
 
         if new_epoch {
+            self.wl_storage.emit_event(
+                TxEvent::new(NEW_EPOCH_EVENT)
+                    .with("epoch", current_epoch.0)
+                    .with("height", height.0),
+            )?;
+
             namada::ledger::storage::update_allowed_conversions(
                 &mut self.wl_storage,
             )?;
 
             execute_governance_proposals(self, &mut response)?;
+            // The gas costs take effect at the epoch after they're changed
+            self.update_gas_costs();
+            // Governance may have upgraded or allowlisted some WASM codes
+            #[cfg(not(test))]
+            self.warm_up_wasm_caches();
 
             // Copy the new_epoch + pipeline_len - 1 validator set into
             // new_epoch + pipeline_len
@@ -111,6 +134,13 @@ where
                 &mut self.wl_storage,
                 current_epoch,
             )?;
+
+            // Prune the replay protection hashes of the expired txs
+            let pruned = replay_protection::prune_expired_hashes(
+                &mut self.wl_storage,
+                block_time,
+            )?;
+            tracing::debug!("Pruned {pruned} expired replay protection hashes");
         }
 
         // Invariant: Has to be applied before `record_slashes_from_evidence`
@@ -129,8 +159,13 @@ where
         if new_epoch {
             self.process_slashes();
         }
+        // The events from the block-level logic must not be mixed up with the
+        // ones from the transactions
+        self.take_block_events(&mut response, height);
 
         let mut stats = InternalStats::default();
+        // The gas reserved by the wrapper txs included in the block
+        let mut block_gas_used: u64 = 0;
 
         let native_block_proposer_address = {
             let tm_raw_hash_string =
@@ -145,6 +180,9 @@ where
 
         // Tracks the accepted transactions
         self.wl_storage.storage.block.results = BlockResults::default();
+        let mut speculative_txs = self
+            .parallel_tx_execution
+            .then(|| self.execute_speculative_txs(&req.txs));
         for (tx_index, processed_tx) in req.txs.iter().enumerate() {
             let tx = if let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) {
                 tx
@@ -206,18 +244,20 @@ where
                 // from the queue of txs to be processed and remove the hash
                 // from storage
                 if let TxType::Decrypted(_) = &tx_header.tx_type {
-                    let tx_hash = self
+                    let mut tx_in_queue = self
                         .wl_storage
                         .storage
                         .tx_queue
                         .pop()
                         .expect("Missing wrapper tx in queue")
-                        .tx
-                        .clone()
-                        .update_header(TxType::Raw)
-                        .header_hash();
+                        .tx;
                     let tx_hash_key =
-                        replay_protection::get_replay_protection_key(&tx_hash);
+                        replay_protection::get_replay_protection_key(
+                            tx_in_queue.header.expiration,
+                            &tx_in_queue
+                                .update_header(TxType::Raw)
+                                .header_hash(),
+                        );
                     self.wl_storage
                         .delete(&tx_hash_key)
                         .expect("Error while deleting tx hash from storage");
@@ -276,10 +316,12 @@ where
                 continue;
             }
 
-            let (mut tx_event, tx_unsigned_hash, mut tx_gas_meter, wrapper) =
+            let (mut tx_event, inner_hash_key, mut tx_gas_meter, wrapper) =
                 match &tx_header.tx_type {
                     TxType::Wrapper(wrapper) => {
                         stats.increment_wrapper_txs();
+                        block_gas_used = block_gas_used
+                            .saturating_add(u64::from(wrapper.gas_limit));
                         let tx_event = Event::new_tx_event(&tx, height.0);
                         let gas_meter = TxGasMeter::new(wrapper.gas_limit);
                         (tx_event, None, gas_meter, Some(tx.clone()))
@@ -323,12 +365,13 @@ where
 
                         (
                             event,
-                            Some(
-                                tx_in_queue
+                            Some(replay_protection::get_replay_protection_key(
+                                tx_in_queue.tx.header.expiration,
+                                &tx_in_queue
                                     .tx
                                     .update_header(TxType::Raw)
                                     .header_hash(),
-                            ),
+                            )),
                             TxGasMeter::new_from_sub_limit(tx_in_queue.gas),
                             None,
                         )
@@ -417,22 +460,36 @@ where
                     },
                 };
 
-            match protocol::dispatch_tx(
-                tx,
-                processed_tx.tx.as_ref(),
-                TxIndex(
-                    tx_index
-                        .try_into()
-                        .expect("transaction index out of bounds"),
-                ),
-                &mut tx_gas_meter,
-                &mut self.wl_storage,
-                &mut self.vp_wasm_cache,
-                &mut self.tx_wasm_cache,
-                Some(&native_block_proposer_address),
-            )
-            .map_err(Error::TxApply)
-            {
+            let tx_index_in_block = TxIndex(
+                tx_index
+                    .try_into()
+                    .expect("transaction index out of bounds"),
+            );
+            let dispatch_result =
+                match (speculative_txs.as_mut(), &tx_header.tx_type) {
+                    (
+                        Some(speculative_txs),
+                        TxType::Decrypted(DecryptedTx::Decrypted),
+                    ) => speculative_txs.apply_tx(
+                        tx,
+                        tx_index_in_block,
+                        &mut tx_gas_meter,
+                        &mut self.wl_storage,
+                        &mut self.vp_wasm_cache,
+                        &mut self.tx_wasm_cache,
+                    ),
+                    _ => protocol::dispatch_tx(
+                        tx,
+                        processed_tx.tx.as_ref(),
+                        tx_index_in_block,
+                        &mut tx_gas_meter,
+                        &mut self.wl_storage,
+                        &mut self.vp_wasm_cache,
+                        &mut self.tx_wasm_cache,
+                        Some(&native_block_proposer_address),
+                    ),
+                };
+            match dispatch_result.map_err(Error::TxApply) {
                 Ok(result) => {
                     if result.is_accepted() {
                         if let EventType::Accepted = tx_event.event_type {
@@ -470,6 +527,13 @@ where
                             event["height"] = height.to_string();
                             response.events.push(event);
                         }
+                        for emitted in &result.events {
+                            // Add the tx emitted event besides the tx_event
+                            let mut event = Event::from(emitted.clone());
+                            event["hash"] = tx_event["hash"].clone();
+                            event["height"] = height.to_string();
+                            response.events.push(event);
+                        }
                         match serde_json::to_string(
                             &result.initialized_accounts,
                         ) {
@@ -511,14 +575,10 @@ where
                     // If transaction type is Decrypted and failed because of
                     // out of gas, remove its hash from storage to allow
                     // rewrapping it
-                    if let Some(hash) = tx_unsigned_hash {
+                    if let Some(tx_hash_key) = inner_hash_key {
                         if let Error::TxApply(protocol::Error::GasError(_)) =
                             msg
                         {
-                            let tx_hash_key =
-                                replay_protection::get_replay_protection_key(
-                                    &hash,
-                                );
                             self.wl_storage.delete(&tx_hash_key).expect(
                                 "Error while deleting tx hash key from storage",
                             );
@@ -538,6 +598,18 @@ where
             }
             response.events.push(tx_event);
         }
+        if let Some(speculative_txs) = speculative_txs {
+            tracing::debug!(
+                "{} speculatively executed txs had to be re-executed",
+                speculative_txs.reexecuted()
+            );
+        }
+
+        // Adjust the base fees for the next block to the gas used by this one
+        namada::ledger::parameters::update_base_fees(
+            &mut self.wl_storage,
+            block_gas_used,
+        )?;
 
         stats.set_tx_cache_size(
             self.tx_wasm_cache.get_size(),
@@ -563,12 +635,83 @@ where
             native_block_proposer_address,
         )?;
 
+        self.take_block_events(&mut response, height);
+        self.wl_storage.storage.block_events =
+            response.events.iter().map(IndexedEvent::from).collect();
         self.event_log_mut().log_events(response.events.clone());
         tracing::debug!("End finalize_block {height} of epoch {current_epoch}");
 
         Ok(response)
     }
 
+    /// Execute the decrypted txs of the block concurrently on top of the
+    /// current state. The results are applied in order in place of
+    /// [`protocol::dispatch_tx`] if they haven't been invalidated by the
+    /// preceding txs.
+    fn execute_speculative_txs(
+        &self,
+        txs: &[shim::request::ProcessedTx],
+    ) -> SpeculativeTxs {
+        // The decrypted txs pop the wrapper txs from the queue in the same
+        // order as in the main loop of `finalize_block`
+        let mut tx_queue = self.wl_storage.storage.tx_queue.iter();
+        let mut inputs = vec![];
+        for (tx_index, processed_tx) in txs.iter().enumerate() {
+            let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) else {
+                continue;
+            };
+            let code = ErrorCodes::from_u32(processed_tx.result.code).unwrap();
+            if code == ErrorCodes::InvalidSig || tx.validate_tx().is_err() {
+                continue;
+            }
+            if !matches!(tx.header().tx_type, TxType::Decrypted(_)) {
+                continue;
+            }
+            let Some(tx_in_queue) = tx_queue.next() else {
+                break;
+            };
+            if code == ErrorCodes::Ok
+                && matches!(
+                    tx.header().tx_type,
+                    TxType::Decrypted(DecryptedTx::Decrypted)
+                )
+            {
+                inputs.push(SpeculativeTxInput {
+                    tx_index: TxIndex(
+                        tx_index
+                            .try_into()
+                            .expect("transaction index out of bounds"),
+                    ),
+                    tx,
+                    tx_gas_meter: TxGasMeter::new_from_sub_limit(
+                        tx_in_queue.gas,
+                    ),
+                });
+            }
+        }
+        SpeculativeTxs::execute(
+            inputs,
+            &self.wl_storage,
+            &self.vp_wasm_cache,
+            &self.tx_wasm_cache,
+        )
+    }
+
+    /// Move the events emitted by the native logic outside of transactions,
+    /// e.g. the PoS rewards and slashes, from the write log into the response
+    fn take_block_events(
+        &mut self,
+        response: &mut shim::response::FinalizeBlock,
+        height: BlockHeight,
+    ) {
+        for event in self.wl_storage.write_log.take_events() {
+            let mut event = Event::from(event);
+            event.level = EventLevel::Block;
+            event["height"] = height.to_string();
+            response.events.push(event);
+        }
+    }
+
     /// Sets the metadata necessary for a new block, including
     /// the hash, height, validator changes, and evidence of
     /// byzantine behavior. Applies slashes if necessary.
@@ -602,6 +745,35 @@ where
         (height, new_epoch)
     }
 
+    /// Halt the chain at the height of a scheduled upgrade, unless the node
+    /// already runs the upgraded software, in which case the upgrade is
+    /// cleared from storage.
+    fn apply_scheduled_upgrade(&mut self, height: BlockHeight) -> Result<()> {
+        let plan = match gov_api::get_scheduled_upgrade(&self.wl_storage)? {
+            Some(plan) if plan.is_due(height) => plan,
+            _ => return Ok(()),
+        };
+        let mismatches = self.upgrade_mismatches(&plan);
+        if !mismatches.is_empty() {
+            tracing::error!(
+                "Reached the height {} of the scheduled upgrade {}, halting \
+                 the chain. Restart the node with the upgraded software, \
+                 mismatching checksums: {}.",
+                plan.height,
+                plan.name,
+                mismatches.join(", ")
+            );
+            return Err(Error::UpgradeHalt(plan.name, plan.height));
+        }
+        tracing::info!(
+            "Running the upgraded software of {} from height {}.",
+            plan.name,
+            height
+        );
+        gov_api::remove_scheduled_upgrade(&mut self.wl_storage)?;
+        Ok(())
+    }
+
     /// If a new epoch begins, we update the response to include
     /// changes to the validator sets and consensus parameters
     fn update_epoch(&mut self, response: &mut shim::response::FinalizeBlock) {
@@ -617,10 +789,9 @@ where
     /// with respect to the previous epoch.
     fn apply_inflation(&mut self, current_epoch: Epoch) -> Result<()> {
         let last_epoch = current_epoch.prev();
-        // Get input values needed for the PD controller for PoS and MASP.
-        // Run the PD controllers to calculate new rates.
-        //
-        // MASP is included below just for some completeness.
+        // Get input values needed for the PD controller for PoS and run it to
+        // calculate the new rate. The MASP rewards controllers are run when
+        // the allowed conversions are updated.
 
         let params = read_pos_params(&self.wl_storage)?;
 
@@ -652,15 +823,6 @@ where
         let pos_locked_ratio_target = params.target_staked_ratio;
         let pos_max_inflation_rate = params.max_inflation_rate;
 
-        // TODO: properly fetch these values (arbitrary for now)
-        let masp_locked_supply: Amount = Amount::default();
-        let masp_locked_ratio_target = Dec::new(5, 1).expect("Cannot fail");
-        let masp_locked_ratio_last = Dec::new(5, 1).expect("Cannot fail");
-        let masp_max_inflation_rate = Dec::new(2, 1).expect("Cannot fail");
-        let masp_last_inflation_rate = Dec::new(12, 2).expect("Cannot fail");
-        let masp_p_gain = Dec::new(1, 1).expect("Cannot fail");
-        let masp_d_gain = Dec::new(1, 1).expect("Cannot fail");
-
         // Run rewards PD controller
         let pos_controller = inflation::RewardsController {
             locked_tokens: pos_locked_supply,
@@ -673,26 +835,12 @@ where
             d_gain_nom: pos_d_gain_nom,
             epochs_per_year,
         };
-        let _masp_controller = inflation::RewardsController {
-            locked_tokens: masp_locked_supply,
-            total_tokens,
-            locked_ratio_target: masp_locked_ratio_target,
-            locked_ratio_last: masp_locked_ratio_last,
-            max_reward_rate: masp_max_inflation_rate,
-            last_inflation_amount: token::Amount::from(
-                masp_last_inflation_rate,
-            ),
-            p_gain_nom: masp_p_gain,
-            d_gain_nom: masp_d_gain,
-            epochs_per_year,
-        };
 
-        // Run the rewards controllers
+        // Run the rewards controller
         let inflation::ValsToUpdate {
             locked_ratio,
             inflation,
         } = pos_controller.run();
-        // let new_masp_vals = _masp_controller.run();
 
         // Get the number of blocks in the last epoch
         let first_block_of_last_epoch = self
@@ -849,32 +997,7 @@ where
             pgf_inflation_amount.to_string_native()
         );
 
-        let mut pgf_fundings = pgf::get_payments(&self.wl_storage)?;
-        // we want to pay first the oldest fundings
-        pgf_fundings.sort_by(|a, b| a.id.cmp(&b.id));
-
-        for funding in pgf_fundings {
-            if credit_tokens(
-                &mut self.wl_storage,
-                &staking_token,
-                &funding.detail.target,
-                funding.detail.amount,
-            )
-            .is_ok()
-            {
-                tracing::info!(
-                    "Minted {} tokens for {} project.",
-                    funding.detail.amount.to_string_native(),
-                    &funding.detail.target,
-                );
-            } else {
-                tracing::warn!(
-                    "Failed Minting {} tokens for {} project.",
-                    funding.detail.amount.to_string_native(),
-                    &funding.detail.target,
-                );
-            }
-        }
+        self.pay_pgf_fundings(current_epoch, &staking_token)?;
 
         // Pgf steward inflation
         let stewards = pgf::get_stewards(&self.wl_storage)?;
@@ -919,6 +1042,64 @@ where
         Ok(())
     }
 
+    /// Pay out the pgf funding streams that are active in the current epoch,
+    /// recording the payouts and removing the streams that have ended.
+    fn pay_pgf_fundings(
+        &mut self,
+        current_epoch: Epoch,
+        staking_token: &Address,
+    ) -> Result<()> {
+        let mut pgf_fundings = pgf::get_payments(&self.wl_storage)?;
+        // we want to pay first the oldest fundings
+        pgf_fundings.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for funding in pgf_fundings {
+            if funding.is_exhausted(current_epoch) {
+                pgf::remove_funding(
+                    &mut self.wl_storage,
+                    &funding.detail.target,
+                )?;
+                tracing::info!(
+                    "Pgf funding stream to {} has ended.",
+                    &funding.detail.target,
+                );
+                continue;
+            }
+            let amount = match funding.payout(current_epoch) {
+                Some(amount) => amount,
+                None => continue,
+            };
+            if credit_tokens(
+                &mut self.wl_storage,
+                staking_token,
+                &funding.detail.target,
+                amount,
+            )
+            .is_ok()
+            {
+                tracing::info!(
+                    "Minted {} tokens for {} project.",
+                    amount.to_string_native(),
+                    &funding.detail.target,
+                );
+                pgf::record_payout(
+                    &mut self.wl_storage,
+                    funding,
+                    current_epoch,
+                    amount,
+                )?;
+            } else {
+                tracing::warn!(
+                    "Failed Minting {} tokens for {} project.",
+                    amount.to_string_native(),
+                    &funding.detail.target,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     // Process the proposer and votes in the block to assign their PoS rewards.
     fn log_block_rewards(
         &mut self,
@@ -1040,8 +1221,18 @@ mod test_finalize_block {
 
     use data_encoding::HEXUPPER;
     use namada::core::ledger::eth_bridge::storage::wrapped_erc20s;
-    use namada::core::ledger::governance::storage::keys::get_proposal_execution_key;
-    use namada::core::ledger::governance::storage::proposal::ProposalType;
+    use namada::core::ledger::pgf::storage::keys as pgf_storage;
+    use namada::core::ledger::governance::storage::keys::{
+        self as gov_storage, get_proposal_execution_key,
+    };
+    use namada::core::ledger::governance::storage::parameter_change::{
+        GovernanceParamChange, ParameterChange, PgfParamChange,
+        PosParamChange, ProtocolParamChange,
+    };
+    use namada::core::ledger::governance::storage::proposal::{
+        PGFStream, ProposalType, StoragePgfFunding, TreasuryTransfer,
+    };
+    use namada::core::ledger::governance::storage::upgrade::UpgradePlan;
     use namada::core::ledger::governance::storage::vote::{
         StorageProposalVote, VoteType,
     };
@@ -1087,6 +1278,7 @@ mod test_finalize_block {
     use namada::types::transaction::{Fee, WrapperTx};
     use namada::types::uint::Uint;
     use namada::types::vote_extensions::ethereum_events;
+    use namada_test_utils::tx_data::TxWriteData;
     use namada_test_utils::TestWasms;
     use test_log::test;
 
@@ -1148,7 +1340,22 @@ mod test_finalize_block {
         shell: &mut TestShell,
         keypair: &common::SecretKey,
     ) -> ProcessedTx {
-        let tx_code = TestWasms::TxNoOp.read_bytes();
+        mk_decrypted_tx_with(
+            shell,
+            keypair,
+            TestWasms::TxNoOp.read_bytes(),
+            "Decrypted transaction data".as_bytes().to_owned(),
+        )
+    }
+
+    /// Make a decrypted tx with the given code and data, enqueueing its
+    /// wrapper tx
+    fn mk_decrypted_tx_with(
+        shell: &mut TestShell,
+        keypair: &common::SecretKey,
+        tx_code: Vec<u8>,
+        tx_data: Vec<u8>,
+    ) -> ProcessedTx {
         let mut outer_tx =
             Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
                 Fee {
@@ -1162,9 +1369,7 @@ mod test_finalize_block {
             ))));
         outer_tx.header.chain_id = shell.chain_id.clone();
         outer_tx.set_code(Code::new(tx_code));
-        outer_tx.set_data(Data::new(
-            "Decrypted transaction data".as_bytes().to_owned(),
-        ));
+        outer_tx.set_data(Data::new(tx_data));
         let gas_limit =
             Gas::from(outer_tx.header().wrapper().unwrap().gas_limit)
                 .checked_sub(Gas::from(outer_tx.to_bytes().len() as u64))
@@ -1448,6 +1653,64 @@ mod test_finalize_block {
         assert_eq!(counter, 2);
     }
 
+    /// Check that the decrypted txs executed in parallel have the same
+    /// results as when executed sequentially, including the ones which
+    /// conflict with a preceding tx
+    #[test]
+    fn test_parallel_tx_execution() {
+        let keypair = gen_keypair();
+        let key_a = Key::parse("parallel/a").unwrap();
+        let key_b = Key::parse("parallel/b").unwrap();
+        let writes = [
+            (key_a.clone(), "a1"),
+            // conflicts with the previous tx
+            (key_a.clone(), "a2"),
+            (key_b.clone(), "b1"),
+        ];
+
+        let mut results = vec![];
+        for parallel_tx_execution in [false, true] {
+            let (mut shell, _, _, _) = setup();
+            shell.parallel_tx_execution = parallel_tx_execution;
+            let processed_txs = writes
+                .iter()
+                .map(|(key, value)| {
+                    let tx_code = TestWasms::TxWriteStorageKey.read_bytes();
+                    let data = TxWriteData {
+                        key: key.clone(),
+                        value: value.to_string().try_to_vec().unwrap(),
+                    };
+                    mk_decrypted_tx_with(
+                        &mut shell,
+                        &keypair,
+                        tx_code,
+                        data.try_to_vec().unwrap(),
+                    )
+                })
+                .collect();
+            let events: Vec<_> = shell
+                .finalize_block(FinalizeBlock {
+                    txs: processed_txs,
+                    ..Default::default()
+                })
+                .expect("Test failed")
+                .iter()
+                .map(|event| {
+                    (
+                        event.attributes.get("code").cloned(),
+                        event.attributes.get("gas_used").cloned(),
+                    )
+                })
+                .collect();
+            let a: Option<String> = shell.wl_storage.read(&key_a).unwrap();
+            let b: Option<String> = shell.wl_storage.read(&key_b).unwrap();
+            assert_eq!(a.as_deref(), Some("a2"));
+            assert_eq!(b.as_deref(), Some("b1"));
+            results.push(events);
+        }
+        assert_eq!(results[0], results[1]);
+    }
+
     /// Test if a rejected protocol tx is applied and emits
     /// the correct event
     #[test]
@@ -1847,6 +2110,7 @@ mod test_finalize_block {
                 voting_end_epoch: Epoch::default().next(),
                 grace_epoch: Epoch::default().next(),
                 r#type: ProposalType::Default(None),
+                is_expedited: false,
             };
 
             storage_api::governance::init_proposal(
@@ -1966,6 +2230,528 @@ mod test_finalize_block {
         }
     }
 
+    /// Init a proposal of the validator to be executed at the end of the next
+    /// epoch and cast the validator vote on it, if any
+    fn add_proposal(
+        shell: &mut TestShell,
+        proposal_id: u64,
+        r#type: ProposalType,
+        is_expedited: bool,
+        vote: Option<StorageProposalVote>,
+    ) {
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        shell.proposal_data.insert(proposal_id);
+
+        let proposal = InitProposalData {
+            id: Some(proposal_id),
+            content: Hash::default(),
+            author: validator.clone(),
+            voting_start_epoch: Epoch::default(),
+            voting_end_epoch: Epoch::default().next(),
+            grace_epoch: Epoch::default().next(),
+            r#type,
+            is_expedited,
+        };
+        storage_api::governance::init_proposal(
+            &mut shell.wl_storage,
+            proposal,
+            vec![],
+            None,
+        )
+        .unwrap();
+
+        if let Some(vote) = vote {
+            let vote = VoteProposalData {
+                id: proposal_id,
+                vote,
+                voter: validator,
+                delegations: vec![],
+            };
+            storage_api::governance::vote_proposal(&mut shell.wl_storage, vote)
+                .unwrap();
+        }
+    }
+
+    /// Test that a passed parameter change proposal is applied natively and
+    /// that none of the changes of a proposal is applied if they result in
+    /// invalid parameters.
+    #[test]
+    fn test_parameter_change_proposal() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let governance_params =
+            storage_api::governance::get_parameters(&shell.wl_storage).unwrap();
+        // Fund the deposits of the two proposals
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &validator,
+            governance_params.min_proposal_fund * 2,
+        )
+        .unwrap();
+
+        let rate = Dec::new(5, 2).unwrap();
+        add_proposal(
+            &mut shell,
+            0,
+            ProposalType::ParameterChange(vec![
+                ParameterChange::Protocol(ProtocolParamChange::MaxBlockGas(
+                    1_000,
+                )),
+                ParameterChange::Pos(PosParamChange::MaxInflationRate(rate)),
+                ParameterChange::Governance(
+                    GovernanceParamChange::MinProposalGraceEpochs(
+                        governance_params.min_proposal_grace_epochs + 1,
+                    ),
+                ),
+                ParameterChange::Pgf(PgfParamChange::PgfInflationRate(rate)),
+            ]),
+            false,
+            Some(StorageProposalVote::Yay(VoteType::ParameterChange)),
+        );
+        // The voting period can't be longer than the whole proposal period
+        add_proposal(
+            &mut shell,
+            1,
+            ProposalType::ParameterChange(vec![
+                ParameterChange::Protocol(ProtocolParamChange::EpochsPerYear(
+                    1,
+                )),
+                ParameterChange::Governance(
+                    GovernanceParamChange::MinProposalVotingPeriod(
+                        governance_params.max_proposal_period + 1,
+                    ),
+                ),
+            ]),
+            false,
+            Some(StorageProposalVote::Yay(VoteType::ParameterChange)),
+        );
+
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell.shell, &mut response).unwrap();
+
+        let params =
+            namada::ledger::parameters::read(&shell.wl_storage).unwrap();
+        assert_eq!(params.max_block_gas, 1_000);
+        assert_ne!(params.epochs_per_year, 1);
+        let pos_params = read_pos_params(&shell.wl_storage).unwrap();
+        assert_eq!(pos_params.max_inflation_rate, rate);
+        let new_governance_params =
+            storage_api::governance::get_parameters(&shell.wl_storage).unwrap();
+        assert_eq!(
+            new_governance_params.min_proposal_grace_epochs,
+            governance_params.min_proposal_grace_epochs + 1
+        );
+        assert_eq!(
+            new_governance_params.min_proposal_voting_period,
+            governance_params.min_proposal_voting_period
+        );
+        let pgf_params = pgf::get_parameters(&shell.wl_storage).unwrap();
+        assert_eq!(pgf_params.pgf_inflation_rate, rate);
+    }
+
+    /// Test that a passed treasury proposal transfers tokens out of the
+    /// treasury only if it can cover all of its transfers, without spending
+    /// the locked proposal deposits.
+    #[test]
+    fn test_treasury_proposal() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let governance_params =
+            storage_api::governance::get_parameters(&shell.wl_storage).unwrap();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        let gov_address = namada::core::ledger::governance::ADDRESS;
+        let target = address::testing::established_address_1();
+        // Fund the deposits of the two proposals and the treasury
+        credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &validator,
+            governance_params.min_proposal_fund * 2,
+        )
+        .unwrap();
+        credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &gov_address,
+            Amount::native_whole(100),
+        )
+        .unwrap();
+        credit_tokens(
+            &mut shell.wl_storage,
+            &address::btc(),
+            &gov_address,
+            Amount::native_whole(50),
+        )
+        .unwrap();
+
+        add_proposal(
+            &mut shell,
+            0,
+            ProposalType::Treasury(vec![
+                TreasuryTransfer {
+                    token: native_token.clone(),
+                    target: target.clone(),
+                    amount: Amount::native_whole(60),
+                },
+                TreasuryTransfer {
+                    token: address::btc(),
+                    target: target.clone(),
+                    amount: Amount::native_whole(50),
+                },
+            ]),
+            false,
+            Some(StorageProposalVote::Yay(VoteType::Treasury)),
+        );
+        // The proposal deposits can't be spent
+        add_proposal(
+            &mut shell,
+            1,
+            ProposalType::Treasury(vec![TreasuryTransfer {
+                token: native_token.clone(),
+                target: target.clone(),
+                amount: Amount::native_whole(101),
+            }]),
+            false,
+            Some(StorageProposalVote::Yay(VoteType::Treasury)),
+        );
+        assert_eq!(
+            storage_api::governance::get_treasury_balance(
+                &shell.wl_storage,
+                &native_token
+            )
+            .unwrap(),
+            Amount::native_whole(100)
+        );
+
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell.shell, &mut response).unwrap();
+
+        let read_balance = |token: &Address, owner: &Address| {
+            storage_api::token::read_balance(&shell.wl_storage, token, owner)
+                .unwrap()
+        };
+        assert_eq!(
+            read_balance(&native_token, &target),
+            Amount::native_whole(60)
+        );
+        assert_eq!(
+            read_balance(&address::btc(), &target),
+            Amount::native_whole(50)
+        );
+        // The deposits have been returned
+        assert_eq!(
+            read_balance(&native_token, &gov_address),
+            Amount::native_whole(40)
+        );
+        assert_eq!(
+            storage_api::governance::get_locked_deposits(&shell.wl_storage)
+                .unwrap(),
+            Amount::zero()
+        );
+    }
+
+    /// Test that the locked deposits of a chain with proposals submitted before
+    /// they were tracked are initialized from the pending proposals, so that
+    /// these can be settled.
+    #[test]
+    fn test_init_locked_deposits() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let governance_params =
+            storage_api::governance::get_parameters(&shell.wl_storage).unwrap();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &validator,
+            governance_params.min_proposal_fund * 2,
+        )
+        .unwrap();
+        add_proposal(&mut shell, 0, ProposalType::Default(None), false, None);
+        add_proposal(
+            &mut shell,
+            1,
+            ProposalType::Default(None),
+            false,
+            Some(StorageProposalVote::Yay(VoteType::Default)),
+        );
+
+        // The proposal 0 has already been settled and the locked deposits
+        // are not tracked yet
+        shell
+            .wl_storage
+            .write(&gov_storage::get_grace_epoch_key(0), Epoch(0))
+            .unwrap();
+        shell
+            .wl_storage
+            .delete(&gov_storage::get_locked_deposits_key())
+            .unwrap();
+        shell.proposal_data.remove(&0);
+        shell.wl_storage.storage.last_epoch = Epoch(1);
+
+        storage_api::governance::init_locked_deposits(
+            &mut shell.wl_storage,
+            shell.wl_storage.storage.last_epoch,
+        )
+        .unwrap();
+        assert_eq!(
+            storage_api::governance::get_locked_deposits(&shell.wl_storage)
+                .unwrap(),
+            governance_params.min_proposal_fund
+        );
+
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell.shell, &mut response).unwrap();
+        assert_eq!(
+            storage_api::governance::get_locked_deposits(&shell.wl_storage)
+                .unwrap(),
+            Amount::zero()
+        );
+    }
+
+    /// Test that the deposit of a rejected proposal is only returned to its
+    /// author when the proposal missed quorum without being vetoed.
+    #[test]
+    fn test_rejected_proposal_deposit() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let governance_params =
+            storage_api::governance::get_parameters(&shell.wl_storage).unwrap();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        let initial_balance = storage_api::token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &validator,
+        )
+        .unwrap();
+        // Fund the deposits of the three proposals
+        credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &validator,
+            governance_params.min_proposal_fund * 3,
+        )
+        .unwrap();
+
+        // No votes, the proposal misses quorum and the deposit is returned
+        add_proposal(&mut shell, 0, ProposalType::Default(None), false, None);
+        // The only validator vetoes, the deposit is burned
+        add_proposal(
+            &mut shell,
+            1,
+            ProposalType::Default(None),
+            false,
+            Some(StorageProposalVote::NayWithVeto),
+        );
+        // The only validator votes nay, the deposit is burned
+        add_proposal(
+            &mut shell,
+            2,
+            ProposalType::Default(None),
+            false,
+            Some(StorageProposalVote::Nay),
+        );
+
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell.shell, &mut response).unwrap();
+
+        let balance = storage_api::token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &validator,
+        )
+        .unwrap();
+        assert_eq!(
+            balance,
+            initial_balance + governance_params.min_proposal_fund
+        );
+    }
+
+    /// Test that an expedited proposal failing its tally without being vetoed
+    /// becomes a regular proposal with a new voting period.
+    #[test]
+    fn test_expedited_proposal_fallback() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let governance_params =
+            storage_api::governance::get_parameters(&shell.wl_storage).unwrap();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &validator,
+            governance_params.min_proposal_fund * 2,
+        )
+        .unwrap();
+        let read_governance_balance = |shell: &TestShell| {
+            storage_api::token::read_balance(
+                &shell.wl_storage,
+                &native_token,
+                &namada::core::ledger::governance::ADDRESS,
+            )
+            .unwrap()
+        };
+        let initial_governance_balance = read_governance_balance(&shell);
+
+        add_proposal(
+            &mut shell,
+            0,
+            ProposalType::Default(None),
+            true,
+            Some(StorageProposalVote::Yay(VoteType::Default)),
+        );
+        add_proposal(
+            &mut shell,
+            1,
+            ProposalType::Default(None),
+            true,
+            Some(StorageProposalVote::Nay),
+        );
+
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell.shell, &mut response).unwrap();
+
+        // The rejected proposal is now a regular proposal with a new voting
+        // period
+        let proposal =
+            storage_api::governance::get_proposal_by_id(&shell.wl_storage, 1)
+                .unwrap()
+                .unwrap();
+        let current_epoch = shell.wl_storage.storage.get_current_epoch().0;
+        assert!(!proposal.is_expedited);
+        assert_eq!(
+            proposal.voting_end_epoch,
+            current_epoch + governance_params.min_proposal_voting_period
+        );
+        assert_eq!(
+            proposal.grace_epoch,
+            proposal.voting_end_epoch
+                + governance_params.min_proposal_grace_epochs
+        );
+        // Only the deposit of the passed proposal was returned
+        assert_eq!(
+            read_governance_balance(&shell),
+            initial_governance_balance + governance_params.min_proposal_fund
+        );
+    }
+
+    /// Test that the governance parameters can still be read on a chain
+    /// started without the expedited voting period parameter
+    #[test]
+    fn test_missing_expedited_voting_period() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let key = gov_storage::get_min_expedited_proposal_voting_period_key();
+        shell.wl_storage.delete(&key).unwrap();
+
+        let governance_params =
+            storage_api::governance::get_parameters(&shell.wl_storage).unwrap();
+        assert_eq!(
+            governance_params.min_expedited_proposal_voting_period,
+            governance_params.min_proposal_voting_period
+        );
+    }
+
+    /// Test that a pgf funding stream only pays out between its start and end
+    /// epochs, within its budget, and that its payouts are recorded.
+    #[test]
+    fn test_pgf_funding_stream() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        let target = address::testing::established_address_1();
+
+        let stream = PGFStream {
+            target: target.clone(),
+            amount: Amount::native_whole(10),
+            start_epoch: Epoch(1),
+            end_epoch: Epoch(4),
+            budget: Amount::native_whole(25),
+        };
+        pgf_storage::fundings_handle()
+            .insert(
+                &mut shell.wl_storage,
+                target.clone(),
+                StoragePgfFunding::new(stream, 0),
+            )
+            .unwrap();
+
+        for epoch in 0..=5 {
+            shell.pay_pgf_fundings(Epoch(epoch), &native_token).unwrap();
+        }
+
+        // The last payout is capped by the remaining budget
+        let payouts = pgf::get_payouts(&shell.wl_storage, &target).unwrap();
+        assert_eq!(
+            payouts,
+            BTreeMap::from([
+                (Epoch(1), Amount::native_whole(10)),
+                (Epoch(2), Amount::native_whole(10)),
+                (Epoch(3), Amount::native_whole(5)),
+            ])
+        );
+        let balance = storage_api::token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &target,
+        )
+        .unwrap();
+        assert_eq!(balance, Amount::native_whole(25));
+        // The exhausted stream was removed
+        assert!(pgf::get_payments(&shell.wl_storage).unwrap().is_empty());
+    }
+
+    /// Test that the chain halts at the height of a scheduled upgrade, unless
+    /// the node runs the upgraded software.
+    #[test]
+    fn test_scheduled_upgrade() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let plan = UpgradePlan {
+            name: "v2".to_string(),
+            height: shell.wl_storage.storage.get_last_block_height() + 1,
+            binary_checksums: BTreeMap::from([(
+                NODE_TARGET.to_string(),
+                Hash::sha256(b"upgraded binary"),
+            )]),
+            wasm_checksums: BTreeMap::new(),
+        };
+        storage_api::governance::schedule_upgrade(
+            &mut shell.wl_storage,
+            plan.clone(),
+        )
+        .unwrap();
+        // A second upgrade can't replace the scheduled one
+        let other_plan = UpgradePlan {
+            name: "v3".to_string(),
+            ..plan.clone()
+        };
+        assert!(
+            storage_api::governance::schedule_upgrade(
+                &mut shell.wl_storage,
+                other_plan
+            )
+            .is_err()
+        );
+
+        let result = shell.finalize_block(FinalizeBlock::default());
+        assert!(matches!(
+            result,
+            Err(Error::UpgradeHalt(name, height))
+                if name == plan.name && height == plan.height
+        ));
+
+        // Simulate a restart with the upgraded binary
+        shell.binary_checksum = Some(plan.binary_checksums[NODE_TARGET]);
+        shell
+            .finalize_block(FinalizeBlock::default())
+            .expect("Test failed");
+        assert!(
+            storage_api::governance::get_scheduled_upgrade(&shell.wl_storage)
+                .unwrap()
+                .is_none()
+        );
+    }
+
     /// A unit test for PoS inflationary rewards
     #[test]
     fn test_inflation_accounting() {
@@ -2270,12 +3056,14 @@ mod test_finalize_block {
         let (wrapper_tx, processed_tx) =
             mk_wrapper_tx(&shell, &crate::wallet::defaults::albert_keypair());
         let wrapper_hash_key = replay_protection::get_replay_protection_key(
+            wrapper_tx.header.expiration,
             &wrapper_tx.header_hash(),
         );
         let mut decrypted_tx = wrapper_tx;
 
         decrypted_tx.update_header(TxType::Raw);
         let decrypted_hash_key = replay_protection::get_replay_protection_key(
+            decrypted_tx.header.expiration,
             &decrypted_tx.header_hash(),
         );
 
@@ -2362,6 +3150,7 @@ mod test_finalize_block {
 
         // Write inner hash in storage
         let inner_hash_key = replay_protection::get_replay_protection_key(
+            wrapper_tx.header.expiration,
             &wrapper_tx.clone().update_header(TxType::Raw).header_hash(),
         );
         shell
@@ -2436,9 +3225,11 @@ mod test_finalize_block {
         )));
 
         let wrapper_hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
             &wrapper.header_hash(),
         );
         let inner_hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
             &wrapper.clone().update_header(TxType::Raw).header_hash(),
         );
 
@@ -2540,7 +3331,8 @@ mod test_finalize_block {
     }
 
     // Test that the fees collected from a block are withdrew from the wrapper
-    // signer and credited to the block proposer
+    // signer, that the base fee is burned and that the rest is credited to the
+    // block proposer
     #[test]
     fn test_fee_payment_to_block_proposer() {
         let (mut shell, _, _, _) = setup();
@@ -2568,10 +3360,23 @@ mod test_finalize_block {
         wasm_path.push("wasm_for_tests/tx_no_op.wasm");
         let tx_code = std::fs::read(wasm_path)
             .expect("Expected a file at given code path");
+        let base_fee = namada::ledger::parameters::read_base_fee(
+            &shell.wl_storage,
+            &shell.wl_storage.storage.native_token,
+        )
+        .unwrap()
+        .unwrap();
+        let total_supply = storage_api::token::read_total_supply(
+            &shell.wl_storage,
+            &shell.wl_storage.storage.native_token,
+        )
+        .unwrap();
         let mut wrapper =
             Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
                 Fee {
-                    amount_per_gas_unit: 1.into(),
+                    amount_per_gas_unit: base_fee
+                        .checked_add(2.into())
+                        .unwrap(),
                     token: shell.wl_storage.storage.native_token.clone(),
                 },
                 crate::wallet::defaults::albert_keypair().ref_to(),
@@ -2593,6 +3398,9 @@ mod test_finalize_block {
         )));
         let fee_amount =
             wrapper.header().wrapper().unwrap().get_tx_fee().unwrap();
+        let burned_amount =
+            base_fee * u64::from(wrapper.header().wrapper().unwrap().gas_limit);
+        let tip_amount = fee_amount.checked_sub(burned_amount).unwrap();
 
         let signer_balance = storage_api::token::read_balance(
             &shell.wl_storage,
@@ -2630,7 +3438,16 @@ mod test_finalize_block {
         .unwrap();
         assert_eq!(
             new_proposer_balance,
-            proposer_balance.checked_add(fee_amount).unwrap()
+            proposer_balance.checked_add(tip_amount).unwrap()
+        );
+        let new_total_supply = storage_api::token::read_total_supply(
+            &shell.wl_storage,
+            &shell.wl_storage.storage.native_token,
+        )
+        .unwrap();
+        assert_eq!(
+            new_total_supply,
+            total_supply.checked_sub(burned_amount).unwrap()
         );
 
         let new_signer_balance = storage_api::token::read_balance(
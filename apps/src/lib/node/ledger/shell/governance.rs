@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
 use namada::core::ledger::governance::storage::keys as gov_storage;
+use namada::core::ledger::governance::storage::parameter_change::ParameterChange;
 use namada::core::ledger::governance::storage::proposal::{
-    AddRemove, PGFAction, ProposalType, StoragePgfFunding,
+    AddRemove, PGFAction, ProposalType, StoragePgfFunding, TreasuryTransfer,
 };
+use namada::core::ledger::governance::storage::upgrade::UpgradePlan;
 use namada::core::ledger::governance::utils::{
     compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
     VotePower,
@@ -20,10 +22,12 @@ use namada::ledger::storage::types::encode;
 use namada::ledger::storage::{DBIter, StorageHasher, DB};
 use namada::ledger::storage_api::{pgf, token, StorageWrite};
 use namada::proof_of_stake::parameters::PosParams;
-use namada::proof_of_stake::{bond_amount, read_total_stake};
+use namada::proof_of_stake::{
+    bond_amount, read_total_stake, write_pos_params,
+};
 use namada::proto::{Code, Data};
 use namada::types::address::Address;
-use namada::types::storage::Epoch;
+use namada::types::storage::{BlockHeight, Epoch};
 
 use super::utils::force_read;
 use super::*;
@@ -60,12 +64,15 @@ where
             force_read(&shell.wl_storage, &proposal_author_key)?;
 
         let is_steward = pgf::is_steward(&shell.wl_storage, &proposal_author)?;
+        let is_expedited =
+            gov_api::is_proposal_expedited(&shell.wl_storage, id)?;
 
         let params = read_pos_params(&shell.wl_storage)?;
         let total_voting_power =
             read_total_stake(&shell.wl_storage, &params, proposal_end_epoch)?;
 
-        let tally_type = TallyType::from(proposal_type.clone(), is_steward);
+        let tally_type =
+            TallyType::from(proposal_type.clone(), is_steward, is_expedited);
         let votes = compute_proposal_votes(
             &shell.wl_storage,
             &params,
@@ -75,6 +82,24 @@ where
         let proposal_result =
             compute_proposal_result(votes, total_voting_power, tally_type);
 
+        // An expedited proposal that fails its tally without being vetoed
+        // gets a new voting period as a regular proposal, keeping its votes
+        // and deposit
+        if is_expedited
+            && matches!(proposal_result.result, TallyResult::Rejected)
+            && !proposal_result.is_vetoed()
+        {
+            let grace_epoch =
+                convert_expedited_proposal(&mut shell.wl_storage, id)?;
+            tracing::info!(
+                "Expedited governance proposal {} failed its tally and will \
+                 be tallied again as a regular proposal at epoch {}.",
+                id,
+                grace_epoch
+            );
+            continue;
+        }
+
         let transfer_address = match proposal_result.result {
             TallyResult::Passed => {
                 let proposal_event = match proposal_type {
@@ -134,6 +159,58 @@ where
                         ProposalEvent::pgf_payments_proposal_event(id, result)
                             .into()
                     }
+                    ProposalType::ParameterChange(changes) => {
+                        let result = execute_parameter_change_proposal(
+                            &mut shell.wl_storage,
+                            changes,
+                            id,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal (parameter change) {} has \
+                             been executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::parameter_change_proposal_event(
+                            id, result,
+                        )
+                        .into()
+                    }
+                    ProposalType::Upgrade(plan) => {
+                        let current_height =
+                            shell.wl_storage.storage.block.height;
+                        let result = execute_upgrade_proposal(
+                            &mut shell.wl_storage,
+                            plan,
+                            current_height,
+                            id,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal (upgrade) {} has been \
+                             executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::upgrade_proposal_event(id, result).into()
+                    }
+                    ProposalType::Treasury(transfers) => {
+                        let result = execute_treasury_proposal(
+                            &mut shell.wl_storage,
+                            transfers,
+                            id,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal (treasury spending) {} has \
+                             been executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::treasury_proposal_event(id, result)
+                            .into()
+                    }
                 };
                 response.events.push(proposal_event);
                 proposals_result.passed.push(id);
@@ -168,7 +245,26 @@ where
                     id
                 );
 
-                None
+                // The deposit is only returned when the proposal failed to
+                // reach quorum without being vetoed
+                if proposal_result.is_vetoed() {
+                    tracing::info!(
+                        "Governance proposal {} was vetoed, burning its \
+                         deposit.",
+                        id
+                    );
+                    None
+                } else if !proposal_result.has_quorum() {
+                    tracing::info!(
+                        "Governance proposal {} did not reach quorum, \
+                         returning its deposit to {}.",
+                        id,
+                        proposal_author
+                    );
+                    Some(proposal_author)
+                } else {
+                    None
+                }
             }
         };
 
@@ -189,6 +285,8 @@ where
                 funds,
             )?;
         }
+        // The deposit is settled and no longer locked in the treasury
+        gov_api::unlock_deposit(&mut shell.wl_storage, funds)?;
     }
 
     Ok(proposals_result)
@@ -346,33 +444,32 @@ where
 {
     for payment in payments {
         match payment {
-            PGFAction::Continuous(action) => match action {
-                AddRemove::Add(target) => {
-                    pgf_storage::fundings_handle().insert(
-                        storage,
-                        target.target.clone(),
-                        StoragePgfFunding::new(target.clone(), proposal_id),
-                    )?;
-                    tracing::info!(
-                        "Execute ContinousPgf from proposal id {}: set {} to \
-                         {}.",
-                        proposal_id,
-                        target.amount.to_string_native(),
-                        target.target
-                    );
-                }
-                AddRemove::Remove(target) => {
-                    pgf_storage::fundings_handle()
-                        .remove(storage, &target.target)?;
-                    tracing::info!(
-                        "Execute ContinousPgf from proposal id {}: set {} to \
-                         {}.",
-                        proposal_id,
-                        target.amount.to_string_native(),
-                        target.target
-                    );
-                }
-            },
+            PGFAction::Continuous(stream) => {
+                pgf_storage::fundings_handle().insert(
+                    storage,
+                    stream.target.clone(),
+                    StoragePgfFunding::new(stream.clone(), proposal_id),
+                )?;
+                tracing::info!(
+                    "Execute ContinousPgf from proposal id {}: set {} per \
+                     epoch to {} from epoch {} to {} with a budget of {}.",
+                    proposal_id,
+                    stream.amount.to_string_native(),
+                    stream.target,
+                    stream.start_epoch,
+                    stream.end_epoch,
+                    stream.budget.to_string_native()
+                );
+            }
+            PGFAction::Cancel(target) => {
+                pgf::remove_funding(storage, &target)?;
+                tracing::info!(
+                    "Execute ContinousPgf from proposal id {}: cancelled the \
+                     stream to {}.",
+                    proposal_id,
+                    target
+                );
+            }
             PGFAction::Retro(target) => {
                 token::transfer(
                     storage,
@@ -393,3 +490,207 @@ where
 
     Ok(true)
 }
+
+/// Execute the transfers of a treasury spending proposal. None of the
+/// transfers is executed if the treasury can't cover all of them.
+fn execute_treasury_proposal<S>(
+    storage: &mut S,
+    transfers: Vec<TreasuryTransfer>,
+    proposal_id: u64,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    let spendings = match TreasuryTransfer::total_per_token(&transfers) {
+        Some(spendings) => spendings,
+        None => {
+            tracing::info!(
+                "Treasury proposal {} can't be executed: the requested \
+                 amounts overflow.",
+                proposal_id
+            );
+            return Ok(false);
+        }
+    };
+    for (token, amount) in spendings {
+        let balance = gov_api::get_treasury_balance(storage, token)?;
+        if balance < amount {
+            tracing::info!(
+                "Treasury proposal {} can't be executed: the treasury holds \
+                 {} of token {} but {} is requested.",
+                proposal_id,
+                balance.to_string_native(),
+                token,
+                amount.to_string_native()
+            );
+            return Ok(false);
+        }
+    }
+
+    for transfer in transfers {
+        token::transfer(
+            storage,
+            &transfer.token,
+            &gov_address,
+            &transfer.target,
+            transfer.amount,
+        )?;
+        tracing::info!(
+            "Execute treasury proposal {}: sent {} of token {} to {}.",
+            proposal_id,
+            transfer.amount.to_string_native(),
+            transfer.token,
+            transfer.target
+        );
+    }
+
+    Ok(true)
+}
+
+/// Turn an expedited proposal into a regular one, extending its voting period
+/// by the regular minimum voting period from the current epoch. Returns the
+/// new grace epoch of the proposal.
+fn convert_expedited_proposal<S>(
+    storage: &mut S,
+    proposal_id: u64,
+) -> Result<Epoch>
+where
+    S: StorageRead + StorageWrite,
+{
+    let governance_params = gov_api::get_parameters(storage)?;
+    let current_epoch = storage.get_block_epoch()?;
+    let voting_end_epoch =
+        current_epoch + governance_params.min_proposal_voting_period;
+    let grace_epoch =
+        voting_end_epoch + governance_params.min_proposal_grace_epochs;
+
+    storage.delete(&gov_storage::get_proposal_expedited_key(proposal_id))?;
+    storage.write(
+        &gov_storage::get_voting_end_epoch_key(proposal_id),
+        voting_end_epoch,
+    )?;
+    storage
+        .write(&gov_storage::get_grace_epoch_key(proposal_id), grace_epoch)?;
+    storage.write(
+        &gov_storage::get_committing_proposals_key(proposal_id, grace_epoch.0),
+        (),
+    )?;
+
+    Ok(grace_epoch)
+}
+
+/// Schedule the software upgrade of an upgrade proposal. The upgrade is not
+/// scheduled if its height has already been reached.
+fn execute_upgrade_proposal<S>(
+    storage: &mut S,
+    plan: UpgradePlan,
+    current_height: BlockHeight,
+    proposal_id: u64,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !plan.is_valid() || plan.is_due(current_height) {
+        tracing::info!(
+            "Upgrade proposal {} can't schedule upgrade {} at height {}, the \
+             current height is {}.",
+            proposal_id,
+            plan.name,
+            plan.height,
+            current_height
+        );
+        return Ok(false);
+    }
+    if let Some(scheduled) = gov_api::get_scheduled_upgrade(storage)? {
+        tracing::info!(
+            "Upgrade proposal {} can't schedule upgrade {}, upgrade {} is \
+             already scheduled at height {}.",
+            proposal_id,
+            plan.name,
+            scheduled.name,
+            scheduled.height
+        );
+        return Ok(false);
+    }
+
+    tracing::info!(
+        "Execute upgrade proposal {}: scheduled upgrade {} at height {}.",
+        proposal_id,
+        plan.name,
+        plan.height
+    );
+    gov_api::schedule_upgrade(storage, plan)?;
+
+    Ok(true)
+}
+
+/// Apply the changes of a parameter change proposal. None of the changes is
+/// applied if the resulting parameters are not valid.
+fn execute_parameter_change_proposal<S>(
+    storage: &mut S,
+    changes: Vec<ParameterChange>,
+    proposal_id: u64,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    if !changes.iter().all(ParameterChange::is_valid) {
+        tracing::info!(
+            "Parameter change proposal {} contains an invalid value.",
+            proposal_id
+        );
+        return Ok(false);
+    }
+
+    let mut pos_params = read_pos_params(storage)?;
+    let mut governance_params = gov_api::get_parameters(storage)?;
+    for change in &changes {
+        match change {
+            ParameterChange::Pos(change) => pos_params.apply_change(change),
+            ParameterChange::Governance(change) => {
+                change.apply(&mut governance_params)
+            }
+            ParameterChange::Protocol(_) | ParameterChange::Pgf(_) => (),
+        }
+    }
+
+    let pos_errors = pos_params.validate();
+    if !pos_errors.is_empty() {
+        tracing::info!(
+            "Parameter change proposal {} results in invalid PoS parameters: \
+             {:?}",
+            proposal_id,
+            pos_errors
+        );
+        return Ok(false);
+    }
+    if !governance_params.is_valid() {
+        tracing::info!(
+            "Parameter change proposal {} results in invalid governance \
+             parameters: {:?}",
+            proposal_id,
+            governance_params
+        );
+        return Ok(false);
+    }
+
+    let mut is_pos_changed = false;
+    for change in changes {
+        match &change {
+            ParameterChange::Protocol(change) => change.write(storage)?,
+            ParameterChange::Pos(_) => is_pos_changed = true,
+            ParameterChange::Governance(change) => change.write(storage)?,
+            ParameterChange::Pgf(change) => change.write(storage)?,
+        }
+        tracing::info!(
+            "Execute parameter change from proposal id {}: {}.",
+            proposal_id,
+            change
+        );
+    }
+    if is_pos_changed {
+        write_pos_params(storage, pos_params)?;
+    }
+
+    Ok(true)
+}
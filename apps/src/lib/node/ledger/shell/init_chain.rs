@@ -84,6 +84,7 @@ where
             max_proposal_bytes,
             max_block_gas,
             max_expected_time_per_block,
+            max_tx_expiration,
             vp_whitelist,
             tx_whitelist,
             implicit_vp_code_path,
@@ -95,8 +96,11 @@ where
             staked_ratio,
             pos_inflation_amount,
             minimum_gas_price,
+            gas_costs,
+            fee_market,
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
+            shielded_rewards,
         } = genesis.parameters;
         // Store wasm codes into storage
         let checksums = wasm_loader::Checksums::read_checksums(&self.wasm_dir);
@@ -172,6 +176,7 @@ where
             max_proposal_bytes,
             max_block_gas,
             max_expected_time_per_block,
+            max_tx_expiration,
             vp_whitelist,
             tx_whitelist,
             implicit_vp_code_hash,
@@ -182,12 +187,16 @@ where
             staked_ratio,
             pos_inflation_amount,
             minimum_gas_price,
+            gas_costs,
+            fee_market,
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
+            shielded_rewards,
         };
         parameters
             .init_storage(&mut self.wl_storage)
             .expect("Initializing chain parameters must not fail");
+        self.wl_storage.set_gas_costs(gas_costs);
 
         // Initialize governance parameters
         genesis
@@ -450,6 +459,9 @@ where
 
         ibc::init_genesis_storage(&mut self.wl_storage);
 
+        // The genesis state isn't reported with events
+        self.wl_storage.write_log.take_events();
+
         // Set the initial validator set
         response.validators = self
             .get_abci_validator_updates(true)
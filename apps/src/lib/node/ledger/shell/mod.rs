@@ -30,6 +30,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use masp_primitives::transaction::Transaction;
 use namada::core::hints;
 use namada::core::ledger::eth_bridge;
+use namada::core::ledger::governance::storage::upgrade::UpgradePlan;
 use namada::ledger::eth_bridge::{EthBridgeQueries, EthereumOracleConfig};
 use namada::ledger::events::log::EventLog;
 use namada::ledger::events::Event;
@@ -56,7 +57,7 @@ use namada::types::chain::ChainId;
 use namada::types::ethereum_events::EthereumEvent;
 use namada::types::internal::{ExpiredTx, TxInQueue};
 use namada::types::key::*;
-use namada::types::storage::{BlockHeight, Key, TxIndex};
+use namada::types::storage::{BlockHeight, Key, KeySeg, TxIndex};
 use namada::types::time::DateTimeUtc;
 use namada::types::transaction::protocol::EthereumTxData;
 use namada::types::transaction::{
@@ -72,7 +73,6 @@ use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
 use super::ethereum_oracle::{self as oracle, last_processed_block};
-use crate::config;
 use crate::config::{genesis, TendermintMode};
 use crate::facade::tendermint_proto::abci::{
     Misbehavior as Evidence, MisbehaviorType as EvidenceType, ValidatorUpdate,
@@ -87,6 +87,7 @@ use crate::node::ledger::{storage, tendermint_node};
 use crate::wallet;
 #[allow(unused_imports)]
 use crate::wallet::{ValidatorData, ValidatorKeys};
+use crate::{config, wasm_loader};
 
 fn key_to_tendermint(
     pk: &common::PublicKey,
@@ -101,6 +102,10 @@ fn key_to_tendermint(
     }
 }
 
+/// The target triple the node was built for, which selects the binary checksum
+/// of a scheduled upgrade
+const NODE_TARGET: &str = env!("NAMADA_TARGET");
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Error removing the DB data: {0}")]
@@ -129,6 +134,13 @@ pub enum Error {
     StorageApi(#[from] storage_api::Error),
     #[error("Transaction replay attempt: {0}")]
     ReplayAttempt(String),
+    #[error("Invalid transaction expiration: {0}")]
+    TxExpiration(String),
+    #[error(
+        "Reached the height {1} of the scheduled upgrade {0}, halting the \
+         chain. Restart the node with the upgraded software."
+    )]
+    UpgradeHalt(String, BlockHeight),
 }
 
 impl From<Error> for TxResult {
@@ -400,10 +412,16 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `parallel_tx_execution`. When set, the decrypted
+    /// txs of a block are executed speculatively in parallel.
+    parallel_tx_execution: bool,
     /// Proposal execution tracking
     pub proposal_data: HashSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// Checksum of the running node binary, computed when a scheduled
+    /// upgrade is checked
+    binary_checksum: Option<hash::Hash>,
 }
 
 /// Channels for communicating with an Ethereum oracle.
@@ -452,6 +470,7 @@ where
         let mode = config.shell.tendermint_mode;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        let parallel_tx_execution = config.shell.parallel_tx_execution;
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -470,6 +489,7 @@ where
                 tracing::error!("Cannot load the last state from the DB {}", e);
             })
             .expect("PersistentStorage cannot be initialized");
+        storage.event_index_retention = config.shell.event_index_retention;
         let vp_wasm_cache_dir =
             base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
         let tx_wasm_cache_dir =
@@ -547,11 +567,18 @@ where
                 tx_wasm_compilation_cache as usize,
             ),
             storage_read_past_height_limit,
+            parallel_tx_execution,
             proposal_data: HashSet::new(),
             // TODO: config event log params
             event_log: EventLog::default(),
+            binary_checksum: None,
         };
+        shell.check_upgrade_on_start();
         shell.update_eth_oracle();
+        if shell.wl_storage.storage.last_block.is_some() {
+            shell.update_gas_costs();
+            shell.warm_up_wasm_caches();
+        }
         shell
     }
 
@@ -824,6 +851,11 @@ where
             self.wl_storage.storage.get_last_block_height(),
         );
         response.data = root.0.to_vec();
+        tracing::debug!(
+            "WASM compilation cache stats: txs {:?}, VPs {:?}",
+            self.tx_wasm_cache.stats(),
+            self.vp_wasm_cache.stats(),
+        );
 
         // validator specific actions
         if let ShellMode::Validator {
@@ -923,6 +955,172 @@ where
         }
     }
 
+    /// Refuse to start if the chain halted for a scheduled upgrade and the
+    /// node doesn't run the upgraded software.
+    fn check_upgrade_on_start(&mut self) {
+        let next_height = self.wl_storage.storage.get_last_block_height() + 1;
+        let plan = match storage_api::governance::get_scheduled_upgrade(
+            &self.wl_storage,
+        )
+        .expect("Reading the scheduled upgrade shouldn't fail")
+        {
+            Some(plan) if plan.is_due(next_height) => plan,
+            _ => return,
+        };
+        let mismatches = self.upgrade_mismatches(&plan);
+        if !mismatches.is_empty() {
+            tracing::error!(
+                "The chain halted at height {} for the upgrade {}, but this \
+                 node doesn't run the upgraded software. Mismatching \
+                 checksums: {}.",
+                plan.height,
+                plan.name,
+                mismatches.join(", ")
+            );
+            panic!(
+                "Refusing to start with software that doesn't match the \
+                 scheduled upgrade {}",
+                plan.name
+            );
+        }
+    }
+
+    /// Find the components of the running software that don't match the
+    /// checksums of a scheduled upgrade
+    fn upgrade_mismatches(&mut self, plan: &UpgradePlan) -> Vec<String> {
+        let mut mismatches = vec![];
+        let is_matching_binary =
+            self.binary_checksum().map_or(false, |checksum| {
+                plan.is_matching_binary(NODE_TARGET, &checksum)
+            });
+        if !is_matching_binary {
+            mismatches.push(format!("binary for {}", NODE_TARGET));
+        }
+        let available = plan
+            .wasm_checksums
+            .keys()
+            .filter_map(|name| {
+                let wasm = wasm_loader::read_wasm(&self.wasm_dir, name).ok()?;
+                Some((name.clone(), hash::Hash::sha256(wasm)))
+            })
+            .collect();
+        mismatches.extend(plan.mismatching_wasms(&available));
+        mismatches
+    }
+
+    /// The checksum of the running node binary, computed on first use
+    fn binary_checksum(&mut self) -> Option<hash::Hash> {
+        if self.binary_checksum.is_none() {
+            self.binary_checksum = std::env::current_exe()
+                .and_then(std::fs::read)
+                .ok()
+                .map(hash::Hash::sha256);
+        }
+        self.binary_checksum
+    }
+
+    /// Put the gas costs table from the protocol parameters in effect
+    pub fn update_gas_costs(&mut self) {
+        let gas_costs = parameters::read_gas_costs(&self.wl_storage)
+            .expect("Gas costs table parameter must be in storage");
+        self.wl_storage.set_gas_costs(gas_costs);
+    }
+
+    /// Pre-compile the allowlisted WASM codes found in storage in the
+    /// background, so that the first blocks that use them don't have to wait
+    /// for their compilation. Codes that are already compiled, or being
+    /// compiled, are skipped.
+    pub fn warm_up_wasm_caches(&mut self) {
+        let tx_whitelist: Vec<String> = self
+            .read_storage_key(
+                &parameters::storage::get_tx_whitelist_storage_key(),
+            )
+            .unwrap_or_default();
+        let vp_whitelist: Vec<String> = self
+            .read_storage_key(
+                &parameters::storage::get_vp_whitelist_storage_key(),
+            )
+            .unwrap_or_default();
+        let codes: Vec<(String, hash::Hash)> = storage_api::iter_prefix_bytes(
+            &self.wl_storage,
+            &Key::wasm_hash_prefix(),
+        )
+        .expect("Error while iterating over the WASM code hashes")
+        .filter_map(|entry| {
+            let (key, code_hash) =
+                entry.expect("Error while reading a WASM code hash");
+            let name = key.last()?.raw();
+            let code_hash = hash::Hash::try_from(&code_hash[..]).ok()?;
+            Some((name, code_hash))
+        })
+        .collect();
+
+        let mut pre_compiled = 0;
+        for (name, code_hash) in codes {
+            let hash_str = code_hash.to_string().to_lowercase();
+            if !(tx_whitelist.is_empty() && vp_whitelist.is_empty())
+                && !tx_whitelist.contains(&hash_str)
+                && !vp_whitelist.contains(&hash_str)
+            {
+                continue;
+            }
+            let is_tx = name.starts_with("tx_");
+            if !is_tx && !name.starts_with("vp_") {
+                continue;
+            }
+            if (is_tx && self.tx_wasm_cache.contains(&code_hash))
+                || (!is_tx && self.vp_wasm_cache.contains(&code_hash))
+            {
+                continue;
+            }
+            let code = match self
+                .wl_storage
+                .read_bytes(&Key::wasm_code(&code_hash))
+                .expect("Error while reading a WASM code")
+            {
+                Some(code) => code,
+                None => continue,
+            };
+            if is_tx {
+                self.tx_wasm_cache.pre_compile(&code);
+            } else {
+                self.vp_wasm_cache.pre_compile(&code);
+            }
+            pre_compiled += 1;
+        }
+        tracing::info!("Pre-compiling {pre_compiled} WASM codes.");
+    }
+
+    /// Checks that the expiration of a wrapper tx is not further away from
+    /// the given block time than the max tx expiration parameter. If the
+    /// parameter is set, txs without an expiration are rejected too, since
+    /// their replay protection hashes could never be pruned.
+    pub fn tx_expiration_checks(
+        &self,
+        expiration: Option<DateTimeUtc>,
+        block_time: DateTimeUtc,
+    ) -> Result<()> {
+        let max_tx_expiration = parameters::max_tx_expiration(&self.wl_storage)
+            .expect("Error while reading the max tx expiration parameter");
+        match (max_tx_expiration, expiration) {
+            (None, _) => Ok(()),
+            (Some(_), None) => Err(Error::TxExpiration(
+                "Wrapper txs must have an expiration".to_string(),
+            )),
+            (Some(max_tx_expiration), Some(exp)) => {
+                let max_exp = block_time + max_tx_expiration;
+                if exp > max_exp {
+                    Err(Error::TxExpiration(format!(
+                        "Tx expiration {exp:#?} is later than the maximum \
+                         allowed {max_exp:#?}"
+                    )))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
     /// Checks that neither the wrapper nor the inner transaction have already
     /// been applied. Requires a [`TempWlStorage`] to perform the check during
     /// block construction and validation
@@ -934,11 +1132,12 @@ where
     ) -> Result<()> {
         let inner_tx_hash =
             wrapper.clone().update_header(TxType::Raw).header_hash();
-        let inner_hash_key =
-            replay_protection::get_replay_protection_key(&inner_tx_hash);
-        if temp_wl_storage
-            .has_key(&inner_hash_key)
-            .expect("Error while checking inner tx hash key in storage")
+        if replay_protection::has_replay_protection_entry(
+            temp_wl_storage,
+            wrapper.header.expiration,
+            &inner_tx_hash,
+        )
+        .expect("Error while checking inner tx hash key in storage")
         {
             return Err(Error::ReplayAttempt(format!(
                 "Inner transaction hash {} already in storage",
@@ -947,6 +1146,10 @@ where
         }
 
         // Write inner hash to tx WAL
+        let inner_hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
+            &inner_tx_hash,
+        );
         temp_wl_storage
             .write_log
             .write(&inner_hash_key, vec![])
@@ -955,11 +1158,12 @@ where
         let tx =
             Tx::try_from(tx_bytes).expect("Deserialization shouldn't fail");
         let wrapper_hash = tx.header_hash();
-        let wrapper_hash_key =
-            replay_protection::get_replay_protection_key(&wrapper_hash);
-        if temp_wl_storage
-            .has_key(&wrapper_hash_key)
-            .expect("Error while checking wrapper tx hash key in storage")
+        if replay_protection::has_replay_protection_entry(
+            temp_wl_storage,
+            tx.header.expiration,
+            &wrapper_hash,
+        )
+        .expect("Error while checking wrapper tx hash key in storage")
         {
             return Err(Error::ReplayAttempt(format!(
                 "Wrapper transaction hash {} already in storage",
@@ -968,6 +1172,10 @@ where
         }
 
         // Write wrapper hash to tx WAL
+        let wrapper_hash_key = replay_protection::get_replay_protection_key(
+            tx.header.expiration,
+            &wrapper_hash,
+        );
         temp_wl_storage
             .write_log
             .write(&wrapper_hash_key, vec![])
@@ -1239,7 +1447,13 @@ where
             TxType::Wrapper(wrapper) => {
                 // Tx gas limit
                 let mut gas_meter = TxGasMeter::new(wrapper.gas_limit);
-                if gas_meter.add_tx_size_gas(tx_bytes).is_err() {
+                if gas_meter
+                    .add_tx_size_gas(
+                        tx_bytes,
+                        &self.wl_storage.storage.gas_costs,
+                    )
+                    .is_err()
+                {
                     response.code = ErrorCodes::TxGasLimit.into();
                     response.log = "{INVALID_MSG}: Wrapper transactions \
                                     exceeds its gas limit"
@@ -1262,18 +1476,26 @@ where
                     return response;
                 }
 
+                // Max tx expiration
+                if let Err(e) = self.tx_expiration_checks(
+                    tx_expiration,
+                    self.get_block_timestamp(None),
+                ) {
+                    response.code = ErrorCodes::InvalidTx.into();
+                    response.log = format!("{INVALID_MSG}: {e}");
+                    return response;
+                }
+
                 // Replay protection check
                 let mut inner_tx = tx;
                 inner_tx.update_header(TxType::Raw);
                 let inner_tx_hash = &inner_tx.header_hash();
-                let inner_hash_key =
-                    replay_protection::get_replay_protection_key(inner_tx_hash);
-                if self
-                    .wl_storage
-                    .storage
-                    .has_key(&inner_hash_key)
-                    .expect("Error while checking inner tx hash key in storage")
-                    .0
+                if replay_protection::has_replay_protection_entry(
+                    &self.wl_storage,
+                    inner_tx.header.expiration,
+                    inner_tx_hash,
+                )
+                .expect("Error while checking inner tx hash key in storage")
                 {
                     response.code = ErrorCodes::ReplayTx.into();
                     response.log = format!(
@@ -1287,16 +1509,12 @@ where
                 let tx = Tx::try_from(tx_bytes)
                     .expect("Deserialization shouldn't fail");
                 let wrapper_hash = hash::Hash(tx.header_hash().0);
-                let wrapper_hash_key =
-                    replay_protection::get_replay_protection_key(&wrapper_hash);
-                if self
-                    .wl_storage
-                    .storage
-                    .has_key(&wrapper_hash_key)
-                    .expect(
-                        "Error while checking wrapper tx hash key in storage",
-                    )
-                    .0
+                if replay_protection::has_replay_protection_entry(
+                    &self.wl_storage,
+                    tx.header.expiration,
+                    &wrapper_hash,
+                )
+                .expect("Error while checking wrapper tx hash key in storage")
                 {
                     response.code = ErrorCodes::ReplayTx.into();
                     response.log = format!(
@@ -1358,24 +1576,22 @@ where
         CA: 'static + WasmCacheAccess + Sync,
     {
         // Check that fee token is an allowed one
-        let minimum_gas_price = namada::ledger::parameters::read_gas_cost(
+        let base_fee = namada::ledger::parameters::read_base_fee(
             &self.wl_storage,
             &wrapper.fee.token,
         )
-        .expect("Must be able to read gas cost parameter")
+        .expect("Must be able to read the base fee")
         .ok_or(Error::TxApply(protocol::Error::FeeError(format!(
             "The provided {} token is not allowed for fee payment",
             wrapper.fee.token
         ))))?;
 
-        if wrapper.fee.amount_per_gas_unit < minimum_gas_price {
-            // The fees do not match the minimum required
+        if wrapper.fee.amount_per_gas_unit < base_fee {
+            // The gas price doesn't cover the base fee
             return Err(Error::TxApply(protocol::Error::FeeError(format!(
-                "Fee amount {:?} do not match the minimum required amount \
-                 {:?} for token {}",
-                wrapper.fee.amount_per_gas_unit,
-                minimum_gas_price,
-                wrapper.fee.token
+                "Gas price {:?} is below the current base fee {:?} for token \
+                 {}",
+                wrapper.fee.amount_per_gas_unit, base_fee, wrapper.fee.token
             ))));
         }
 
@@ -1518,6 +1734,7 @@ mod test_utils {
 
     use data_encoding::HEXUPPER;
     use namada::core::ledger::storage::EPOCH_SWITCH_BLOCKS_DELAY;
+    use namada::ledger::events::EventLevel;
     use namada::ledger::storage::mockdb::MockDB;
     use namada::ledger::storage::{
         update_allowed_conversions, LastBlock, Sha256Hasher,
@@ -1783,7 +2000,11 @@ mod test_utils {
             req: FinalizeBlock,
         ) -> Result<Vec<Event>> {
             match self.shell.finalize_block(req) {
-                Ok(resp) => Ok(resp.events),
+                Ok(resp) => Ok(resp
+                    .events
+                    .into_iter()
+                    .filter(|event| event.level == EventLevel::Tx)
+                    .collect()),
                 Err(err) => Err(err),
             }
         }
@@ -2465,8 +2686,10 @@ mod tests {
 
         // Write wrapper hash to storage
         let wrapper_hash = wrapper.header_hash();
-        let wrapper_hash_key =
-            replay_protection::get_replay_protection_key(&wrapper_hash);
+        let wrapper_hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
+            &wrapper_hash,
+        );
         shell
             .wl_storage
             .storage
@@ -2505,8 +2728,10 @@ mod tests {
         let inner_tx_hash =
             wrapper.clone().update_header(TxType::Raw).header_hash();
         // Write inner hash in storage
-        let inner_hash_key =
-            replay_protection::get_replay_protection_key(&inner_tx_hash);
+        let inner_hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
+            &inner_tx_hash,
+        );
         shell
             .wl_storage
             .storage
@@ -238,12 +238,21 @@ where
                 return Err(());
             }
         }
+        // Mirror the max tx expiration check of process proposal, which
+        // defaults to the last block time
+        self.tx_expiration_checks(
+            tx.header().expiration,
+            block_time.unwrap_or_else(|| self.get_block_timestamp(None)),
+        )
+        .map_err(|_| ())?;
 
         tx.validate_tx().map_err(|_| ())?;
         if let TxType::Wrapper(wrapper) = tx.header().tx_type {
             // Check tx gas limit for tx size
             let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
-            tx_gas_meter.add_tx_size_gas(tx_bytes).map_err(|_| ())?;
+            tx_gas_meter
+                .add_tx_size_gas(tx_bytes, &temp_wl_storage.storage.gas_costs)
+                .map_err(|_| ())?;
 
             // Check replay protection
             self.replay_protection_checks(&tx, tx_bytes, temp_wl_storage)
@@ -1189,6 +1198,7 @@ mod test_prepare_proposal {
         // Write wrapper hash to storage
         let wrapper_unsigned_hash = wrapper.header_hash();
         let hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
             &wrapper_unsigned_hash,
         );
         shell
@@ -1283,8 +1293,10 @@ mod test_prepare_proposal {
             wrapper.clone().update_header(TxType::Raw).header_hash();
 
         // Write inner hash to storage
-        let hash_key =
-            replay_protection::get_replay_protection_key(&inner_unsigned_hash);
+        let hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
+            &inner_unsigned_hash,
+        );
         shell
             .wl_storage
             .storage
@@ -800,7 +800,13 @@ where
                 // valid transaction and avoid wasting block
                 // resources (ABCI only)
                 let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
-                if tx_gas_meter.add_tx_size_gas(tx_bytes).is_err() {
+                if tx_gas_meter
+                    .add_tx_size_gas(
+                        tx_bytes,
+                        &temp_wl_storage.storage.gas_costs,
+                    )
+                    .is_err()
+                {
                     // Account for the tx's resources even in case of an error.
                     // Ignore any allocation error
                     let _ = metadata
@@ -866,6 +872,14 @@ where
                         };
                     }
                 }
+                if let Err(e) =
+                    self.tx_expiration_checks(tx_expiration, block_time)
+                {
+                    return TxResult {
+                        code: ErrorCodes::InvalidTx.into(),
+                        info: e.to_string(),
+                    };
+                }
 
                 // validate the ciphertext via Ferveo
                 if !tx.validate_ciphertext() {
@@ -996,7 +1010,7 @@ mod test_process_proposal {
     use namada::types::hash::Hash;
     use namada::types::key::*;
     use namada::types::storage::Epoch;
-    use namada::types::time::DateTimeUtc;
+    use namada::types::time::{DateTimeUtc, Duration, DurationSecs};
     use namada::types::token;
     use namada::types::token::Amount;
     use namada::types::transaction::protocol::EthereumTxData;
@@ -2133,6 +2147,7 @@ mod test_process_proposal {
         // Write wrapper hash to storage
         let wrapper_unsigned_hash = wrapper.header_hash();
         let hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
             &wrapper_unsigned_hash,
         );
         shell
@@ -2267,8 +2282,10 @@ mod test_process_proposal {
             wrapper.clone().update_header(TxType::Raw).header_hash();
 
         // Write inner hash to storage
-        let hash_key =
-            replay_protection::get_replay_protection_key(&inner_unsigned_hash);
+        let hash_key = replay_protection::get_replay_protection_key(
+            wrapper.header.expiration,
+            &inner_unsigned_hash,
+        );
         shell
             .wl_storage
             .storage
@@ -2538,6 +2555,62 @@ mod test_process_proposal {
         }
     }
 
+    /// Test that, if the max tx expiration parameter is set, a wrapper
+    /// transaction without an expiration or with an expiration too far in
+    /// the future causes a block rejection
+    #[test]
+    fn test_max_tx_expiration() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let keypair = crate::wallet::defaults::daewon_keypair();
+        shell
+            .wl_storage
+            .write(
+                &parameters::storage::get_max_tx_expiration_key(),
+                Some(DurationSecs(3600)),
+            )
+            .unwrap();
+        let block_time =
+            shell.wl_storage.storage.get_last_block_timestamp().unwrap();
+
+        for expiration in [None, Some(block_time + Duration::hours(2))] {
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: 1.into(),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    GAS_LIMIT_MULTIPLIER.into(),
+                    None,
+                ))));
+            wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper.header.expiration = expiration;
+            wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned()));
+            wrapper
+                .set_data(Data::new("transaction data".as_bytes().to_owned()));
+            wrapper.add_section(Section::Signature(Signature::new(
+                wrapper.sechashes(),
+                [(0, keypair.clone())].into_iter().collect(),
+                None,
+            )));
+
+            // Run validation
+            let request = ProcessProposal {
+                txs: vec![wrapper.to_bytes()],
+            };
+            match shell.process_proposal(request) {
+                Ok(_) => panic!("Test failed"),
+                Err(TestError::RejectProposal(response)) => {
+                    assert_eq!(
+                        response[0].result.code,
+                        u32::from(ErrorCodes::InvalidTx)
+                    );
+                }
+            }
+        }
+    }
+
     /// Test that an expired decrypted transaction is correctly marked as so
     /// without rejecting the entire block
     #[test]
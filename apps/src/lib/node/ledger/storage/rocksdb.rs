@@ -32,6 +32,12 @@
 //!     - `epoch`: block epoch
 //!     - `address_gen`: established address generator
 //!     - `header`: block's header
+//! - `events`: the event index
+//!   - `block/{h}/{i}`: the `i`-th event emitted in the block at height `h`
+//!   - `type/{event_type}/{h}/{i}`: the event indexed by its type
+//!   - `attr/{key}/{value}/{h}/{i}`: the event indexed by an attribute value
+//!
+//! The event types and the attributes in the index keys are hex-encoded.
 
 use std::fs::File;
 use std::io::BufWriter;
@@ -43,6 +49,9 @@ use ark_serialize::Write;
 use borsh::{BorshDeserialize, BorshSerialize};
 use data_encoding::HEXLOWER;
 use namada::core::types::ethereum_structs;
+use namada::core::types::event::{
+    EventIndexQuery, IndexedEvent, MAX_INDEXED_ATTRIBUTE_LEN,
+};
 use namada::ledger::storage::types::PrefixIterator;
 use namada::ledger::storage::{
     types, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error,
@@ -73,6 +82,7 @@ const SUBSPACE_CF: &str = "subspace";
 const DIFFS_CF: &str = "diffs";
 const STATE_CF: &str = "state";
 const BLOCK_CF: &str = "block";
+const EVENTS_CF: &str = "events";
 
 /// RocksDB handle
 #[derive(Debug)]
@@ -160,6 +170,14 @@ pub fn open(
     block_cf_opts.set_block_based_table_factory(&table_opts);
     cfs.push(ColumnFamilyDescriptor::new(BLOCK_CF, block_cf_opts));
 
+    // for the event index (insert-intensive)
+    let mut events_cf_opts = Options::default();
+    events_cf_opts.set_compression_type(rocksdb::DBCompressionType::Zstd);
+    events_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    events_cf_opts.set_compaction_style(rocksdb::DBCompactionStyle::Universal);
+    events_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(EVENTS_CF, events_cf_opts));
+
     rocksdb::DB::open_cf_descriptors(&db_opts, path, cfs)
         .map(RocksDB)
         .map_err(|e| Error::DBError(e.into_string()))
@@ -178,6 +196,44 @@ impl RocksDB {
             .ok_or(Error::DBError("No {cf_name} column family".to_string()))
     }
 
+    /// Delete the events emitted from the `from` height, or from the lowest
+    /// height if `None`, until the `to` height exclusively from the event
+    /// index.
+    fn delete_events(
+        &self,
+        batch: &mut WriteBatch,
+        from: Option<BlockHeight>,
+        to: BlockHeight,
+    ) -> Result<()> {
+        let events_cf = self.get_column_family(EVENTS_CF)?;
+        let prefix = "block/";
+        let start = match from {
+            Some(height) => format!("{prefix}{}", height.raw()),
+            None => prefix.to_owned(),
+        };
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_total_order_seek(true);
+        read_opts.set_iterate_upper_bound(format!("{prefix}{}", to.raw()));
+        let iter = self.0.iterator_cf_opt(
+            events_cf,
+            read_opts,
+            IteratorMode::From(start.as_bytes(), Direction::Forward),
+        );
+        for result in iter {
+            let (key, value) =
+                result.map_err(|e| Error::DBError(e.into_string()))?;
+            let key = String::from_utf8(key.to_vec())
+                .map_err(|e| Error::DBError(e.to_string()))?;
+            let event: IndexedEvent =
+                types::decode(value).map_err(Error::CodingError)?;
+            for index_key in event_index_keys(&key[prefix.len()..], &event) {
+                batch.delete_cf(events_cf, index_key);
+            }
+            batch.delete_cf(events_cf, key);
+        }
+        Ok(())
+    }
+
     /// Persist the diff of an account subspace key-val under the height where
     /// it was changed.
     fn write_subspace_diff(
@@ -520,6 +576,12 @@ impl RocksDB {
         delete_keys(diffs_cf);
         // Delete any height-prepended key in the block
         delete_keys(block_cf);
+        // Delete the events emitted in the block
+        self.delete_events(
+            &mut batch,
+            Some(last_block.height),
+            last_block.height.next_height(),
+        )?;
 
         // Write the batch and persist changes to disk
         tracing::info!("Flushing restored state to disk");
@@ -1341,6 +1403,140 @@ impl DB for RocksDB {
             None => Ok(()),
         }
     }
+
+    fn add_events_to_batch(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        events: &[IndexedEvent],
+    ) -> Result<()> {
+        let events_cf = self.get_column_family(EVENTS_CF)?;
+        for (index, event) in events.iter().enumerate() {
+            let event_key =
+                format!("{}/{}", height.raw(), (index as u64).raw());
+            for index_key in event_index_keys(&event_key, event) {
+                batch.0.put_cf(events_cf, index_key, []);
+            }
+            batch.0.put_cf(
+                events_cf,
+                format!("block/{event_key}"),
+                types::encode(event),
+            );
+        }
+        Ok(())
+    }
+
+    fn read_events(
+        &self,
+        query: &EventIndexQuery,
+    ) -> Result<Vec<(BlockHeight, IndexedEvent)>> {
+        let limit = query.limit();
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let events_cf = self.get_column_family(EVENTS_CF)?;
+        // Look up the events by an indexed attribute if any, by the type
+        // otherwise, or go through all the events
+        let indexed_attribute = query
+            .attributes
+            .iter()
+            .find(|(_, value)| value.len() <= MAX_INDEXED_ATTRIBUTE_LEN);
+        let prefix = match (indexed_attribute, &query.event_type) {
+            (Some((key, value)), _) => format!(
+                "attr/{}/{}/",
+                HEXLOWER.encode(key.as_bytes()),
+                HEXLOWER.encode(value.as_bytes())
+            ),
+            (None, Some(event_type)) => {
+                format!("type/{}/", HEXLOWER.encode(event_type.as_bytes()))
+            }
+            (None, None) => "block/".to_owned(),
+        };
+        let start = match query.min_height {
+            Some(height) => format!("{prefix}{}", height.raw()),
+            None => prefix.clone(),
+        };
+        let read_opts = make_iter_read_opts(Some(prefix.clone()));
+        let iter = self.0.iterator_cf_opt(
+            events_cf,
+            read_opts,
+            IteratorMode::From(start.as_bytes(), Direction::Forward),
+        );
+
+        let mut events = Vec::new();
+        let mut skipped = 0;
+        for result in iter {
+            let (key, value) =
+                result.map_err(|e| Error::DBError(e.into_string()))?;
+            let key = String::from_utf8(key.to_vec())
+                .map_err(|e| Error::DBError(e.to_string()))?;
+            let event_key = &key[prefix.len()..];
+            let height = parse_event_key(event_key)?;
+            if query.max_height.map_or(false, |max| height > max) {
+                break;
+            }
+            let value = if prefix == "block/" {
+                value.to_vec()
+            } else {
+                self.0
+                    .get_cf(events_cf, format!("block/{event_key}"))
+                    .map_err(|e| Error::DBError(e.into_string()))?
+                    .ok_or(Error::UnknownKey { key })?
+            };
+            let event: IndexedEvent =
+                types::decode(value).map_err(Error::CodingError)?;
+            if !query.matches(height, &event) {
+                continue;
+            }
+            if skipped < query.offset {
+                skipped += 1;
+                continue;
+            }
+            events.push((height, event));
+            if events.len() == limit {
+                break;
+            }
+        }
+        Ok(events)
+    }
+
+    fn prune_events(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        min_height: BlockHeight,
+    ) -> Result<()> {
+        self.delete_events(&mut batch.0, None, min_height)
+    }
+}
+
+/// The keys to look up an event with the given key `{height}/{index}` by its
+/// type and by its indexed attributes
+fn event_index_keys<'a>(
+    event_key: &'a str,
+    event: &'a IndexedEvent,
+) -> impl Iterator<Item = String> + 'a {
+    let type_key = format!(
+        "type/{}/{event_key}",
+        HEXLOWER.encode(event.event_type.as_bytes())
+    );
+    std::iter::once(type_key).chain(event.indexed_attributes().map(
+        move |(key, value)| {
+            format!(
+                "attr/{}/{}/{event_key}",
+                HEXLOWER.encode(key.as_bytes()),
+                HEXLOWER.encode(value.as_bytes())
+            )
+        },
+    ))
+}
+
+/// Parse the block height of an event from its key `{height}/{index}`
+fn parse_event_key(event_key: &str) -> Result<BlockHeight> {
+    let height = event_key
+        .split(KEY_SEGMENT_SEPARATOR)
+        .next()
+        .unwrap_or_default();
+    BlockHeight::parse(height.to_owned()).map_err(Error::KeyError)
 }
 
 impl<'iter> DBIter<'iter> for RocksDB {
@@ -1811,6 +2007,73 @@ mod test {
         assert_eq!(deleted, Some(to_delete_val));
     }
 
+    /// Test that the events can be looked up by their attributes and pruned
+    #[test]
+    fn test_event_index() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let transfer = |source: &str, target: &str| IndexedEvent {
+            event_type: "transfer".to_string(),
+            attributes: [("source", source), ("target", target)]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            data: source.as_bytes().to_vec(),
+        };
+        for height in 1..=3 {
+            let mut batch = RocksDB::batch();
+            let events = [transfer("alice", "bob"), transfer("bob", "alice")];
+            db.add_events_to_batch(&mut batch, BlockHeight(height), &events)
+                .unwrap();
+            db.exec_batch(batch.0).unwrap();
+        }
+
+        let query = EventIndexQuery::new("transfer").with("source", "bob");
+        let events = db.read_events(&query).unwrap();
+        let heights: Vec<u64> = events.iter().map(|(h, _)| h.0).collect();
+        assert_eq!(heights, vec![1, 2, 3]);
+        assert!(events.iter().all(|(_, event)| event.data == b"bob"));
+
+        // Filter by multiple attributes and paginate
+        let query = EventIndexQuery {
+            offset: 1,
+            limit: 1,
+            ..EventIndexQuery::new("transfer")
+                .with("source", "alice")
+                .with("target", "bob")
+        };
+        let events = db.read_events(&query).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, BlockHeight(2));
+        let query = EventIndexQuery {
+            min_height: Some(BlockHeight(3)),
+            ..EventIndexQuery::new("transfer")
+        };
+        assert_eq!(db.read_events(&query).unwrap().len(), 2);
+        let query = EventIndexQuery::new("transfer").with("source", "carol");
+        assert!(db.read_events(&query).unwrap().is_empty());
+
+        // Prune the events below height 3
+        let mut batch = RocksDB::batch();
+        db.prune_events(&mut batch, BlockHeight(3)).unwrap();
+        db.exec_batch(batch.0).unwrap();
+        let query = EventIndexQuery::new("transfer").with("source", "bob");
+        let events = db.read_events(&query).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, BlockHeight(3));
+        let events_cf = db.get_column_family(EVENTS_CF).unwrap();
+        let index_keys =
+            db.0.iterator_cf(events_cf, IteratorMode::Start)
+                .filter(|result| {
+                    let (key, _) = result.as_ref().unwrap();
+                    !key.starts_with(b"block/")
+                })
+                .count();
+        // A type key and two attribute keys per event
+        assert_eq!(index_keys, 6);
+    }
+
     /// A test helper to write a block
     fn add_block_to_batch(
         db: &RocksDB,
@@ -0,0 +1,312 @@
+//! Gas calibration tool.
+//!
+//! Measures the operations metered by the ledger on the current machine and
+//! prints a proposed gas costs table, in the format of the
+//! `[parameters.gas_costs]` section of the genesis files. The table can be
+//! adopted by governance by changing the `gas_costs` protocol parameter.
+//!
+//! The measurements are converted to gas with the rate of gas per nanosecond
+//! of a token transfer charged under the gas costs currently in effect, so
+//! that the overall cost of the txs stays the same while the cost of the
+//! individual operations is rebalanced to match the machine's performance.
+//!
+//! Run with:
+//!
+//! ```shell
+//! cargo bench --bench gas_calibration
+//! ```
+
+use std::collections::{BTreeSet, HashSet};
+use std::time::{Duration, Instant};
+
+use namada::core::types::account::AccountPublicKeysMap;
+use namada::core::types::address::{self, Address};
+use namada::core::types::token::{Amount, Transfer};
+use namada::ledger::gas::{GasCosts, GasMetering, TxGasMeter, VpGasMeter};
+use namada::ledger::native_vp::multitoken::MultitokenVp;
+use namada::ledger::native_vp::{Ctx, NativeVp};
+use namada::ledger::protocol::{apply_wasm_tx, ShellParams};
+use namada::proto::{Data, Section, Signature, Tx};
+use namada::types::address::InternalAddress;
+use namada::types::hash::Hash;
+use namada::types::key::{common, SigScheme};
+use namada::types::storage::{Key, TxIndex};
+use namada::vm::wasm::memory;
+use namada::vm::wasm::run::{prepare_wasm_code, untrusted_wasm_store};
+use namada_apps::wallet::defaults;
+use namada_apps::wasm_loader;
+use namada_benches::{generate_tx, BenchShell, TX_TRANSFER_WASM, WASM_DIR};
+
+/// Number of times that every measurement is repeated. The fastest run is
+/// kept, as it's the least disturbed by the rest of the system.
+const RUNS: u32 = 5;
+/// The size of the data used for the per-byte measurements
+const DATA_LEN: usize = 64 * 1024;
+/// Number of iterations of the loop used to measure the WASM instructions
+const WASM_LOOPS: i64 = 10_000_000;
+/// Number of WASM instructions executed by every iteration of the loop
+const WASM_INSTRUCTIONS_PER_LOOP: i64 = 8;
+
+/// Measure the time of a single call to the given function
+fn measure<T>(iterations: u32, mut f: impl FnMut() -> T) -> Duration {
+    // Warm up
+    std::hint::black_box(f());
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                std::hint::black_box(f());
+            }
+            start.elapsed() / iterations
+        })
+        .min()
+        .expect("There's at least one run")
+}
+
+/// Nanoseconds per unit of a measured operation
+fn per_unit(duration: Duration, units: usize) -> f64 {
+    duration.as_nanos() as f64 / units as f64
+}
+
+fn transfer_tx() -> Tx {
+    generate_tx(
+        TX_TRANSFER_WASM,
+        Transfer {
+            source: defaults::albert_address(),
+            target: defaults::bertha_address(),
+            token: address::nam(),
+            amount: Amount::native_whole(1).native_denominated(),
+            key: None,
+            shielded: None,
+        },
+        None,
+        None,
+        Some(&defaults::albert_keypair()),
+    )
+}
+
+/// Apply a transfer with the native and WASM VPs that it triggers. Returns
+/// the time it took and the gas that it was charged.
+fn transfer(shell: &mut BenchShell, tx: &Tx) -> (Duration, u64) {
+    let initial_write_log = shell.wl_storage.write_log.clone();
+    let mut gas_used = 0;
+    let duration = measure(10, || {
+        shell.wl_storage.write_log = initial_write_log.clone();
+        let result = apply_wasm_tx(
+            tx.clone(),
+            &TxIndex(0),
+            ShellParams::new(
+                &mut TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+                &mut shell.inner.wl_storage,
+                &mut shell.inner.vp_wasm_cache,
+                &mut shell.inner.tx_wasm_cache,
+            ),
+        )
+        .unwrap();
+        assert!(result.is_accepted());
+        gas_used = result.gas_used.into();
+    });
+    shell.wl_storage.write_log = initial_write_log;
+    (duration, gas_used)
+}
+
+/// Run the multitoken VP on a transfer with the given gas costs. Returns the
+/// time it took and the gas that it was charged.
+fn multitoken_vp(tx: &Tx, gas_costs: GasCosts) -> (Duration, u64) {
+    let mut shell = BenchShell::default();
+    shell.wl_storage.set_gas_costs(gas_costs);
+    shell.execute_tx(tx);
+    let (verifiers, keys_changed) = shell
+        .wl_storage
+        .write_log
+        .verifiers_and_changed_keys(&BTreeSet::default());
+
+    let mut gas_used = 0;
+    let duration = measure(100, || {
+        let multitoken = MultitokenVp {
+            ctx: Ctx::new(
+                &Address::Internal(InternalAddress::Multitoken),
+                &shell.wl_storage.storage,
+                &shell.wl_storage.write_log,
+                tx,
+                &TxIndex(0),
+                VpGasMeter::new_from_tx_meter(&TxGasMeter::new_from_sub_limit(
+                    u64::MAX.into(),
+                )),
+                &keys_changed,
+                &verifiers,
+                shell.vp_wasm_cache.clone(),
+            ),
+        };
+        assert!(
+            multitoken
+                .validate_tx(tx, &keys_changed, &verifiers)
+                .unwrap()
+        );
+        gas_used = multitoken.ctx.gas_meter.into_inner().get_tx_consumed_gas();
+    });
+    (duration, u64::from(gas_used))
+}
+
+/// Nanoseconds per byte of a storage read
+fn storage_access(shell: &mut BenchShell) -> f64 {
+    let key = Key::parse("gas_calibration/read").unwrap();
+    let value = vec![1_u8; DATA_LEN];
+    shell.wl_storage.storage.write(&key, &value).unwrap();
+    let duration =
+        measure(1_000, || shell.wl_storage.storage.read(&key).unwrap());
+    per_unit(duration, key.len() + value.len())
+}
+
+/// Nanoseconds per byte of a storage write
+fn storage_write(shell: &mut BenchShell) -> f64 {
+    let key = Key::parse("gas_calibration/write").unwrap();
+    let value = vec![1_u8; DATA_LEN];
+    let duration = measure(1_000, || {
+        shell.wl_storage.storage.write(&key, &value).unwrap()
+    });
+    per_unit(duration, key.len() + value.len())
+}
+
+/// Nanoseconds per byte of decoding a tx
+fn tx_size(tx: &Tx) -> f64 {
+    let bytes = tx.to_bytes();
+    let duration = measure(1_000, || Tx::try_from(bytes.as_slice()).unwrap());
+    per_unit(duration, bytes.len())
+}
+
+/// Nanoseconds per byte of the WASM code preparation and compilation
+fn compile() -> f64 {
+    let code = wasm_loader::read_wasm_or_exit(WASM_DIR, TX_TRANSFER_WASM);
+    let duration = measure(10, || {
+        let code = prepare_wasm_code(&code).unwrap();
+        let store = untrusted_wasm_store(memory::tx_limit());
+        wasmer::Module::new(&store, code).unwrap()
+    });
+    per_unit(duration, code.len())
+}
+
+/// Nanoseconds per byte of hashing
+fn hash() -> f64 {
+    let data = vec![1_u8; DATA_LEN];
+    let duration = measure(1_000, || Hash::sha256(&data));
+    per_unit(duration, data.len())
+}
+
+/// Nanoseconds of the verification of a signature over a hash
+fn verify_sig() -> f64 {
+    let keypair = defaults::albert_keypair();
+    let data = Hash::sha256(b"gas calibration");
+    let signature = common::SigScheme::sign(&keypair, data);
+    let public_key = keypair.to_public();
+    let duration = measure(1_000, || {
+        common::SigScheme::verify_signature(&public_key, &data, &signature)
+            .unwrap()
+    });
+    per_unit(duration, 1)
+}
+
+/// Nanoseconds of the verification of a single signature of a tx
+fn verify_tx_sig() -> f64 {
+    let section = Section::Data(Data::new(vec![1_u8; 32]));
+    let section_hash = section.get_hash();
+    let pkim = AccountPublicKeysMap::from_iter([
+        defaults::albert_keypair().to_public()
+    ]);
+    let multisig = Signature::new(
+        vec![section_hash],
+        pkim.index_secret_keys(vec![defaults::albert_keypair()]),
+        None,
+    );
+    let duration = measure(1_000, || {
+        multisig
+            .verify_signature(&mut HashSet::new(), &pkim, &None)
+            .unwrap()
+    });
+    per_unit(duration, 1)
+}
+
+/// Nanoseconds per byte of a copy between the host and the WASM memory
+fn vm_memory_access() -> f64 {
+    let src = vec![1_u8; DATA_LEN];
+    let mut dst = vec![0_u8; DATA_LEN];
+    let duration = measure(10_000, || dst.copy_from_slice(&src));
+    per_unit(duration, src.len())
+}
+
+/// Nanoseconds per executed WASM instruction
+fn wasm_instruction() -> f64 {
+    let code = wasmer::wat2wasm(
+        br#"
+        (module
+            (func (export "run") (param i64) (result i64)
+            (loop $loop
+                (br_if 1 (i64.eqz (local.get 0)) (local.get 0))
+                (local.set 0 (i64.sub (local.get 0) (i64.const 1)))
+                (br $loop))
+            (local.get 0)))
+        "#,
+    )
+    .unwrap();
+    let store = untrusted_wasm_store(memory::tx_limit());
+    let module = wasmer::Module::new(&store, code).unwrap();
+    let instance =
+        wasmer::Instance::new(&module, &wasmer::imports! {}).unwrap();
+    let run = instance
+        .exports
+        .get_native_function::<i64, i64>("run")
+        .unwrap();
+    let duration = measure(1, || run.call(WASM_LOOPS).unwrap());
+    per_unit(duration, (WASM_LOOPS * WASM_INSTRUCTIONS_PER_LOOP) as usize)
+}
+
+fn main() {
+    let mut shell = BenchShell::default();
+    let tx = transfer_tx();
+
+    let current = shell.wl_storage.storage.gas_costs;
+    let (transfer_duration, transfer_gas) = transfer(&mut shell, &tx);
+    let gas_per_ns = transfer_gas as f64 / transfer_duration.as_nanos() as f64;
+    let to_gas = |ns: f64| ((ns * gas_per_ns).ceil() as u64).max(1);
+
+    let proposed = GasCosts {
+        tx_size_per_byte: to_gas(tx_size(&tx)),
+        compile_per_byte: to_gas(compile()),
+        storage_access_per_byte: to_gas(storage_access(&mut shell)),
+        storage_write_per_byte: to_gas(storage_write(&mut shell)),
+        verify_tx_sig: to_gas(verify_tx_sig()),
+        verify_sig: to_gas(verify_sig()),
+        hash_per_byte: to_gas(hash()),
+        vm_memory_access_per_byte: to_gas(vm_memory_access()),
+    };
+
+    // Check how the transfer and a native VP are charged with the proposed
+    // costs
+    let (vp_duration, vp_gas) = multitoken_vp(&tx, current);
+    shell.wl_storage.set_gas_costs(proposed);
+    let (_, proposed_transfer_gas) = transfer(&mut shell, &tx);
+    let (_, proposed_vp_gas) = multitoken_vp(&tx, proposed);
+
+    println!("# Measured with a transfer of {transfer_duration:?}");
+    println!("# Transfer gas: {transfer_gas} -> {proposed_transfer_gas}");
+    println!(
+        "# Multitoken VP of {vp_duration:?}, gas: {vp_gas} -> \
+         {proposed_vp_gas} (expected {})",
+        to_gas(vp_duration.as_nanos() as f64)
+    );
+    // The instructions cost is injected into the WASM code when it's
+    // compiled, so it's not part of the gas costs table
+    println!(
+        "# WASM instruction gas: {:.4}",
+        wasm_instruction() * gas_per_ns
+    );
+    println!("[parameters.gas_costs]");
+    print!("{}", toml::to_string(&proposed).unwrap());
+    println!(
+        "# Current gas costs:\n# {}",
+        toml::to_string(&current)
+            .unwrap()
+            .trim_end()
+            .replace('\n', "\n# ")
+    );
+}
@@ -228,6 +228,7 @@ impl Default for BenchShell {
                 voting_start_epoch,
                 voting_end_epoch: 28.into(),
                 grace_epoch: 34.into(),
+                is_expedited: false,
             },
             None,
             Some(vec![content_section]),
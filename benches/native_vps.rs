@@ -147,6 +147,7 @@ fn governance(c: &mut Criterion) {
                         voting_start_epoch,
                         voting_end_epoch: 28.into(),
                         grace_epoch: 34.into(),
+                        is_expedited: false,
                     },
                     None,
                     Some(vec![content_section]),
@@ -196,6 +197,7 @@ fn governance(c: &mut Criterion) {
                         voting_start_epoch,
                         voting_end_epoch: 28.into(),
                         grace_epoch: 34.into(),
+                        is_expedited: false,
                     },
                     None,
                     Some(vec![content_section, wasm_code_section]),
@@ -0,0 +1,142 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use namada::core::types::address::{self, Address};
+use namada::core::types::key::{
+    common, ed25519, RefTo, SecretKey as SecretKeyInterface,
+};
+use namada::core::types::token::{Amount, Transfer};
+use namada::ledger::gas::TxGasMeter;
+use namada::ledger::protocol::parallel::{SpeculativeTxInput, SpeculativeTxs};
+use namada::ledger::protocol::{apply_wasm_tx, ShellParams};
+use namada::proto::Tx;
+use namada::types::key::testing::gen_keypair;
+use namada::types::storage::TxIndex;
+use namada_apps::wallet::defaults;
+use namada_benches::{generate_tx, BenchShell, TX_TRANSFER_WASM};
+
+const TX_GAS_LIMIT: u64 = 1_000_000_000;
+
+/// Transfers from all the default accounts to fresh implicit accounts, which
+/// are independent of each other, or from a single source, which conflict
+/// with each other
+fn transfers(independent: bool) -> Vec<Tx> {
+    let sources: [(Address, common::SecretKey); 5] = [
+        (defaults::albert_address(), defaults::albert_keypair()),
+        (defaults::bertha_address(), defaults::bertha_keypair()),
+        (defaults::christel_address(), defaults::christel_keypair()),
+        (defaults::daewon_address(), defaults::daewon_keypair()),
+        (defaults::ester_address(), defaults::ester_keypair()),
+    ];
+    sources
+        .into_iter()
+        .map(|(source, keypair)| {
+            let (source, keypair) = if independent {
+                (source, keypair)
+            } else {
+                (defaults::albert_address(), defaults::albert_keypair())
+            };
+            let target: common::SecretKey =
+                gen_keypair::<ed25519::SigScheme>().try_to_sk().unwrap();
+            generate_tx(
+                TX_TRANSFER_WASM,
+                Transfer {
+                    source,
+                    target: (&target.ref_to()).into(),
+                    token: address::nam(),
+                    amount: Amount::native_whole(1).native_denominated(),
+                    key: None,
+                    shielded: None,
+                },
+                None,
+                None,
+                Some(&keypair),
+            )
+        })
+        .collect()
+}
+
+fn parallel_txs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_txs");
+    let mut shell = BenchShell::default();
+    let initial_write_log = shell.wl_storage.write_log.clone();
+
+    for (txs_name, independent) in
+        [("independent", true), ("conflicting", false)]
+    {
+        let txs = transfers(independent);
+
+        group.bench_function(format!("sequential_{txs_name}"), |b| {
+            b.iter_batched(
+                || initial_write_log.clone(),
+                |write_log| {
+                    shell.wl_storage.write_log = write_log;
+                    for (index, tx) in txs.iter().enumerate() {
+                        let result = apply_wasm_tx(
+                            tx.clone(),
+                            &TxIndex(index as u32),
+                            ShellParams::new(
+                                &mut TxGasMeter::new_from_sub_limit(
+                                    TX_GAS_LIMIT.into(),
+                                ),
+                                &mut shell.inner.wl_storage,
+                                &mut shell.inner.vp_wasm_cache,
+                                &mut shell.inner.tx_wasm_cache,
+                            ),
+                        )
+                        .unwrap();
+                        assert!(result.is_accepted());
+                        shell.wl_storage.commit_tx();
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+
+        group.bench_function(format!("parallel_{txs_name}"), |b| {
+            b.iter_batched(
+                || initial_write_log.clone(),
+                |write_log| {
+                    shell.wl_storage.write_log = write_log;
+                    let inputs = txs
+                        .iter()
+                        .enumerate()
+                        .map(|(index, tx)| SpeculativeTxInput {
+                            tx_index: TxIndex(index as u32),
+                            tx: tx.clone(),
+                            tx_gas_meter: TxGasMeter::new_from_sub_limit(
+                                TX_GAS_LIMIT.into(),
+                            ),
+                        })
+                        .collect();
+                    let mut speculative_txs = SpeculativeTxs::execute(
+                        inputs,
+                        &shell.inner.wl_storage,
+                        &shell.inner.vp_wasm_cache,
+                        &shell.inner.tx_wasm_cache,
+                    );
+                    for (index, tx) in txs.iter().enumerate() {
+                        let result = speculative_txs
+                            .apply_tx(
+                                tx.clone(),
+                                TxIndex(index as u32),
+                                &mut TxGasMeter::new_from_sub_limit(
+                                    TX_GAS_LIMIT.into(),
+                                ),
+                                &mut shell.inner.wl_storage,
+                                &mut shell.inner.vp_wasm_cache,
+                                &mut shell.inner.tx_wasm_cache,
+                            )
+                            .unwrap();
+                        assert!(result.is_accepted());
+                        shell.wl_storage.commit_tx();
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(parallel_execution, parallel_txs);
+criterion_main!(parallel_execution);
@@ -407,6 +407,7 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    is_expedited: false,
                                 },
                                 None,
                                 Some(vec![content_section]),
@@ -459,6 +460,7 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    is_expedited: false,
                                 },
                                 None,
                                 Some(vec![content_section, wasm_code_section]),
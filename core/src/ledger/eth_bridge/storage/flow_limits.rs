@@ -0,0 +1,357 @@
+//! ERC20 token flow limits storage data.
+//!
+//! Flow limits bound the amount of some asset that may cross the
+//! Ethereum bridge, in either direction, during a single epoch.
+//!
+//! The limits should only ever be written to by governance. The
+//! flows themselves are updated by transfers to the Bridge pool
+//! (outbound), and by the protocol, when wrapped ERC20 assets are
+//! minted on Namada (inbound).
+
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+
+use super::super::ADDRESS as BRIDGE_ADDRESS;
+use super::prefix as ethbridge_key_prefix;
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::ethereum_events::EthAddress;
+use crate::types::storage;
+use crate::types::storage::{DbKeySeg, Epoch};
+use crate::types::token::Amount;
+
+mod segments {
+    //! Storage key segments under the flow limits sub-space.
+    use namada_macros::StorageKeys;
+
+    use crate::types::address::Address;
+    use crate::types::storage::{DbKeySeg, Key};
+
+    /// The name of the main storage segment.
+    pub(super) const MAIN_SEGMENT: &str = "flow_limits";
+
+    /// Storage key segments under the flow limits sub-space.
+    #[derive(StorageKeys)]
+    pub(super) struct Segments {
+        /// The maximum amount of an asset that may enter Namada
+        /// in a single epoch.
+        pub inbound_limit: &'static str,
+        /// The maximum amount of an asset that may leave Namada
+        /// in a single epoch.
+        pub outbound_limit: &'static str,
+        /// The amount of an asset that entered Namada in the
+        /// last recorded epoch.
+        pub inbound_flow: &'static str,
+        /// The amount of an asset that left Namada in the
+        /// last recorded epoch.
+        pub outbound_flow: &'static str,
+    }
+
+    /// All the values of the generated [`Segments`].
+    pub(super) const VALUES: Segments = Segments::VALUES;
+
+    /// Listing of each of the generated [`Segments`].
+    pub(super) const ALL: &[&str] = Segments::ALL;
+}
+
+/// The direction in which some asset crosses the Ethereum bridge.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum FlowDirection {
+    /// Transfers from Ethereum to Namada.
+    Inbound,
+    /// Transfers from Namada to Ethereum.
+    Outbound,
+}
+
+/// Represents the type of a key relating to ERC20 flow limits.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum KeyType {
+    /// The per epoch flow limit of an ERC20 asset, in some direction.
+    Limit(FlowDirection),
+    /// The flow of an ERC20 asset in the last recorded epoch, in
+    /// some direction.
+    Flow(FlowDirection),
+}
+
+/// ERC20 token flow limits storage sub-space.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Key {
+    /// The specific ERC20 as identified by its Ethereum address.
+    pub asset: EthAddress,
+    /// The type of this key.
+    pub suffix: KeyType,
+}
+
+/// The amount of some asset that crossed the Ethereum bridge
+/// in a given epoch.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct EpochedFlow {
+    /// The epoch this flow was recorded in.
+    pub epoch: Epoch,
+    /// The amount that crossed the bridge in `epoch`.
+    pub amount: Amount,
+}
+
+impl EpochedFlow {
+    /// Return the amount that crossed the bridge in the given `epoch`.
+    ///
+    /// Flows recorded in past epochs do not count towards the
+    /// flow of the current epoch.
+    #[inline]
+    pub fn amount_at(&self, epoch: Epoch) -> Amount {
+        if self.epoch == epoch {
+            self.amount
+        } else {
+            Amount::zero()
+        }
+    }
+
+    /// Record a new flow of `amount` in the given `epoch`, checking
+    /// for overflows.
+    #[inline]
+    pub fn checked_add_at(&self, epoch: Epoch, amount: Amount) -> Option<Self> {
+        Some(Self {
+            epoch,
+            amount: self.amount_at(epoch).checked_add(amount)?,
+        })
+    }
+}
+
+/// Return the flow limits storage key sub-space prefix.
+fn flow_limits_prefix(asset: &EthAddress) -> storage::Key {
+    ethbridge_key_prefix()
+        .push(&segments::MAIN_SEGMENT.to_owned())
+        .expect("Should be able to push a storage key segment")
+        .push(&asset.to_canonical())
+        .expect("Should be able to push a storage key segment")
+}
+
+impl From<Key> for storage::Key {
+    #[inline]
+    fn from(key: Key) -> Self {
+        (&key).into()
+    }
+}
+
+impl From<&Key> for storage::Key {
+    fn from(key: &Key) -> Self {
+        let segment = match &key.suffix {
+            KeyType::Limit(FlowDirection::Inbound) => {
+                segments::VALUES.inbound_limit
+            }
+            KeyType::Limit(FlowDirection::Outbound) => {
+                segments::VALUES.outbound_limit
+            }
+            KeyType::Flow(FlowDirection::Inbound) => {
+                segments::VALUES.inbound_flow
+            }
+            KeyType::Flow(FlowDirection::Outbound) => {
+                segments::VALUES.outbound_flow
+            }
+        };
+        flow_limits_prefix(&key.asset)
+            .push(&segment.to_owned())
+            .expect("Should be able to push a storage key segment")
+    }
+}
+
+/// Parse some [`storage::Key`] as an Ethereum bridge flow limits key.
+pub fn is_flow_limits_key(key: &storage::Key) -> Option<Key> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(s1),
+            DbKeySeg::StringSeg(s2),
+            DbKeySeg::StringSeg(s3),
+            DbKeySeg::StringSeg(s4),
+        ] if s1 == &BRIDGE_ADDRESS
+            && s2 == segments::MAIN_SEGMENT
+            && segments::ALL.binary_search(&s4.as_str()).is_ok() =>
+        {
+            let asset = EthAddress::from_str(s3).ok()?;
+            let suffix = if s4 == segments::VALUES.inbound_limit {
+                KeyType::Limit(FlowDirection::Inbound)
+            } else if s4 == segments::VALUES.outbound_limit {
+                KeyType::Limit(FlowDirection::Outbound)
+            } else if s4 == segments::VALUES.inbound_flow {
+                KeyType::Flow(FlowDirection::Inbound)
+            } else {
+                KeyType::Flow(FlowDirection::Outbound)
+            };
+            Some(Key { asset, suffix })
+        }
+        _ => None,
+    }
+}
+
+/// Read the per epoch flow limit of `asset` in the given `direction`.
+///
+/// If no limit has been configured, return [`None`].
+pub fn read_flow_limit<S>(
+    storage: &S,
+    asset: &EthAddress,
+    direction: FlowDirection,
+) -> storage_api::Result<Option<Amount>>
+where
+    S: StorageRead,
+{
+    let key = Key {
+        asset: *asset,
+        suffix: KeyType::Limit(direction),
+    }
+    .into();
+    storage.read(&key)
+}
+
+/// Read the last recorded flow of `asset` in the given `direction`.
+pub fn read_flow<S>(
+    storage: &S,
+    asset: &EthAddress,
+    direction: FlowDirection,
+) -> storage_api::Result<EpochedFlow>
+where
+    S: StorageRead,
+{
+    let key = Key {
+        asset: *asset,
+        suffix: KeyType::Flow(direction),
+    }
+    .into();
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Return the amount of `asset` that may still cross the Ethereum
+/// bridge in the given `direction`, during the current epoch.
+///
+/// If no limit has been configured, return [`None`].
+pub fn remaining_flow<S>(
+    storage: &S,
+    asset: &EthAddress,
+    direction: FlowDirection,
+) -> storage_api::Result<Option<Amount>>
+where
+    S: StorageRead,
+{
+    let Some(limit) = read_flow_limit(storage, asset, direction)? else {
+        return Ok(None);
+    };
+    let epoch = storage.get_block_epoch()?;
+    let flow = read_flow(storage, asset, direction)?.amount_at(epoch);
+    Ok(Some(limit.checked_sub(flow).unwrap_or_default()))
+}
+
+/// Record a flow of `amount` of `asset` in the given `direction`,
+/// during the current epoch. Flows from past epochs are discarded.
+///
+/// Returns an error if the configured flow limit of `asset` would
+/// be exceeded.
+pub fn record_flow<S>(
+    storage: &mut S,
+    asset: &EthAddress,
+    direction: FlowDirection,
+    amount: Amount,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let epoch = storage.get_block_epoch()?;
+    let flow = read_flow(storage, asset, direction)?
+        .checked_add_at(epoch, amount)
+        .ok_or_else(|| {
+            storage_api::Error::new_const("Ethereum bridge flow overflowed")
+        })?;
+    if let Some(limit) = read_flow_limit(storage, asset, direction)? {
+        if flow.amount > limit {
+            return Err(storage_api::Error::new_const(
+                "Ethereum bridge flow limit exceeded",
+            ));
+        }
+    }
+    let key = Key {
+        asset: *asset,
+        suffix: KeyType::Flow(direction),
+    }
+    .into();
+    storage.write(&key, flow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
+
+    /// Test that storage key serialization yields the expected value.
+    #[test]
+    fn test_keys_flow_limits_to_string() {
+        let key: storage::Key = Key {
+            asset: DAI_ERC20_ETH_ADDRESS,
+            suffix: KeyType::Limit(FlowDirection::Outbound),
+        }
+        .into();
+        let expected = "#atest1v9hx7w36g42ysgzzwf5kgem9ypqkgerjv4ehxgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpq8f99ew/flow_limits/0x6b175474e89094c44da98b954eedeac495271d0f/outbound_limit";
+        assert_eq!(expected, key.to_string());
+    }
+
+    /// Test that parsing flow limits keys round trips.
+    #[test]
+    fn test_is_flow_limits_key() {
+        for suffix in [
+            KeyType::Limit(FlowDirection::Inbound),
+            KeyType::Limit(FlowDirection::Outbound),
+            KeyType::Flow(FlowDirection::Inbound),
+            KeyType::Flow(FlowDirection::Outbound),
+        ] {
+            let key = Key {
+                asset: DAI_ERC20_ETH_ADDRESS,
+                suffix,
+            };
+            let storage_key: storage::Key = (&key).into();
+            assert_eq!(is_flow_limits_key(&storage_key), Some(key));
+        }
+
+        let unexpected_key = {
+            let mut k: storage::Key = Key {
+                asset: DAI_ERC20_ETH_ADDRESS,
+                suffix: KeyType::Flow(FlowDirection::Inbound),
+            }
+            .into();
+            k.segments[3] = DbKeySeg::StringSeg("abc".to_owned());
+            k
+        };
+        assert!(is_flow_limits_key(&unexpected_key).is_none());
+    }
+
+    /// Test that flows from past epochs are discarded.
+    #[test]
+    fn test_epoched_flow_resets() {
+        let flow = EpochedFlow {
+            epoch: Epoch(1),
+            amount: Amount::from(100),
+        };
+        assert_eq!(flow.amount_at(Epoch(1)), Amount::from(100));
+        assert_eq!(flow.amount_at(Epoch(2)), Amount::zero());
+        assert_eq!(
+            flow.checked_add_at(Epoch(2), Amount::from(5)),
+            Some(EpochedFlow {
+                epoch: Epoch(2),
+                amount: Amount::from(5),
+            })
+        );
+        assert_eq!(
+            flow.checked_add_at(Epoch(1), Amount::from(5)),
+            Some(EpochedFlow {
+                epoch: Epoch(1),
+                amount: Amount::from(105),
+            })
+        );
+    }
+}
@@ -5,6 +5,7 @@ use std::fmt::Display;
 use std::ops::Div;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use super::parameters;
@@ -24,21 +25,72 @@ pub enum Error {
     ConversionError,
 }
 
-const TX_SIZE_GAS_PER_BYTE: u64 = 10;
-const COMPILE_GAS_PER_BYTE: u64 = 1;
 const PARALLEL_GAS_DIVIDER: u64 = 10;
 
-/// The cost of accessing the storage, per byte
-pub const STORAGE_ACCESS_GAS_PER_BYTE: u64 = 1;
-/// The cost of writing to storage, per byte
-pub const STORAGE_WRITE_GAS_PER_BYTE: u64 = 100;
-/// The cost of verifying a signle signature of a transaction
-pub const VERIFY_TX_SIG_GAS_COST: u64 = 10;
-/// The cost of accessing the WASM memory, per byte
-pub const VM_MEMORY_ACCESS_GAS_PER_BYTE: u64 = 1;
-/// The cost for requesting one more page in wasm (64KB)
+/// The cost for requesting one more page in wasm (64KB). Unlike the costs in
+/// [`GasCosts`], this one is injected into the WASM code before it's compiled,
+/// so it cannot change without invalidating the compilation caches.
 pub const WASM_MEMORY_PAGE_GAS_COST: u32 = 100;
 
+/// The table of gas costs. It's a protocol parameter, so that governance can
+/// adopt the tables proposed by the `gas_calibration` benchmark. The table in
+/// effect is loaded from storage into the ledger's state and passed down from
+/// there to everything that charges gas.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct GasCosts {
+    /// The cost of the space that a tx takes in a block, per byte
+    pub tx_size_per_byte: u64,
+    /// The cost of compiling WASM code, per byte
+    pub compile_per_byte: u64,
+    /// The cost of accessing the storage, per byte
+    pub storage_access_per_byte: u64,
+    /// The cost of writing to storage, per byte
+    pub storage_write_per_byte: u64,
+    /// The cost of verifying a single signature of a transaction
+    pub verify_tx_sig: u64,
+    /// The cost of verifying a signature over arbitrary data, not including
+    /// the hashing of the data
+    pub verify_sig: u64,
+    /// The cost of hashing data, per byte
+    pub hash_per_byte: u64,
+    /// The cost of accessing the WASM memory, per byte
+    pub vm_memory_access_per_byte: u64,
+}
+
+impl GasCosts {
+    /// The initial gas costs
+    pub const DEFAULT: Self = Self {
+        tx_size_per_byte: 10,
+        compile_per_byte: 1,
+        storage_access_per_byte: 1,
+        storage_write_per_byte: 100,
+        verify_tx_sig: 10,
+        verify_sig: 10,
+        hash_per_byte: 2,
+        vm_memory_access_per_byte: 1,
+    };
+}
+
+impl Default for GasCosts {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// Gas module result for functions that may fail
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -150,19 +202,27 @@ pub trait GasMetering {
     fn consume(&mut self, gas: u64) -> Result<()>;
 
     /// Add the compiling cost proportionate to the code length
-    fn add_compiling_gas(&mut self, bytes_len: u64) -> Result<()> {
+    fn add_compiling_gas(
+        &mut self,
+        bytes_len: u64,
+        gas_costs: &GasCosts,
+    ) -> Result<()> {
         self.consume(
             bytes_len
-                .checked_mul(COMPILE_GAS_PER_BYTE)
+                .checked_mul(gas_costs.compile_per_byte)
                 .ok_or(Error::GasOverflow)?,
         )
     }
 
     /// Add the gas for loading the wasm code from storage
-    fn add_wasm_load_from_storage_gas(&mut self, bytes_len: u64) -> Result<()> {
+    fn add_wasm_load_from_storage_gas(
+        &mut self,
+        bytes_len: u64,
+        gas_costs: &GasCosts,
+    ) -> Result<()> {
         self.consume(
             bytes_len
-                .checked_mul(STORAGE_ACCESS_GAS_PER_BYTE)
+                .checked_mul(gas_costs.storage_access_per_byte)
                 .ok_or(Error::GasOverflow)?,
         )
     }
@@ -245,14 +305,18 @@ impl TxGasMeter {
     }
 
     /// Add the gas for the space that the transaction requires in the block
-    pub fn add_tx_size_gas(&mut self, tx_bytes: &[u8]) -> Result<()> {
+    pub fn add_tx_size_gas(
+        &mut self,
+        tx_bytes: &[u8],
+        gas_costs: &GasCosts,
+    ) -> Result<()> {
         let bytes_len: u64 = tx_bytes
             .len()
             .try_into()
             .map_err(|_| Error::ConversionError)?;
         self.consume(
             bytes_len
-                .checked_mul(TX_SIZE_GAS_PER_BYTE)
+                .checked_mul(gas_costs.tx_size_per_byte)
                 .ok_or(Error::GasOverflow)?,
         )
     }
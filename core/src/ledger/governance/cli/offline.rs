@@ -1,18 +1,22 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
 use std::fs::{File, ReadDir};
 use std::path::PathBuf;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use super::onchain::ProposalVote;
 use super::validation::{is_valid_tally_epoch, ProposalValidation};
+use crate::ledger::governance::utils::{ProposalResult, TallyResult};
 use crate::proto::SignatureIndex;
 use crate::types::account::AccountPublicKeysMap;
 use crate::types::address::Address;
 use crate::types::hash::Hash;
 use crate::types::key::{common, RefTo, SigScheme};
 use crate::types::storage::Epoch;
+use crate::types::token;
 
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -246,6 +250,16 @@ impl OfflineVote {
         self.vote.is_yay()
     }
 
+    /// Check if the vote is abstain
+    pub fn is_abstain(&self) -> bool {
+        self.vote.is_abstain()
+    }
+
+    /// Check if the vote is nay with veto
+    pub fn is_veto(&self) -> bool {
+        self.vote.is_veto()
+    }
+
     /// compute the hash of a proposal
     pub fn compute_hash(&self) -> Hash {
         let proposal_hash_data = self
@@ -309,6 +323,208 @@ impl OfflineVote {
     }
 }
 
+/// The reason an offline vote is left out of a tally
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum OfflineVoteRejection {
+    /// The vote is for a different proposal
+    WrongProposal,
+    /// The vote is neither yay, nay, abstain nor veto
+    InvalidVote,
+    /// The vote signatures don't reach the threshold of the voter account
+    InvalidSignature,
+    /// The voter cast different votes
+    ConflictingVotes,
+    /// The voter has no stake at the tally epoch
+    NoVotingPower,
+}
+
+impl Display for OfflineVoteRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OfflineVoteRejection::WrongProposal => {
+                write!(f, "vote for a different proposal")
+            }
+            OfflineVoteRejection::InvalidVote => write!(f, "invalid vote"),
+            OfflineVoteRejection::InvalidSignature => {
+                write!(f, "invalid signature")
+            }
+            OfflineVoteRejection::ConflictingVotes => {
+                write!(f, "conflicting votes")
+            }
+            OfflineVoteRejection::NoVotingPower => {
+                write!(f, "no voting power at the tally epoch")
+            }
+        }
+    }
+}
+
+/// Deduplicate the offline votes by voter address. Copies of the same vote
+/// are counted once, while an address that cast different votes gets all of
+/// its votes rejected, so that the outcome doesn't depend on the order in
+/// which the votes are read. Returns the unique votes and the addresses with
+/// conflicting votes.
+pub fn dedup_offline_votes(
+    votes: Vec<OfflineVote>,
+) -> (BTreeMap<Address, OfflineVote>, BTreeSet<Address>) {
+    let mut unique_votes: BTreeMap<Address, OfflineVote> = BTreeMap::new();
+    let mut conflicting = BTreeSet::new();
+    for vote in votes {
+        match unique_votes.get(&vote.address) {
+            Some(other) if other.compute_hash() != vote.compute_hash() => {
+                conflicting.insert(vote.address.clone());
+            }
+            Some(_) => {}
+            None => {
+                unique_votes.insert(vote.address.clone(), vote);
+            }
+        }
+    }
+    for address in &conflicting {
+        unique_votes.remove(address);
+    }
+    (unique_votes, conflicting)
+}
+
+/// Error from writing an offline tally report to file
+#[derive(Error, Debug)]
+pub enum OfflineTallyReportError {
+    /// The report file couldn't be written
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The report couldn't be encoded
+    #[error("JSON encoding error: {0}")]
+    Encoding(#[from] serde_json::Error),
+}
+
+/// A vote counted in the tally of an offline proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineTallyVote {
+    /// The vote
+    pub vote: ProposalVote,
+    /// The stake of the voter at the tally epoch, including only the bonds
+    /// to the delegated validators for delegators
+    pub voting_power: token::Amount,
+}
+
+/// A reproducible report of the tally of an offline proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineTallyReport {
+    /// The hash of the offline proposal
+    pub proposal_hash: Hash,
+    /// The epoch of the stake used to tally the votes
+    pub tally_epoch: Epoch,
+    /// Whether the proposal passed
+    pub passed: bool,
+    /// The total voting power at the tally epoch
+    pub total_voting_power: token::Amount,
+    /// The total voting power from yay votes
+    pub total_yay_power: token::Amount,
+    /// The total voting power from nay votes
+    pub total_nay_power: token::Amount,
+    /// The total voting power from abstain votes
+    pub total_abstain_power: token::Amount,
+    /// The total voting power from nay with veto votes
+    pub total_veto_power: token::Amount,
+    /// The counted votes, by voter address
+    pub votes: BTreeMap<Address, OfflineTallyVote>,
+    /// The reasons the other votes were rejected, by voter address
+    pub rejected_votes: BTreeMap<Address, BTreeSet<OfflineVoteRejection>>,
+}
+
+impl OfflineTallyReport {
+    /// Build the report of an offline tally
+    pub fn new(
+        proposal_hash: Hash,
+        tally_epoch: Epoch,
+        result: &ProposalResult,
+        votes: BTreeMap<Address, OfflineTallyVote>,
+        rejected_votes: BTreeMap<Address, BTreeSet<OfflineVoteRejection>>,
+    ) -> Self {
+        Self {
+            proposal_hash,
+            tally_epoch,
+            passed: matches!(result.result, TallyResult::Passed),
+            total_voting_power: result.total_voting_power,
+            total_yay_power: result.total_yay_power,
+            total_nay_power: result.total_nay_power,
+            total_abstain_power: result.total_abstain_power,
+            total_veto_power: result.total_veto_power,
+            votes,
+            rejected_votes,
+        }
+    }
+
+    /// Serialize the report to file. Returns the file path if successful.
+    pub fn serialize(
+        &self,
+        output_folder: Option<PathBuf>,
+    ) -> Result<PathBuf, OfflineTallyReportError> {
+        let report_filename = format!(
+            "offline_tally_{}_{}.json",
+            self.proposal_hash, self.tally_epoch
+        );
+        let filepath = match output_folder {
+            Some(base_path) => base_path.join(report_filename),
+            None => PathBuf::from(report_filename),
+        };
+        let out = File::create(&filepath)?;
+        serde_json::to_writer_pretty(out, self)?;
+
+        Ok(filepath)
+    }
+}
+
+impl Display for OfflineTallyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Proposal offline: {}", self.proposal_hash)?;
+        writeln!(f, "Tally epoch: {}", self.tally_epoch)?;
+        writeln!(
+            f,
+            "Result: {}",
+            if self.passed { "passed" } else { "rejected" }
+        )?;
+        writeln!(
+            f,
+            "{:4}Total voting power: {}",
+            "",
+            self.total_voting_power.to_string_native()
+        )?;
+        writeln!(
+            f,
+            "{:4}Yay: {}, nay: {}, abstain: {}, veto: {}",
+            "",
+            self.total_yay_power.to_string_native(),
+            self.total_nay_power.to_string_native(),
+            self.total_abstain_power.to_string_native(),
+            self.total_veto_power.to_string_native()
+        )?;
+        write!(f, "Counted votes: {}", self.votes.len())?;
+        for (address, vote) in &self.votes {
+            write!(
+                f,
+                "\n{:4}- {}: {} with {}",
+                "",
+                address,
+                vote.vote,
+                vote.voting_power.to_string_native()
+            )?;
+        }
+        if !self.rejected_votes.is_empty() {
+            write!(f, "\nRejected votes: {}", self.rejected_votes.len())?;
+            for (address, reasons) in &self.rejected_votes {
+                let reasons = reasons
+                    .iter()
+                    .map(|reason| reason.to_string())
+                    .collect::<Vec<String>>();
+                write!(f, "\n{:4}- {}: {}", "", address, reasons.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Compute the signatures index
 fn compute_signatures_index(
     keys: &[common::SecretKey],
@@ -391,3 +607,163 @@ pub fn find_offline_votes(files: &[PathBuf]) -> Vec<PathBuf> {
         .cloned()
         .collect::<Vec<PathBuf>>()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::ledger::governance::utils::{
+        compute_proposal_result, ProposalVotes, TallyType,
+    };
+    use crate::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+
+    fn vote(address: Address, vote: ProposalVote) -> OfflineVote {
+        OfflineVote {
+            proposal_hash: Hash::sha256(b"proposal"),
+            vote,
+            signatures: BTreeSet::default(),
+            address,
+            delegations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_dedup_offline_votes() {
+        let voter = established_address_1();
+        let equivocator = established_address_2();
+        let votes = vec![
+            vote(voter.clone(), ProposalVote::Yay),
+            vote(equivocator.clone(), ProposalVote::Yay),
+            vote(voter.clone(), ProposalVote::Yay),
+            vote(equivocator.clone(), ProposalVote::Nay),
+        ];
+
+        let (unique_votes, conflicting) = dedup_offline_votes(votes.clone());
+        assert_eq!(unique_votes.len(), 1);
+        assert!(unique_votes[&voter].is_yay());
+        assert_eq!(conflicting, BTreeSet::from([equivocator]));
+
+        // The outcome doesn't depend on the order of the votes
+        let (reversed_votes, reversed_conflicting) =
+            dedup_offline_votes(votes.into_iter().rev().collect());
+        assert_eq!(
+            reversed_votes.keys().collect::<Vec<_>>(),
+            unique_votes.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(reversed_conflicting, conflicting);
+    }
+
+    /// Tally the given validator votes the way the client does and build the
+    /// report
+    fn tally(
+        votes: Vec<(OfflineVote, u64)>,
+        total_voting_power: u64,
+    ) -> OfflineTallyReport {
+        let mut validators_vote = HashMap::new();
+        let mut validator_voting_power = HashMap::new();
+        let mut counted_votes = BTreeMap::new();
+        for (vote, power) in votes {
+            let voting_power = token::Amount::native_whole(power);
+            counted_votes.insert(
+                vote.address.clone(),
+                OfflineTallyVote {
+                    vote: vote.vote.clone(),
+                    voting_power,
+                },
+            );
+            validator_voting_power.insert(vote.address.clone(), voting_power);
+            validators_vote.insert(vote.address.clone(), vote.into());
+        }
+        let result = compute_proposal_result(
+            ProposalVotes {
+                validators_vote,
+                validator_voting_power,
+                delegators_vote: HashMap::new(),
+                delegator_voting_power: HashMap::new(),
+            },
+            token::Amount::native_whole(total_voting_power),
+            TallyType::TwoThirdWithVeto,
+        );
+        OfflineTallyReport::new(
+            Hash::sha256(b"proposal"),
+            Epoch(3),
+            &result,
+            counted_votes,
+            BTreeMap::from([(
+                established_address_3(),
+                BTreeSet::from([OfflineVoteRejection::InvalidSignature]),
+            )]),
+        )
+    }
+
+    #[test]
+    fn test_offline_tally_report() {
+        let yay_voter = established_address_1();
+        let nay_voter = established_address_2();
+
+        let report = tally(
+            vec![
+                (vote(yay_voter.clone(), ProposalVote::Yay), 50),
+                (vote(nay_voter.clone(), ProposalVote::Nay), 10),
+            ],
+            90,
+        );
+        assert!(report.passed);
+        assert_eq!(report.total_voting_power, token::Amount::native_whole(90));
+        assert_eq!(report.total_yay_power, token::Amount::native_whole(50));
+        assert_eq!(report.total_nay_power, token::Amount::native_whole(10));
+        assert_eq!(report.votes.len(), 2);
+        assert_eq!(
+            report.votes[&nay_voter].voting_power,
+            token::Amount::native_whole(10)
+        );
+        assert_eq!(report.rejected_votes.len(), 1);
+
+        // More than a third of the voted power vetoed
+        let report = tally(
+            vec![
+                (vote(yay_voter, ProposalVote::Yay), 50),
+                (vote(nay_voter, ProposalVote::NayWithVeto), 30),
+            ],
+            90,
+        );
+        assert!(!report.passed);
+        assert_eq!(report.total_veto_power, token::Amount::native_whole(30));
+    }
+
+    #[test]
+    fn test_serialize_offline_tally_report() {
+        let report = tally(
+            vec![(vote(established_address_1(), ProposalVote::Yay), 70)],
+            90,
+        );
+        let output_folder = tempfile::tempdir().unwrap();
+
+        let path = report
+            .serialize(Some(output_folder.path().to_path_buf()))
+            .unwrap();
+        assert_eq!(path.parent(), Some(output_folder.path()));
+        let file = File::open(&path).unwrap();
+        let decoded: OfflineTallyReport =
+            serde_json::from_reader(file).unwrap();
+        assert_eq!(decoded.proposal_hash, report.proposal_hash);
+        assert_eq!(decoded.tally_epoch, report.tally_epoch);
+        assert_eq!(decoded.passed, report.passed);
+        assert_eq!(decoded.total_yay_power, report.total_yay_power);
+        assert_eq!(
+            decoded.votes.keys().collect::<Vec<_>>(),
+            report.votes.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(decoded.rejected_votes, report.rejected_votes);
+
+        // Writing to a missing folder is an error rather than a panic
+        let missing_folder = output_folder.path().join("missing");
+        assert!(matches!(
+            report.serialize(Some(missing_folder)),
+            Err(OfflineTallyReportError::Io(_))
+        ));
+    }
+}
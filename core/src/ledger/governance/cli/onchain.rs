@@ -1,17 +1,23 @@
 use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 use super::validation::{
     is_valid_author_balance, is_valid_content, is_valid_default_proposal_data,
-    is_valid_end_epoch, is_valid_grace_epoch, is_valid_pgf_funding_data,
+    is_valid_end_epoch, is_valid_grace_epoch, is_valid_not_expedited,
+    is_valid_parameter_change_data, is_valid_pgf_funding_data,
     is_valid_pgf_stewards_data, is_valid_proposal_period, is_valid_start_epoch,
-    ProposalValidation,
+    is_valid_treasury_data, is_valid_upgrade_data, ProposalValidation,
 };
 use crate::ledger::governance::parameters::GovernanceParameters;
+use crate::ledger::governance::storage::parameter_change::ParameterChange;
+use crate::ledger::governance::storage::proposal::TreasuryTransfer;
 use crate::ledger::storage_api::token;
 use crate::types::address::Address;
+use crate::types::dec::Dec;
 use crate::types::storage::Epoch;
 
 #[derive(
@@ -31,6 +37,25 @@ pub struct OnChainProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// Whether the proposal is expedited, with a shorter voting period and a
+    /// higher yay threshold
+    #[serde(default)]
+    pub is_expedited: bool,
+}
+
+impl OnChainProposal {
+    /// Return the minimum voting period of the proposal, which is shorter for
+    /// expedited proposals
+    pub fn min_voting_period(
+        &self,
+        governance_parameters: &GovernanceParameters,
+    ) -> u64 {
+        if self.is_expedited {
+            governance_parameters.min_expedited_proposal_voting_period
+        } else {
+            governance_parameters.min_proposal_voting_period
+        }
+    }
 }
 
 /// Pgf default proposal
@@ -56,17 +81,19 @@ impl DefaultProposal {
         if force {
             return Ok(self);
         }
+        let min_voting_period =
+            self.proposal.min_voting_period(governance_parameters);
         is_valid_start_epoch(
             self.proposal.voting_start_epoch,
             current_epoch,
-            governance_parameters.min_proposal_voting_period,
+            min_voting_period,
         )?;
         is_valid_end_epoch(
             self.proposal.voting_start_epoch,
             self.proposal.voting_end_epoch,
             current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
+            min_voting_period,
+            min_voting_period,
             governance_parameters.max_proposal_period,
         )?;
         is_valid_grace_epoch(
@@ -134,6 +161,7 @@ impl PgfStewardProposal {
         if force {
             return Ok(self);
         }
+        is_valid_not_expedited(self.proposal.is_expedited)?;
         is_valid_start_epoch(
             self.proposal.voting_start_epoch,
             current_epoch,
@@ -201,6 +229,7 @@ impl PgfFundingProposal {
         if force {
             return Ok(self);
         }
+        is_valid_not_expedited(self.proposal.is_expedited)?;
         is_valid_start_epoch(
             self.proposal.voting_start_epoch,
             current_epoch,
@@ -228,7 +257,7 @@ impl PgfFundingProposal {
             &self.proposal.content,
             governance_parameters.max_proposal_content_size,
         )?;
-        is_valid_pgf_funding_data(&self.data)?;
+        is_valid_pgf_funding_data(&self.data, self.proposal.grace_epoch)?;
 
         Ok(self)
     }
@@ -242,6 +271,230 @@ impl TryFrom<&[u8]> for PgfFundingProposal {
     }
 }
 
+/// Parameter change proposal
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ParameterChangeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The parameter changes applied if the proposal passes
+    pub data: Vec<ParameterChange>,
+}
+
+impl ParameterChangeProposal {
+    /// Validate a parameter change proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        let min_voting_period =
+            self.proposal.min_voting_period(governance_parameters);
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            min_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            min_voting_period,
+            min_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_grace_epoch(
+            self.proposal.grace_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_parameter_change_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for ParameterChangeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
+/// Software upgrade proposal
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct UpgradeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The upgrade scheduled if the proposal passes
+    pub data: UpgradeData,
+}
+
+impl UpgradeProposal {
+    /// Validate a software upgrade proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_not_expedited(self.proposal.is_expedited)?;
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_grace_epoch(
+            self.proposal.grace_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_upgrade_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for UpgradeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
+/// Treasury spending proposal
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct TreasuryProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The transfers from the treasury executed if the proposal passes
+    pub data: Vec<TreasuryTransfer>,
+}
+
+impl TreasuryProposal {
+    /// Validate a treasury spending proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_not_expedited(self.proposal.is_expedited)?;
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_grace_epoch(
+            self.proposal.grace_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_treasury_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for TreasuryProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
+/// Software upgrade proposal extra data
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct UpgradeData {
+    /// The name of the upgrade
+    pub name: String,
+    /// The block height at which the upgrade takes place
+    pub height: u64,
+    /// The hex encoded sha256 checksums of the node binaries, by target triple
+    pub binary_checksums: BTreeMap<String, String>,
+    /// The hex encoded sha256 checksums of the protocol wasms, by wasm name
+    #[serde(default)]
+    pub wasm_checksums: BTreeMap<String, String>,
+}
+
 /// Pgf stewards
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -277,9 +530,12 @@ impl PgfAction {
 )]
 pub struct PgfFunding {
     /// Pgf continous funding
-    pub continous: Vec<PgfFundingTarget>,
+    pub continous: Vec<PgfFundingStream>,
     /// pgf retro fundings
     pub retro: Vec<PgfFundingTarget>,
+    /// Targets whose funding stream should be cancelled
+    #[serde(default)]
+    pub cancel: Vec<Address>,
 }
 
 /// Pgf continous funding
@@ -288,7 +544,7 @@ pub struct PgfFunding {
 )]
 pub struct PgfContinous {
     /// Pgf target
-    pub target: PgfFundingTarget,
+    pub target: PgfFundingStream,
     /// Pgf action
     pub action: PgfAction,
 }
@@ -313,6 +569,23 @@ pub struct PgfFundingTarget {
     pub address: Address,
 }
 
+/// Pgf funding stream
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct PgfFundingStream {
+    /// Amount paid out every epoch
+    pub amount: token::Amount,
+    /// Target address
+    pub address: Address,
+    /// First epoch of the stream
+    pub start_epoch: Epoch,
+    /// Last epoch of the stream
+    pub end_epoch: Epoch,
+    /// Total budget of the stream
+    pub budget: token::Amount,
+}
+
 /// Rappresent an proposal vote
 #[derive(
     Debug,
@@ -330,6 +603,10 @@ pub enum ProposalVote {
     Nay,
     /// Rappresent an invalid proposal vote
     Invalid,
+    /// Rappresent an abstain proposal vote
+    Abstain,
+    /// Rappresent a nay proposal vote with veto
+    NayWithVeto,
 }
 
 impl TryFrom<String> for ProposalVote {
@@ -339,14 +616,63 @@ impl TryFrom<String> for ProposalVote {
         match value.trim().to_lowercase().as_str() {
             "yay" => Ok(ProposalVote::Yay),
             "nay" => Ok(ProposalVote::Nay),
+            "abstain" => Ok(ProposalVote::Abstain),
+            "veto" => Ok(ProposalVote::NayWithVeto),
             _ => Err("invalid vote".to_string()),
         }
     }
 }
 
+/// Rappresent a proposal vote split by weight across multiple options, parsed
+/// from a list like `yay=0.6,nay=0.3,abstain=0.1`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitProposalVote(pub Vec<(ProposalVote, Dec)>);
+
+impl TryFrom<String> for SplitProposalVote {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .split(',')
+            .map(|option| {
+                let (vote, weight) = option
+                    .split_once('=')
+                    .ok_or_else(|| "invalid split vote option".to_string())?;
+                let vote = ProposalVote::try_from(vote.to_string())?;
+                let weight = Dec::from_str(weight.trim())
+                    .map_err(|_| "invalid split vote weight".to_string())?;
+                Ok((vote, weight))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(SplitProposalVote)
+    }
+}
+
 impl ProposalVote {
     /// Check if the proposal type is yay
     pub fn is_yay(&self) -> bool {
         matches!(self, ProposalVote::Yay)
     }
+
+    /// Check if the proposal vote is abstain
+    pub fn is_abstain(&self) -> bool {
+        matches!(self, ProposalVote::Abstain)
+    }
+
+    /// Check if the proposal vote is nay with veto
+    pub fn is_veto(&self) -> bool {
+        matches!(self, ProposalVote::NayWithVeto)
+    }
+}
+
+impl Display for ProposalVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProposalVote::Yay => write!(f, "yay"),
+            ProposalVote::Nay => write!(f, "nay"),
+            ProposalVote::Invalid => write!(f, "invalid"),
+            ProposalVote::Abstain => write!(f, "abstain"),
+            ProposalVote::NayWithVeto => write!(f, "veto"),
+        }
+    }
 }
@@ -1,8 +1,11 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use thiserror::Error;
 
-use super::onchain::{PgfFunding, StewardsUpdate};
+use super::onchain::{PgfFunding, StewardsUpdate, UpgradeData};
+use crate::ledger::governance::storage::parameter_change::ParameterChange;
+use crate::ledger::governance::storage::proposal::TreasuryTransfer;
+use crate::ledger::governance::storage::upgrade::UpgradePlan;
 use crate::types::address::Address;
 use crate::types::storage::Epoch;
 use crate::types::token;
@@ -69,6 +72,38 @@ pub enum ProposalValidation {
     /// The pgf funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// A pgf funding stream is not valid
+    #[error(
+        "Invalid pgf funding stream for {0}: amount and budget must be \
+         positive and the stream must end after the proposal grace epoch."
+    )]
+    InvalidPgfFundingStream(Address),
+    /// The parameter change data is not valid
+    #[error("Invalid proposal extra data: cannot be empty.")]
+    EmptyParameterChangeExtraData,
+    /// A parameter is changed more than once
+    #[error("Invalid proposal extra data: parameter {0} is changed twice.")]
+    DuplicateParameterChange(String),
+    /// A parameter value is out of its allowed range
+    #[error("Invalid proposal extra data: invalid value for {0}.")]
+    InvalidParameterChange(String),
+    /// The upgrade data is not valid
+    #[error("Invalid proposal extra data: invalid upgrade plan, {0}.")]
+    InvalidUpgradeExtraData(String),
+    /// The treasury spending data is not valid
+    #[error("Invalid proposal extra data: cannot be empty.")]
+    EmptyTreasuryExtraData,
+    /// A treasury transfer is not valid
+    #[error(
+        "Invalid treasury transfer of {0} to {1}: amount must be positive."
+    )]
+    InvalidTreasuryTransfer(Address, Address),
+    /// The proposal type can't be expedited
+    #[error(
+        "Invalid expedited proposal: only default and parameter change \
+         proposals can be expedited."
+    )]
+    InvalidExpeditedProposal,
 }
 
 pub fn is_valid_author_balance(
@@ -85,6 +120,16 @@ pub fn is_valid_author_balance(
     }
 }
 
+pub fn is_valid_not_expedited(
+    is_expedited: bool,
+) -> Result<(), ProposalValidation> {
+    if is_expedited {
+        Err(ProposalValidation::InvalidExpeditedProposal)
+    } else {
+        Ok(())
+    }
+}
+
 pub fn is_valid_start_epoch(
     proposal_start_epoch: Epoch,
     current_epoch: Epoch,
@@ -243,10 +288,77 @@ pub fn is_valid_pgf_stewards_data(
 
 pub fn is_valid_pgf_funding_data(
     data: &PgfFunding,
+    grace_epoch: Epoch,
+) -> Result<(), ProposalValidation> {
+    if data.continous.is_empty()
+        && data.retro.is_empty()
+        && data.cancel.is_empty()
+    {
+        return Err(ProposalValidation::InvalidPgfFundingExtraData);
+    }
+    match data.continous.iter().find(|stream| {
+        stream.amount.is_zero()
+            || stream.budget.is_zero()
+            || stream.start_epoch > stream.end_epoch
+            || stream.end_epoch < grace_epoch
+    }) {
+        Some(stream) => Err(ProposalValidation::InvalidPgfFundingStream(
+            stream.address.clone(),
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn is_valid_parameter_change_data(
+    data: &[ParameterChange],
 ) -> Result<(), ProposalValidation> {
-    if !data.continous.is_empty() || !data.retro.is_empty() {
+    if data.is_empty() {
+        return Err(ProposalValidation::EmptyParameterChangeExtraData);
+    }
+    let mut changed_parameters = BTreeSet::new();
+    for change in data {
+        if !changed_parameters.insert(change.name()) {
+            return Err(ProposalValidation::DuplicateParameterChange(
+                change.name(),
+            ));
+        }
+        if !change.is_valid() {
+            return Err(ProposalValidation::InvalidParameterChange(
+                change.to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn is_valid_treasury_data(
+    data: &[TreasuryTransfer],
+) -> Result<(), ProposalValidation> {
+    if data.is_empty() {
+        return Err(ProposalValidation::EmptyTreasuryExtraData);
+    }
+    match data.iter().find(|transfer| transfer.amount.is_zero()) {
+        Some(transfer) => Err(ProposalValidation::InvalidTreasuryTransfer(
+            transfer.token.clone(),
+            transfer.target.clone(),
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn is_valid_upgrade_data(
+    data: &UpgradeData,
+) -> Result<(), ProposalValidation> {
+    let plan = UpgradePlan::try_from(data.clone()).map_err(|e| {
+        ProposalValidation::InvalidUpgradeExtraData(e.to_string())
+    })?;
+    if plan.is_valid() {
         Ok(())
     } else {
-        Err(ProposalValidation::InvalidPgfFundingExtraData)
+        Err(ProposalValidation::InvalidUpgradeExtraData(
+            "the name can't be empty, the height must be positive and there \
+             must be a binary checksum"
+                .to_string(),
+        ))
     }
 }
@@ -23,6 +23,8 @@ pub struct GovernanceParameters {
     pub max_proposal_code_size: u64,
     /// Minimum proposal voting period in epochs
     pub min_proposal_voting_period: u64,
+    /// Minimum voting period in epochs of expedited proposals
+    pub min_expedited_proposal_voting_period: u64,
     /// Maximum proposal voting period in epochs
     pub max_proposal_period: u64,
     /// Maximum number of characters for proposal content
@@ -37,6 +39,7 @@ impl Default for GovernanceParameters {
             min_proposal_fund: token::Amount::native_whole(500),
             max_proposal_code_size: 300_000,
             min_proposal_voting_period: 3,
+            min_expedited_proposal_voting_period: 1,
             max_proposal_period: 27,
             max_proposal_content_size: 10_000,
             min_proposal_grace_epochs: 6,
@@ -45,6 +48,20 @@ impl Default for GovernanceParameters {
 }
 
 impl GovernanceParameters {
+    /// Check that a proposal can fit its voting period and grace epochs in
+    /// the max proposal period and that expedited proposals can be voted in
+    /// a shorter period
+    pub fn is_valid(&self) -> bool {
+        self.min_proposal_voting_period > 0
+            && self.min_expedited_proposal_voting_period > 0
+            && self.min_expedited_proposal_voting_period
+                <= self.min_proposal_voting_period
+            && self
+                .min_proposal_voting_period
+                .checked_add(self.min_proposal_grace_epochs)
+                .map_or(false, |period| period <= self.max_proposal_period)
+    }
+
     /// Initialize governance parameters into storage
     pub fn init_storage<S>(&self, storage: &mut S) -> storage_api::Result<()>
     where
@@ -54,6 +71,7 @@ impl GovernanceParameters {
             min_proposal_fund,
             max_proposal_code_size,
             min_proposal_voting_period,
+            min_expedited_proposal_voting_period,
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
@@ -74,6 +92,13 @@ impl GovernanceParameters {
             min_proposal_voting_period,
         )?;
 
+        let min_expedited_proposal_voting_period_key =
+            goverance_storage::get_min_expedited_proposal_voting_period_key();
+        storage.write(
+            &min_expedited_proposal_voting_period_key,
+            min_expedited_proposal_voting_period,
+        )?;
+
         let max_proposal_period_key =
             goverance_storage::get_max_proposal_period_key();
         storage.write(&max_proposal_period_key, max_proposal_period)?;
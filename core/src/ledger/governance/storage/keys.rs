@@ -17,15 +17,19 @@ struct Keys {
     grace_epoch: &'static str,
     funds: &'static str,
     proposal_code: &'static str,
+    expedited: &'static str,
     committing_epoch: &'static str,
     min_fund: &'static str,
     max_code_size: &'static str,
     min_period: &'static str,
+    min_expedited_period: &'static str,
     max_period: &'static str,
     max_content: &'static str,
     min_grace_epoch: &'static str,
     counter: &'static str,
     pending: &'static str,
+    upgrade: &'static str,
+    locked_deposits: &'static str,
 }
 
 /// Check if key is inside governance address space
@@ -197,11 +201,37 @@ pub fn is_proposal_type_key(key: &Key) -> bool {
     }
 }
 
+/// Check if key is expedited key
+pub fn is_expedited_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(expedited),
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && expedited == Keys::VALUES.expedited =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
 /// Check if key is counter key
 pub fn is_counter_key(key: &Key) -> bool {
     matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(counter)] if addr == &ADDRESS && counter == Keys::VALUES.counter)
 }
 
+/// Check if key is the locked proposal deposits key
+pub fn is_locked_deposits_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(locked_deposits),
+         ] if addr == &ADDRESS && locked_deposits == Keys::VALUES.locked_deposits)
+}
+
 /// Check if key is a proposal fund parameter key
 pub fn is_min_proposal_fund_key(key: &Key) -> bool {
     matches!(&key.segments[..], [
@@ -237,6 +267,16 @@ pub fn is_min_proposal_voting_period_key(key: &Key) -> bool {
              && min_proposal_voting_period_param == Keys::VALUES.min_period)
 }
 
+/// Check if key is a min expedited proposal period param key
+pub fn is_min_expedited_proposal_voting_period_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(min_expedited_period_param),
+         ] if addr == &ADDRESS
+             && min_expedited_period_param
+                == Keys::VALUES.min_expedited_period)
+}
+
 /// Check if key is a max proposal period param key
 pub fn is_max_proposal_period_key(key: &Key) -> bool {
     matches!(&key.segments[..], [
@@ -275,6 +315,7 @@ pub fn is_parameter_key(key: &Key) -> bool {
         || is_max_content_size_key(key)
         || is_max_proposal_code_size_key(key)
         || is_min_proposal_voting_period_key(key)
+        || is_min_expedited_proposal_voting_period_key(key)
         || is_max_proposal_period_key(key)
         || is_min_grace_epoch_key(key)
 }
@@ -312,6 +353,27 @@ pub fn get_min_proposal_voting_period_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get minimum expedited proposal period key
+pub fn get_min_expedited_proposal_voting_period_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.min_expedited_period.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the scheduled software upgrade
+pub fn get_upgrade_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.upgrade.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the total amount of locked proposal deposits
+pub fn get_locked_deposits_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.locked_deposits.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get maximum proposal period key
 pub fn get_max_proposal_period_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -403,6 +465,15 @@ pub fn get_grace_epoch_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the key of the flag marking a proposal as expedited
+pub fn get_proposal_expedited_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.expedited.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the proposal committing key prefix
 pub fn get_commiting_proposals_prefix(epoch: u64) -> Key {
     proposal_prefix()
@@ -1,6 +1,10 @@
 /// Governance proposal keys
 pub mod keys;
+/// Parameter change structures
+pub mod parameter_change;
 /// Proposal structures
 pub mod proposal;
+/// Software upgrade structures
+pub mod upgrade;
 /// Vote structures
 pub mod vote;
@@ -0,0 +1,792 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::ledger::governance::parameters::GovernanceParameters;
+use crate::ledger::governance::storage::keys as governance_keys;
+use crate::ledger::parameters::{
+    self, storage as parameters_keys, FeeMarketParams, Parameters,
+    ShieldedRewardParams,
+};
+use crate::ledger::pgf::parameters::PgfParameters;
+use crate::ledger::pgf::storage::keys as pgf_keys;
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::address::Address;
+use crate::types::chain::ProposalBytes;
+use crate::types::dec::Dec;
+use crate::types::time::DurationSecs;
+use crate::types::token;
+
+/// A typed change to a parameter, applied by the ledger when the proposal
+/// carrying it passes
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterChange {
+    /// A change to a protocol parameter
+    Protocol(ProtocolParamChange),
+    /// A change to a proof-of-stake parameter
+    Pos(PosParamChange),
+    /// A change to a governance parameter
+    Governance(GovernanceParamChange),
+    /// A change to a public goods funding parameter
+    Pgf(PgfParamChange),
+}
+
+/// A change to one of the protocol [`Parameters`]. The parameters that are
+/// updated by the ledger itself (e.g. the staked ratio) or that can't change
+/// on a live chain (e.g. the epoch duration) are left out.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolParamChange {
+    /// Max expected time per block, in seconds
+    MaxExpectedTimePerBlock(u64),
+    /// Max duration, in seconds, between a block's time and the expiration
+    /// of a wrapper tx included in it
+    MaxTxExpiration(Option<u64>),
+    /// Max payload size, in bytes, for a tx batch proposal
+    MaxProposalBytes(ProposalBytes),
+    /// Max gas for block
+    MaxBlockGas(u64),
+    /// Whitelisted validity predicate hashes
+    VpWhitelist(Vec<String>),
+    /// Whitelisted tx hashes
+    TxWhitelist(Vec<String>),
+    /// Expected number of epochs per year
+    EpochsPerYear(u64),
+    /// Maximum number of signature per transaction
+    MaxSignaturesPerTransaction(u8),
+    /// PoS gain p
+    PosGainP(Dec),
+    /// PoS gain d
+    PosGainD(Dec),
+    /// Fee unshielding gas limit
+    FeeUnshieldingGasLimit(u64),
+    /// Fee unshielding descriptions limit
+    FeeUnshieldingDescriptionsLimit(u64),
+    /// The cost per gas unit of a token allowed for fee payment. `None`
+    /// disallows the token.
+    MinimumGasPrice {
+        /// The fee token
+        token: Address,
+        /// The new minimum gas price
+        amount: Option<token::Amount>,
+    },
+    /// The parameters of the adjustment of the base fee
+    FeeMarket(FeeMarketParams),
+    /// The shielded rewards parameters of a token. `None` stops rewarding
+    /// the token.
+    ShieldedRewards {
+        /// The rewarded token
+        token: Address,
+        /// The new shielded rewards parameters
+        params: Option<ShieldedRewardParams>,
+    },
+}
+
+/// A change to one of the proof-of-stake parameters. The parameters that
+/// shape the epoched validator sets and bonds (the pipeline and unbonding
+/// lengths, the max number of validator slots, the stake threshold, the
+/// votes per token and the slashing window) are left out.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PosParamChange {
+    /// Amount of tokens rewarded to a validator for proposing a block
+    BlockProposerReward(Dec),
+    /// Amount of tokens rewarded to each validator that voted on a block
+    /// proposal
+    BlockVoteReward(Dec),
+    /// Maximum staking rewards rate per annum
+    MaxInflationRate(Dec),
+    /// Target ratio of staked NAM tokens to total NAM tokens
+    TargetStakedRatio(Dec),
+    /// Fraction of validator's stake that should be slashed on a duplicate
+    /// vote
+    DuplicateVoteMinSlashRate(Dec),
+    /// Fraction of validator's stake that should be slashed on a light client
+    /// attack
+    LightClientAttackMinSlashRate(Dec),
+}
+
+/// A change to one of the [`GovernanceParameters`]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceParamChange {
+    /// Minimum amount of locked funds
+    MinProposalFund(token::Amount),
+    /// Maximum kibibyte length for proposal code
+    MaxProposalCodeSize(u64),
+    /// Minimum proposal voting period in epochs
+    MinProposalVotingPeriod(u64),
+    /// Minimum expedited proposal voting period in epochs
+    MinExpeditedProposalVotingPeriod(u64),
+    /// Maximum proposal voting period in epochs
+    MaxProposalPeriod(u64),
+    /// Maximum number of characters for proposal content
+    MaxProposalContentSize(u64),
+    /// Minimum epochs between end and grace epochs
+    MinProposalGraceEpochs(u64),
+}
+
+/// A change to one of the [`PgfParameters`]. The stewards are changed by
+/// [`super::proposal::ProposalType::PGFSteward`] proposals instead.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PgfParamChange {
+    /// The pgf funding inflation rate
+    PgfInflationRate(Dec),
+    /// The pgf stewards inflation rate
+    StewardsInflationRate(Dec),
+}
+
+impl ParameterChange {
+    /// Check that the new value is in the range allowed for the parameter
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ParameterChange::Protocol(change) => change.is_valid(),
+            ParameterChange::Pos(change) => change.is_valid(),
+            ParameterChange::Governance(change) => change.is_valid(),
+            ParameterChange::Pgf(change) => change.is_valid(),
+        }
+    }
+
+    /// The name of the changed parameter, prefixed with its domain
+    pub fn name(&self) -> String {
+        match self {
+            ParameterChange::Protocol(change) => match change.token() {
+                Some(token) => format!("protocol.{}[{}]", change.name(), token),
+                None => format!("protocol.{}", change.name()),
+            },
+            ParameterChange::Pos(change) => format!("pos.{}", change.name()),
+            ParameterChange::Governance(change) => {
+                format!("governance.{}", change.name())
+            }
+            ParameterChange::Pgf(change) => format!("pgf.{}", change.name()),
+        }
+    }
+
+    /// The proposed value of the parameter
+    pub fn value(&self) -> String {
+        match self {
+            ParameterChange::Protocol(change) => change.value(),
+            ParameterChange::Pos(change) => change.value(),
+            ParameterChange::Governance(change) => change.value(),
+            ParameterChange::Pgf(change) => change.value(),
+        }
+    }
+}
+
+impl Display for ParameterChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.name(), self.value())
+    }
+}
+
+/// Check that a rate is within [0, 1]
+fn is_valid_rate(rate: &Dec) -> bool {
+    !rate.is_negative() && *rate <= Dec::one()
+}
+
+impl ProtocolParamChange {
+    /// Check that the new value is in the range allowed for the parameter
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ProtocolParamChange::MaxExpectedTimePerBlock(value)
+            | ProtocolParamChange::MaxBlockGas(value)
+            | ProtocolParamChange::EpochsPerYear(value) => *value > 0,
+            ProtocolParamChange::MaxSignaturesPerTransaction(value) => {
+                *value > 0
+            }
+            ProtocolParamChange::PosGainP(gain)
+            | ProtocolParamChange::PosGainD(gain) => !gain.is_negative(),
+            ProtocolParamChange::FeeMarket(fee_market) => {
+                fee_market.elasticity_multiplier > 0
+                    && fee_market.base_fee_max_change_denominator > 0
+            }
+            ProtocolParamChange::ShieldedRewards {
+                params: Some(params),
+                ..
+            } => {
+                !params.max_reward_rate.is_negative()
+                    && !params.kp_gain_nom.is_negative()
+                    && !params.kd_gain_nom.is_negative()
+                    && is_valid_rate(&params.locked_ratio_target)
+            }
+            ProtocolParamChange::MaxTxExpiration(_)
+            | ProtocolParamChange::MaxProposalBytes(_)
+            | ProtocolParamChange::VpWhitelist(_)
+            | ProtocolParamChange::TxWhitelist(_)
+            | ProtocolParamChange::FeeUnshieldingGasLimit(_)
+            | ProtocolParamChange::FeeUnshieldingDescriptionsLimit(_)
+            | ProtocolParamChange::MinimumGasPrice { .. }
+            | ProtocolParamChange::ShieldedRewards { params: None, .. } => true,
+        }
+    }
+
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProtocolParamChange::MaxExpectedTimePerBlock(_) => {
+                "max_expected_time_per_block"
+            }
+            ProtocolParamChange::MaxTxExpiration(_) => "max_tx_expiration",
+            ProtocolParamChange::MaxProposalBytes(_) => "max_proposal_bytes",
+            ProtocolParamChange::MaxBlockGas(_) => "max_block_gas",
+            ProtocolParamChange::VpWhitelist(_) => "vp_whitelist",
+            ProtocolParamChange::TxWhitelist(_) => "tx_whitelist",
+            ProtocolParamChange::EpochsPerYear(_) => "epochs_per_year",
+            ProtocolParamChange::MaxSignaturesPerTransaction(_) => {
+                "max_signatures_per_transaction"
+            }
+            ProtocolParamChange::PosGainP(_) => "pos_gain_p",
+            ProtocolParamChange::PosGainD(_) => "pos_gain_d",
+            ProtocolParamChange::FeeUnshieldingGasLimit(_) => {
+                "fee_unshielding_gas_limit"
+            }
+            ProtocolParamChange::FeeUnshieldingDescriptionsLimit(_) => {
+                "fee_unshielding_descriptions_limit"
+            }
+            ProtocolParamChange::MinimumGasPrice { .. } => "minimum_gas_price",
+            ProtocolParamChange::FeeMarket(_) => "fee_market",
+            ProtocolParamChange::ShieldedRewards { .. } => "shielded_rewards",
+        }
+    }
+
+    /// The token of a parameter set per token
+    pub fn token(&self) -> Option<&Address> {
+        match self {
+            ProtocolParamChange::MinimumGasPrice { token, .. }
+            | ProtocolParamChange::ShieldedRewards { token, .. } => Some(token),
+            _ => None,
+        }
+    }
+
+    /// The proposed value of the parameter
+    pub fn value(&self) -> String {
+        match self {
+            ProtocolParamChange::MaxExpectedTimePerBlock(value)
+            | ProtocolParamChange::MaxBlockGas(value)
+            | ProtocolParamChange::EpochsPerYear(value)
+            | ProtocolParamChange::FeeUnshieldingGasLimit(value)
+            | ProtocolParamChange::FeeUnshieldingDescriptionsLimit(value) => {
+                value.to_string()
+            }
+            ProtocolParamChange::MaxTxExpiration(value) => {
+                format!("{:?}", value)
+            }
+            ProtocolParamChange::MaxProposalBytes(value) => {
+                value.get().to_string()
+            }
+            ProtocolParamChange::VpWhitelist(value)
+            | ProtocolParamChange::TxWhitelist(value) => {
+                format!("{:?}", value)
+            }
+            ProtocolParamChange::MaxSignaturesPerTransaction(value) => {
+                value.to_string()
+            }
+            ProtocolParamChange::PosGainP(value)
+            | ProtocolParamChange::PosGainD(value) => value.to_string(),
+            ProtocolParamChange::MinimumGasPrice { amount, .. } => {
+                format!("{:?}", amount)
+            }
+            ProtocolParamChange::FeeMarket(value) => format!("{:?}", value),
+            ProtocolParamChange::ShieldedRewards { params, .. } => {
+                format!("{:?}", params)
+            }
+        }
+    }
+
+    /// The current value of the parameter
+    pub fn current_value(&self, params: &Parameters) -> String {
+        match self {
+            ProtocolParamChange::MaxExpectedTimePerBlock(_) => {
+                params.max_expected_time_per_block.0.to_string()
+            }
+            ProtocolParamChange::MaxTxExpiration(_) => format!(
+                "{:?}",
+                params.max_tx_expiration.map(|expiration| expiration.0)
+            ),
+            ProtocolParamChange::MaxProposalBytes(_) => {
+                params.max_proposal_bytes.get().to_string()
+            }
+            ProtocolParamChange::MaxBlockGas(_) => {
+                params.max_block_gas.to_string()
+            }
+            ProtocolParamChange::VpWhitelist(_) => {
+                format!("{:?}", params.vp_whitelist)
+            }
+            ProtocolParamChange::TxWhitelist(_) => {
+                format!("{:?}", params.tx_whitelist)
+            }
+            ProtocolParamChange::EpochsPerYear(_) => {
+                params.epochs_per_year.to_string()
+            }
+            ProtocolParamChange::MaxSignaturesPerTransaction(_) => {
+                params.max_signatures_per_transaction.to_string()
+            }
+            ProtocolParamChange::PosGainP(_) => params.pos_gain_p.to_string(),
+            ProtocolParamChange::PosGainD(_) => params.pos_gain_d.to_string(),
+            ProtocolParamChange::FeeUnshieldingGasLimit(_) => {
+                params.fee_unshielding_gas_limit.to_string()
+            }
+            ProtocolParamChange::FeeUnshieldingDescriptionsLimit(_) => {
+                params.fee_unshielding_descriptions_limit.to_string()
+            }
+            ProtocolParamChange::MinimumGasPrice { token, .. } => {
+                format!("{:?}", params.minimum_gas_price.get(token))
+            }
+            ProtocolParamChange::FeeMarket(_) => {
+                format!("{:?}", params.fee_market)
+            }
+            ProtocolParamChange::ShieldedRewards { token, .. } => {
+                format!("{:?}", params.shielded_rewards.get(token))
+            }
+        }
+    }
+
+    /// Write the new value of the parameter to storage
+    pub fn write<S>(&self, storage: &mut S) -> storage_api::Result<()>
+    where
+        S: StorageRead + StorageWrite,
+    {
+        match self {
+            ProtocolParamChange::MaxExpectedTimePerBlock(value) => {
+                parameters::update_max_expected_time_per_block_parameter(
+                    storage,
+                    &DurationSecs(*value),
+                )
+            }
+            ProtocolParamChange::MaxTxExpiration(value) => {
+                let key = parameters_keys::get_max_tx_expiration_key();
+                storage.write(&key, value.map(DurationSecs))
+            }
+            ProtocolParamChange::MaxProposalBytes(value) => {
+                let key = parameters_keys::get_max_proposal_bytes_key();
+                storage.write(&key, value)
+            }
+            ProtocolParamChange::MaxBlockGas(value) => {
+                let key = parameters_keys::get_max_block_gas_key();
+                storage.write(&key, value)
+            }
+            ProtocolParamChange::VpWhitelist(value) => {
+                parameters::update_vp_whitelist_parameter(
+                    storage,
+                    value.clone(),
+                )
+            }
+            ProtocolParamChange::TxWhitelist(value) => {
+                parameters::update_tx_whitelist_parameter(
+                    storage,
+                    value.clone(),
+                )
+            }
+            ProtocolParamChange::EpochsPerYear(value) => {
+                parameters::update_epochs_per_year_parameter(storage, value)
+            }
+            ProtocolParamChange::MaxSignaturesPerTransaction(value) => {
+                parameters::update_max_signature_per_tx(storage, *value)
+            }
+            ProtocolParamChange::PosGainP(value) => {
+                parameters::update_pos_gain_p_parameter(storage, value)
+            }
+            ProtocolParamChange::PosGainD(value) => {
+                parameters::update_pos_gain_d_parameter(storage, value)
+            }
+            ProtocolParamChange::FeeUnshieldingGasLimit(value) => {
+                let key = parameters_keys::get_fee_unshielding_gas_limit_key();
+                storage.write(&key, value)
+            }
+            ProtocolParamChange::FeeUnshieldingDescriptionsLimit(value) => {
+                let key =
+                    parameters_keys::get_fee_unshielding_descriptions_limit_key(
+                    );
+                storage.write(&key, value)
+            }
+            ProtocolParamChange::MinimumGasPrice { token, amount } => {
+                let key = parameters_keys::get_gas_cost_key();
+                let mut minimum_gas_price: BTreeMap<Address, token::Amount> =
+                    storage.read(&key)?.unwrap_or_default();
+                match amount {
+                    Some(amount) => {
+                        minimum_gas_price.insert(token.clone(), *amount)
+                    }
+                    None => minimum_gas_price.remove(token),
+                };
+                storage.write(&key, minimum_gas_price)
+            }
+            ProtocolParamChange::FeeMarket(value) => {
+                let key = parameters_keys::get_fee_market_key();
+                storage.write(&key, value)
+            }
+            ProtocolParamChange::ShieldedRewards { token, params } => {
+                let mut shielded_rewards =
+                    parameters::read_shielded_rewards(storage)?;
+                match params {
+                    Some(params) => {
+                        shielded_rewards.insert(token.clone(), params.clone())
+                    }
+                    None => shielded_rewards.remove(token),
+                };
+                let key = parameters_keys::get_shielded_rewards_key();
+                storage.write(&key, shielded_rewards)
+            }
+        }
+    }
+}
+
+impl PosParamChange {
+    /// Check that the new value is in the range allowed for the parameter
+    pub fn is_valid(&self) -> bool {
+        match self {
+            PosParamChange::BlockProposerReward(rate)
+            | PosParamChange::BlockVoteReward(rate)
+            | PosParamChange::MaxInflationRate(rate)
+            | PosParamChange::TargetStakedRatio(rate)
+            | PosParamChange::DuplicateVoteMinSlashRate(rate)
+            | PosParamChange::LightClientAttackMinSlashRate(rate) => {
+                is_valid_rate(rate)
+            }
+        }
+    }
+
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            PosParamChange::BlockProposerReward(_) => "block_proposer_reward",
+            PosParamChange::BlockVoteReward(_) => "block_vote_reward",
+            PosParamChange::MaxInflationRate(_) => "max_inflation_rate",
+            PosParamChange::TargetStakedRatio(_) => "target_staked_ratio",
+            PosParamChange::DuplicateVoteMinSlashRate(_) => {
+                "duplicate_vote_min_slash_rate"
+            }
+            PosParamChange::LightClientAttackMinSlashRate(_) => {
+                "light_client_attack_min_slash_rate"
+            }
+        }
+    }
+
+    /// The proposed value of the parameter
+    pub fn value(&self) -> String {
+        match self {
+            PosParamChange::BlockProposerReward(value)
+            | PosParamChange::BlockVoteReward(value)
+            | PosParamChange::MaxInflationRate(value)
+            | PosParamChange::TargetStakedRatio(value)
+            | PosParamChange::DuplicateVoteMinSlashRate(value)
+            | PosParamChange::LightClientAttackMinSlashRate(value) => {
+                value.to_string()
+            }
+        }
+    }
+}
+
+impl GovernanceParamChange {
+    /// Check that the new value is in the range allowed for the parameter
+    pub fn is_valid(&self) -> bool {
+        match self {
+            GovernanceParamChange::MinProposalFund(_) => true,
+            GovernanceParamChange::MaxProposalCodeSize(value)
+            | GovernanceParamChange::MinProposalVotingPeriod(value)
+            | GovernanceParamChange::MinExpeditedProposalVotingPeriod(value)
+            | GovernanceParamChange::MaxProposalPeriod(value)
+            | GovernanceParamChange::MaxProposalContentSize(value)
+            | GovernanceParamChange::MinProposalGraceEpochs(value) => {
+                *value > 0
+            }
+        }
+    }
+
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            GovernanceParamChange::MinProposalFund(_) => "min_proposal_fund",
+            GovernanceParamChange::MaxProposalCodeSize(_) => {
+                "max_proposal_code_size"
+            }
+            GovernanceParamChange::MinProposalVotingPeriod(_) => {
+                "min_proposal_voting_period"
+            }
+            GovernanceParamChange::MinExpeditedProposalVotingPeriod(_) => {
+                "min_expedited_proposal_voting_period"
+            }
+            GovernanceParamChange::MaxProposalPeriod(_) => {
+                "max_proposal_period"
+            }
+            GovernanceParamChange::MaxProposalContentSize(_) => {
+                "max_proposal_content_size"
+            }
+            GovernanceParamChange::MinProposalGraceEpochs(_) => {
+                "min_proposal_grace_epochs"
+            }
+        }
+    }
+
+    /// The proposed value of the parameter
+    pub fn value(&self) -> String {
+        match self {
+            GovernanceParamChange::MinProposalFund(value) => {
+                value.to_string_native()
+            }
+            GovernanceParamChange::MaxProposalCodeSize(value)
+            | GovernanceParamChange::MinProposalVotingPeriod(value)
+            | GovernanceParamChange::MinExpeditedProposalVotingPeriod(value)
+            | GovernanceParamChange::MaxProposalPeriod(value)
+            | GovernanceParamChange::MaxProposalContentSize(value)
+            | GovernanceParamChange::MinProposalGraceEpochs(value) => {
+                value.to_string()
+            }
+        }
+    }
+
+    /// The current value of the parameter
+    pub fn current_value(&self, params: &GovernanceParameters) -> String {
+        match self {
+            GovernanceParamChange::MinProposalFund(_) => {
+                params.min_proposal_fund.to_string_native()
+            }
+            GovernanceParamChange::MaxProposalCodeSize(_) => {
+                params.max_proposal_code_size.to_string()
+            }
+            GovernanceParamChange::MinProposalVotingPeriod(_) => {
+                params.min_proposal_voting_period.to_string()
+            }
+            GovernanceParamChange::MinExpeditedProposalVotingPeriod(_) => {
+                params.min_expedited_proposal_voting_period.to_string()
+            }
+            GovernanceParamChange::MaxProposalPeriod(_) => {
+                params.max_proposal_period.to_string()
+            }
+            GovernanceParamChange::MaxProposalContentSize(_) => {
+                params.max_proposal_content_size.to_string()
+            }
+            GovernanceParamChange::MinProposalGraceEpochs(_) => {
+                params.min_proposal_grace_epochs.to_string()
+            }
+        }
+    }
+
+    /// Apply the change to the given parameters
+    pub fn apply(&self, params: &mut GovernanceParameters) {
+        match self {
+            GovernanceParamChange::MinProposalFund(value) => {
+                params.min_proposal_fund = *value
+            }
+            GovernanceParamChange::MaxProposalCodeSize(value) => {
+                params.max_proposal_code_size = *value
+            }
+            GovernanceParamChange::MinProposalVotingPeriod(value) => {
+                params.min_proposal_voting_period = *value
+            }
+            GovernanceParamChange::MinExpeditedProposalVotingPeriod(value) => {
+                params.min_expedited_proposal_voting_period = *value
+            }
+            GovernanceParamChange::MaxProposalPeriod(value) => {
+                params.max_proposal_period = *value
+            }
+            GovernanceParamChange::MaxProposalContentSize(value) => {
+                params.max_proposal_content_size = *value
+            }
+            GovernanceParamChange::MinProposalGraceEpochs(value) => {
+                params.min_proposal_grace_epochs = *value
+            }
+        }
+    }
+
+    /// Write the new value of the parameter to storage
+    pub fn write<S>(&self, storage: &mut S) -> storage_api::Result<()>
+    where
+        S: StorageRead + StorageWrite,
+    {
+        match self {
+            GovernanceParamChange::MinProposalFund(value) => {
+                let key = governance_keys::get_min_proposal_fund_key();
+                storage.write(&key, value)
+            }
+            GovernanceParamChange::MaxProposalCodeSize(value) => {
+                let key = governance_keys::get_max_proposal_code_size_key();
+                storage.write(&key, value)
+            }
+            GovernanceParamChange::MinProposalVotingPeriod(value) => {
+                let key = governance_keys::get_min_proposal_voting_period_key();
+                storage.write(&key, value)
+            }
+            GovernanceParamChange::MinExpeditedProposalVotingPeriod(value) => {
+                let key = governance_keys::get_min_expedited_proposal_voting_period_key();
+                storage.write(&key, value)
+            }
+            GovernanceParamChange::MaxProposalPeriod(value) => {
+                let key = governance_keys::get_max_proposal_period_key();
+                storage.write(&key, value)
+            }
+            GovernanceParamChange::MaxProposalContentSize(value) => {
+                let key = governance_keys::get_max_proposal_content_key();
+                storage.write(&key, value)
+            }
+            GovernanceParamChange::MinProposalGraceEpochs(value) => {
+                let key = governance_keys::get_min_proposal_grace_epoch_key();
+                storage.write(&key, value)
+            }
+        }
+    }
+}
+
+impl PgfParamChange {
+    /// Check that the new value is in the range allowed for the parameter
+    pub fn is_valid(&self) -> bool {
+        match self {
+            PgfParamChange::PgfInflationRate(rate)
+            | PgfParamChange::StewardsInflationRate(rate) => {
+                is_valid_rate(rate)
+            }
+        }
+    }
+
+    /// The name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            PgfParamChange::PgfInflationRate(_) => "pgf_inflation_rate",
+            PgfParamChange::StewardsInflationRate(_) => {
+                "stewards_inflation_rate"
+            }
+        }
+    }
+
+    /// The proposed value of the parameter
+    pub fn value(&self) -> String {
+        match self {
+            PgfParamChange::PgfInflationRate(value)
+            | PgfParamChange::StewardsInflationRate(value) => value.to_string(),
+        }
+    }
+
+    /// The current value of the parameter
+    pub fn current_value(&self, params: &PgfParameters) -> String {
+        match self {
+            PgfParamChange::PgfInflationRate(_) => {
+                params.pgf_inflation_rate.to_string()
+            }
+            PgfParamChange::StewardsInflationRate(_) => {
+                params.stewards_inflation_rate.to_string()
+            }
+        }
+    }
+
+    /// Write the new value of the parameter to storage
+    pub fn write<S>(&self, storage: &mut S) -> storage_api::Result<()>
+    where
+        S: StorageRead + StorageWrite,
+    {
+        match self {
+            PgfParamChange::PgfInflationRate(value) => {
+                let key = pgf_keys::get_pgf_inflation_rate_key();
+                storage.write(&key, value)
+            }
+            PgfParamChange::StewardsInflationRate(value) => {
+                let key = pgf_keys::get_steward_inflation_rate_key();
+                storage.write(&key, value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameter_change_validity() {
+        assert!(
+            ParameterChange::Protocol(ProtocolParamChange::MaxBlockGas(1))
+                .is_valid()
+        );
+        assert!(
+            !ParameterChange::Protocol(ProtocolParamChange::MaxBlockGas(0))
+                .is_valid()
+        );
+        assert!(
+            !ParameterChange::Protocol(ProtocolParamChange::FeeMarket(
+                FeeMarketParams {
+                    elasticity_multiplier: 0,
+                    ..Default::default()
+                }
+            ))
+            .is_valid()
+        );
+        assert!(
+            ParameterChange::Pos(PosParamChange::MaxInflationRate(Dec::one()))
+                .is_valid()
+        );
+        assert!(
+            !ParameterChange::Pos(PosParamChange::MaxInflationRate(
+                Dec::new(11, 1).unwrap()
+            ))
+            .is_valid()
+        );
+        assert!(
+            !ParameterChange::Pgf(PgfParamChange::PgfInflationRate(
+                Dec::new(-1, 2).unwrap()
+            ))
+            .is_valid()
+        );
+        assert!(
+            !ParameterChange::Governance(
+                GovernanceParamChange::MinProposalVotingPeriod(0)
+            )
+            .is_valid()
+        );
+    }
+
+    #[test]
+    fn test_governance_parameter_change() {
+        let mut params = GovernanceParameters::default();
+        let change = GovernanceParamChange::MaxProposalPeriod(30);
+        assert_eq!(change.current_value(&params), "27");
+        change.apply(&mut params);
+        assert_eq!(change.current_value(&params), change.value());
+        assert_eq!(
+            ParameterChange::Governance(change).to_string(),
+            "governance.max_proposal_period = 30"
+        );
+    }
+}
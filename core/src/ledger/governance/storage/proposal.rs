@@ -1,10 +1,13 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fmt::Display;
+use std::io::{ErrorKind, Write};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::parameter_change::ParameterChange;
+use super::upgrade::UpgradePlan;
 use crate::ledger::governance::cli::onchain::{
     PgfAction, PgfContinous, PgfRetro, PgfSteward, StewardsUpdate,
 };
@@ -29,21 +32,66 @@ pub enum ProposalTypeError {
     Eq,
     PartialOrd,
     BorshSerialize,
-    BorshDeserialize,
     Serialize,
     Deserialize,
 )]
 pub struct StoragePgfFunding {
-    /// The data about the pgf funding
-    pub detail: PGFTarget,
+    /// The data about the pgf funding stream
+    pub detail: PGFStream,
     /// The id of the proposal that added this funding
     pub id: u64,
+    /// The amount paid out so far by this stream
+    pub paid: Amount,
 }
 
 impl StoragePgfFunding {
     /// Init a new pgf funding struct
-    pub fn new(detail: PGFTarget, id: u64) -> Self {
-        Self { detail, id }
+    pub fn new(detail: PGFStream, id: u64) -> Self {
+        Self {
+            detail,
+            id,
+            paid: Amount::zero(),
+        }
+    }
+
+    /// The budget of the stream that is yet to be paid out
+    pub fn remaining_budget(&self) -> Amount {
+        self.detail
+            .budget
+            .checked_sub(self.paid)
+            .unwrap_or_default()
+    }
+
+    /// The amount to pay out in the given epoch, capped by the remaining
+    /// budget. Returns `None` if the stream is not active in the epoch.
+    pub fn payout(&self, epoch: Epoch) -> Option<Amount> {
+        if !self.detail.is_active(epoch) {
+            return None;
+        }
+        let amount = std::cmp::min(self.detail.amount, self.remaining_budget());
+        if amount.is_zero() { None } else { Some(amount) }
+    }
+
+    /// Check if the stream has nothing left to pay from the given epoch on
+    pub fn is_exhausted(&self, epoch: Epoch) -> bool {
+        self.remaining_budget().is_zero() || epoch > self.detail.end_epoch
+    }
+}
+
+impl BorshDeserialize for StoragePgfFunding {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let mut current = *buf;
+        if let Ok((detail, id, paid)) =
+            <(PGFStream, u64, Amount)>::deserialize(&mut current)
+        {
+            *buf = current;
+            return Ok(Self { detail, id, paid });
+        }
+        // Fundings stored before the streams had a lifetime and a budget only
+        // hold the target and the proposal id. Their encoding is shorter than
+        // the current one, so it never decodes as the current layout.
+        let (target, id) = <(PGFTarget, u64)>::deserialize(buf)?;
+        Ok(Self::new(PGFStream::unbounded(target), id))
     }
 }
 
@@ -87,7 +135,60 @@ pub struct PGFTarget {
     pub amount: Amount,
 }
 
-/// The actions that a PGF Steward can propose to execute
+/// A continuous funding stream, paying out a fixed amount every epoch between
+/// the start and end epochs (both inclusive) until the budget is spent
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    Ord,
+    Eq,
+    PartialOrd,
+)]
+pub struct PGFStream {
+    /// The target address
+    pub target: Address,
+    /// The amount of token paid out to the target address every epoch
+    pub amount: Amount,
+    /// The first epoch in which the stream pays out
+    pub start_epoch: Epoch,
+    /// The last epoch in which the stream pays out
+    pub end_epoch: Epoch,
+    /// The maximum amount of token paid out over the stream lifetime
+    pub budget: Amount,
+}
+
+impl PGFStream {
+    /// Check if the stream data is consistent
+    pub fn is_valid(&self) -> bool {
+        !self.amount.is_zero()
+            && !self.budget.is_zero()
+            && self.start_epoch <= self.end_epoch
+    }
+
+    /// Check if the stream pays out in the given epoch
+    pub fn is_active(&self, epoch: Epoch) -> bool {
+        self.start_epoch <= epoch && epoch <= self.end_epoch
+    }
+
+    /// A stream paying the target every epoch until it's cancelled, as the
+    /// continuous fundings did before the streams had a lifetime and a budget
+    pub fn unbounded(target: PGFTarget) -> Self {
+        Self {
+            target: target.target,
+            amount: target.amount,
+            start_epoch: Epoch(0),
+            end_epoch: Epoch(u64::MAX),
+            budget: Amount::max(),
+        }
+    }
+}
+
+/// A transfer of tokens from the governance treasury
 #[derive(
     Debug,
     Clone,
@@ -96,12 +197,108 @@ pub struct PGFTarget {
     BorshDeserialize,
     Serialize,
     Deserialize,
+    Ord,
+    Eq,
+    PartialOrd,
 )]
+pub struct TreasuryTransfer {
+    /// The token to transfer
+    pub token: Address,
+    /// The target address
+    pub target: Address,
+    /// The amount of token to transfer to the target address
+    pub amount: Amount,
+}
+
+impl TreasuryTransfer {
+    /// Sum the amounts of the given transfers per token. Returns `None` if
+    /// any of the sums overflows.
+    pub fn total_per_token(
+        transfers: &[TreasuryTransfer],
+    ) -> Option<BTreeMap<&Address, Amount>> {
+        let mut totals: BTreeMap<&Address, Amount> = BTreeMap::new();
+        for transfer in transfers {
+            let total = totals.entry(&transfer.token).or_default();
+            *total = total.checked_add(transfer.amount)?;
+        }
+        Some(totals)
+    }
+}
+
+/// The actions that a PGF Steward can propose to execute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PGFAction {
     /// A continuous payment
-    Continuous(AddRemove<PGFTarget>),
+    Continuous(PGFStream),
     /// A retro payment
     Retro(PGFTarget),
+    /// Cancel the funding stream of the target address
+    Cancel(Address),
+}
+
+impl PGFAction {
+    /// Check if the action cancels a funding stream
+    pub fn is_cancel(&self) -> bool {
+        matches!(self, PGFAction::Cancel(_))
+    }
+}
+
+// The borsh tags of the pgf actions. The legacy tag is the one of the
+// continuous payments encoded before the funding streams, as
+// `AddRemove<PGFTarget>`, which are still decoded from the proposals stored
+// back then but never encoded anymore.
+const PGF_ACTION_LEGACY_CONTINUOUS_TAG: u8 = 0;
+const PGF_ACTION_RETRO_TAG: u8 = 1;
+const PGF_ACTION_CONTINUOUS_TAG: u8 = 2;
+const PGF_ACTION_CANCEL_TAG: u8 = 3;
+
+impl BorshSerialize for PGFAction {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            PGFAction::Continuous(stream) => {
+                BorshSerialize::serialize(&PGF_ACTION_CONTINUOUS_TAG, writer)?;
+                BorshSerialize::serialize(stream, writer)
+            }
+            PGFAction::Retro(target) => {
+                BorshSerialize::serialize(&PGF_ACTION_RETRO_TAG, writer)?;
+                BorshSerialize::serialize(target, writer)
+            }
+            PGFAction::Cancel(target) => {
+                BorshSerialize::serialize(&PGF_ACTION_CANCEL_TAG, writer)?;
+                BorshSerialize::serialize(target, writer)
+            }
+        }
+    }
+}
+
+impl BorshDeserialize for PGFAction {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        match u8::deserialize(buf)? {
+            PGF_ACTION_LEGACY_CONTINUOUS_TAG => {
+                match AddRemove::<PGFTarget>::deserialize(buf)? {
+                    AddRemove::Add(target) => {
+                        Ok(PGFAction::Continuous(PGFStream::unbounded(target)))
+                    }
+                    AddRemove::Remove(target) => {
+                        Ok(PGFAction::Cancel(target.target))
+                    }
+                }
+            }
+            PGF_ACTION_RETRO_TAG => {
+                Ok(PGFAction::Retro(PGFTarget::deserialize(buf)?))
+            }
+            PGF_ACTION_CONTINUOUS_TAG => {
+                Ok(PGFAction::Continuous(PGFStream::deserialize(buf)?))
+            }
+            PGF_ACTION_CANCEL_TAG => {
+                Ok(PGFAction::Cancel(Address::deserialize(buf)?))
+            }
+            tag => Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid PGF action tag: {}", tag),
+            )),
+        }
+    }
 }
 
 /// The type of a Proposal
@@ -121,6 +318,12 @@ pub enum ProposalType {
     PGFSteward(HashSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(Vec<PGFAction>),
+    /// Parameter change proposal
+    ParameterChange(Vec<ParameterChange>),
+    /// Software upgrade proposal
+    Upgrade(UpgradePlan),
+    /// Treasury spending proposal
+    Treasury(Vec<TreasuryTransfer>),
 }
 
 impl ProposalType {
@@ -136,6 +339,9 @@ impl Display for ProposalType {
             ProposalType::Default(_) => write!(f, "Default"),
             ProposalType::PGFSteward(_) => write!(f, "Pgf steward"),
             ProposalType::PGFPayment(_) => write!(f, "Pgf funding"),
+            ProposalType::ParameterChange(_) => write!(f, "Parameter change"),
+            ProposalType::Upgrade(_) => write!(f, "Upgrade"),
+            ProposalType::Treasury(_) => write!(f, "Treasury spending"),
         }
     }
 }
@@ -172,18 +378,14 @@ impl TryFrom<PgfContinous> for PGFAction {
 
     fn try_from(value: PgfContinous) -> Result<Self, Self::Error> {
         match value.action {
-            PgfAction::Add => {
-                Ok(PGFAction::Continuous(AddRemove::Add(PGFTarget {
-                    target: value.target.address,
-                    amount: value.target.amount,
-                })))
-            }
-            PgfAction::Remove => {
-                Ok(PGFAction::Continuous(AddRemove::Remove(PGFTarget {
-                    target: value.target.address,
-                    amount: value.target.amount,
-                })))
-            }
+            PgfAction::Add => Ok(PGFAction::Continuous(PGFStream {
+                target: value.target.address,
+                amount: value.target.amount,
+                start_epoch: value.target.start_epoch,
+                end_epoch: value.target.end_epoch,
+                budget: value.target.budget,
+            })),
+            PgfAction::Remove => Ok(PGFAction::Cancel(value.target.address)),
         }
     }
 }
@@ -216,6 +418,8 @@ pub struct StorageProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// Whether the proposal is expedited
+    pub is_expedited: bool,
 }
 
 impl StorageProposal {
@@ -238,7 +442,7 @@ impl StorageProposal {
 
     /// Return the type of tally for the proposal
     pub fn get_tally_type(&self, is_steward: bool) -> TallyType {
-        TallyType::from(self.r#type.clone(), is_steward)
+        TallyType::from(self.r#type.clone(), is_steward, self.is_expedited)
     }
 
     /// Return the status of a proposal
@@ -264,6 +468,7 @@ impl StorageProposal {
         {:2}Start Epoch: {}
         {:2}End Epoch: {}
         {:2}Grace Epoch: {}
+        {:2}Expedited: {}
         {:2}Status: {}
         ",
             self.id,
@@ -280,6 +485,8 @@ impl StorageProposal {
             "",
             self.grace_epoch,
             "",
+            self.is_expedited,
+            "",
             self.get_status(current_epoch)
         )
     }
@@ -295,6 +502,7 @@ impl Display for StorageProposal {
             {:2}Start Epoch: {}
             {:2}End Epoch: {}
             {:2}Grace Epoch: {}
+            {:2}Expedited: {}
             ",
             self.id,
             "",
@@ -306,7 +514,78 @@ impl Display for StorageProposal {
             "",
             self.voting_end_epoch,
             "",
-            self.grace_epoch
+            self.grace_epoch,
+            "",
+            self.is_expedited
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::address::testing::established_address_1;
+
+    fn target() -> PGFTarget {
+        PGFTarget {
+            target: established_address_1(),
+            amount: Amount::native_whole(10),
+        }
+    }
+
+    #[test]
+    fn test_decode_legacy_pgf_funding() {
+        let legacy = (target(), 3_u64).try_to_vec().unwrap();
+        let funding = StoragePgfFunding::try_from_slice(&legacy).unwrap();
+        assert_eq!(
+            funding,
+            StoragePgfFunding::new(PGFStream::unbounded(target()), 3)
+        );
+        assert!(funding.payout(Epoch(1000)).is_some());
+        assert!(!funding.is_exhausted(Epoch(1000)));
+
+        let mut funding = StoragePgfFunding::new(
+            PGFStream {
+                start_epoch: Epoch(2),
+                end_epoch: Epoch(4),
+                budget: Amount::native_whole(25),
+                ..PGFStream::unbounded(target())
+            },
+            4,
+        );
+        funding.paid = Amount::native_whole(10);
+        let bytes = funding.try_to_vec().unwrap();
+        assert_eq!(StoragePgfFunding::try_from_slice(&bytes).unwrap(), funding);
+    }
+
+    #[test]
+    fn test_decode_legacy_pgf_actions() {
+        let add = (PGF_ACTION_LEGACY_CONTINUOUS_TAG, AddRemove::Add(target()))
+            .try_to_vec()
+            .unwrap();
+        assert_eq!(
+            PGFAction::try_from_slice(&add).unwrap(),
+            PGFAction::Continuous(PGFStream::unbounded(target()))
+        );
+        let remove = (
+            PGF_ACTION_LEGACY_CONTINUOUS_TAG,
+            AddRemove::Remove(target()),
         )
+            .try_to_vec()
+            .unwrap();
+        assert_eq!(
+            PGFAction::try_from_slice(&remove).unwrap(),
+            PGFAction::Cancel(established_address_1())
+        );
+
+        for action in [
+            PGFAction::Continuous(PGFStream::unbounded(target())),
+            PGFAction::Retro(target()),
+            PGFAction::Cancel(established_address_1()),
+        ] {
+            let bytes = action.try_to_vec().unwrap();
+            assert_eq!(PGFAction::try_from_slice(&bytes).unwrap(), action);
+        }
+        assert!(PGFAction::try_from_slice(&[4]).is_err());
     }
 }
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ledger::governance::cli::onchain::UpgradeData;
+use crate::types::hash::Hash;
+use crate::types::storage::BlockHeight;
+
+#[allow(missing_docs)]
+#[derive(Debug, Error)]
+pub enum UpgradePlanError {
+    #[error("Invalid checksum for {0}: {1}")]
+    InvalidChecksum(String, String),
+}
+
+/// A software upgrade scheduled by a governance proposal. Once the proposal
+/// passes, the nodes halt at the upgrade height and can only be restarted
+/// with a binary and protocol wasms matching the recorded checksums.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpgradePlan {
+    /// The name of the upgrade
+    pub name: String,
+    /// The block height at which the upgrade takes place
+    pub height: BlockHeight,
+    /// The sha256 checksums of the node binaries to run from the upgrade
+    /// height, indexed by target triple. A node built for a target that
+    /// isn't listed doesn't match the upgrade.
+    pub binary_checksums: BTreeMap<String, Hash>,
+    /// The sha256 checksums of the protocol wasms to run from the upgrade
+    /// height, indexed by wasm name
+    pub wasm_checksums: BTreeMap<String, Hash>,
+}
+
+impl UpgradePlan {
+    /// Check if the upgrade plan is well formed
+    pub fn is_valid(&self) -> bool {
+        !self.name.trim().is_empty()
+            && self.height.0 > 0
+            && !self.binary_checksums.is_empty()
+    }
+
+    /// Check if the upgrade has to be applied at the given height
+    pub fn is_due(&self, height: BlockHeight) -> bool {
+        height >= self.height
+    }
+
+    /// Check if the checksum of a node binary built for the given target
+    /// triple matches the upgrade
+    pub fn is_matching_binary(&self, target: &str, checksum: &Hash) -> bool {
+        self.binary_checksums.get(target) == Some(checksum)
+    }
+
+    /// Find the protocol wasms that don't match the upgrade checksums, given
+    /// the checksums of the wasms available to the node
+    pub fn mismatching_wasms(
+        &self,
+        available: &BTreeMap<String, Hash>,
+    ) -> Vec<String> {
+        self.wasm_checksums
+            .iter()
+            .filter(|(name, checksum)| available.get(*name) != Some(checksum))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+impl TryFrom<UpgradeData> for UpgradePlan {
+    type Error = UpgradePlanError;
+
+    fn try_from(value: UpgradeData) -> Result<Self, Self::Error> {
+        let parse_checksum = |name: &str, checksum: &str| {
+            Hash::from_str(checksum).map_err(|_| {
+                UpgradePlanError::InvalidChecksum(
+                    name.to_string(),
+                    checksum.to_string(),
+                )
+            })
+        };
+        let parse_checksums = |checksums: &BTreeMap<String, String>| {
+            checksums
+                .iter()
+                .map(|(name, checksum)| {
+                    Ok((name.clone(), parse_checksum(name, checksum)?))
+                })
+                .collect::<Result<BTreeMap<String, Hash>, UpgradePlanError>>()
+        };
+        let binary_checksums = parse_checksums(&value.binary_checksums)?;
+        let wasm_checksums = parse_checksums(&value.wasm_checksums)?;
+
+        Ok(Self {
+            name: value.name,
+            height: BlockHeight(value.height),
+            binary_checksums,
+            wasm_checksums,
+        })
+    }
+}
+
+impl Display for UpgradePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Upgrade {} at height {}", self.name, self.height)?;
+        for (target, checksum) in &self.binary_checksums {
+            write!(f, "\nBinary for {}: {}", target, checksum)?;
+        }
+        for (name, checksum) in &self.wasm_checksums {
+            write!(f, "\n{}: {}", name, checksum)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mismatching_wasms() {
+        let plan = UpgradePlan {
+            name: "v2".to_string(),
+            height: BlockHeight(100),
+            binary_checksums: BTreeMap::from([(
+                "x86_64-unknown-linux-gnu".to_string(),
+                Hash::sha256(b"namada"),
+            )]),
+            wasm_checksums: BTreeMap::from([
+                ("tx_transfer.wasm".to_string(), Hash::sha256(b"transfer")),
+                ("vp_user.wasm".to_string(), Hash::sha256(b"user")),
+            ]),
+        };
+        assert!(plan.is_valid());
+        assert!(!plan.is_due(BlockHeight(99)));
+        assert!(plan.is_due(BlockHeight(100)));
+
+        // Only the binary of a listed target can match
+        assert!(plan.is_matching_binary(
+            "x86_64-unknown-linux-gnu",
+            &Hash::sha256(b"namada")
+        ));
+        assert!(!plan.is_matching_binary(
+            "aarch64-apple-darwin",
+            &Hash::sha256(b"namada")
+        ));
+        assert!(
+            !UpgradePlan {
+                binary_checksums: BTreeMap::new(),
+                ..plan.clone()
+            }
+            .is_valid()
+        );
+
+        let available = BTreeMap::from([
+            ("tx_transfer.wasm".to_string(), Hash::sha256(b"transfer")),
+            ("vp_user.wasm".to_string(), Hash::sha256(b"old user")),
+        ]);
+        assert_eq!(
+            plan.mismatching_wasms(&available),
+            vec!["vp_user.wasm".to_string()]
+        );
+
+        let available = BTreeMap::from([(
+            "tx_transfer.wasm".to_string(),
+            Hash::sha256(b"transfer"),
+        )]);
+        assert_eq!(
+            plan.mismatching_wasms(&available),
+            vec!["vp_user.wasm".to_string()]
+        );
+    }
+}
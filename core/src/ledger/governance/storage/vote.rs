@@ -3,8 +3,12 @@ use std::fmt::Display;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
-use super::super::cli::onchain::ProposalVote;
+use super::super::cli::onchain::{ProposalVote, SplitProposalVote};
 use super::proposal::ProposalType;
+use crate::types::dec::Dec;
+
+/// The maximum number of options a split vote can be divided into
+pub const MAX_SPLIT_VOTE_OPTIONS: usize = 4;
 
 /// The type of a governance vote with the optional associated Memo
 #[derive(
@@ -24,6 +28,12 @@ pub enum VoteType {
     PGFSteward,
     /// A vote for a PGF payment proposal
     PGFPayment,
+    /// A vote for a parameter change proposal
+    ParameterChange,
+    /// A vote for an upgrade proposal
+    Upgrade,
+    /// A vote for a treasury spending proposal
+    Treasury,
 }
 
 #[derive(
@@ -42,6 +52,30 @@ pub enum StorageProposalVote {
     Yay(VoteType),
     /// No
     Nay,
+    /// Abstain, only counted toward the quorum
+    Abstain,
+    /// No, vetoing the proposal
+    NayWithVeto,
+    /// A vote split by weight across multiple options
+    Split(Vec<WeightedVote>),
+}
+
+/// A vote option with the share of the voting power allocated to it
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct WeightedVote {
+    /// The vote option
+    pub vote: StorageProposalVote,
+    /// The share of the voting power, in the range (0, 1]
+    pub weight: Dec,
 }
 
 impl StorageProposalVote {
@@ -50,12 +84,57 @@ impl StorageProposalVote {
         matches!(self, StorageProposalVote::Yay(_))
     }
 
+    /// Check if a vote is abstain
+    pub fn is_abstain(&self) -> bool {
+        matches!(self, StorageProposalVote::Abstain)
+    }
+
+    /// Check if a vote is nay with veto
+    pub fn is_veto(&self) -> bool {
+        matches!(self, StorageProposalVote::NayWithVeto)
+    }
+
+    /// Check if a vote is split across multiple options
+    pub fn is_split(&self) -> bool {
+        matches!(self, StorageProposalVote::Split(_))
+    }
+
+    /// Check if a vote is well formed. The options of a split vote must be
+    /// distinct, not split themselves and have positive weights summing up to
+    /// one.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            StorageProposalVote::Split(options) => {
+                let valid_options = !options.is_empty()
+                    && options.len() <= MAX_SPLIT_VOTE_OPTIONS
+                    && options.iter().enumerate().all(|(index, option)| {
+                        !option.vote.is_split()
+                            && option.weight > Dec::zero()
+                            && option.weight <= Dec::one()
+                            && options[..index]
+                                .iter()
+                                .all(|other| other.vote != option.vote)
+                    });
+                if !valid_options {
+                    return false;
+                }
+                let total_weight = options
+                    .iter()
+                    .fold(Dec::zero(), |total, option| total + option.weight);
+                total_weight == Dec::one()
+            }
+            _ => true,
+        }
+    }
+
     /// Check if vote is of type default
     pub fn is_default_vote(&self) -> bool {
         matches!(
             self,
             StorageProposalVote::Yay(VoteType::Default)
                 | StorageProposalVote::Nay
+                | StorageProposalVote::Abstain
+                | StorageProposalVote::NayWithVeto
         )
     }
 
@@ -63,7 +142,12 @@ impl StorageProposalVote {
     pub fn is_compatible(&self, proposal_type: &ProposalType) -> bool {
         match self {
             StorageProposalVote::Yay(vote_type) => proposal_type.eq(vote_type),
-            StorageProposalVote::Nay => true,
+            StorageProposalVote::Nay
+            | StorageProposalVote::Abstain
+            | StorageProposalVote::NayWithVeto => true,
+            StorageProposalVote::Split(options) => options
+                .iter()
+                .all(|option| option.vote.is_compatible(proposal_type)),
         }
     }
 
@@ -82,6 +166,15 @@ impl StorageProposalVote {
             (ProposalVote::Yay, ProposalType::PGFPayment(_)) => {
                 Some(StorageProposalVote::Yay(VoteType::PGFPayment))
             }
+            (ProposalVote::Yay, ProposalType::ParameterChange(_)) => {
+                Some(StorageProposalVote::Yay(VoteType::ParameterChange))
+            }
+            (ProposalVote::Yay, ProposalType::Upgrade(_)) => {
+                Some(StorageProposalVote::Yay(VoteType::Upgrade))
+            }
+            (ProposalVote::Yay, ProposalType::Treasury(_)) => {
+                Some(StorageProposalVote::Yay(VoteType::Treasury))
+            }
             (ProposalVote::Nay, ProposalType::Default(_)) => {
                 Some(StorageProposalVote::Nay)
             }
@@ -91,9 +184,40 @@ impl StorageProposalVote {
             (ProposalVote::Nay, ProposalType::PGFPayment(_)) => {
                 Some(StorageProposalVote::Nay)
             }
+            (ProposalVote::Nay, ProposalType::ParameterChange(_)) => {
+                Some(StorageProposalVote::Nay)
+            }
+            (ProposalVote::Nay, ProposalType::Upgrade(_)) => {
+                Some(StorageProposalVote::Nay)
+            }
+            (ProposalVote::Nay, ProposalType::Treasury(_)) => {
+                Some(StorageProposalVote::Nay)
+            }
+            (ProposalVote::Abstain, _) => Some(StorageProposalVote::Abstain),
+            (ProposalVote::NayWithVeto, _) => {
+                Some(StorageProposalVote::NayWithVeto)
+            }
             _ => None,
         }
     }
+
+    /// Create a new vote split across multiple options
+    pub fn build_split(
+        split_vote: &SplitProposalVote,
+        proposal_type: &ProposalType,
+    ) -> Option<Self> {
+        let options = split_vote
+            .0
+            .iter()
+            .map(|(vote, weight)| {
+                Some(WeightedVote {
+                    vote: Self::build(vote, proposal_type)?,
+                    weight: *weight,
+                })
+            })
+            .collect::<Option<Vec<WeightedVote>>>()?;
+        Some(StorageProposalVote::Split(options))
+    }
 }
 
 impl Display for StorageProposalVote {
@@ -102,10 +226,22 @@ impl Display for StorageProposalVote {
             StorageProposalVote::Yay(vote_type) => match vote_type {
                 VoteType::Default
                 | VoteType::PGFSteward
-                | VoteType::PGFPayment => write!(f, "yay"),
+                | VoteType::PGFPayment
+                | VoteType::ParameterChange
+                | VoteType::Upgrade
+                | VoteType::Treasury => write!(f, "yay"),
             },
 
             StorageProposalVote::Nay => write!(f, "nay"),
+            StorageProposalVote::Abstain => write!(f, "abstain"),
+            StorageProposalVote::NayWithVeto => write!(f, "veto"),
+            StorageProposalVote::Split(options) => {
+                let options = options
+                    .iter()
+                    .map(|option| format!("{}={}", option.vote, option.weight))
+                    .collect::<Vec<String>>();
+                write!(f, "{}", options.join(","))
+            }
         }
     }
 }
@@ -122,6 +258,43 @@ impl PartialEq<VoteType> for ProposalType {
             Self::PGFPayment(_) => {
                 matches!(other, VoteType::PGFPayment)
             }
+            Self::ParameterChange(_) => {
+                matches!(other, VoteType::ParameterChange)
+            }
+            Self::Upgrade(_) => {
+                matches!(other, VoteType::Upgrade)
+            }
+            Self::Treasury(_) => {
+                matches!(other, VoteType::Treasury)
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_vote_validity() {
+        let proposal_type = ProposalType::Default(None);
+        let build = |vote: &str| {
+            let split_vote = SplitProposalVote::try_from(vote.to_string())
+                .expect("Test failed");
+            StorageProposalVote::build_split(&split_vote, &proposal_type)
+                .expect("Test failed")
+        };
+
+        assert!(build("yay=0.6,nay=0.3,abstain=0.1").is_valid());
+        assert!(build("veto=1").is_valid());
+        // The weights must sum up to one
+        assert!(!build("yay=0.6,nay=0.3").is_valid());
+        assert!(!build("yay=0.6,nay=0.6").is_valid());
+        // The options must be distinct and have positive weights
+        assert!(!build("yay=0.5,yay=0.5").is_valid());
+        assert!(!build("yay=1,nay=0").is_valid());
+        // Vote options are parsed like single votes
+        assert!(SplitProposalVote::try_from("maybe=1".to_string()).is_err());
+        assert!(SplitProposalVote::try_from("yay".to_string()).is_err());
+    }
+}
@@ -52,24 +52,74 @@ impl Vote {
 }
 
 /// Rappresent a tally type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TallyType {
     /// Rappresent a tally type for proposal requiring 2/3 of the votes
     TwoThird,
+    /// Rappresent a tally type for proposal requiring 2/3 of the votes that
+    /// is rejected when more than 1/3 of the votes are nay with veto
+    TwoThirdWithVeto,
     /// Rappresent a tally type for proposal requiring 1/3 of the votes
     OneThird,
     /// Rappresent a tally type for proposal requiring less than 1/3 of the
     /// votes to be nay
     LessOneThirdNay,
+    /// Rappresent a tally type for expedited proposal requiring 2/3 of the
+    /// votes and more than 2/3 of the non abstain votes to be yay
+    Expedited,
 }
 
 impl TallyType {
     /// Compute the type of tally for a proposal
-    pub fn from(proposal_type: ProposalType, is_steward: bool) -> Self {
+    pub fn from(
+        proposal_type: ProposalType,
+        is_steward: bool,
+        is_expedited: bool,
+    ) -> Self {
+        if is_expedited {
+            return TallyType::Expedited;
+        }
         match (proposal_type, is_steward) {
-            (ProposalType::Default(_), _) => TallyType::TwoThird,
+            (ProposalType::Default(_), _) => TallyType::TwoThirdWithVeto,
             (ProposalType::PGFSteward(_), _) => TallyType::TwoThird,
             (ProposalType::PGFPayment(_), true) => TallyType::LessOneThirdNay,
             (ProposalType::PGFPayment(_), false) => TallyType::OneThird,
+            (ProposalType::ParameterChange(_), _)
+            | (ProposalType::Upgrade(_), _)
+            | (ProposalType::Treasury(_), _) => TallyType::TwoThirdWithVeto,
+        }
+    }
+
+    /// Check if enough voting power, abstain votes included, took part in
+    /// the vote
+    pub fn has_quorum(
+        &self,
+        voted_power: VotePower,
+        total_voting_power: VotePower,
+    ) -> bool {
+        match self {
+            TallyType::TwoThird
+            | TallyType::TwoThirdWithVeto
+            | TallyType::Expedited => voted_power >= total_voting_power / 3 * 2,
+            TallyType::OneThird => voted_power >= total_voting_power / 3,
+            TallyType::LessOneThirdNay => true,
+        }
+    }
+
+    /// Check if the veto votes exceed a third of the voting power that took
+    /// part in the vote
+    pub fn is_vetoed(
+        &self,
+        veto_voting_power: VotePower,
+        voted_power: VotePower,
+    ) -> bool {
+        match self {
+            TallyType::TwoThirdWithVeto | TallyType::Expedited => {
+                veto_voting_power > voted_power / 3
+            }
+            TallyType::TwoThird
+            | TallyType::OneThird
+            | TallyType::LessOneThirdNay => false,
         }
     }
 }
@@ -97,27 +147,37 @@ impl TallyResult {
         tally_type: &TallyType,
         yay_voting_power: VotePower,
         nay_voting_power: VotePower,
+        abstain_voting_power: VotePower,
+        veto_voting_power: VotePower,
         total_voting_power: VotePower,
     ) -> Self {
+        let voted_power = yay_voting_power
+            + nay_voting_power
+            + abstain_voting_power
+            + veto_voting_power;
+        let against_power = nay_voting_power + veto_voting_power;
         let passed = match tally_type {
-            TallyType::TwoThird => {
-                let at_least_two_third_voted = yay_voting_power
-                    + nay_voting_power
-                    >= total_voting_power / 3 * 2;
-                let at_last_half_voted_yay =
-                    yay_voting_power > nay_voting_power;
-                at_least_two_third_voted && at_last_half_voted_yay
-            }
-            TallyType::OneThird => {
-                let at_least_two_third_voted = yay_voting_power
-                    + nay_voting_power
-                    >= total_voting_power / 3;
-                let at_last_half_voted_yay =
-                    yay_voting_power > nay_voting_power;
-                at_least_two_third_voted && at_last_half_voted_yay
+            TallyType::TwoThird
+            | TallyType::TwoThirdWithVeto
+            | TallyType::OneThird => {
+                let has_quorum =
+                    tally_type.has_quorum(voted_power, total_voting_power);
+                let is_vetoed =
+                    tally_type.is_vetoed(veto_voting_power, voted_power);
+                let at_last_half_voted_yay = yay_voting_power > against_power;
+                has_quorum && !is_vetoed && at_last_half_voted_yay
             }
             TallyType::LessOneThirdNay => {
-                nay_voting_power <= total_voting_power / 3
+                against_power <= total_voting_power / 3
+            }
+            TallyType::Expedited => {
+                let has_quorum =
+                    tally_type.has_quorum(voted_power, total_voting_power);
+                let is_vetoed =
+                    tally_type.is_vetoed(veto_voting_power, voted_power);
+                let more_than_two_third_voted_yay = yay_voting_power * 3
+                    > (yay_voting_power + against_power) * 2;
+                has_quorum && !is_vetoed && more_than_two_third_voted_yay
             }
         };
 
@@ -129,12 +189,18 @@ impl TallyResult {
 pub struct ProposalResult {
     /// The result of a proposal
     pub result: TallyResult,
+    /// The type of tally used to compute the result
+    pub tally_type: TallyType,
     /// The total voting power during the proposal tally
     pub total_voting_power: VotePower,
     /// The total voting power from yay votes
     pub total_yay_power: VotePower,
-    /// The total voting power from nay votes (unused at the moment)
+    /// The total voting power from nay votes
     pub total_nay_power: VotePower,
+    /// The total voting power from abstain votes
+    pub total_abstain_power: VotePower,
+    /// The total voting power from nay with veto votes
+    pub total_veto_power: VotePower,
 }
 
 impl Display for ProposalResult {
@@ -146,10 +212,13 @@ impl Display for ProposalResult {
 
         write!(
             f,
-            "{} with {} yay votes and {} nay votes ({:.2}%)",
+            "{} with {} yay votes, {} nay votes, {} abstain votes and {} veto \
+             votes ({:.2}%)",
             self.result,
             self.total_yay_power.to_string_native(),
             self.total_nay_power.to_string_native(),
+            self.total_abstain_power.to_string_native(),
+            self.total_veto_power.to_string_native(),
             percentage
                 .checked_mul(token::Amount::from_u64(100))
                 .unwrap_or_default()
@@ -161,7 +230,28 @@ impl Display for ProposalResult {
 impl ProposalResult {
     /// Return true if two third of total voting power voted nay
     pub fn two_third_nay(&self) -> bool {
-        self.total_nay_power >= (self.total_voting_power / 3) * 2
+        self.total_nay_power + self.total_veto_power
+            >= (self.total_voting_power / 3) * 2
+    }
+
+    /// Return the voting power that took part in the vote
+    pub fn voted_power(&self) -> VotePower {
+        self.total_yay_power
+            + self.total_nay_power
+            + self.total_abstain_power
+            + self.total_veto_power
+    }
+
+    /// Return true if enough voting power took part in the vote
+    pub fn has_quorum(&self) -> bool {
+        self.tally_type
+            .has_quorum(self.voted_power(), self.total_voting_power)
+    }
+
+    /// Return true if the proposal was vetoed
+    pub fn is_vetoed(&self) -> bool {
+        self.tally_type
+            .is_vetoed(self.total_veto_power, self.voted_power())
     }
 }
 
@@ -194,12 +284,84 @@ impl TallyVote {
         }
     }
 
+    /// Check if a vote is abstain
+    pub fn is_abstain(&self) -> bool {
+        match self {
+            TallyVote::OnChain(vote) => vote.is_abstain(),
+            TallyVote::Offline(vote) => vote.is_abstain(),
+        }
+    }
+
+    /// Check if a vote is nay with veto
+    pub fn is_veto(&self) -> bool {
+        match self {
+            TallyVote::OnChain(vote) => vote.is_veto(),
+            TallyVote::Offline(vote) => vote.is_veto(),
+        }
+    }
+
     /// Check if two votes are equal
     pub fn is_same_side(&self, other: &TallyVote) -> bool {
-        let both_yay = self.is_yay() && other.is_yay();
-        let both_nay = !self.is_yay() && !other.is_yay();
+        if let (TallyVote::OnChain(vote), TallyVote::OnChain(other_vote)) =
+            (self, other)
+        {
+            if vote.is_split() || other_vote.is_split() {
+                return vote == other_vote;
+            }
+        }
+        self.is_yay() == other.is_yay()
+            && self.is_abstain() == other.is_abstain()
+            && self.is_veto() == other.is_veto()
+    }
+}
 
-        both_yay || !both_nay
+/// The voting power accumulated by each vote option
+#[derive(Default)]
+struct TallyPowers {
+    yay: VotePower,
+    nay: VotePower,
+    abstain: VotePower,
+    veto: VotePower,
+}
+
+impl TallyPowers {
+    /// Get the voting power counter for the option of a vote
+    fn power_mut(&mut self, vote: &TallyVote) -> &mut VotePower {
+        if vote.is_yay() {
+            &mut self.yay
+        } else if vote.is_abstain() {
+            &mut self.abstain
+        } else if vote.is_veto() {
+            &mut self.veto
+        } else {
+            &mut self.nay
+        }
+    }
+
+    /// Add the voting power of a vote, divided by weight if the vote is split
+    fn add(&mut self, vote: &TallyVote, power: VotePower) {
+        match vote {
+            TallyVote::OnChain(StorageProposalVote::Split(options)) => {
+                for option in options {
+                    let option_vote = TallyVote::OnChain(option.vote.clone());
+                    *self.power_mut(&option_vote) += option.weight * power;
+                }
+            }
+            _ => *self.power_mut(vote) += power,
+        }
+    }
+
+    /// Remove the voting power previously added for a vote
+    fn remove(&mut self, vote: &TallyVote, power: VotePower) {
+        match vote {
+            TallyVote::OnChain(StorageProposalVote::Split(options)) => {
+                for option in options {
+                    let option_vote = TallyVote::OnChain(option.vote.clone());
+                    *self.power_mut(&option_vote) -= option.weight * power;
+                }
+            }
+            _ => *self.power_mut(vote) -= power,
+        }
     }
 }
 
@@ -222,17 +384,12 @@ pub fn compute_proposal_result(
     total_voting_power: VotePower,
     tally_at: TallyType,
 ) -> ProposalResult {
-    let mut yay_voting_power = VotePower::default();
-    let mut nay_voting_power = VotePower::default();
+    let mut powers = TallyPowers::default();
 
     for (address, vote_power) in votes.validator_voting_power {
         let vote_type = votes.validators_vote.get(&address);
         if let Some(vote) = vote_type {
-            if vote.is_yay() {
-                yay_voting_power += vote_power;
-            } else {
-                nay_voting_power += vote_power;
-            }
+            powers.add(vote, vote_power);
         }
     }
 
@@ -245,34 +402,32 @@ pub fn compute_proposal_result(
             let validator_vote = votes.validators_vote.get(&validator);
             if let Some(validator_vote) = validator_vote {
                 if !validator_vote.is_same_side(delegator_vote) {
-                    if delegator_vote.is_yay() {
-                        yay_voting_power += voting_power;
-                        nay_voting_power -= voting_power;
-                    } else {
-                        nay_voting_power += voting_power;
-                        yay_voting_power -= voting_power;
-                    }
+                    powers.add(delegator_vote, voting_power);
+                    powers.remove(validator_vote, voting_power);
                 }
-            } else if delegator_vote.is_yay() {
-                yay_voting_power += voting_power;
             } else {
-                nay_voting_power += voting_power;
+                powers.add(delegator_vote, voting_power);
             }
         }
     }
 
     let tally_result = TallyResult::new(
         &tally_at,
-        yay_voting_power,
-        nay_voting_power,
+        powers.yay,
+        powers.nay,
+        powers.abstain,
+        powers.veto,
         total_voting_power,
     );
 
     ProposalResult {
         result: tally_result,
+        tally_type: tally_at,
         total_voting_power,
-        total_yay_power: yay_voting_power,
-        total_nay_power: nay_voting_power,
+        total_yay_power: powers.yay,
+        total_nay_power: powers.nay,
+        total_abstain_power: powers.abstain,
+        total_veto_power: powers.veto,
     }
 }
 
@@ -291,3 +446,145 @@ pub fn is_valid_validator_voting_period(
         current_epoch <= voting_start_epoch + two_third_duration
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::ledger::governance::storage::vote::{VoteType, WeightedVote};
+    use crate::types::address;
+    use crate::types::dec::Dec;
+
+    fn tally(
+        tally_type: TallyType,
+        yay: u64,
+        nay: u64,
+        abstain: u64,
+        veto: u64,
+    ) -> TallyResult {
+        TallyResult::new(
+            &tally_type,
+            VotePower::from_u64(yay),
+            VotePower::from_u64(nay),
+            VotePower::from_u64(abstain),
+            VotePower::from_u64(veto),
+            VotePower::from_u64(90),
+        )
+    }
+
+    #[test]
+    fn test_abstain_counts_toward_quorum_only() {
+        // Without the abstain votes quorum is not reached
+        assert!(matches!(
+            tally(TallyType::TwoThirdWithVeto, 40, 10, 0, 0),
+            TallyResult::Rejected
+        ));
+        assert!(matches!(
+            tally(TallyType::TwoThirdWithVeto, 40, 10, 20, 0),
+            TallyResult::Passed
+        ));
+        // Abstain votes never help a proposal pass
+        assert!(matches!(
+            tally(TallyType::TwoThirdWithVeto, 10, 10, 60, 0),
+            TallyResult::Rejected
+        ));
+    }
+
+    #[test]
+    fn test_veto() {
+        // The veto votes count as nay votes
+        assert!(matches!(
+            tally(TallyType::TwoThird, 35, 20, 0, 20),
+            TallyResult::Rejected
+        ));
+        // More than a third of the voted power vetoed
+        assert!(matches!(
+            tally(TallyType::TwoThirdWithVeto, 50, 0, 0, 26),
+            TallyResult::Rejected
+        ));
+        assert!(
+            TallyType::TwoThirdWithVeto
+                .is_vetoed(VotePower::from_u64(26), VotePower::from_u64(76))
+        );
+        assert!(
+            !TallyType::TwoThird
+                .is_vetoed(VotePower::from_u64(26), VotePower::from_u64(76))
+        );
+        assert!(matches!(
+            tally(TallyType::TwoThirdWithVeto, 50, 0, 0, 20),
+            TallyResult::Passed
+        ));
+    }
+
+    #[test]
+    fn test_expedited_threshold() {
+        // A majority is not enough for an expedited proposal
+        assert!(matches!(
+            tally(TallyType::TwoThirdWithVeto, 40, 30, 0, 0),
+            TallyResult::Passed
+        ));
+        assert!(matches!(
+            tally(TallyType::Expedited, 40, 30, 0, 0),
+            TallyResult::Rejected
+        ));
+        assert!(matches!(
+            tally(TallyType::Expedited, 50, 20, 10, 0),
+            TallyResult::Passed
+        ));
+        // Abstain votes alone can't pass an expedited proposal
+        assert!(matches!(
+            tally(TallyType::Expedited, 0, 0, 80, 0),
+            TallyResult::Rejected
+        ));
+    }
+
+    #[test]
+    fn test_split_votes() {
+        let validator = address::testing::established_address_1();
+        let delegator = address::testing::established_address_2();
+        let weighted = |vote, weight| WeightedVote {
+            vote,
+            weight: Dec::from_str(weight).unwrap(),
+        };
+        let validator_vote = StorageProposalVote::Split(vec![
+            weighted(StorageProposalVote::Yay(VoteType::Default), "0.5"),
+            weighted(StorageProposalVote::Nay, "0.5"),
+        ]);
+        let delegator_vote = StorageProposalVote::Split(vec![
+            weighted(StorageProposalVote::Yay(VoteType::Default), "0.6"),
+            weighted(StorageProposalVote::Abstain, "0.4"),
+        ]);
+
+        let votes = ProposalVotes {
+            validators_vote: HashMap::from([(
+                validator.clone(),
+                validator_vote.into(),
+            )]),
+            validator_voting_power: HashMap::from([(
+                validator.clone(),
+                VotePower::from_u64(100),
+            )]),
+            delegators_vote: HashMap::from([(
+                delegator.clone(),
+                delegator_vote.into(),
+            )]),
+            delegator_voting_power: HashMap::from([(
+                delegator,
+                HashMap::from([(validator, VotePower::from_u64(50))]),
+            )]),
+        };
+        let result = compute_proposal_result(
+            votes,
+            VotePower::from_u64(100),
+            TallyType::TwoThirdWithVeto,
+        );
+
+        // The delegator overrides the split vote of its validator for its
+        // own voting power
+        assert_eq!(result.total_yay_power, VotePower::from_u64(55));
+        assert_eq!(result.total_nay_power, VotePower::from_u64(25));
+        assert_eq!(result.total_abstain_power, VotePower::from_u64(20));
+        assert!(matches!(result.result, TallyResult::Passed));
+    }
+}
@@ -17,7 +17,9 @@ use crate::ibc::core::ics04_channel::commitment::PacketCommitment;
 use crate::ibc::core::ics04_channel::error::{ChannelError, PacketError};
 use crate::ibc::core::ics04_channel::packet::Sequence;
 use crate::ibc::core::ics04_channel::timeout::TimeoutHeight;
-use crate::ibc::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::ibc::core::ics24_host::identifier::{
+    ChannelId, ClientId, ConnectionId, PortId,
+};
 use crate::ibc::core::ics24_host::path::{
     ChannelEndPath, ClientConsensusStatePath, CommitmentPath, Path, SeqSendPath,
 };
@@ -31,6 +33,9 @@ use crate::ibc_proto::google::protobuf::Any;
 use crate::ibc_proto::protobuf::Protobuf;
 use crate::ledger::ibc::storage;
 use crate::types::address::Address;
+use crate::ledger::ibc::forward::ForwardedPacket;
+use crate::ledger::ibc::nft::{ibc_nft_token, NftClass, NftMetadata};
+use crate::types::ibc::{RateLimit, RateLimitFlow};
 use crate::types::storage::Key;
 use crate::types::token;
 
@@ -446,4 +451,291 @@ pub trait IbcCommonContext: IbcStorageContext {
         }
         Ok(())
     }
+
+    /// Read the rate limit of the token through the channel
+    fn read_rate_limit(
+        &self,
+        channel_id: &ChannelId,
+        token: &Address,
+    ) -> Result<Option<RateLimit>, ContextError> {
+        let key = storage::rate_limit_key(channel_id, token);
+        let bytes = self.read(&key).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Reading the rate limit failed: Key {}",
+                    key
+                ),
+            })
+        })?;
+        bytes
+            .map(|b| RateLimit::try_from_slice(&b))
+            .transpose()
+            .map_err(|_| {
+                ContextError::ChannelError(ChannelError::Other {
+                    description: format!(
+                        "Decoding the rate limit failed: Key {}",
+                        key
+                    ),
+                })
+            })
+    }
+
+    /// Read the last recorded flow of the token through the channel
+    fn read_rate_limit_flow(
+        &self,
+        channel_id: &ChannelId,
+        token: &Address,
+    ) -> Result<RateLimitFlow, ContextError> {
+        let key = storage::rate_limit_flow_key(channel_id, token);
+        let bytes = self.read(&key).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Reading the rate limit flow failed: Key {}",
+                    key
+                ),
+            })
+        })?;
+        bytes
+            .map(|b| RateLimitFlow::try_from_slice(&b))
+            .transpose()
+            .map(Option::unwrap_or_default)
+            .map_err(|_| {
+                ContextError::ChannelError(ChannelError::Other {
+                    description: format!(
+                        "Decoding the rate limit flow failed: Key {}",
+                        key
+                    ),
+                })
+            })
+    }
+
+    /// Write the flow of the token through the channel
+    fn store_rate_limit_flow(
+        &mut self,
+        channel_id: &ChannelId,
+        token: &Address,
+        flow: RateLimitFlow,
+    ) -> Result<(), ContextError> {
+        let key = storage::rate_limit_flow_key(channel_id, token);
+        let bytes = flow.try_to_vec().expect("encoding shouldn't fail");
+        self.write(&key, bytes).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Writing the rate limit flow failed: Key {}",
+                    key
+                ),
+            })
+        })
+    }
+
+    /// Returns true if the owner has the NFT
+    fn is_nft_owned(
+        &self,
+        class_id: &str,
+        token_id: &str,
+        owner: &Address,
+    ) -> Result<bool, ContextError> {
+        let token = ibc_nft_token(class_id, token_id);
+        let key = token::balance_key(&token, owner);
+        let bytes = self.read(&key).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Reading the NFT balance failed: Key {}",
+                    key
+                ),
+            })
+        })?;
+        let balance = bytes
+            .map(|b| token::Amount::try_from_slice(&b))
+            .transpose()
+            .map_err(|_| {
+                ContextError::ChannelError(ChannelError::Other {
+                    description: format!(
+                        "Decoding the NFT balance failed: Key {}",
+                        key
+                    ),
+                })
+            })?
+            .unwrap_or_default();
+        Ok(balance == token::Amount::from_u64(1))
+    }
+
+    /// Read the NFT class
+    fn read_nft_class(
+        &self,
+        class_id: &str,
+    ) -> Result<Option<NftClass>, ContextError> {
+        let key = storage::nft_class_key(class_id);
+        let bytes = self.read(&key).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Reading the NFT class failed: Key {}",
+                    key
+                ),
+            })
+        })?;
+        bytes
+            .map(|b| NftClass::try_from_slice(&b))
+            .transpose()
+            .map_err(|_| {
+                ContextError::ChannelError(ChannelError::Other {
+                    description: format!(
+                        "Decoding the NFT class failed: Key {}",
+                        key
+                    ),
+                })
+            })
+    }
+
+    /// Write the NFT class
+    fn store_nft_class(&mut self, class: NftClass) -> Result<(), ContextError> {
+        let key = storage::nft_class_key(&class.id);
+        let bytes = class.try_to_vec().expect("encoding shouldn't fail");
+        self.write(&key, bytes).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Writing the NFT class failed: Key {}",
+                    key
+                ),
+            })
+        })
+    }
+
+    /// Read the NFT metadata
+    fn read_nft_metadata(
+        &self,
+        class_id: &str,
+        token_id: &str,
+    ) -> Result<Option<NftMetadata>, ContextError> {
+        let key = storage::nft_metadata_key(class_id, token_id);
+        let bytes = self.read(&key).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Reading the NFT metadata failed: Key {}",
+                    key
+                ),
+            })
+        })?;
+        bytes
+            .map(|b| NftMetadata::try_from_slice(&b))
+            .transpose()
+            .map_err(|_| {
+                ContextError::ChannelError(ChannelError::Other {
+                    description: format!(
+                        "Decoding the NFT metadata failed: Key {}",
+                        key
+                    ),
+                })
+            })
+    }
+
+    /// Write the NFT metadata
+    fn store_nft_metadata(
+        &mut self,
+        metadata: NftMetadata,
+    ) -> Result<(), ContextError> {
+        let key =
+            storage::nft_metadata_key(&metadata.class_id, &metadata.token_id);
+        let bytes = metadata.try_to_vec().expect("encoding shouldn't fail");
+        self.write(&key, bytes).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Writing the NFT metadata failed: Key {}",
+                    key
+                ),
+            })
+        })
+    }
+
+    /// Delete the NFT metadata
+    fn delete_nft_metadata(
+        &mut self,
+        class_id: &str,
+        token_id: &str,
+    ) -> Result<(), ContextError> {
+        let key = storage::nft_metadata_key(class_id, token_id);
+        self.delete(&key).map_err(|_| {
+            ContextError::ChannelError(ChannelError::Other {
+                description: format!(
+                    "Deleting the NFT metadata failed: Key {}",
+                    key
+                ),
+            })
+        })
+    }
+
+    /// Read the forwarded packet in flight
+    fn read_forwarded_packet(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<ForwardedPacket>, ContextError> {
+        let key = storage::forwarded_packet_key(port_id, channel_id, sequence);
+        let bytes = self.read(&key).map_err(|_| {
+            ContextError::PacketError(PacketError::Channel(
+                ChannelError::Other {
+                    description: format!(
+                        "Reading the forwarded packet failed: Key {}",
+                        key
+                    ),
+                },
+            ))
+        })?;
+        bytes
+            .map(|b| ForwardedPacket::try_from_slice(&b))
+            .transpose()
+            .map_err(|_| {
+                ContextError::PacketError(PacketError::Channel(
+                    ChannelError::Other {
+                        description: format!(
+                            "Decoding the forwarded packet failed: Key {}",
+                            key
+                        ),
+                    },
+                ))
+            })
+    }
+
+    /// Write the forwarded packet in flight
+    fn store_forwarded_packet(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        forwarded: ForwardedPacket,
+    ) -> Result<(), ContextError> {
+        let key = storage::forwarded_packet_key(port_id, channel_id, sequence);
+        let bytes = forwarded.try_to_vec().expect("encoding shouldn't fail");
+        self.write(&key, bytes).map_err(|_| {
+            ContextError::PacketError(PacketError::Channel(
+                ChannelError::Other {
+                    description: format!(
+                        "Writing the forwarded packet failed: Key {}",
+                        key
+                    ),
+                },
+            ))
+        })
+    }
+
+    /// Delete the forwarded packet in flight
+    fn delete_forwarded_packet(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<(), ContextError> {
+        let key = storage::forwarded_packet_key(port_id, channel_id, sequence);
+        self.delete(&key).map_err(|_| {
+            ContextError::PacketError(PacketError::Channel(
+                ChannelError::Other {
+                    description: format!(
+                        "Deleting the forwarded packet failed: Key {}",
+                        key
+                    ),
+                },
+            ))
+        })
+    }
 }
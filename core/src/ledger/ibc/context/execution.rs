@@ -254,6 +254,10 @@ where
         path: &AckPath,
         ack_commitment: AcknowledgementCommitment,
     ) -> Result<(), ContextError> {
+        if let Some(held_ack) = self.held_ack.as_mut() {
+            held_ack.commitment = Some((path.clone(), ack_commitment));
+            return Ok(());
+        }
         let path = Path::Ack(path.clone());
         let key = storage::ibc_key(path.to_string())
             .expect("Creating a key for the client state shouldn't fail");
@@ -347,6 +351,12 @@ where
     }
 
     fn emit_ibc_event(&mut self, event: IbcEvent) {
+        if let (IbcEvent::WriteAcknowledgement(_), Some(held_ack)) =
+            (&event, self.held_ack.as_mut())
+        {
+            held_ack.event = Some(event);
+            return;
+        }
         let event = event.try_into().expect("The event should be converted");
         self.ctx
             .borrow_mut()
@@ -2,6 +2,7 @@
 
 pub mod common;
 pub mod execution;
+pub mod nft_transfer_mod;
 pub mod router;
 pub mod storage;
 pub mod transfer_mod;
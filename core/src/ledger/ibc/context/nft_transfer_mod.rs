@@ -0,0 +1,746 @@
+//! IBC module for NFT transfer
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use super::common::IbcCommonContext;
+use super::transfer_mod::ModuleWrapper;
+use crate::ibc::applications::transfer::acknowledgement::TokenTransferAcknowledgement;
+use crate::ibc::core::events::{IbcEvent, ModuleEvent};
+use crate::ibc::core::ics02_client::client_state::ClientState;
+use crate::ibc::core::ics02_client::consensus_state::ConsensusState;
+use crate::ibc::core::ics03_connection::connection::ConnectionEnd;
+use crate::ibc::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty, Order,
+};
+use crate::ibc::core::ics04_channel::commitment::PacketCommitment;
+use crate::ibc::core::ics04_channel::context::{
+    SendPacketExecutionContext, SendPacketValidationContext,
+};
+use crate::ibc::core::ics04_channel::error::{ChannelError, PacketError};
+use crate::ibc::core::ics04_channel::handler::send_packet::{
+    send_packet_execute, send_packet_validate,
+};
+use crate::ibc::core::ics04_channel::packet::{
+    Acknowledgement, Packet, Sequence,
+};
+use crate::ibc::core::ics04_channel::Version;
+use crate::ibc::core::ics24_host::identifier::{
+    ChannelId, ClientId, ConnectionId, PortId,
+};
+use crate::ibc::core::ics24_host::path::{
+    ChannelEndPath, ClientConsensusStatePath, CommitmentPath, SeqSendPath,
+};
+use crate::ibc::core::router::{Module, ModuleExtras, ModuleId};
+use crate::ibc::core::ContextError;
+use crate::ibc::Signer;
+use crate::ledger::ibc::nft::{
+    ibc_nft_token, is_receiver_chain_source, is_sender_chain_source,
+    nft_amount, trace_prefix, Error, MsgNftTransfer, NftClass, NftMetadata,
+    NftPacketData, EVENT_TYPE_PACKET, EVENT_TYPE_TRANSFER, MODULE_ID_STR,
+    VERSION,
+};
+use crate::ledger::ibc::storage;
+use crate::types::address::{Address, InternalAddress};
+
+/// IBC module for NFT transfer
+#[derive(Debug)]
+pub struct NftTransferModule<C>
+where
+    C: IbcCommonContext,
+{
+    /// IBC actions
+    pub ctx: Rc<RefCell<C>>,
+}
+
+impl<C> NftTransferModule<C>
+where
+    C: IbcCommonContext,
+{
+    /// Make a new module
+    pub fn new(ctx: Rc<RefCell<C>>) -> Self {
+        Self { ctx }
+    }
+
+    /// Get the module ID
+    pub fn module_id(&self) -> ModuleId {
+        ModuleId::new(MODULE_ID_STR.to_string())
+    }
+
+    /// Validate sending NFTs
+    pub fn send_validate(&self, msg: &MsgNftTransfer) -> Result<(), Error> {
+        let sender = decode_address(&msg.sender)?;
+        let burned = !is_sender_chain_source(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &msg.class_id,
+        );
+        for token_id in &msg.token_ids {
+            if !self.ctx.borrow().is_nft_owned(
+                &msg.class_id,
+                token_id,
+                &sender,
+            )? {
+                return Err(Error::Ownership(format!(
+                    "The sender doesn't own the NFT: Class {}, Token {}, \
+                     Sender {}",
+                    msg.class_id, token_id, sender
+                )));
+            }
+            // The metadata is sent to restore the NFT on the counterparty
+            // chain
+            if burned
+                && self
+                    .ctx
+                    .borrow()
+                    .read_nft_metadata(&msg.class_id, token_id)?
+                    .is_none()
+            {
+                return Err(Error::Storage(format!(
+                    "No NFT metadata: Class {}, Token {}",
+                    msg.class_id, token_id
+                )));
+            }
+        }
+        let packet = self.make_packet(msg)?;
+        send_packet_validate(self, &packet)
+            .map_err(|e| Error::Context(ContextError::PacketError(e)))
+    }
+
+    /// Execute sending NFTs. The NFTs are escrowed if this chain is the
+    /// source of the class, otherwise they are burned.
+    pub fn send_execute(&mut self, msg: &MsgNftTransfer) -> Result<(), Error> {
+        let sender = decode_address(&msg.sender)?;
+        // The packet includes the metadata before being burned
+        let packet = self.make_packet(msg)?;
+        let escrow = Address::Internal(InternalAddress::Ibc);
+        let is_source = is_sender_chain_source(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &msg.class_id,
+        );
+        for token_id in &msg.token_ids {
+            let token = ibc_nft_token(&msg.class_id, token_id);
+            if is_source {
+                self.ctx
+                    .borrow_mut()
+                    .transfer_token(&sender, &escrow, &token, nft_amount())
+                    .map_err(|e| {
+                        Error::Storage(format!(
+                            "Escrowing the NFT failed: {:?}",
+                            e
+                        ))
+                    })?;
+            } else {
+                self.ctx
+                    .borrow_mut()
+                    .burn_token(&sender, &token, nft_amount())
+                    .map_err(|e| {
+                        Error::Storage(format!(
+                            "Burning the NFT failed: {:?}",
+                            e
+                        ))
+                    })?;
+                self.ctx
+                    .borrow_mut()
+                    .delete_nft_metadata(&msg.class_id, token_id)?;
+            }
+        }
+        send_packet_execute(self, packet)
+            .map_err(|e| Error::Context(ContextError::PacketError(e)))?;
+
+        let event = ModuleEvent {
+            kind: EVENT_TYPE_TRANSFER.to_string(),
+            module_name: self.module_id(),
+            attributes: vec![
+                ("sender", msg.sender.clone()).into(),
+                ("receiver", msg.receiver.clone()).into(),
+                ("classId", msg.class_id.clone()).into(),
+                ("tokenIds", msg.token_ids.join(",")).into(),
+            ],
+        };
+        self.emit_ibc_event(IbcEvent::Module(event));
+        Ok(())
+    }
+
+    fn make_packet(&self, msg: &MsgNftTransfer) -> Result<Packet, Error> {
+        let chan_end_path_on_a =
+            ChannelEndPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
+        let chan_end_on_a =
+            self.ctx.borrow().channel_end(&chan_end_path_on_a)?;
+        let port_id_on_b = chan_end_on_a.counterparty().port_id().clone();
+        let chan_id_on_b = chan_end_on_a
+            .counterparty()
+            .channel_id()
+            .ok_or_else(|| {
+                Error::InvalidChannel(format!(
+                    "No counterparty channel: Port {}, Channel {}",
+                    msg.port_id_on_a, msg.chan_id_on_a
+                ))
+            })?
+            .clone();
+        let seq_send_path_on_a =
+            SeqSendPath::new(&msg.port_id_on_a, &msg.chan_id_on_a);
+        let seq_on_a = self
+            .ctx
+            .borrow()
+            .get_next_sequence_send(&seq_send_path_on_a)?;
+
+        let class = self.ctx.borrow().read_nft_class(&msg.class_id)?;
+        let mut token_uris = Vec::new();
+        let mut token_data = Vec::new();
+        for token_id in &msg.token_ids {
+            let metadata = self
+                .ctx
+                .borrow()
+                .read_nft_metadata(&msg.class_id, token_id)?;
+            let (uri, data) = metadata
+                .map(|metadata| (metadata.uri, metadata.data))
+                .unwrap_or_default();
+            token_uris.push(uri.unwrap_or_default());
+            token_data.push(data.unwrap_or_default());
+        }
+        let has_any =
+            |values: &Vec<String>| values.iter().any(|v| !v.is_empty());
+        let data = NftPacketData {
+            class_id: msg.class_id.clone(),
+            class_uri: class.as_ref().and_then(|c| c.uri.clone()),
+            class_data: class.and_then(|c| c.data),
+            token_ids: msg.token_ids.clone(),
+            token_uris: has_any(&token_uris).then_some(token_uris),
+            token_data: has_any(&token_data).then_some(token_data),
+            sender: msg.sender.clone(),
+            receiver: msg.receiver.clone(),
+            memo: msg.memo.clone(),
+        };
+        let data = serde_json::to_vec(&data)
+            .expect("Encoding the packet data shouldn't fail");
+
+        Ok(Packet {
+            seq_on_a,
+            port_id_on_a: msg.port_id_on_a.clone(),
+            chan_id_on_a: msg.chan_id_on_a.clone(),
+            port_id_on_b,
+            chan_id_on_b,
+            data,
+            timeout_height_on_b: msg.timeout_height_on_b,
+            timeout_timestamp_on_b: msg.timeout_timestamp_on_b,
+        })
+    }
+
+    /// Validate receiving NFTs and returns the packet data and the receiver
+    fn receive_validate(
+        &self,
+        packet: &Packet,
+    ) -> Result<(NftPacketData, Address), Error> {
+        let data = decode_packet_data(packet)?;
+        let receiver = decode_address(&data.receiver)?;
+        let escrow = Address::Internal(InternalAddress::Ibc);
+        let prefix = trace_prefix(&packet.port_id_on_a, &packet.chan_id_on_a);
+        for token_id in &data.token_ids {
+            if is_receiver_chain_source(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                &data.class_id,
+            ) {
+                let class_id = &data.class_id[prefix.len()..];
+                if !self
+                    .ctx
+                    .borrow()
+                    .is_nft_owned(class_id, token_id, &escrow)?
+                {
+                    return Err(Error::Ownership(format!(
+                        "The NFT hasn't been escrowed: Class {}, Token {}",
+                        class_id, token_id
+                    )));
+                }
+            } else {
+                let class_id = received_class_id(packet, &data.class_id);
+                if self
+                    .ctx
+                    .borrow()
+                    .read_nft_metadata(&class_id, token_id)?
+                    .is_some()
+                {
+                    return Err(Error::Ownership(format!(
+                        "The NFT already exists: Class {}, Token {}",
+                        class_id, token_id
+                    )));
+                }
+            }
+        }
+        Ok((data, receiver))
+    }
+
+    /// Execute receiving NFTs. The escrowed NFTs are unescrowed if this chain
+    /// is the source of the class, otherwise new NFTs are minted.
+    fn receive_execute(&mut self, packet: &Packet) -> Result<(), Error> {
+        let (data, receiver) = self.receive_validate(packet)?;
+        let escrow = Address::Internal(InternalAddress::Ibc);
+        if is_receiver_chain_source(
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            &data.class_id,
+        ) {
+            let prefix =
+                trace_prefix(&packet.port_id_on_a, &packet.chan_id_on_a);
+            let class_id = &data.class_id[prefix.len()..];
+            for token_id in &data.token_ids {
+                let token = ibc_nft_token(class_id, token_id);
+                self.ctx
+                    .borrow_mut()
+                    .transfer_token(&escrow, &receiver, &token, nft_amount())
+                    .map_err(|e| {
+                        Error::Storage(format!(
+                            "Unescrowing the NFT failed: {:?}",
+                            e
+                        ))
+                    })?;
+            }
+            return Ok(());
+        }
+
+        let class_id = received_class_id(packet, &data.class_id);
+        if self.ctx.borrow().read_nft_class(&class_id)?.is_none() {
+            self.ctx.borrow_mut().store_nft_class(NftClass {
+                id: class_id.clone(),
+                uri: data.class_uri.clone(),
+                data: data.class_data.clone(),
+            })?;
+        }
+        for (i, token_id) in data.token_ids.iter().enumerate() {
+            let (uri, token_data) = data.token_uri_data(i);
+            self.mint(&receiver, &class_id, token_id, uri, token_data)?;
+        }
+        Ok(())
+    }
+
+    /// Give back the NFTs sent by the failed packet
+    fn refund(&mut self, packet: &Packet) -> Result<(), Error> {
+        let data = decode_packet_data(packet)?;
+        let sender = decode_address(&data.sender)?;
+        let escrow = Address::Internal(InternalAddress::Ibc);
+        let is_source = is_sender_chain_source(
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            &data.class_id,
+        );
+        for (i, token_id) in data.token_ids.iter().enumerate() {
+            if is_source {
+                let token = ibc_nft_token(&data.class_id, token_id);
+                self.ctx
+                    .borrow_mut()
+                    .transfer_token(&escrow, &sender, &token, nft_amount())
+                    .map_err(|e| {
+                        Error::Storage(format!(
+                            "Refunding the NFT failed: {:?}",
+                            e
+                        ))
+                    })?;
+            } else {
+                let (uri, token_data) = data.token_uri_data(i);
+                self.mint(&sender, &data.class_id, token_id, uri, token_data)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn mint(
+        &mut self,
+        owner: &Address,
+        class_id: &str,
+        token_id: &str,
+        uri: Option<String>,
+        data: Option<String>,
+    ) -> Result<(), Error> {
+        let token = ibc_nft_token(class_id, token_id);
+        let nft = format!("{}/{}", class_id, token_id);
+        let mut ctx = self.ctx.borrow_mut();
+        ctx.store_nft_metadata(NftMetadata {
+            class_id: class_id.to_string(),
+            token_id: token_id.to_string(),
+            uri,
+            data,
+        })?;
+        ctx.store_ibc_denom(storage::calc_hash(&nft), &nft)?;
+        ctx.store_token_denom(&token)?;
+        ctx.mint_token(owner, &token, nft_amount()).map_err(|e| {
+            Error::Storage(format!("Minting the NFT failed: {:?}", e))
+        })
+    }
+}
+
+/// Decode and check the NFT packet data
+fn decode_packet_data(packet: &Packet) -> Result<NftPacketData, Error> {
+    let data = serde_json::from_slice::<NftPacketData>(&packet.data)
+        .map_err(|e| Error::InvalidPacketData(e.to_string()))?;
+    data.validate()?;
+    Ok(data)
+}
+
+fn decode_address(address: &str) -> Result<Address, Error> {
+    Address::decode(address).map_err(|e| Error::InvalidAddress(e.to_string()))
+}
+
+/// The class ID on this chain of NFTs received from the counterparty chain
+fn received_class_id(packet: &Packet, class_id: &str) -> String {
+    format!(
+        "{}{}",
+        trace_prefix(&packet.port_id_on_b, &packet.chan_id_on_b),
+        class_id
+    )
+}
+
+fn validate_channel(
+    order: Order,
+    version: &Version,
+) -> Result<Version, ChannelError> {
+    if order != Order::Unordered {
+        return Err(into_channel_error(Error::InvalidChannel(format!(
+            "The channel should be unordered: Order {:?}",
+            order
+        ))));
+    }
+    let expected = Version::new(VERSION.to_string());
+    if *version != expected && *version != Version::empty() {
+        return Err(into_channel_error(Error::InvalidChannel(format!(
+            "Unexpected version: Version {}",
+            version
+        ))));
+    }
+    Ok(expected)
+}
+
+fn validate_counterparty_version(
+    version: &Version,
+) -> Result<(), ChannelError> {
+    if *version != Version::new(VERSION.to_string()) {
+        return Err(into_channel_error(Error::InvalidChannel(format!(
+            "Unexpected counterparty version: Version {}",
+            version
+        ))));
+    }
+    Ok(())
+}
+
+impl<C> ModuleWrapper for NftTransferModule<C>
+where
+    C: IbcCommonContext + Debug,
+{
+    fn as_module(&self) -> &dyn Module {
+        self
+    }
+
+    fn as_module_mut(&mut self) -> &mut dyn Module {
+        self
+    }
+}
+
+impl<C> Module for NftTransferModule<C>
+where
+    C: IbcCommonContext + Debug,
+{
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_validate(
+        &self,
+        order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        validate_channel(order, version)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_execute(
+        &mut self,
+        order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        let version = validate_channel(order, version)?;
+        Ok((ModuleExtras::empty(), version))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_validate(
+        &self,
+        order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        validate_counterparty_version(counterparty_version)?;
+        validate_channel(order, counterparty_version)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_try_execute(
+        &mut self,
+        order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        validate_counterparty_version(counterparty_version)?;
+        let version = validate_channel(order, counterparty_version)?;
+        Ok((ModuleExtras::empty(), version))
+    }
+
+    fn on_chan_open_ack_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        validate_counterparty_version(counterparty_version)
+    }
+
+    fn on_chan_open_ack_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        validate_counterparty_version(counterparty_version)?;
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_chan_open_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_chan_close_init_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        // Escrowed NFTs would be locked if the channel were closed
+        Err(into_channel_error(Error::InvalidChannel(
+            "The channel for NFT transfer cannot be closed".to_string(),
+        )))
+    }
+
+    fn on_chan_close_init_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.on_chan_close_init_validate(port_id, channel_id)?;
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_chan_close_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        let (ack, success) = match self.receive_execute(packet) {
+            Ok(()) => (TokenTransferAcknowledgement::success(), true),
+            Err(e) => {
+                (TokenTransferAcknowledgement::Error(e.to_string()), false)
+            }
+        };
+        let event = ModuleEvent {
+            kind: EVENT_TYPE_PACKET.to_string(),
+            module_name: self.module_id(),
+            attributes: vec![("success", success.to_string()).into()],
+        };
+        let extras = ModuleExtras {
+            events: vec![event],
+            log: Vec::new(),
+        };
+        (extras, ack.into())
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        decode_packet_data(packet).map_err(into_packet_error)?;
+        serde_json::from_slice::<TokenTransferAcknowledgement>(
+            acknowledgement.as_ref(),
+        )
+        .map_err(|e| {
+            into_packet_error(Error::InvalidPacketData(format!(
+                "Decoding the acknowledgement failed: {}",
+                e
+            )))
+        })?;
+        Ok(())
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        if let Err(e) = self.on_acknowledgement_packet_validate(
+            packet,
+            acknowledgement,
+            relayer,
+        ) {
+            return (ModuleExtras::empty(), Err(e));
+        }
+        let is_success =
+            serde_json::from_slice::<TokenTransferAcknowledgement>(
+                acknowledgement.as_ref(),
+            )
+            .map(|ack| ack.is_successful())
+            .unwrap_or_default();
+        let result = if is_success {
+            Ok(())
+        } else {
+            self.refund(packet).map_err(into_packet_error)
+        };
+        (ModuleExtras::empty(), result)
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        decode_packet_data(packet).map_err(into_packet_error)?;
+        Ok(())
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        let result = self.refund(packet).map_err(into_packet_error);
+        (ModuleExtras::empty(), result)
+    }
+}
+
+impl<C> SendPacketValidationContext for NftTransferModule<C>
+where
+    C: IbcCommonContext,
+{
+    fn channel_end(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<ChannelEnd, ContextError> {
+        self.ctx.borrow().channel_end(channel_end_path)
+    }
+
+    fn connection_end(
+        &self,
+        connection_id: &ConnectionId,
+    ) -> Result<ConnectionEnd, ContextError> {
+        self.ctx.borrow().connection_end(connection_id)
+    }
+
+    fn client_state(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Box<dyn ClientState>, ContextError> {
+        self.ctx.borrow().client_state(client_id)
+    }
+
+    fn client_consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Box<dyn ConsensusState>, ContextError> {
+        self.ctx.borrow().consensus_state(client_cons_state_path)
+    }
+
+    fn get_next_sequence_send(
+        &self,
+        seq_send_path: &SeqSendPath,
+    ) -> Result<Sequence, ContextError> {
+        self.ctx.borrow().get_next_sequence_send(seq_send_path)
+    }
+}
+
+impl<C> SendPacketExecutionContext for NftTransferModule<C>
+where
+    C: IbcCommonContext,
+{
+    fn store_next_sequence_send(
+        &mut self,
+        seq_send_path: &SeqSendPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        self.ctx
+            .borrow_mut()
+            .store_next_sequence_send(seq_send_path, seq)
+    }
+
+    fn store_packet_commitment(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        commitment: PacketCommitment,
+    ) -> Result<(), ContextError> {
+        self.ctx
+            .borrow_mut()
+            .store_packet_commitment(commitment_path, commitment)
+    }
+
+    fn emit_ibc_event(&mut self, event: IbcEvent) {
+        let event = event.try_into().expect("IBC event conversion failed");
+        self.ctx
+            .borrow_mut()
+            .emit_ibc_event(event)
+            .expect("Emitting an IBC event failed")
+    }
+
+    fn log_message(&mut self, message: String) {
+        self.ctx.borrow_mut().log_string(message)
+    }
+}
+
+fn into_channel_error(error: Error) -> ChannelError {
+    ChannelError::AppModule {
+        description: error.to_string(),
+    }
+}
+
+fn into_packet_error(error: Error) -> PacketError {
+    PacketError::AppModule {
+        description: error.to_string(),
+    }
+}
@@ -7,8 +7,8 @@ pub use ics23::ProofSpec;
 use super::super::Error;
 use crate::ledger::storage_api;
 use crate::types::address::Address;
-use crate::types::ibc::IbcEvent;
-use crate::types::storage::{BlockHeight, Header, Key};
+use crate::types::ibc::{IbcEvent, IbcShieldedTransfer};
+use crate::types::storage::{BlockHeight, Epoch, Header, Key};
 use crate::types::token::DenominatedAmount;
 
 // This is needed to use `ibc::Handler::Error` with `IbcActions` in
@@ -86,9 +86,18 @@ pub trait IbcStorageContext {
         amount: DenominatedAmount,
     ) -> Result<(), Self::Error>;
 
+    /// Handle the MASP transaction of a shielded IBC transfer
+    fn handle_masp_tx(
+        &mut self,
+        shielded: &IbcShieldedTransfer,
+    ) -> Result<(), Self::Error>;
+
     /// Get the current height of this chain
     fn get_height(&self) -> Result<BlockHeight, Self::Error>;
 
+    /// Get the current epoch of this chain
+    fn get_epoch(&self) -> Result<Epoch, Self::Error>;
+
     /// Get the block header of this chain
     fn get_header(
         &self,
@@ -5,6 +5,7 @@ use std::fmt::Debug;
 use std::rc::Rc;
 
 use super::common::IbcCommonContext;
+use crate::ibc::applications::transfer::acknowledgement::TokenTransferAcknowledgement;
 use crate::ibc::applications::transfer::coin::PrefixedCoin;
 use crate::ibc::applications::transfer::context::{
     on_acknowledgement_packet_execute, on_acknowledgement_packet_validate,
@@ -20,6 +21,7 @@ use crate::ibc::applications::transfer::context::{
 };
 use crate::ibc::applications::transfer::denom::PrefixedDenom;
 use crate::ibc::applications::transfer::error::TokenTransferError;
+use crate::ibc::applications::transfer::packet::PacketData;
 use crate::ibc::applications::transfer::MODULE_ID_STR;
 use crate::ibc::core::events::IbcEvent;
 use crate::ibc::core::ics02_client::client_state::ClientState;
@@ -46,6 +48,7 @@ use crate::ibc::core::ics24_host::path::{
 use crate::ibc::core::router::{Module, ModuleExtras, ModuleId};
 use crate::ibc::core::ContextError;
 use crate::ibc::Signer;
+use crate::ledger::ibc::forward::ForwardMemo;
 use crate::ledger::ibc::storage;
 use crate::types::address::{Address, InternalAddress};
 use crate::types::token;
@@ -86,7 +89,7 @@ where
 
     /// Get the token address and the amount from PrefixedCoin. If the base
     /// denom is not an address, it returns `IbcToken`
-    fn get_token_amount(
+    pub fn get_token_amount(
         &self,
         coin: &PrefixedCoin,
     ) -> Result<(Address, token::DenominatedAmount), TokenTransferError> {
@@ -315,7 +318,14 @@ where
         packet: &Packet,
         _relayer: &Signer,
     ) -> (ModuleExtras, Acknowledgement) {
-        on_recv_packet_execute(self, packet)
+        match forwarding_packet(packet) {
+            Ok(Some(packet)) => on_recv_packet_execute(self, &packet),
+            Ok(None) => on_recv_packet_execute(self, packet),
+            Err(e) => (
+                ModuleExtras::empty(),
+                TokenTransferAcknowledgement::Error(e).into(),
+            ),
+        }
     }
 
     fn on_acknowledgement_packet_validate(
@@ -579,6 +589,28 @@ where
     }
 }
 
+/// Returns the packet whose receiver is replaced with the IBC address when the
+/// received tokens are forwarded to the next chain. The IBC address holds the
+/// tokens until they are sent on.
+fn forwarding_packet(packet: &Packet) -> Result<Option<Packet>, String> {
+    let Ok(mut data) = serde_json::from_slice::<PacketData>(&packet.data)
+    else {
+        return Ok(None);
+    };
+    let Some(memo) = ForwardMemo::parse(data.memo.as_ref()) else {
+        return Ok(None);
+    };
+    memo.forward
+        .port_channel()
+        .and(memo.forward.timeout())
+        .map_err(|e| format!("Invalid forward memo: {}", e))?;
+    data.receiver = Address::Internal(InternalAddress::Ibc).to_string().into();
+    let mut packet = packet.clone();
+    packet.data =
+        serde_json::to_vec(&data).expect("Encoding PacketData shouldn't fail");
+    Ok(Some(packet))
+}
+
 fn into_channel_error(error: TokenTransferError) -> ChannelError {
     ChannelError::AppModule {
         description: error.to_string(),
@@ -595,7 +627,6 @@ fn into_packet_error(error: TokenTransferError) -> PacketError {
 #[cfg(any(test, feature = "testing"))]
 pub mod testing {
     use super::*;
-    use crate::ibc::applications::transfer::acknowledgement::TokenTransferAcknowledgement;
 
     /// Dummy IBC module for token transfer
     #[derive(Debug)]
@@ -0,0 +1,201 @@
+//! Packet forwarding for multi-hop IBC transfers
+//!
+//! A received ICS-20 packet whose memo has the `forward` field, e.g.
+//! `{"forward":{"receiver":"...","port":"transfer","channel":"channel-1"}}`,
+//! is forwarded to the next chain through the given channel. The received
+//! tokens are held by the IBC address until they are sent on. The received
+//! packet is acknowledged only when the forwarded packet completes. When the
+//! forwarded packet fails, the received packet gets an error acknowledgement
+//! so that the source chain refunds the original sender.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::ibc::core::ics04_channel::packet::Packet;
+use crate::ibc::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
+
+/// The default timeout of a forwarded packet
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Packet memo to forward the received tokens
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardMemo {
+    /// Forwarding metadata
+    pub forward: ForwardMetadata,
+}
+
+/// Metadata to forward the received tokens to the next chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardMetadata {
+    /// The receiver on the next chain
+    pub receiver: String,
+    /// The port on this chain to send the tokens
+    pub port: String,
+    /// The channel on this chain to send the tokens
+    pub channel: String,
+    /// The timeout relative to the current block time, either nanoseconds or
+    /// a duration like "10m"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<ForwardTimeout>,
+    /// The memo for the next hop, which can forward the tokens again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<serde_json::Value>,
+}
+
+/// The relative timeout of a forwarded packet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ForwardTimeout {
+    /// Nanoseconds
+    Nanos(u64),
+    /// Duration string, e.g. "1h30m"
+    Duration(String),
+}
+
+impl ForwardMemo {
+    /// Parse the memo of a received packet. Returns `None` if the memo
+    /// isn't for forwarding.
+    pub fn parse(memo: &str) -> Option<Self> {
+        serde_json::from_str(memo).ok()
+    }
+}
+
+impl ForwardMetadata {
+    /// Get the port ID and the channel ID to send the tokens
+    pub fn port_channel(&self) -> Result<(PortId, ChannelId), String> {
+        let port_id = PortId::from_str(&self.port)
+            .map_err(|e| format!("Invalid port ID: {}", e))?;
+        let channel_id = ChannelId::from_str(&self.channel)
+            .map_err(|e| format!("Invalid channel ID: {}", e))?;
+        Ok((port_id, channel_id))
+    }
+
+    /// Get the timeout of the forwarded packet
+    pub fn timeout(&self) -> Result<Duration, String> {
+        match &self.timeout {
+            None => Ok(DEFAULT_FORWARD_TIMEOUT),
+            Some(ForwardTimeout::Nanos(nanos)) => {
+                Ok(Duration::from_nanos(*nanos))
+            }
+            Some(ForwardTimeout::Duration(duration)) => {
+                parse_duration(duration)
+            }
+        }
+    }
+
+    /// Get the memo of the forwarded packet
+    pub fn next_memo(&self) -> String {
+        match &self.next {
+            Some(serde_json::Value::String(memo)) => memo.clone(),
+            Some(next) => next.to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+/// Parse a duration string which is a sequence of numbers with a unit, e.g.
+/// "1h30m" or "90s"
+fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid duration: {}", duration);
+    let mut total = Duration::ZERO;
+    let mut rest = duration.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let value: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "ns" => Duration::from_nanos(1),
+            "us" | "µs" => Duration::from_micros(1),
+            "ms" => Duration::from_millis(1),
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(60 * 60),
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+        let value = u32::try_from(value).map_err(|_| invalid())?;
+        total = unit
+            .checked_mul(value)
+            .and_then(|d| total.checked_add(d))
+            .ok_or_else(invalid)?;
+    }
+    Ok(total)
+}
+
+/// A forwarded packet in flight. It's stored until the acknowledgement or
+/// the timeout of the forwarded packet to acknowledge the original packet.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct ForwardedPacket {
+    /// The original packet received by this chain, encoded in Protobuf
+    pub packet: Vec<u8>,
+}
+
+impl ForwardedPacket {
+    /// Make a forwarded packet in flight for the received packet
+    pub fn new(packet: &Packet) -> Self {
+        Self {
+            packet: RawPacket::from(packet.clone()).encode_to_vec(),
+        }
+    }
+
+    /// Decode the original packet
+    pub fn original_packet(&self) -> Result<Packet, String> {
+        let raw = RawPacket::decode(&self.packet[..])
+            .map_err(|e| format!("Decoding the packet failed: {}", e))?;
+        Packet::try_from(raw).map_err(|e| format!("Invalid packet: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_memo() {
+        let memo = r#"{"forward":{"receiver":"cosmos1","port":"transfer","channel":"channel-1","timeout":"1h30m","next":{"forward":{"receiver":"osmo1","port":"transfer","channel":"channel-2"}}}}"#;
+        let forward = ForwardMemo::parse(memo).unwrap().forward;
+        let (port_id, channel_id) = forward.port_channel().unwrap();
+        assert_eq!(port_id, PortId::transfer());
+        assert_eq!(channel_id, ChannelId::new(1));
+        assert_eq!(forward.timeout().unwrap(), Duration::from_secs(5400));
+        let next = ForwardMemo::parse(&forward.next_memo()).unwrap();
+        assert_eq!(next.forward.receiver, "osmo1");
+        assert_eq!(next.forward.timeout().unwrap(), DEFAULT_FORWARD_TIMEOUT);
+
+        assert!(ForwardMemo::parse("memo").is_none());
+        assert!(ForwardMemo::parse(r#"{"wasm":{}}"#).is_none());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(
+            parse_duration("1m500ms").unwrap(),
+            Duration::from_millis(60_500)
+        );
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("10d").is_err());
+    }
+}
@@ -1,32 +1,61 @@
 //! IBC library code
 
 pub mod context;
+pub mod forward;
+pub mod nft;
 pub mod storage;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::str::FromStr;
 use std::time::Duration;
 
+use borsh::{BorshDeserialize, BorshSerialize};
 pub use context::common::IbcCommonContext;
+pub use context::nft_transfer_mod::NftTransferModule;
 pub use context::storage::{IbcStorageContext, ProofSpec};
 pub use context::transfer_mod::{ModuleWrapper, TransferModule};
+use forward::{ForwardMemo, ForwardedPacket};
+use nft::{nft_transfer_port, MsgNftTransfer};
 use prost::Message;
 use thiserror::Error;
 
+use crate::ibc::applications::transfer::acknowledgement::TokenTransferAcknowledgement;
+use crate::ibc::applications::transfer::coin::PrefixedCoin;
+use crate::ibc::applications::transfer::denom::{
+    is_receiver_chain_source, TracePrefix,
+};
 use crate::ibc::applications::transfer::error::TokenTransferError;
 use crate::ibc::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::ibc::applications::transfer::packet::PacketData;
 use crate::ibc::applications::transfer::{
     send_transfer_execute, send_transfer_validate,
 };
+use crate::ibc::core::events::{IbcEvent, MessageEvent};
+use crate::ibc::core::ics04_channel::commitment::AcknowledgementCommitment;
+use crate::ibc::core::ics04_channel::events::WriteAcknowledgement;
 use crate::ibc::core::ics04_channel::msgs::PacketMsg;
+use crate::ibc::core::ics04_channel::packet::{Acknowledgement, Packet};
+use crate::ibc::core::ics04_channel::timeout::TimeoutHeight;
 use crate::ibc::core::ics23_commitment::specs::ProofSpecs;
-use crate::ibc::core::ics24_host::identifier::{ChainId as IbcChainId, PortId};
+use crate::ibc::core::ics24_host::identifier::{
+    ChainId as IbcChainId, ChannelId, PortId,
+};
+use crate::ibc::core::ics24_host::path::{AckPath, ChannelEndPath, SeqSendPath};
 use crate::ibc::core::router::{Module, ModuleId, Router};
-use crate::ibc::core::{execute, validate, MsgEnvelope, RouterError};
+use crate::ibc::core::timestamp::Timestamp;
+use crate::ibc::core::{
+    execute, validate, ExecutionContext, MsgEnvelope, RouterError,
+    ValidationContext,
+};
 use crate::ibc_proto::google::protobuf::Any;
+use crate::ibc_proto::protobuf::Protobuf;
+use crate::types::address::{masp, Address, InternalAddress};
 use crate::types::chain::ChainId;
+use crate::types::ibc::IbcShieldedTransfer;
+use crate::types::masp::PaymentAddress;
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -49,6 +78,200 @@ pub enum Error {
     Denom(String),
     #[error("Invalid chain ID: {0}")]
     ChainId(ChainId),
+    #[error(
+        "Invalid IBC message: the data is neither an IBC message nor a \
+         shielded transfer"
+    )]
+    InvalidMessage,
+    #[error("Decoding PacketData error: {0}")]
+    DecodingPacketData(serde_json::Error),
+    #[error("Decoding the shielded transfer error: {0}")]
+    DecodingShieldedTransfer(crate::types::ibc::Error),
+    #[error("Invalid shielded transfer: {0}")]
+    ShieldedTransfer(String),
+    #[error("Handling the MASP transaction failed: {0}")]
+    MaspTx(String),
+    #[error("IBC event error: {0}")]
+    IbcEvent(String),
+    #[error("Rate limit error: {0}")]
+    RateLimit(String),
+    #[error("IBC NFT transfer error: {0}")]
+    NftTransfer(nft::Error),
+    #[error("Packet forwarding error: {0}")]
+    Forward(String),
+}
+
+/// IBC messages which can be carried by an IBC transaction
+#[derive(Debug, Clone)]
+pub enum IbcMessage {
+    /// IBC envelope message handled by the IBC router
+    Envelope(MsgEnvelope),
+    /// IBC transfer from a transparent address
+    Transfer(MsgTransfer),
+    /// IBC transfer from a shielded address
+    ShieldedTransfer(MsgShieldedTransfer),
+    /// ICS-721 NFT transfer
+    NftTransfer(MsgNftTransfer),
+}
+
+/// IBC transfer message to send tokens from the MASP. The MASP transaction
+/// unshields the tokens to be sent by the IBC transfer.
+#[derive(Debug, Clone)]
+pub struct MsgShieldedTransfer {
+    /// IBC transfer message whose sender is the MASP address
+    pub message: MsgTransfer,
+    /// The shielded part of the transfer
+    pub shielded_transfer: IbcShieldedTransfer,
+}
+
+impl BorshSerialize for MsgShieldedTransfer {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        let encoded_msg = self.message.clone().encode_vec();
+        let members = (encoded_msg, self.shielded_transfer.clone());
+        BorshSerialize::serialize(&members, writer)
+    }
+}
+
+impl BorshDeserialize for MsgShieldedTransfer {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        let (msg, shielded_transfer): (Vec<u8>, IbcShieldedTransfer) =
+            BorshDeserialize::deserialize(buf)?;
+        let message = MsgTransfer::decode_vec(&msg).map_err(|err| {
+            Error::new(ErrorKind::InvalidData, err.to_string())
+        })?;
+        Ok(Self {
+            message,
+            shielded_transfer,
+        })
+    }
+}
+
+/// Decode the IBC transaction data as an IBC message
+pub fn decode_message(tx_data: &[u8]) -> Result<IbcMessage, Error> {
+    // ibc-rs message
+    if let Ok(any_msg) = Any::decode(tx_data) {
+        if let Ok(msg) = MsgTransfer::try_from(any_msg.clone()) {
+            return Ok(IbcMessage::Transfer(msg));
+        }
+        if let Ok(msg) = MsgNftTransfer::try_from(any_msg.clone()) {
+            return Ok(IbcMessage::NftTransfer(msg));
+        }
+        if let Ok(envelope) = MsgEnvelope::try_from(any_msg) {
+            return Ok(IbcMessage::Envelope(envelope));
+        }
+    }
+
+    // IBC transfer with the shielded part
+    if let Ok(msg) = MsgShieldedTransfer::try_from_slice(tx_data) {
+        return Ok(IbcMessage::ShieldedTransfer(msg));
+    }
+
+    Err(Error::InvalidMessage)
+}
+
+/// Get the shielded part of an IBC transaction, if any. It is found either in
+/// a transfer from the MASP, or in the memo of a received packet whose
+/// receiver is a payment address.
+pub fn get_shielded_transfer(
+    tx_data: &[u8],
+) -> Result<Option<IbcShieldedTransfer>, Error> {
+    match decode_message(tx_data)? {
+        IbcMessage::ShieldedTransfer(msg) => Ok(Some(msg.shielded_transfer)),
+        IbcMessage::Envelope(MsgEnvelope::Packet(PacketMsg::Recv(msg))) => {
+            get_shielded_transfer_from_packet(&msg.packet)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Get the shielded transfer from the memo of the packet data, if the
+/// receiver is a payment address
+fn get_shielded_transfer_from_packet(
+    packet: &Packet,
+) -> Result<Option<IbcShieldedTransfer>, Error> {
+    if packet.port_id_on_b != PortId::transfer() {
+        return Ok(None);
+    }
+    let data = serde_json::from_slice::<PacketData>(&packet.data)
+        .map_err(Error::DecodingPacketData)?;
+    if PaymentAddress::from_str(data.receiver.as_ref()).is_err() {
+        return Ok(None);
+    }
+    IbcShieldedTransfer::try_from(data.memo)
+        .map(Some)
+        .map_err(Error::DecodingShieldedTransfer)
+}
+
+/// Get the coin received by the packet, with the denom traced on this chain
+fn received_coin(packet: &Packet) -> Result<PrefixedCoin, Error> {
+    let data = serde_json::from_slice::<PacketData>(&packet.data)
+        .map_err(Error::DecodingPacketData)?;
+    let mut coin = data.token;
+    if is_receiver_chain_source(
+        packet.port_id_on_a.clone(),
+        packet.chan_id_on_a.clone(),
+        &coin.denom,
+    ) {
+        coin.denom.remove_trace_prefix(&TracePrefix::new(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+        ));
+    } else {
+        coin.denom.add_trace_prefix(TracePrefix::new(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+        ));
+    }
+    Ok(coin)
+}
+
+/// Returns true if the message receives an ICS-20 packet whose tokens are
+/// forwarded to the next chain
+fn is_forwarding(envelope: &MsgEnvelope) -> bool {
+    match envelope {
+        MsgEnvelope::Packet(PacketMsg::Recv(msg))
+            if msg.packet.port_id_on_b == PortId::transfer() =>
+        {
+            serde_json::from_slice::<PacketData>(&msg.packet.data)
+                .ok()
+                .and_then(|data| ForwardMemo::parse(data.memo.as_ref()))
+                .is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if the acknowledgement of an ICS-20 packet is successful
+fn is_ack_successful(ack: &Acknowledgement) -> bool {
+    serde_json::from_slice::<TokenTransferAcknowledgement>(ack.as_ref())
+        .map(|ack| ack.is_successful())
+        .unwrap_or_default()
+}
+
+/// The direction of a token flow through an IBC channel
+#[derive(Debug, Clone, Copy)]
+enum FlowDirection {
+    /// Tokens received from the counterparty chain
+    Inflow,
+    /// Tokens sent to the counterparty chain
+    Outflow,
+    /// Sent tokens refunded on a timeout or an error acknowledgement
+    Refund,
+    /// Received tokens given back because their forwarding has failed
+    Revert,
+}
+
+/// The acknowledgement of a received packet written by the IBC handler. It's
+/// held back while the received tokens are forwarded, and written when the
+/// forwarded packet completes.
+#[derive(Debug, Default)]
+struct HeldAck {
+    commitment: Option<(AckPath, AcknowledgementCommitment)>,
+    event: Option<IbcEvent>,
 }
 
 /// IBC actions to handle IBC operations
@@ -61,6 +284,7 @@ where
     modules: HashMap<ModuleId, Rc<dyn ModuleWrapper + 'a>>,
     ports: HashMap<PortId, ModuleId>,
     validation_params: ValidationParams,
+    held_ack: Option<HeldAck>,
 }
 
 impl<'a, C> IbcActions<'a, C>
@@ -74,6 +298,7 @@ where
             modules: HashMap::new(),
             ports: HashMap::new(),
             validation_params: ValidationParams::default(),
+            held_ack: None,
         }
     }
 
@@ -92,6 +317,16 @@ where
         self.ports.insert(PortId::transfer(), module_id);
     }
 
+    /// Add NFT transfer route
+    pub fn add_nft_transfer_route(
+        &mut self,
+        module_id: ModuleId,
+        module: impl ModuleWrapper + 'a,
+    ) {
+        self.modules.insert(module_id.clone(), Rc::new(module));
+        self.ports.insert(nft_transfer_port(), module_id);
+    }
+
     fn get_route_by_port(&self, port_id: &PortId) -> Option<&dyn Module> {
         self.lookup_module_by_port(port_id)
             .and_then(|id| self.get_route(&id))
@@ -107,28 +342,493 @@ where
 
     /// Execute according to the message in an IBC transaction or VP
     pub fn execute(&mut self, tx_data: &[u8]) -> Result<(), Error> {
-        let any_msg = Any::decode(tx_data).map_err(Error::DecodingData)?;
-        match MsgTransfer::try_from(any_msg.clone()) {
-            Ok(msg) => {
-                let port_id = msg.port_id_on_a.clone();
-                match self.get_route_mut_by_port(&port_id) {
-                    Some(_module) => {
-                        let mut module = TransferModule::new(self.ctx.clone());
-                        send_transfer_execute(&mut module, msg)
-                            .map_err(Error::TokenTransfer)
-                    }
-                    None => Err(Error::NoModule),
-                }
+        match decode_message(tx_data)? {
+            IbcMessage::Transfer(msg) => self.execute_transfer(msg),
+            IbcMessage::ShieldedTransfer(msg) => {
+                self.check_sending_shielded_transfer(&msg)?;
+                self.execute_transfer(msg.message)?;
+                self.handle_masp_tx(&msg.shielded_transfer)
             }
-            Err(_) => {
-                let envelope =
-                    MsgEnvelope::try_from(any_msg).map_err(Error::Execution)?;
-                execute(self, envelope.clone()).map_err(Error::Execution)?;
+            IbcMessage::NftTransfer(msg) => self.execute_nft_transfer(msg),
+            IbcMessage::Envelope(envelope) => {
+                self.held_ack = is_forwarding(&envelope).then(HeldAck::default);
+                let result =
+                    execute(self, envelope.clone()).map_err(Error::Execution);
+                let held_ack = self.held_ack.take();
+                result?;
+                // Tokens received or refunded count towards the rate limit
+                self.record_packet_flow(&envelope)?;
+                // For receiving the token to a shielded address
+                self.handle_received_masp_tx(&envelope)?;
                 // the current ibc-rs execution doesn't store the denom for the
                 // token hash when transfer with MsgRecvPacket
-                self.store_denom(envelope)
+                self.store_denom(envelope.clone())?;
+                // Forward the received tokens, or acknowledge the received
+                // packet when the forwarded packet has completed
+                self.forward_packet(&envelope, held_ack)
+            }
+        }
+    }
+
+    fn execute_transfer(&mut self, msg: MsgTransfer) -> Result<(), Error> {
+        let port_id = msg.port_id_on_a.clone();
+        let channel_id = msg.chan_id_on_a.clone();
+        let coin = msg.packet_data.token.clone();
+        match self.get_route_mut_by_port(&port_id) {
+            Some(_module) => {
+                let mut module = TransferModule::new(self.ctx.clone());
+                send_transfer_execute(&mut module, msg)
+                    .map_err(Error::TokenTransfer)?;
+            }
+            None => return Err(Error::NoModule),
+        }
+        self.record_flow(&channel_id, &coin, FlowDirection::Outflow)
+    }
+
+    fn execute_nft_transfer(
+        &mut self,
+        msg: MsgNftTransfer,
+    ) -> Result<(), Error> {
+        match self.get_route_mut_by_port(&msg.port_id_on_a) {
+            Some(_module) => {
+                let mut module = NftTransferModule::new(self.ctx.clone());
+                module.send_execute(&msg).map_err(Error::NftTransfer)
+            }
+            None => Err(Error::NoModule),
+        }
+    }
+
+    /// Check that the tokens sent by the IBC transfer are those unshielded
+    /// by the MASP transaction
+    fn check_sending_shielded_transfer(
+        &self,
+        msg: &MsgShieldedTransfer,
+    ) -> Result<(), Error> {
+        let transfer = &msg.shielded_transfer.transfer;
+        let sender =
+            Address::try_from(msg.message.packet_data.sender.clone()).ok();
+        if sender != Some(masp()) || transfer.source != masp() {
+            return Err(Error::ShieldedTransfer(
+                "The sender of a shielded transfer should be the MASP address"
+                    .to_string(),
+            ));
+        }
+        let module = TransferModule::new(self.ctx.clone());
+        let (token, amount) = module
+            .get_token_amount(&msg.message.packet_data.token)
+            .map_err(Error::TokenTransfer)?;
+        if token != transfer.token || amount.amount != transfer.amount.amount {
+            return Err(Error::ShieldedTransfer(format!(
+                "The MASP transaction doesn't unshield the sent token: Token \
+                 {}, Amount {}",
+                token,
+                amount.amount.to_string_native(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Handle the MASP transaction in the memo of a received packet when the
+    /// receiver is a payment address
+    fn handle_received_masp_tx(
+        &mut self,
+        envelope: &MsgEnvelope,
+    ) -> Result<(), Error> {
+        let packet = match envelope {
+            MsgEnvelope::Packet(PacketMsg::Recv(msg)) => &msg.packet,
+            _ => return Ok(()),
+        };
+        let Some(shielded_transfer) =
+            get_shielded_transfer_from_packet(packet)?
+        else {
+            return Ok(());
+        };
+        if !self.is_packet_received()? {
+            return Ok(());
+        }
+
+        // Check that the MASP transaction shields the received tokens
+        let coin = received_coin(packet)?;
+        let module = TransferModule::new(self.ctx.clone());
+        let (token, amount) = module
+            .get_token_amount(&coin)
+            .map_err(Error::TokenTransfer)?;
+        let transfer = &shielded_transfer.transfer;
+        if transfer.target != masp()
+            || token != transfer.token
+            || amount.amount != transfer.amount.amount
+        {
+            return Err(Error::ShieldedTransfer(format!(
+                "The MASP transaction doesn't shield the received token: \
+                 Token {}, Amount {}",
+                token,
+                amount.amount.to_string_native(),
+            )));
+        }
+
+        self.handle_masp_tx(&shielded_transfer)
+    }
+
+    /// Check if the tokens of the received packet have been received. They
+    /// aren't when the acknowledgement is an error.
+    fn is_packet_received(&self) -> Result<bool, Error> {
+        Ok(self
+            .ctx
+            .borrow()
+            .get_ibc_event("fungible_token_packet")
+            .map_err(|_| {
+                Error::IbcEvent("Reading the IBC event failed".to_string())
+            })?
+            .and_then(|event| event.attributes.get("success").cloned())
+            .map(|success| success == "true")
+            .unwrap_or_default())
+    }
+
+    /// Record the flow of the tokens received by a packet, or refunded
+    /// because a sent packet has failed
+    fn record_packet_flow(
+        &mut self,
+        envelope: &MsgEnvelope,
+    ) -> Result<(), Error> {
+        match envelope {
+            MsgEnvelope::Packet(PacketMsg::Recv(msg))
+                if msg.packet.port_id_on_b == PortId::transfer() =>
+            {
+                if !self.is_packet_received()? {
+                    return Ok(());
+                }
+                let coin = received_coin(&msg.packet)?;
+                self.record_flow(
+                    &msg.packet.chan_id_on_b,
+                    &coin,
+                    FlowDirection::Inflow,
+                )
+            }
+            MsgEnvelope::Packet(PacketMsg::Ack(msg))
+                if msg.packet.port_id_on_a == PortId::transfer() =>
+            {
+                if is_ack_successful(&msg.acknowledgement) {
+                    return Ok(());
+                }
+                self.record_refund(&msg.packet)
+            }
+            MsgEnvelope::Packet(PacketMsg::Timeout(msg))
+                if msg.packet.port_id_on_a == PortId::transfer() =>
+            {
+                self.record_refund(&msg.packet)
+            }
+            MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(msg))
+                if msg.packet.port_id_on_a == PortId::transfer() =>
+            {
+                self.record_refund(&msg.packet)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn record_refund(&mut self, packet: &Packet) -> Result<(), Error> {
+        let data = serde_json::from_slice::<PacketData>(&packet.data)
+            .map_err(Error::DecodingPacketData)?;
+        self.record_flow(
+            &packet.chan_id_on_a,
+            &data.token,
+            FlowDirection::Refund,
+        )
+    }
+
+    /// Record the flow of the token through the channel if a rate limit has
+    /// been set by governance, and check that the limit isn't exceeded
+    fn record_flow(
+        &mut self,
+        channel_id: &ChannelId,
+        coin: &PrefixedCoin,
+        direction: FlowDirection,
+    ) -> Result<(), Error> {
+        let module = TransferModule::new(self.ctx.clone());
+        let (token, amount) = module
+            .get_token_amount(coin)
+            .map_err(Error::TokenTransfer)?;
+        let Some(limit) = self
+            .ctx
+            .borrow()
+            .read_rate_limit(channel_id, &token)
+            .map_err(|e| Error::RateLimit(e.to_string()))?
+        else {
+            return Ok(());
+        };
+        let epoch = self.ctx.borrow().get_epoch().map_err(|e| {
+            Error::RateLimit(format!("Reading the epoch failed: {:?}", e))
+        })?;
+        let mut flow = self
+            .ctx
+            .borrow()
+            .read_rate_limit_flow(channel_id, &token)
+            .map_err(|e| Error::RateLimit(e.to_string()))?
+            .at(epoch, limit.period);
+        let overflow = || {
+            Error::RateLimit(format!(
+                "The flow overflowed: Channel {}, Token {}",
+                channel_id, token
+            ))
+        };
+        let exceeded = match direction {
+            FlowDirection::Inflow => {
+                flow.inflow = flow
+                    .inflow
+                    .checked_add(amount.amount)
+                    .ok_or_else(overflow)?;
+                flow.net_inflow() > limit.max_inflow
+            }
+            FlowDirection::Outflow => {
+                flow.outflow = flow
+                    .outflow
+                    .checked_add(amount.amount)
+                    .ok_or_else(overflow)?;
+                flow.net_outflow() > limit.max_outflow
+            }
+            // The refunded tokens have never left this chain
+            FlowDirection::Refund => {
+                flow.outflow =
+                    flow.outflow.checked_sub(amount.amount).unwrap_or_default();
+                false
+            }
+            // The reverted tokens have never been received by this chain
+            FlowDirection::Revert => {
+                flow.inflow =
+                    flow.inflow.checked_sub(amount.amount).unwrap_or_default();
+                false
+            }
+        };
+        if exceeded {
+            return Err(Error::RateLimit(format!(
+                "The rate limit is exceeded: Channel {}, Token {}, Amount {}",
+                channel_id,
+                token,
+                amount.amount.to_string_native(),
+            )));
+        }
+        self.ctx
+            .borrow_mut()
+            .store_rate_limit_flow(channel_id, &token, flow)
+            .map_err(|e| Error::RateLimit(e.to_string()))
+    }
+
+    fn forward_packet(
+        &mut self,
+        envelope: &MsgEnvelope,
+        held_ack: Option<HeldAck>,
+    ) -> Result<(), Error> {
+        match envelope {
+            MsgEnvelope::Packet(PacketMsg::Recv(msg)) => match held_ack {
+                Some(held_ack) => {
+                    self.forward_received_packet(&msg.packet, held_ack)
+                }
+                None => Ok(()),
+            },
+            MsgEnvelope::Packet(PacketMsg::Ack(msg))
+                if msg.packet.port_id_on_a == PortId::transfer() =>
+            {
+                let is_success = is_ack_successful(&msg.acknowledgement);
+                self.finish_forwarding(&msg.packet, is_success)
+            }
+            MsgEnvelope::Packet(PacketMsg::Timeout(msg))
+                if msg.packet.port_id_on_a == PortId::transfer() =>
+            {
+                self.finish_forwarding(&msg.packet, false)
             }
+            MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(msg))
+                if msg.packet.port_id_on_a == PortId::transfer() =>
+            {
+                self.finish_forwarding(&msg.packet, false)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Send the tokens received by the IBC address to the next chain
+    /// according to the forward memo. The acknowledgement of the received
+    /// packet is held until the forwarded packet completes.
+    fn forward_received_packet(
+        &mut self,
+        packet: &Packet,
+        held_ack: HeldAck,
+    ) -> Result<(), Error> {
+        let data = serde_json::from_slice::<PacketData>(&packet.data)
+            .map_err(Error::DecodingPacketData)?;
+        let memo = ForwardMemo::parse(data.memo.as_ref())
+            .ok_or_else(|| Error::Forward("No forward memo".to_string()))?;
+        // The error acknowledgement is written as usual
+        if !self.is_packet_received()? {
+            return self.release_ack(held_ack);
+        }
+        let forward = memo.forward;
+        let (port_id, channel_id) =
+            forward.port_channel().map_err(Error::Forward)?;
+        let timeout = forward.timeout().map_err(Error::Forward)?;
+        let sequence = self
+            .ctx
+            .borrow()
+            .get_next_sequence_send(&SeqSendPath::new(&port_id, &channel_id))
+            .map_err(|e| Error::Forward(e.to_string()))?;
+        let coin = received_coin(packet)?;
+        let msg = MsgTransfer {
+            port_id_on_a: port_id.clone(),
+            chan_id_on_a: channel_id.clone(),
+            packet_data: PacketData {
+                token: coin.clone(),
+                sender: Address::Internal(InternalAddress::Ibc)
+                    .to_string()
+                    .into(),
+                receiver: forward.receiver.clone().into(),
+                memo: forward.next_memo().into(),
+            },
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b: self.timeout_timestamp(timeout)?,
+        };
+        self.execute_transfer(msg)?;
+
+        let forwarded = ForwardedPacket::new(packet);
+        self.ctx
+            .borrow_mut()
+            .store_forwarded_packet(&port_id, &channel_id, sequence, forwarded)
+            .map_err(|e| Error::Forward(e.to_string()))
+    }
+
+    /// Write the acknowledgement and the event held back by the IBC handler
+    fn release_ack(&mut self, held_ack: HeldAck) -> Result<(), Error> {
+        if let Some((path, commitment)) = held_ack.commitment {
+            self.store_packet_acknowledgement(&path, commitment)
+                .map_err(|e| Error::Forward(e.to_string()))?;
+        }
+        if let Some(event) = held_ack.event {
+            self.emit_ibc_event(event);
+        }
+        Ok(())
+    }
+
+    /// Remove the forwarded packet in flight when it has been acknowledged
+    /// or timed out, and acknowledge the original packet. If the forwarded
+    /// packet has failed, the original packet gets an error acknowledgement
+    /// and the source chain refunds the original sender.
+    fn finish_forwarding(
+        &mut self,
+        packet: &Packet,
+        is_success: bool,
+    ) -> Result<(), Error> {
+        let Some(forwarded) = self
+            .ctx
+            .borrow()
+            .read_forwarded_packet(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                packet.seq_on_a,
+            )
+            .map_err(|e| Error::Forward(e.to_string()))?
+        else {
+            return Ok(());
+        };
+        self.ctx
+            .borrow_mut()
+            .delete_forwarded_packet(
+                &packet.port_id_on_a,
+                &packet.chan_id_on_a,
+                packet.seq_on_a,
+            )
+            .map_err(|e| Error::Forward(e.to_string()))?;
+        let original = forwarded.original_packet().map_err(Error::Forward)?;
+
+        let ack = if is_success {
+            TokenTransferAcknowledgement::success()
+        } else {
+            self.revert_received_packet(&original)?;
+            TokenTransferAcknowledgement::Error(
+                "Forwarding the received tokens failed".to_string(),
+            )
+        };
+        self.write_ack(&original, ack.into())
+    }
+
+    /// Revert the receipt of the tokens whose forwarding has failed. They
+    /// have been refunded to the IBC address by the forwarded packet.
+    fn revert_received_packet(&mut self, packet: &Packet) -> Result<(), Error> {
+        let data = serde_json::from_slice::<PacketData>(&packet.data)
+            .map_err(Error::DecodingPacketData)?;
+        let coin = received_coin(packet)?;
+        // The minted vouchers are burned. The unescrowed tokens are already
+        // back in the escrow account, which is the IBC address.
+        if !is_receiver_chain_source(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+            &data.token.denom,
+        ) {
+            let module = TransferModule::new(self.ctx.clone());
+            let (token, amount) = module
+                .get_token_amount(&coin)
+                .map_err(Error::TokenTransfer)?;
+            self.ctx
+                .borrow_mut()
+                .burn_token(
+                    &Address::Internal(InternalAddress::Ibc),
+                    &token,
+                    amount,
+                )
+                .map_err(|e| {
+                    Error::Forward(format!(
+                        "Burning the tokens failed: {:?}",
+                        e
+                    ))
+                })?;
         }
+        self.record_flow(&packet.chan_id_on_b, &coin, FlowDirection::Revert)
+    }
+
+    /// Write the acknowledgement of a received packet and emit the event
+    fn write_ack(
+        &mut self,
+        packet: &Packet,
+        ack: Acknowledgement,
+    ) -> Result<(), Error> {
+        let ack_path = AckPath::new(
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+            packet.seq_on_a,
+        );
+        let commitment = C::hash(ack.as_ref()).into();
+        self.store_packet_acknowledgement(&ack_path, commitment)
+            .map_err(|e| Error::Forward(e.to_string()))?;
+        let channel_end = self
+            .channel_end(&ChannelEndPath::new(
+                &packet.port_id_on_b,
+                &packet.chan_id_on_b,
+            ))
+            .map_err(|e| Error::Forward(e.to_string()))?;
+        let conn_id =
+            channel_end.connection_hops().first().cloned().ok_or_else(
+                || Error::Forward("The channel has no connection".to_string()),
+            )?;
+        self.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel));
+        self.emit_ibc_event(IbcEvent::WriteAcknowledgement(
+            WriteAcknowledgement::new(packet.clone(), ack, conn_id),
+        ));
+        Ok(())
+    }
+
+    /// Get the timeout timestamp relative to the current block time
+    fn timeout_timestamp(&self, timeout: Duration) -> Result<Timestamp, Error> {
+        let now = self
+            .host_timestamp()
+            .map_err(|e| Error::Forward(e.to_string()))?;
+        (now + timeout).map_err(|e| Error::Forward(e.to_string()))
+    }
+
+    fn handle_masp_tx(
+        &mut self,
+        shielded_transfer: &IbcShieldedTransfer,
+    ) -> Result<(), Error> {
+        self.ctx
+            .borrow_mut()
+            .handle_masp_tx(shielded_transfer)
+            .map_err(|e| Error::MaspTx(format!("{:?}", e)))
     }
 
     /// Store the denom when transfer with MsgRecvPacket
@@ -184,26 +884,40 @@ where
 
     /// Validate according to the message in IBC VP
     pub fn validate(&self, tx_data: &[u8]) -> Result<(), Error> {
-        let any_msg = Any::decode(tx_data).map_err(Error::DecodingData)?;
-        match MsgTransfer::try_from(any_msg.clone()) {
-            Ok(msg) => {
-                let port_id = msg.port_id_on_a.clone();
-                match self.get_route_by_port(&port_id) {
-                    Some(_module) => {
-                        let module = TransferModule::new(self.ctx.clone());
-                        send_transfer_validate(&module, msg)
-                            .map_err(Error::TokenTransfer)
-                    }
-                    None => Err(Error::NoModule),
-                }
+        match decode_message(tx_data)? {
+            IbcMessage::Transfer(msg) => self.validate_transfer(msg),
+            // The shielded part is validated by the MASP VP
+            IbcMessage::ShieldedTransfer(msg) => {
+                self.validate_transfer(msg.message)
             }
-            Err(_) => {
-                let envelope = MsgEnvelope::try_from(any_msg)
-                    .map_err(Error::Validation)?;
+            IbcMessage::NftTransfer(msg) => self.validate_nft_transfer(msg),
+            IbcMessage::Envelope(envelope) => {
                 validate(self, envelope).map_err(Error::Validation)
             }
         }
     }
+
+    fn validate_transfer(&self, msg: MsgTransfer) -> Result<(), Error> {
+        let port_id = msg.port_id_on_a.clone();
+        match self.get_route_by_port(&port_id) {
+            Some(_module) => {
+                let module = TransferModule::new(self.ctx.clone());
+                send_transfer_validate(&module, msg)
+                    .map_err(Error::TokenTransfer)
+            }
+            None => Err(Error::NoModule),
+        }
+    }
+
+    fn validate_nft_transfer(&self, msg: MsgNftTransfer) -> Result<(), Error> {
+        match self.get_route_by_port(&msg.port_id_on_a) {
+            Some(_module) => {
+                let module = NftTransferModule::new(self.ctx.clone());
+                module.send_validate(&msg).map_err(Error::NftTransfer)
+            }
+            None => Err(Error::NoModule),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,424 @@
+//! ICS-721 non-fungible token transfer
+//!
+//! NFTs are held on Namada as tokens whose balance is one. The class and the
+//! metadata of an NFT are stored in the IBC storage to be sent back.
+
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibc::core::ics04_channel::timeout::TimeoutHeight;
+use crate::ibc::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::ibc::core::timestamp::Timestamp;
+use crate::ibc::core::ContextError;
+use crate::ibc::Height;
+use crate::ibc_proto::google::protobuf::Any;
+use crate::ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use crate::ledger::ibc::storage::ibc_token;
+use crate::types::address::Address;
+use crate::types::token::{Amount, DenominatedAmount, Denomination};
+
+/// The module ID of the NFT transfer module
+pub const MODULE_ID_STR: &str = "nft-transfer";
+/// The port ID bound to the NFT transfer module
+pub const PORT_ID_STR: &str = "nft-transfer";
+/// The version of the ICS-721 application
+pub const VERSION: &str = "ics721-1";
+/// The type URL of the NFT transfer message
+pub const MSG_TRANSFER_TYPE_URL: &str =
+    "/ibc.applications.nft_transfer.v1.MsgTransfer";
+/// The event type of a received NFT packet
+pub const EVENT_TYPE_PACKET: &str = "non_fungible_token_packet";
+/// The event type of a sent NFT packet
+pub const EVENT_TYPE_TRANSFER: &str = "ibc_nft_transfer";
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invalid channel for NFT transfer: {0}")]
+    InvalidChannel(String),
+    #[error("Invalid NFT transfer message: {0}")]
+    InvalidMessage(String),
+    #[error("Decoding the NFT transfer message error: {0}")]
+    DecodingMessage(prost::DecodeError),
+    #[error("Invalid NFT packet data: {0}")]
+    InvalidPacketData(String),
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("NFT storage error: {0}")]
+    Storage(String),
+    #[error("NFT ownership error: {0}")]
+    Ownership(String),
+    #[error("IBC context error: {0}")]
+    Context(ContextError),
+}
+
+impl From<ContextError> for Error {
+    fn from(error: ContextError) -> Self {
+        Self::Context(error)
+    }
+}
+
+/// Get the port ID bound to the NFT transfer module
+pub fn nft_transfer_port() -> PortId {
+    PortId::from_str(PORT_ID_STR).expect("The NFT port ID should be valid")
+}
+
+/// The prefix added to a class ID when the NFT is received through the
+/// channel
+pub fn trace_prefix(port_id: &PortId, channel_id: &ChannelId) -> String {
+    format!("{}/{}/", port_id, channel_id)
+}
+
+/// Returns true if the class has been received from the chain which now
+/// receives the NFT back through the given source port and channel
+pub fn is_receiver_chain_source(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    class_id: &str,
+) -> bool {
+    class_id.starts_with(&trace_prefix(source_port, source_channel))
+}
+
+/// Returns true if the class hasn't been received through the given source
+/// port and channel, i.e. the NFT is escrowed instead of burned when sending
+/// it
+pub fn is_sender_chain_source(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    class_id: &str,
+) -> bool {
+    !is_receiver_chain_source(source_port, source_channel, class_id)
+}
+
+/// Obtain the token address representing the NFT on Namada
+pub fn ibc_nft_token(class_id: &str, token_id: &str) -> Address {
+    ibc_token(format!("{}/{}", class_id, token_id))
+}
+
+/// The amount of an NFT, which is minted, burned, escrowed or unescrowed as a
+/// token
+pub fn nft_amount() -> DenominatedAmount {
+    DenominatedAmount {
+        amount: Amount::from_u64(1),
+        denom: Denomination(0),
+    }
+}
+
+/// NFT class
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct NftClass {
+    /// The class ID prefixed with the trace path
+    pub id: String,
+    /// The URI of the class
+    pub uri: Option<String>,
+    /// The data of the class
+    pub data: Option<String>,
+}
+
+/// NFT metadata
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct NftMetadata {
+    /// The class ID prefixed with the trace path
+    pub class_id: String,
+    /// The token ID in the class
+    pub token_id: String,
+    /// The URI of the token
+    pub uri: Option<String>,
+    /// The data of the token
+    pub data: Option<String>,
+}
+
+/// ICS-721 packet data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftPacketData {
+    /// The class ID prefixed with the trace path on the sender chain
+    pub class_id: String,
+    /// The URI of the class
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_uri: Option<String>,
+    /// The data of the class
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_data: Option<String>,
+    /// The IDs of the sent tokens
+    pub token_ids: Vec<String>,
+    /// The URIs of the sent tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_uris: Option<Vec<String>>,
+    /// The data of the sent tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_data: Option<Vec<String>>,
+    /// The sender on the sender chain
+    pub sender: String,
+    /// The receiver on the receiver chain
+    pub receiver: String,
+    /// Memo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+impl NftPacketData {
+    /// Check the packet data
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.class_id.is_empty() {
+            return Err(Error::InvalidPacketData(
+                "The class ID is empty".to_string(),
+            ));
+        }
+        if self.token_ids.is_empty() {
+            return Err(Error::InvalidPacketData(
+                "No token ID is given".to_string(),
+            ));
+        }
+        let len = self.token_ids.len();
+        if self
+            .token_uris
+            .as_ref()
+            .map_or(false, |uris| uris.len() != len)
+            || self
+                .token_data
+                .as_ref()
+                .map_or(false, |data| data.len() != len)
+        {
+            return Err(Error::InvalidPacketData(
+                "The number of the token URIs or the token data mismatched"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the URI and the data of the token at the given index
+    pub fn token_uri_data(
+        &self,
+        index: usize,
+    ) -> (Option<String>, Option<String>) {
+        let uri = self
+            .token_uris
+            .as_ref()
+            .and_then(|uris| uris.get(index).cloned());
+        let data = self
+            .token_data
+            .as_ref()
+            .and_then(|data| data.get(index).cloned());
+        (uri, data)
+    }
+}
+
+/// Message to send NFTs through an IBC channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgNftTransfer {
+    /// The port on this chain
+    pub port_id_on_a: PortId,
+    /// The channel on this chain
+    pub chan_id_on_a: ChannelId,
+    /// The class ID prefixed with the trace path on this chain
+    pub class_id: String,
+    /// The IDs of the tokens to be sent
+    pub token_ids: Vec<String>,
+    /// The sender address
+    pub sender: String,
+    /// The receiver on the counterparty chain
+    pub receiver: String,
+    /// Memo
+    pub memo: Option<String>,
+    /// The timeout height relative to the counterparty chain
+    pub timeout_height_on_b: TimeoutHeight,
+    /// The timeout timestamp relative to the counterparty chain
+    pub timeout_timestamp_on_b: Timestamp,
+}
+
+/// Protobuf message to send NFTs, compatible with
+/// `ibc.applications.nft_transfer.v1.MsgTransfer`
+#[derive(Clone, PartialEq, Message)]
+pub struct RawMsgNftTransfer {
+    /// The port on the sender chain
+    #[prost(string, tag = "1")]
+    pub source_port: String,
+    /// The channel on the sender chain
+    #[prost(string, tag = "2")]
+    pub source_channel: String,
+    /// The class ID
+    #[prost(string, tag = "3")]
+    pub class_id: String,
+    /// The IDs of the tokens
+    #[prost(string, repeated, tag = "4")]
+    pub token_ids: Vec<String>,
+    /// The sender address
+    #[prost(string, tag = "5")]
+    pub sender: String,
+    /// The receiver on the counterparty chain
+    #[prost(string, tag = "6")]
+    pub receiver: String,
+    /// The timeout height, zero to disable it
+    #[prost(message, optional, tag = "7")]
+    pub timeout_height: Option<RawHeight>,
+    /// The timeout timestamp in nanoseconds, zero to disable it
+    #[prost(uint64, tag = "8")]
+    pub timeout_timestamp: u64,
+    /// Memo
+    #[prost(string, tag = "9")]
+    pub memo: String,
+}
+
+impl TryFrom<RawMsgNftTransfer> for MsgNftTransfer {
+    type Error = Error;
+
+    fn try_from(raw: RawMsgNftTransfer) -> Result<Self, Self::Error> {
+        let port_id_on_a = PortId::from_str(&raw.source_port)
+            .map_err(|e| Error::InvalidMessage(e.to_string()))?;
+        let chan_id_on_a = ChannelId::from_str(&raw.source_channel)
+            .map_err(|e| Error::InvalidMessage(e.to_string()))?;
+        let timeout_height_on_b = match raw.timeout_height {
+            Some(height)
+                if height.revision_number != 0
+                    || height.revision_height != 0 =>
+            {
+                let height =
+                    Height::new(height.revision_number, height.revision_height)
+                        .map_err(|e| Error::InvalidMessage(e.to_string()))?;
+                TimeoutHeight::At(height)
+            }
+            _ => TimeoutHeight::Never,
+        };
+        let timeout_timestamp_on_b =
+            Timestamp::from_nanoseconds(raw.timeout_timestamp)
+                .map_err(|e| Error::InvalidMessage(e.to_string()))?;
+        let msg = Self {
+            port_id_on_a,
+            chan_id_on_a,
+            class_id: raw.class_id,
+            token_ids: raw.token_ids,
+            sender: raw.sender,
+            receiver: raw.receiver,
+            memo: (!raw.memo.is_empty()).then_some(raw.memo),
+            timeout_height_on_b,
+            timeout_timestamp_on_b,
+        };
+        if msg.class_id.is_empty() || msg.token_ids.is_empty() {
+            return Err(Error::InvalidMessage(
+                "The class ID or the token IDs are empty".to_string(),
+            ));
+        }
+        Ok(msg)
+    }
+}
+
+impl From<MsgNftTransfer> for RawMsgNftTransfer {
+    fn from(msg: MsgNftTransfer) -> Self {
+        let timeout_height = match msg.timeout_height_on_b {
+            TimeoutHeight::At(height) => Some(RawHeight {
+                revision_number: height.revision_number(),
+                revision_height: height.revision_height(),
+            }),
+            TimeoutHeight::Never => None,
+        };
+        Self {
+            source_port: msg.port_id_on_a.to_string(),
+            source_channel: msg.chan_id_on_a.to_string(),
+            class_id: msg.class_id,
+            token_ids: msg.token_ids,
+            sender: msg.sender,
+            receiver: msg.receiver,
+            timeout_height,
+            timeout_timestamp: msg.timeout_timestamp_on_b.nanoseconds(),
+            memo: msg.memo.unwrap_or_default(),
+        }
+    }
+}
+
+impl TryFrom<Any> for MsgNftTransfer {
+    type Error = Error;
+
+    fn try_from(any: Any) -> Result<Self, Self::Error> {
+        if any.type_url != MSG_TRANSFER_TYPE_URL {
+            return Err(Error::InvalidMessage(format!(
+                "Unexpected type URL: {}",
+                any.type_url
+            )));
+        }
+        let raw = RawMsgNftTransfer::decode(any.value.as_slice())
+            .map_err(Error::DecodingMessage)?;
+        Self::try_from(raw)
+    }
+}
+
+impl MsgNftTransfer {
+    /// Wrap the message in `Any` to be set to an IBC transaction
+    pub fn to_any(&self) -> Any {
+        Any {
+            type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+            value: RawMsgNftTransfer::from(self.clone()).encode_to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nft_transfer_msg_roundtrip() {
+        let msg = MsgNftTransfer {
+            port_id_on_a: nft_transfer_port(),
+            chan_id_on_a: ChannelId::new(0),
+            class_id: "nft-transfer/channel-1/class".to_string(),
+            token_ids: vec!["token0".to_string(), "token1".to_string()],
+            sender: "sender".to_string(),
+            receiver: "receiver".to_string(),
+            memo: None,
+            timeout_height_on_b: TimeoutHeight::At(Height::new(0, 10).unwrap()),
+            timeout_timestamp_on_b: Timestamp::none(),
+        };
+        let decoded = MsgNftTransfer::try_from(msg.to_any()).unwrap();
+        assert_eq!(decoded, msg);
+    }
+
+    #[test]
+    fn test_class_trace() {
+        let port_id = nft_transfer_port();
+        let channel_id = ChannelId::new(0);
+        let class_id = format!("{}class", trace_prefix(&port_id, &channel_id));
+        assert!(is_receiver_chain_source(&port_id, &channel_id, &class_id));
+        assert!(is_sender_chain_source(
+            &port_id,
+            &ChannelId::new(1),
+            &class_id
+        ));
+        assert!(is_sender_chain_source(&port_id, &channel_id, "class"));
+    }
+
+    #[test]
+    fn test_nft_packet_data() {
+        let json = r#"{"classId":"class","tokenIds":["id0","id1"],"tokenUris":["uri0","uri1"],"sender":"a","receiver":"b"}"#;
+        let data: NftPacketData = serde_json::from_str(json).unwrap();
+        data.validate().unwrap();
+        assert_eq!(data.token_uri_data(1), (Some("uri1".to_string()), None));
+
+        let data = NftPacketData {
+            token_uris: Some(vec!["uri0".to_string()]),
+            ..data
+        };
+        assert!(data.validate().is_err());
+    }
+}
@@ -22,6 +22,12 @@ const CLIENTS_COUNTER: &str = "clients/counter";
 const CONNECTIONS_COUNTER: &str = "connections/counter";
 const CHANNELS_COUNTER: &str = "channelEnds/counter";
 const DENOM: &str = "ibc_denom";
+const RATE_LIMIT: &str = "rate_limit";
+const RATE_LIMIT_LIMIT: &str = "limit";
+const RATE_LIMIT_FLOW: &str = "flow";
+const NFT_CLASS: &str = "nft_class";
+const NFT_METADATA: &str = "nft_metadata";
+const FORWARD: &str = "forward";
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -386,6 +392,118 @@ pub fn ibc_token(denom: impl AsRef<str>) -> Address {
     Address::Internal(InternalAddress::IbcToken(hash))
 }
 
+/// The storage key of the NFT class
+pub fn nft_class_key(class_id: impl AsRef<str>) -> Key {
+    let path = format!("{}/{}", NFT_CLASS, calc_hash(class_id));
+    ibc_key(path).expect("Creating a key for the NFT class shouldn't fail")
+}
+
+/// The storage key of the NFT metadata
+pub fn nft_metadata_key(
+    class_id: impl AsRef<str>,
+    token_id: impl AsRef<str>,
+) -> Key {
+    let nft = format!("{}/{}", class_id.as_ref(), token_id.as_ref());
+    let path = format!("{}/{}", NFT_METADATA, calc_hash(nft));
+    ibc_key(path).expect("Creating a key for the NFT metadata shouldn't fail")
+}
+
+fn parse_nft_key(key: &Key, expected_prefix: &str) -> Option<String> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(hash),
+        ] if addr == &Address::Internal(InternalAddress::Ibc)
+            && prefix == expected_prefix =>
+        {
+            Some(hash.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the hash of the class ID if the given key is the NFT class key
+pub fn is_nft_class_key(key: &Key) -> Option<String> {
+    parse_nft_key(key, NFT_CLASS)
+}
+
+/// Returns the hash of the class ID and the token ID if the given key is the
+/// NFT metadata key
+pub fn is_nft_metadata_key(key: &Key) -> Option<String> {
+    parse_nft_key(key, NFT_METADATA)
+}
+
+/// The storage key of the forwarded packet in flight, which is sent through
+/// the given port and channel with the sequence
+pub fn forwarded_packet_key(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Key {
+    let path = format!("{}/{}/{}/{}", FORWARD, port_id, channel_id, sequence);
+    ibc_key(path)
+        .expect("Creating a key for the forwarded packet shouldn't fail")
+}
+
+/// Returns a key prefix of the rate limit of the token through the channel
+fn rate_limit_prefix(channel_id: &ChannelId, token: &Address) -> Key {
+    let path = format!("{}/{}", RATE_LIMIT, channel_id);
+    ibc_key(path)
+        .and_then(|key| key.push(token).map_err(Error::StorageKey))
+        .expect("Creating a key prefix of the rate limit shouldn't fail")
+}
+
+/// The storage key of the rate limit of the token through the channel. It
+/// should be written only by governance.
+pub fn rate_limit_key(channel_id: &ChannelId, token: &Address) -> Key {
+    rate_limit_prefix(channel_id, token)
+        .push(&RATE_LIMIT_LIMIT.to_owned())
+        .expect("Creating a key for the rate limit shouldn't fail")
+}
+
+/// The storage key of the flow of the token through the channel in the
+/// current rate limit period
+pub fn rate_limit_flow_key(channel_id: &ChannelId, token: &Address) -> Key {
+    rate_limit_prefix(channel_id, token)
+        .push(&RATE_LIMIT_FLOW.to_owned())
+        .expect("Creating a key for the rate limit flow shouldn't fail")
+}
+
+fn parse_rate_limit_key(
+    key: &Key,
+    suffix: &str,
+) -> Option<(ChannelId, Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(channel_id),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(key_suffix),
+        ] if addr == &Address::Internal(InternalAddress::Ibc)
+            && prefix == RATE_LIMIT
+            && key_suffix == suffix =>
+        {
+            let channel_id = ChannelId::from_str(channel_id).ok()?;
+            Some((channel_id, token.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the channel ID and the token if the given key is the rate limit
+/// key
+pub fn is_rate_limit_key(key: &Key) -> Option<(ChannelId, Address)> {
+    parse_rate_limit_key(key, RATE_LIMIT_LIMIT)
+}
+
+/// Returns the channel ID and the token if the given key is the rate limit
+/// flow key
+pub fn is_rate_limit_flow_key(key: &Key) -> Option<(ChannelId, Address)> {
+    parse_rate_limit_key(key, RATE_LIMIT_FLOW)
+}
+
 /// Returns true if the given key is for IBC
 pub fn is_ibc_key(key: &Key) -> bool {
     matches!(&key.segments[0],
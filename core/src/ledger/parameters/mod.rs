@@ -1,11 +1,14 @@
 //! Protocol parameters
 pub mod storage;
 
+use std::cmp;
 use std::collections::BTreeMap;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::gas::GasCosts;
 use super::storage::types;
 use super::storage_api::token::Amount;
 use super::storage_api::{self, ResultExt, StorageRead, StorageWrite};
@@ -16,6 +19,7 @@ use crate::types::dec::Dec;
 use crate::types::hash::Hash;
 use crate::types::time::DurationSecs;
 use crate::types::token;
+use crate::types::uint::{self, Uint};
 
 /// The internal address for storage keys representing parameters than
 /// can be changed via governance.
@@ -39,6 +43,9 @@ pub struct Parameters {
     pub epoch_duration: EpochDuration,
     /// Maximum expected time per block (read only)
     pub max_expected_time_per_block: DurationSecs,
+    /// Max duration between a block's time and the expiration of a wrapper
+    /// tx included in it. If set, wrapper txs must have an expiration.
+    pub max_tx_expiration: Option<DurationSecs>,
     /// Max payload size, in bytes, for a tx batch proposal.
     pub max_proposal_bytes: ProposalBytes,
     /// Max gas for block
@@ -67,6 +74,145 @@ pub struct Parameters {
     pub fee_unshielding_descriptions_limit: u64,
     /// Map of the cost per gas unit for every token allowed for fee payment
     pub minimum_gas_price: BTreeMap<Address, token::Amount>,
+    /// The gas costs of the operations metered by the ledger
+    pub gas_costs: GasCosts,
+    /// The parameters of the adjustment of the base fee
+    pub fee_market: FeeMarketParams,
+    /// Map of the shielded rewards parameters for every token rewarded for
+    /// being held in the MASP
+    pub shielded_rewards: BTreeMap<Address, ShieldedRewardParams>,
+}
+
+/// The parameters of the base fee adjustment. The base fee per unit of gas of
+/// every token allowed for fee payment goes up when a block uses more gas
+/// than the target, and down when it uses less, but never below the token's
+/// minimum gas price.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct FeeMarketParams {
+    /// The ratio of the max block gas to the target gas used by a block
+    pub elasticity_multiplier: u64,
+    /// The inverse of the max change of the base fee from a block to the
+    /// next, e.g. 8 for 12.5%
+    pub base_fee_max_change_denominator: u64,
+}
+
+impl Default for FeeMarketParams {
+    fn default() -> Self {
+        Self {
+            elasticity_multiplier: 2,
+            base_fee_max_change_denominator: 8,
+        }
+    }
+}
+
+impl FeeMarketParams {
+    /// The base fee following the given one after a block that used
+    /// `gas_used` out of `max_block_gas`
+    pub fn next_base_fee(
+        &self,
+        base_fee: Amount,
+        gas_used: u64,
+        max_block_gas: u64,
+    ) -> Amount {
+        let target = max_block_gas / cmp::max(self.elasticity_multiplier, 1);
+        if target == 0 || self.base_fee_max_change_denominator == 0 {
+            return base_fee;
+        }
+        let change = |gas_delta: u64| {
+            let change = base_fee
+                .raw_amount()
+                .checked_mul(Uint::from(gas_delta))
+                .unwrap_or(uint::MAX_VALUE)
+                / Uint::from(target)
+                / Uint::from(self.base_fee_max_change_denominator);
+            Amount::from_uint(change, 0).expect("This can't fail")
+        };
+        match gas_used.cmp(&target) {
+            cmp::Ordering::Equal => base_fee,
+            // The base fee always goes up after a block above the target
+            cmp::Ordering::Greater => base_fee
+                .checked_add(cmp::max(change(gas_used - target), 1.into()))
+                .unwrap_or_else(Amount::max),
+            cmp::Ordering::Less => base_fee
+                .checked_sub(change(target - gas_used))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The parameters of the PD controller that sets the rewards of a token held
+/// in the MASP. The controller targets a ratio of the token's total supply
+/// locked in the MASP, like the PoS controller does for the staked ratio.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ShieldedRewardParams {
+    /// Maximum yearly reward rate, relative to the token's total supply
+    pub max_reward_rate: Dec,
+    /// Nominal proportional gain of the controller
+    pub kp_gain_nom: Dec,
+    /// Nominal derivative gain of the controller
+    pub kd_gain_nom: Dec,
+    /// Target ratio of the token's total supply locked in the MASP
+    pub locked_ratio_target: Dec,
+}
+
+impl Default for ShieldedRewardParams {
+    fn default() -> Self {
+        Self {
+            max_reward_rate: Dec::new(1, 1).expect("This can't fail"),
+            kp_gain_nom: Dec::new(25, 2).expect("This can't fail"),
+            kd_gain_nom: Dec::new(25, 2).expect("This can't fail"),
+            locked_ratio_target: Dec::new(6, 1).expect("This can't fail"),
+        }
+    }
+}
+
+/// The state of the shielded rewards controller of a token, updated on every
+/// new epoch
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct ShieldedRewardState {
+    /// The rewards distributed in the last epoch, in the token's raw units
+    pub last_inflation: token::Amount,
+    /// The ratio of the token's total supply locked in the MASP in the last
+    /// epoch
+    pub last_locked_ratio: Dec,
+    /// The rewards distributed in the last epoch for every held unit of the
+    /// token, as a ratio of the reward token to the held token
+    pub last_reward_rate: (u32, u32),
 }
 
 /// Epoch duration. A new epoch begins as soon as both the `min_num_of_blocks`
@@ -119,6 +265,7 @@ impl Parameters {
         let Self {
             epoch_duration,
             max_expected_time_per_block,
+            max_tx_expiration,
             max_proposal_bytes,
             max_block_gas,
             vp_whitelist,
@@ -131,8 +278,11 @@ impl Parameters {
             staked_ratio,
             pos_inflation_amount,
             minimum_gas_price,
+            gas_costs,
+            fee_market,
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
+            shielded_rewards,
         } = self;
 
         // write max proposal bytes parameter
@@ -185,6 +335,10 @@ impl Parameters {
             max_expected_time_per_block,
         )?;
 
+        // write max tx expiration
+        let max_tx_expiration_key = storage::get_max_tx_expiration_key();
+        storage.write(&max_tx_expiration_key, max_tx_expiration)?;
+
         // write implicit vp parameter
         let implicit_vp_key = storage::get_implicit_vp_key();
         // Using `fn write_bytes` here, because implicit_vp code hash doesn't
@@ -216,6 +370,19 @@ impl Parameters {
         let gas_cost_key = storage::get_gas_cost_key();
         storage.write(&gas_cost_key, minimum_gas_price)?;
 
+        let gas_costs_key = storage::get_gas_costs_key();
+        storage.write(&gas_costs_key, gas_costs)?;
+
+        let fee_market_key = storage::get_fee_market_key();
+        storage.write(&fee_market_key, fee_market)?;
+
+        // The base fees start from the minimum gas prices
+        let base_fee_key = storage::get_base_fee_key();
+        storage.write(&base_fee_key, minimum_gas_price)?;
+
+        let shielded_rewards_key = storage::get_shielded_rewards_key();
+        storage.write(&shielded_rewards_key, shielded_rewards)?;
+
         Ok(())
     }
 }
@@ -231,6 +398,145 @@ where
     storage.read(&key)
 }
 
+/// Read the max tx expiration parameter
+pub fn max_tx_expiration<S>(
+    storage: &S,
+) -> storage_api::Result<Option<DurationSecs>>
+where
+    S: StorageRead,
+{
+    let key = storage::get_max_tx_expiration_key();
+    storage
+        .read(&key)?
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()
+}
+
+/// Read the gas costs table parameter
+pub fn read_gas_costs<S>(storage: &S) -> storage_api::Result<GasCosts>
+where
+    S: StorageRead,
+{
+    let key = storage::get_gas_costs_key();
+    storage
+        .read(&key)?
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()
+}
+
+/// Read the fee market parameters
+pub fn read_fee_market<S>(storage: &S) -> storage_api::Result<FeeMarketParams>
+where
+    S: StorageRead,
+{
+    let key = storage::get_fee_market_key();
+    storage
+        .read(&key)?
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()
+}
+
+/// Read the current base fee per unit of gas for the provided token. Returns
+/// `None` if the token is not allowed for fee payment.
+pub fn read_base_fee<S>(
+    storage: &S,
+    token: &Address,
+) -> storage_api::Result<Option<Amount>>
+where
+    S: StorageRead,
+{
+    let minimum_gas_price = match read_gas_cost(storage, token)? {
+        Some(price) => price,
+        None => return Ok(None),
+    };
+    // A token allowed by governance since the last block has no base fee
+    // yet, and the minimum gas price may have been raised since then
+    let base_fees: BTreeMap<Address, Amount> = storage
+        .read(&storage::get_base_fee_key())?
+        .unwrap_or_default();
+    let base_fee = base_fees
+        .get(token)
+        .map_or(minimum_gas_price, |fee| cmp::max(*fee, minimum_gas_price));
+    Ok(Some(base_fee))
+}
+
+/// Adjust the base fees of all the tokens allowed for fee payment to the gas
+/// used by the last block
+pub fn update_base_fees<S>(
+    storage: &mut S,
+    block_gas_used: u64,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let minimum_gas_price: BTreeMap<Address, Amount> = storage
+        .read(&storage::get_gas_cost_key())?
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+    let max_block_gas: u64 = storage
+        .read(&storage::get_max_block_gas_key())?
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+    let fee_market = read_fee_market(storage)?;
+    let key = storage::get_base_fee_key();
+    let base_fees: BTreeMap<Address, Amount> =
+        storage.read(&key)?.unwrap_or_default();
+
+    let base_fees: BTreeMap<Address, Amount> = minimum_gas_price
+        .into_iter()
+        .map(|(token, minimum)| {
+            let base_fee = base_fees.get(&token).copied().unwrap_or(minimum);
+            let next = fee_market.next_base_fee(
+                base_fee,
+                block_gas_used,
+                max_block_gas,
+            );
+            (token, cmp::max(next, minimum))
+        })
+        .collect();
+    storage.write(&key, base_fees)
+}
+
+/// Read the shielded rewards parameters of all the rewarded tokens
+pub fn read_shielded_rewards<S>(
+    storage: &S,
+) -> storage_api::Result<BTreeMap<Address, ShieldedRewardParams>>
+where
+    S: StorageRead,
+{
+    let key = storage::get_shielded_rewards_key();
+    storage
+        .read(&key)?
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()
+}
+
+/// Read the state of the shielded rewards controller of a token. The state is
+/// zero before the token's first rewards.
+pub fn read_shielded_rewards_state<S>(
+    storage: &S,
+    token: &Address,
+) -> storage_api::Result<ShieldedRewardState>
+where
+    S: StorageRead,
+{
+    let key = storage::get_shielded_rewards_state_key(token);
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Write the state of the shielded rewards controller of a token
+pub fn write_shielded_rewards_state<S>(
+    storage: &mut S,
+    token: &Address,
+    state: &ShieldedRewardState,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage::get_shielded_rewards_state_key(token);
+    storage.write(&key, state)
+}
+
 /// Update the max_expected_time_per_block parameter in storage. Returns the
 /// parameters and gas cost.
 pub fn update_max_expected_time_per_block_parameter<S>(
@@ -467,6 +773,9 @@ where
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
+    // read max tx expiration
+    let max_tx_expiration = max_tx_expiration(storage)?;
+
     let implicit_vp_key = storage::get_implicit_vp_key();
     let value = storage
         .read_bytes(&implicit_vp_key)?
@@ -542,9 +851,19 @@ where
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
+    // read gas costs table
+    let gas_costs = read_gas_costs(storage)?;
+
+    // read fee market parameters
+    let fee_market = read_fee_market(storage)?;
+
+    // read shielded rewards
+    let shielded_rewards = read_shielded_rewards(storage)?;
+
     Ok(Parameters {
         epoch_duration,
         max_expected_time_per_block,
+        max_tx_expiration,
         max_proposal_bytes,
         max_block_gas,
         vp_whitelist,
@@ -557,7 +876,68 @@ where
         staked_ratio,
         pos_inflation_amount,
         minimum_gas_price,
+        gas_costs,
+        fee_market,
         fee_unshielding_gas_limit,
         fee_unshielding_descriptions_limit,
+        shielded_rewards,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BLOCK_GAS: u64 = 20_000_000;
+
+    #[test]
+    fn test_next_base_fee() {
+        let params = FeeMarketParams::default();
+        let base_fee = Amount::from(800);
+
+        // A block at the target doesn't change the base fee
+        assert_eq!(
+            params.next_base_fee(base_fee, MAX_BLOCK_GAS / 2, MAX_BLOCK_GAS),
+            base_fee
+        );
+        // A full block raises the base fee by the max change
+        assert_eq!(
+            params.next_base_fee(base_fee, MAX_BLOCK_GAS, MAX_BLOCK_GAS),
+            Amount::from(900)
+        );
+        // An empty block lowers the base fee by the max change
+        assert_eq!(
+            params.next_base_fee(base_fee, 0, MAX_BLOCK_GAS),
+            Amount::from(700)
+        );
+        // A block half-way between the target and a full block
+        assert_eq!(
+            params.next_base_fee(
+                base_fee,
+                MAX_BLOCK_GAS / 4 * 3,
+                MAX_BLOCK_GAS
+            ),
+            Amount::from(850)
+        );
+    }
+
+    #[test]
+    fn test_next_base_fee_bounds() {
+        let params = FeeMarketParams::default();
+        // The change rounds down to zero, but a block above the target must
+        // still raise the base fee
+        assert_eq!(
+            params.next_base_fee(
+                Amount::from(1),
+                MAX_BLOCK_GAS / 2 + 1,
+                MAX_BLOCK_GAS
+            ),
+            Amount::from(2)
+        );
+        // The base fee can't go below zero
+        assert_eq!(
+            params.next_base_fee(Amount::zero(), 0, MAX_BLOCK_GAS),
+            Amount::zero()
+        );
+    }
+}
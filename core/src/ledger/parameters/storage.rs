@@ -4,7 +4,16 @@ use namada_macros::StorageKeys;
 
 use super::ADDRESS;
 use crate::types::address::Address;
-use crate::types::storage::{DbKeySeg, Key};
+use crate::types::storage::{DbKeySeg, Key, KeySeg};
+
+/// Sub-key prefix of the state of the shielded rewards controllers. The state
+/// is updated by the protocol on every new epoch, so it's not a protocol
+/// parameter.
+const SHIELDED_REWARDS_STATE_PREFIX: &str = "shielded_rewards_state";
+
+/// Sub-key of the current base fees. The base fees are adjusted by the
+/// protocol on every block, so they're not a protocol parameter.
+const BASE_FEE_KEY: &str = "base_fee_per_gas";
 
 #[derive(StorageKeys)]
 struct Keys {
@@ -31,17 +40,24 @@ struct Keys {
     pos_inflation_amount: &'static str,
     staked_ratio: &'static str,
     // ========================================
+    // MASP parameters
+    // ========================================
+    shielded_rewards: &'static str,
+    // ========================================
     // Core parameters
     // ========================================
     epoch_duration: &'static str,
     epochs_per_year: &'static str,
     implicit_vp: &'static str,
     max_expected_time_per_block: &'static str,
+    max_tx_expiration: &'static str,
     tx_whitelist: &'static str,
     vp_whitelist: &'static str,
     max_proposal_bytes: &'static str,
     max_block_gas: &'static str,
     minimum_gas_price: &'static str,
+    gas_costs: &'static str,
+    fee_market: &'static str,
     fee_unshielding_gas_limit: &'static str,
     fee_unshielding_descriptions_limit: &'static str,
     max_signatures_per_transaction: &'static str,
@@ -115,6 +131,11 @@ pub fn is_pos_inflation_amount_key(key: &Key) -> bool {
     is_pos_inflation_amount_key_at_addr(key, &ADDRESS)
 }
 
+/// Returns if the key is the shielded rewards key.
+pub fn is_shielded_rewards_key(key: &Key) -> bool {
+    is_shielded_rewards_key_at_addr(key, &ADDRESS)
+}
+
 /// Returns if the key is the max proposal bytes key.
 pub fn is_max_proposal_bytes_key(key: &Key) -> bool {
     is_max_proposal_bytes_key_at_addr(key, &ADDRESS)
@@ -150,6 +171,16 @@ pub fn get_max_expected_time_per_block_key() -> Key {
     get_max_expected_time_per_block_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for max_tx_expiration parameter.
+pub fn get_max_tx_expiration_key() -> Key {
+    get_max_tx_expiration_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the gas costs table parameter.
+pub fn get_gas_costs_key() -> Key {
+    get_gas_costs_key_at_addr(ADDRESS)
+}
+
 /// Storage key used for implicit VP parameter.
 pub fn get_implicit_vp_key() -> Key {
     get_implicit_vp_key_at_addr(ADDRESS)
@@ -180,6 +211,21 @@ pub fn get_pos_inflation_amount_key() -> Key {
     get_pos_inflation_amount_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for the shielded rewards parameters of the tokens.
+pub fn get_shielded_rewards_key() -> Key {
+    get_shielded_rewards_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the state of the shielded rewards controller of the
+/// given token.
+pub fn get_shielded_rewards_state_key(token: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&SHIELDED_REWARDS_STATE_PREFIX.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&token.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Storage key used for the max proposal bytes.
 pub fn get_max_proposal_bytes_key() -> Key {
     get_max_proposal_bytes_key_at_addr(ADDRESS)
@@ -195,6 +241,19 @@ pub fn get_gas_cost_key() -> Key {
     get_minimum_gas_price_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for the fee market parameters
+pub fn get_fee_market_key() -> Key {
+    get_fee_market_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the current base fee per unit of gas of the tokens
+/// allowed for fee payment.
+pub fn get_base_fee_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&BASE_FEE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Storage key used for the max signatures per transaction key
 pub fn get_max_signatures_per_transaction_key() -> Key {
     get_max_signatures_per_transaction_key_at_addr(ADDRESS)
@@ -3,17 +3,20 @@ use namada_macros::StorageKeys;
 use super::steward::StewardDetail;
 use crate::ledger::governance::storage::proposal::StoragePgfFunding;
 use crate::ledger::pgf::ADDRESS;
+use crate::ledger::storage_api::collections::lazy_map::NestedMap;
 use crate::ledger::storage_api::collections::{
     lazy_map, LazyCollection, LazyMap,
 };
 use crate::types::address::Address;
-use crate::types::storage::{DbKeySeg, Key, KeySeg};
+use crate::types::storage::{DbKeySeg, Epoch, Key, KeySeg};
+use crate::types::token;
 
 /// Storage keys for pgf internal address.
 #[derive(StorageKeys)]
 struct Keys {
     stewards: &'static str,
     fundings: &'static str,
+    payouts: &'static str,
     pgf_inflation_rate: &'static str,
     steward_inflation_rate: &'static str,
 }
@@ -74,6 +77,22 @@ pub fn is_fundings_key(key: &Key) -> bool {
                 && data.as_str() == lazy_map::DATA_SUBKEY)
 }
 
+/// Obtain a storage key for pgf funding payouts.
+pub fn payouts_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.payouts.to_string()),
+        ],
+    }
+}
+
+/// NestedMap handler for the history of the pgf funding payouts, indexed by
+/// target address and epoch
+pub fn payouts_handle() -> NestedMap<Address, LazyMap<Epoch, token::Amount>> {
+    NestedMap::open(payouts_key_prefix())
+}
+
 /// Check if key is inside governance address space
 pub fn is_pgf_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
@@ -1,21 +1,213 @@
 //! Replay protection storage
+//!
+//! The hashes of the processed txs are bucketed by the expiration of the tx,
+//! rounded up to [`BUCKET_DURATION_SECS`]. Once a bucket has expired, none of
+//! its txs can be included in a block anymore, so its hashes can be pruned.
+//! The hashes of txs without an expiration are kept forever, as are the
+//! hashes written before the bucketing, which sit directly under the
+//! [`ADDRESS`].
 
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
 use crate::types::address::{Address, InternalAddress};
 use crate::types::hash::Hash;
 use crate::types::storage::{DbKeySeg, Key, KeySeg};
+use crate::types::time::DateTimeUtc;
 
 /// Internal replay protection address
 pub const ADDRESS: Address =
     Address::Internal(InternalAddress::ReplayProtection);
 
+/// The duration of a bucket of replay protection hashes, in seconds
+pub const BUCKET_DURATION_SECS: i64 = 60 * 60;
+
+/// The key segment of the bucket of txs without an expiration. It must sort
+/// after all the expiring buckets.
+const PERMANENT_BUCKET: &str = "permanent";
+
 /// Check if a key is a replay protection key
 pub fn is_replay_protection_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
 }
 
-/// Get the transaction hash key
-pub fn get_replay_protection_key(hash: &Hash) -> Key {
+/// Get the transaction hash key, in the bucket of the given expiration
+pub fn get_replay_protection_key(
+    expiration: Option<DateTimeUtc>,
+    hash: &Hash,
+) -> Key {
+    get_bucket_prefix(expiration)
+        .push(&hash.to_string())
+        .expect("Cannot obtain a valid db key")
+}
+
+/// Get the key of a transaction hash written before the hashes were bucketed
+pub fn get_legacy_replay_protection_key(hash: &Hash) -> Key {
     Key::from(ADDRESS.to_db_key())
         .push(&hash.to_string())
         .expect("Cannot obtain a valid db key")
 }
+
+/// Check if the hash of a tx with the given expiration is in storage, either
+/// in its bucket or under its legacy key
+pub fn has_replay_protection_entry<S>(
+    storage: &S,
+    expiration: Option<DateTimeUtc>,
+    hash: &Hash,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    let key = get_replay_protection_key(expiration, hash);
+    let legacy_key = get_legacy_replay_protection_key(hash);
+    Ok(storage.has_key(&key)? || storage.has_key(&legacy_key)?)
+}
+
+/// Get the prefix of the bucket of txs with the given expiration
+fn get_bucket_prefix(expiration: Option<DateTimeUtc>) -> Key {
+    let bucket = match expiration {
+        // Zero-padded so that the buckets are sorted by their end time
+        Some(expiration) => format!("{:020}", bucket_end(expiration)),
+        None => PERMANENT_BUCKET.to_string(),
+    };
+    Key::from(ADDRESS.to_db_key())
+        .push(&bucket)
+        .expect("Cannot obtain a valid db key")
+}
+
+/// The end of the bucket containing the given expiration, as a unix
+/// timestamp in seconds
+fn bucket_end(expiration: DateTimeUtc) -> u64 {
+    let secs = expiration.0.timestamp();
+    let end = secs
+        .div_euclid(BUCKET_DURATION_SECS)
+        .saturating_add(i64::from(secs.rem_euclid(BUCKET_DURATION_SECS) != 0))
+        .saturating_mul(BUCKET_DURATION_SECS);
+    // Anything before the unix epoch has long expired
+    u64::try_from(end).unwrap_or_default()
+}
+
+/// Delete the hashes of all the buckets that have expired before the given
+/// time. Returns the number of deleted hashes.
+pub fn prune_expired_hashes<S>(
+    storage: &mut S,
+    time: DateTimeUtc,
+) -> storage_api::Result<usize>
+where
+    S: StorageRead + StorageWrite,
+{
+    let now = bucket_end(time);
+    let prefix = Key::from(ADDRESS.to_db_key());
+    let mut expired = vec![];
+    // The expiring buckets are sorted by their end time, so we can stop at
+    // the first bucket that's still live
+    for entry in storage_api::iter_prefix_bytes(storage, &prefix)? {
+        let (key, _) = entry?;
+        // The legacy hashes are never pruned
+        if key.segments.len() < 3 {
+            continue;
+        }
+        let bucket_end = match key.segments.get(1) {
+            Some(DbKeySeg::StringSeg(bucket)) => bucket.parse::<u64>().ok(),
+            _ => None,
+        };
+        match bucket_end {
+            Some(end) if end < now => expired.push(key),
+            _ => break,
+        }
+    }
+    for key in &expired {
+        storage.delete(key)?;
+    }
+    Ok(expired.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::*;
+    use crate::ledger::storage::testing::TestWlStorage;
+
+    #[test]
+    fn test_prune_expired_hashes() {
+        let mut storage = TestWlStorage::default();
+        // 2023-07-01T12:30:00Z
+        let time: DateTimeUtc = Utc
+            .timestamp_opt(1_688_214_600, 0)
+            .single()
+            .expect("expected valid timestamp")
+            .into();
+
+        let expired = get_replay_protection_key(
+            Some(time - Duration::hours(2)),
+            &Hash::sha256(b"expired"),
+        );
+        // Expired, but in the same bucket as the current time
+        let same_bucket = get_replay_protection_key(
+            Some(time - Duration::minutes(10)),
+            &Hash::sha256(b"same bucket"),
+        );
+        let live = get_replay_protection_key(
+            Some(time + Duration::hours(2)),
+            &Hash::sha256(b"live"),
+        );
+        let permanent =
+            get_replay_protection_key(None, &Hash::sha256(b"permanent"));
+        for key in [&expired, &same_bucket, &live, &permanent] {
+            storage.write(key, ()).unwrap();
+        }
+
+        let pruned = prune_expired_hashes(&mut storage, time).unwrap();
+        assert_eq!(pruned, 1);
+        assert!(!storage.has_key(&expired).unwrap());
+        assert!(storage.has_key(&same_bucket).unwrap());
+        assert!(storage.has_key(&live).unwrap());
+        assert!(storage.has_key(&permanent).unwrap());
+
+        // Everything but the permanent hashes eventually gets pruned
+        let pruned =
+            prune_expired_hashes(&mut storage, time + Duration::days(1))
+                .unwrap();
+        assert_eq!(pruned, 2);
+        assert!(storage.has_key(&permanent).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_replay_protection_entry() {
+        let mut storage = TestWlStorage::default();
+        // 2023-07-01T12:30:00Z
+        let time: DateTimeUtc = Utc
+            .timestamp_opt(1_688_214_600, 0)
+            .single()
+            .expect("expected valid timestamp")
+            .into();
+        // A legacy hash sorting before all the buckets
+        let hash = Hash([0; 32]);
+        let legacy = get_legacy_replay_protection_key(&hash);
+        storage.write(&legacy, ()).unwrap();
+        let expired = get_replay_protection_key(
+            Some(time - Duration::hours(2)),
+            &Hash::sha256(b"expired"),
+        );
+        storage.write(&expired, ()).unwrap();
+
+        // The legacy hash protects the tx, whatever its expiration
+        assert!(has_replay_protection_entry(&storage, None, &hash).unwrap());
+        assert!(
+            has_replay_protection_entry(&storage, Some(time), &hash).unwrap()
+        );
+        assert!(
+            !has_replay_protection_entry(
+                &storage,
+                Some(time),
+                &Hash::sha256(b"other")
+            )
+            .unwrap()
+        );
+
+        // The legacy hash neither gets pruned nor stops the pruning
+        let pruned = prune_expired_hashes(&mut storage, time).unwrap();
+        assert_eq!(pruned, 1);
+        assert!(!storage.has_key(&expired).unwrap());
+        assert!(storage.has_key(&legacy).unwrap());
+    }
+}
@@ -8,9 +8,18 @@ use masp_primitives::convert::AllowedConversion;
 use masp_primitives::merkle_tree::FrozenCommitmentTree;
 use masp_primitives::sapling::Node;
 
-use crate::types::address::Address;
+use crate::ledger::inflation::{RewardsController, ValsToUpdate};
+use crate::ledger::parameters::{self, ShieldedRewardParams};
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::address::{self, Address};
 use crate::types::storage::Epoch;
 use crate::types::token::MaspDenom;
+use crate::types::uint::Uint;
+
+/// The denominator of the reward rates of the tokens held in the MASP. A
+/// reward rate of `(n, REWARD_PRECISION)` dispenses `n` units of the reward
+/// token for every `REWARD_PRECISION` units of the held token.
+pub const REWARD_PRECISION: u32 = 10_000;
 
 /// A representation of the conversion state
 #[derive(Debug, Default, BorshSerialize, BorshDeserialize)]
@@ -27,6 +36,67 @@ pub struct ConversionState {
     >,
 }
 
+/// Run the shielded rewards controller of a token and return the reward rate
+/// of the token for the last epoch. The new state of the controller is
+/// written to storage.
+pub fn calculate_masp_rewards<S>(
+    storage: &mut S,
+    addr: &Address,
+    params: &ShieldedRewardParams,
+) -> storage_api::Result<(u32, u32)>
+where
+    S: StorageRead + StorageWrite,
+{
+    let locked_tokens =
+        storage_api::token::read_balance(storage, addr, &address::masp())?;
+    let total_tokens = storage_api::token::read_total_supply(storage, addr)?;
+    if total_tokens.is_zero() {
+        return Ok((0, REWARD_PRECISION));
+    }
+    let epochs_per_year: u64 = storage
+        .read(&parameters::storage::get_epochs_per_year_key())?
+        .expect("Epochs per year should exist in storage");
+    let state = parameters::read_shielded_rewards_state(storage, addr)?;
+
+    let controller = RewardsController {
+        locked_tokens,
+        total_tokens,
+        locked_ratio_target: params.locked_ratio_target,
+        locked_ratio_last: state.last_locked_ratio,
+        max_reward_rate: params.max_reward_rate,
+        last_inflation_amount: state.last_inflation,
+        p_gain_nom: params.kp_gain_nom,
+        d_gain_nom: params.kd_gain_nom,
+        epochs_per_year,
+    };
+    let ValsToUpdate {
+        locked_ratio,
+        inflation,
+    } = controller.run();
+
+    // The inflation is spread over the locked tokens. The rate is discretized
+    // to fit in the conversions, which hold the amounts as `i32`
+    let reward = if locked_tokens.is_zero() {
+        0
+    } else {
+        let rate = inflation.raw_amount() * Uint::from(REWARD_PRECISION)
+            / locked_tokens.raw_amount();
+        std::cmp::min(rate, Uint::from(i32::MAX)).as_u32()
+    };
+    let reward_rate = (reward, REWARD_PRECISION);
+
+    parameters::write_shielded_rewards_state(
+        storage,
+        addr,
+        &parameters::ShieldedRewardState {
+            last_inflation: inflation,
+            last_locked_ratio: locked_ratio,
+            last_reward_rate: reward_rate,
+        },
+    )?;
+    Ok(reward_rate)
+}
+
 // This is only enabled when "wasm-runtime" is on, because we're using rayon
 #[cfg(feature = "wasm-runtime")]
 /// Update the MASP's allowed conversions
@@ -44,15 +114,23 @@ where
     };
     use rayon::prelude::ParallelSlice;
 
-    use crate::ledger::storage_api::{ResultExt, StorageRead, StorageWrite};
+    use crate::ledger::storage_api::ResultExt;
     use crate::types::storage::{self, KeySeg};
-    use crate::types::{address, token};
+    use crate::types::token;
 
     // The derived conversions will be placed in MASP address space
     let masp_addr = address::masp();
     let key_prefix: storage::Key = masp_addr.to_db_key().into();
 
-    let masp_rewards = address::masp_rewards();
+    // A chain without the shielded rewards parameter doesn't reward any token
+    let shielded_rewards: BTreeMap<Address, ShieldedRewardParams> = wl_storage
+        .read(&parameters::storage::get_shielded_rewards_key())?
+        .unwrap_or_default();
+    let mut masp_rewards = BTreeMap::new();
+    for (addr, params) in shielded_rewards {
+        let reward = calculate_masp_rewards(wl_storage, &addr, &params)?;
+        masp_rewards.insert(addr, reward);
+    }
     // The total transparent value of the rewards being distributed
     let mut total_reward = token::Amount::native_whole(0);
 
@@ -112,6 +190,11 @@ where
         }
     }
 
+    // There are no conversions to commit until some token gets rewarded
+    if wl_storage.storage.conversion_state.assets.is_empty() {
+        return Ok(());
+    }
+
     // Try to distribute Merkle leaf updating as evenly as possible across
     // multiple cores
     let num_threads = rayon::current_num_threads();
@@ -224,3 +307,90 @@ pub fn encode_asset_type(
     AssetType::new(new_asset_bytes.as_ref())
         .expect("unable to derive asset identifier")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::storage::testing::TestWlStorage;
+    use crate::types::dec::Dec;
+    use crate::types::token;
+
+    /// Parameters that keep the rewards at their maximum rate
+    fn pinned_params() -> ShieldedRewardParams {
+        ShieldedRewardParams {
+            max_reward_rate: Dec::new(1, 1).unwrap(),
+            kp_gain_nom: Dec::two(),
+            kd_gain_nom: Dec::zero(),
+            locked_ratio_target: Dec::one(),
+        }
+    }
+
+    #[test]
+    fn test_calculate_masp_rewards() {
+        let mut wl_storage = TestWlStorage::default();
+        let token = address::btc();
+        let holder = address::testing::established_address_1();
+        wl_storage
+            .write(&parameters::storage::get_epochs_per_year_key(), 100_u64)
+            .unwrap();
+        storage_api::token::credit_tokens(
+            &mut wl_storage,
+            &token,
+            &holder,
+            token::Amount::from_uint(900, 8).unwrap(),
+        )
+        .unwrap();
+        storage_api::token::credit_tokens(
+            &mut wl_storage,
+            &token,
+            &address::masp(),
+            token::Amount::from_uint(100, 8).unwrap(),
+        )
+        .unwrap();
+
+        let reward =
+            calculate_masp_rewards(&mut wl_storage, &token, &pinned_params())
+                .unwrap();
+        // The max inflation per epoch is 10% of the supply over 100 epochs,
+        // i.e. 1 token, spread over 100 locked tokens
+        assert_eq!(reward, (100, REWARD_PRECISION));
+
+        let state =
+            parameters::read_shielded_rewards_state(&wl_storage, &token)
+                .unwrap();
+        assert_eq!(
+            state.last_inflation,
+            token::Amount::from_uint(1, 8).unwrap()
+        );
+        assert_eq!(state.last_locked_ratio, Dec::new(1, 1).unwrap());
+        assert_eq!(state.last_reward_rate, reward);
+    }
+
+    #[test]
+    fn test_calculate_masp_rewards_nothing_locked() {
+        let mut wl_storage = TestWlStorage::default();
+        let token = address::btc();
+        wl_storage
+            .write(&parameters::storage::get_epochs_per_year_key(), 100_u64)
+            .unwrap();
+
+        // No supply
+        let reward =
+            calculate_masp_rewards(&mut wl_storage, &token, &pinned_params())
+                .unwrap();
+        assert_eq!(reward, (0, REWARD_PRECISION));
+
+        // Supply, but nothing is locked in the MASP
+        storage_api::token::credit_tokens(
+            &mut wl_storage,
+            &token,
+            &address::testing::established_address_1(),
+            token::Amount::from_uint(1000, 8).unwrap(),
+        )
+        .unwrap();
+        let reward =
+            calculate_masp_rewards(&mut wl_storage, &token, &pinned_params())
+                .unwrap();
+        assert_eq!(reward, (0, REWARD_PRECISION));
+    }
+}
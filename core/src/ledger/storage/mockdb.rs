@@ -14,6 +14,7 @@ use super::{
 };
 use crate::ledger::storage::types::{self, KVBytes, PrefixIterator};
 use crate::types::ethereum_structs;
+use crate::types::event::{EventIndexQuery, IndexedEvent};
 #[cfg(feature = "ferveo-tpke")]
 use crate::types::internal::TxQueue;
 use crate::types::storage::{
@@ -540,6 +541,64 @@ impl DB for MockDB {
             None => Ok(()),
         }
     }
+
+    fn add_events_to_batch(
+        &self,
+        _batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        events: &[IndexedEvent],
+    ) -> Result<()> {
+        let mut db = self.0.borrow_mut();
+        for (index, event) in events.iter().enumerate() {
+            let key =
+                format!("events/{}/{}", height.raw(), (index as u64).raw());
+            db.insert(key, types::encode(event));
+        }
+        Ok(())
+    }
+
+    fn read_events(
+        &self,
+        query: &EventIndexQuery,
+    ) -> Result<Vec<(BlockHeight, IndexedEvent)>> {
+        let mut events = Vec::new();
+        for (key, value) in self.0.borrow().range("events/".to_owned()..) {
+            let Some(key) = key.strip_prefix("events/") else {
+                break;
+            };
+            let height = key
+                .split(KEY_SEGMENT_SEPARATOR)
+                .next()
+                .map(|height| BlockHeight::parse(height.to_owned()))
+                .transpose()
+                .map_err(Error::KeyError)?
+                .ok_or_else(|| Error::UnknownKey {
+                    key: key.to_owned(),
+                })?;
+            let event: IndexedEvent =
+                types::decode(value).map_err(Error::CodingError)?;
+            if query.matches(height, &event) {
+                events.push((height, event));
+            }
+        }
+        Ok(events
+            .into_iter()
+            .skip(query.offset as usize)
+            .take(query.limit())
+            .collect())
+    }
+
+    fn prune_events(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        min_height: BlockHeight,
+    ) -> Result<()> {
+        let upper = format!("events/{}", min_height.raw());
+        self.0
+            .borrow_mut()
+            .retain(|key, _| !(key.starts_with("events/") && *key < upper));
+        Ok(())
+    }
 }
 
 impl<'iter> DBIter<'iter> for MockDB {
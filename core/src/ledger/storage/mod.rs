@@ -29,9 +29,7 @@ pub use self::masp_conversions::update_allowed_conversions;
 pub use self::masp_conversions::{encode_asset_type, ConversionState};
 use super::replay_protection::is_replay_protection_key;
 use crate::ledger::eth_bridge::storage::bridge_pool::is_pending_transfer_key;
-use crate::ledger::gas::{
-    STORAGE_ACCESS_GAS_PER_BYTE, STORAGE_WRITE_GAS_PER_BYTE,
-};
+use crate::ledger::gas::GasCosts;
 use crate::ledger::parameters::{self, EpochDuration, Parameters};
 use crate::ledger::storage::merkle_tree::{
     Error as MerkleTreeError, MerkleRoot,
@@ -42,6 +40,7 @@ use crate::types::address::{
     masp, Address, EstablishedAddressGen, InternalAddress,
 };
 use crate::types::chain::{ChainId, CHAIN_ID_LENGTH};
+use crate::types::event::{EventIndexQuery, IndexedEvent};
 use crate::types::hash::{Error as HashError, Hash};
 use crate::types::internal::ExpiredTxsQueue;
 // TODO
@@ -118,6 +117,14 @@ where
     pub eth_events_queue: EthEventsQueue,
     /// How many block heights in the past can the storage be queried
     pub storage_read_past_height_limit: Option<u64>,
+    /// The events emitted in the current block, written to the event index
+    /// on commit
+    pub block_events: Vec<IndexedEvent>,
+    /// How many block heights in the past are the events kept in the event
+    /// index. When `None`, the events are never pruned.
+    pub event_index_retention: Option<u64>,
+    /// The gas costs in effect, loaded from the protocol parameters
+    pub gas_costs: GasCosts,
 }
 
 /// Last committed block
@@ -353,6 +360,28 @@ pub trait DB: std::fmt::Debug {
         pruned_epoch: Epoch,
         pred_epochs: &Epochs,
     ) -> Result<()>;
+
+    /// Write the events emitted in the block at the given height to the event
+    /// index
+    fn add_events_to_batch(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        events: &[IndexedEvent],
+    ) -> Result<()>;
+
+    /// Read the events matching the query from the event index
+    fn read_events(
+        &self,
+        query: &EventIndexQuery,
+    ) -> Result<Vec<(BlockHeight, IndexedEvent)>>;
+
+    /// Prune the events emitted below the given height from the event index
+    fn prune_events(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        min_height: BlockHeight,
+    ) -> Result<()>;
 }
 
 /// A database prefix iterator.
@@ -424,6 +453,9 @@ where
             ethereum_height: None,
             eth_events_queue: EthEventsQueue::default(),
             storage_read_past_height_limit,
+            block_events: Vec::new(),
+            event_index_retention: None,
+            gas_costs: GasCosts::default(),
         }
     }
 
@@ -559,6 +591,15 @@ where
             // prune old merkle tree stores
             self.prune_merkle_tree_stores(&mut batch)?;
         }
+        let events = std::mem::take(&mut self.block_events);
+        self.db
+            .add_events_to_batch(&mut batch, self.block.height, &events)?;
+        if let Some(retention) = self.event_index_retention {
+            if let Some(min_height) = self.block.height.0.checked_sub(retention)
+            {
+                self.db.prune_events(&mut batch, min_height.into())?;
+            }
+        }
         self.db.exec_batch(batch)
     }
 
@@ -575,12 +616,12 @@ where
             // tree
             Ok((
                 self.db.read_subspace_val(key)?.is_some(),
-                key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE,
+                key.len() as u64 * self.gas_costs.storage_access_per_byte,
             ))
         } else {
             Ok((
                 self.block.tree.has_key(key)?,
-                key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE,
+                key.len() as u64 * self.gas_costs.storage_access_per_byte,
             ))
         }
     }
@@ -595,11 +636,14 @@ where
 
         match self.db.read_subspace_val(key)? {
             Some(v) => {
-                let gas =
-                    (key.len() + v.len()) as u64 * STORAGE_ACCESS_GAS_PER_BYTE;
+                let gas = (key.len() + v.len()) as u64
+                    * self.gas_costs.storage_access_per_byte;
                 Ok((Some(v), gas))
             }
-            None => Ok((None, key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE)),
+            None => Ok((
+                None,
+                key.len() as u64 * self.gas_costs.storage_access_per_byte,
+            )),
         }
     }
 
@@ -620,12 +664,13 @@ where
             )? {
                 Some(v) => {
                     let gas = (key.len() + v.len()) as u64
-                        * STORAGE_ACCESS_GAS_PER_BYTE;
+                        * self.gas_costs.storage_access_per_byte;
                     Ok((Some(v), gas))
                 }
-                None => {
-                    Ok((None, key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE))
-                }
+                None => Ok((
+                    None,
+                    key.len() as u64 * self.gas_costs.storage_access_per_byte,
+                )),
             }
         }
     }
@@ -641,7 +686,7 @@ where
     ) -> (<D as DBIter<'_>>::PrefixIter, u64) {
         (
             self.db.iter_prefix(Some(prefix)),
-            prefix.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE,
+            prefix.len() as u64 * self.gas_costs.storage_access_per_byte,
         )
     }
 
@@ -673,7 +718,8 @@ where
         }
 
         let len = value.len();
-        let gas = (key.len() + len) as u64 * STORAGE_WRITE_GAS_PER_BYTE;
+        let gas =
+            (key.len() + len) as u64 * self.gas_costs.storage_write_per_byte;
         let size_diff =
             self.db.write_subspace_val(self.block.height, key, value)?;
         Ok((gas, size_diff))
@@ -693,7 +739,7 @@ where
                 self.db.delete_subspace_val(self.block.height, key)?;
         }
         let gas = (key.len() + deleted_bytes_len as usize) as u64
-            * STORAGE_WRITE_GAS_PER_BYTE;
+            * self.gas_costs.storage_write_per_byte;
         Ok((gas, deleted_bytes_len))
     }
 
@@ -749,20 +795,20 @@ where
     pub fn get_chain_id(&self) -> (String, u64) {
         (
             self.chain_id.to_string(),
-            CHAIN_ID_LENGTH as u64 * STORAGE_ACCESS_GAS_PER_BYTE,
+            CHAIN_ID_LENGTH as u64 * self.gas_costs.storage_access_per_byte,
         )
     }
 
     /// Get the block height
     pub fn get_block_height(&self) -> (BlockHeight, u64) {
-        (self.block.height, STORAGE_ACCESS_GAS_PER_BYTE)
+        (self.block.height, self.gas_costs.storage_access_per_byte)
     }
 
     /// Get the block hash
     pub fn get_block_hash(&self) -> (BlockHash, u64) {
         (
             self.block.hash.clone(),
-            BLOCK_HASH_LENGTH as u64 * STORAGE_ACCESS_GAS_PER_BYTE,
+            BLOCK_HASH_LENGTH as u64 * self.gas_costs.storage_access_per_byte,
         )
     }
 
@@ -942,12 +988,12 @@ where
 
     /// Get the current (yet to be committed) block epoch
     pub fn get_current_epoch(&self) -> (Epoch, u64) {
-        (self.block.epoch, STORAGE_ACCESS_GAS_PER_BYTE)
+        (self.block.epoch, self.gas_costs.storage_access_per_byte)
     }
 
     /// Get the epoch of the last committed block
     pub fn get_last_epoch(&self) -> (Epoch, u64) {
-        (self.last_epoch, STORAGE_ACCESS_GAS_PER_BYTE)
+        (self.last_epoch, self.gas_costs.storage_access_per_byte)
     }
 
     /// Initialize the first epoch. The first epoch begins at genesis time.
@@ -972,21 +1018,35 @@ where
         height: Option<BlockHeight>,
     ) -> Result<(Option<Header>, u64)> {
         match height {
-            Some(h) if h == self.get_block_height().0 => {
-                Ok((self.header.clone(), STORAGE_ACCESS_GAS_PER_BYTE))
-            }
+            Some(h) if h == self.get_block_height().0 => Ok((
+                self.header.clone(),
+                self.gas_costs.storage_access_per_byte,
+            )),
             Some(h) => match self.db.read_block_header(h)? {
                 Some(header) => {
                     let gas = header.encoded_len() as u64
-                        * STORAGE_ACCESS_GAS_PER_BYTE;
+                        * self.gas_costs.storage_access_per_byte;
                     Ok((Some(header), gas))
                 }
-                None => Ok((None, STORAGE_ACCESS_GAS_PER_BYTE)),
+                None => Ok((None, self.gas_costs.storage_access_per_byte)),
             },
-            None => Ok((self.header.clone(), STORAGE_ACCESS_GAS_PER_BYTE)),
+            None => Ok((
+                self.header.clone(),
+                self.gas_costs.storage_access_per_byte,
+            )),
         }
     }
 
+    /// Get the time of the current block from its header. Before the header
+    /// is set, e.g. in a dry-run, it's the time of the last committed block.
+    pub fn get_block_time(&self) -> Result<(DateTimeUtc, u64)> {
+        let time = match &self.header {
+            Some(header) => header.time,
+            None => self.get_last_block_timestamp()?,
+        };
+        Ok((time, self.gas_costs.storage_access_per_byte))
+    }
+
     /// Get the timestamp of the last committed block, or the current timestamp
     /// if no blocks have been produced yet
     pub fn get_last_block_timestamp(&self) -> Result<DateTimeUtc> {
@@ -1181,6 +1241,9 @@ pub mod testing {
                 ethereum_height: None,
                 eth_events_queue: EthEventsQueue::default(),
                 storage_read_past_height_limit: Some(1000),
+                block_events: Vec::new(),
+                event_index_retention: None,
+                gas_costs: GasCosts::default(),
             }
         }
     }
@@ -1283,6 +1346,7 @@ mod tests {
                 max_block_gas: 20_000_000,
                 epoch_duration: epoch_duration.clone(),
                 max_expected_time_per_block: Duration::seconds(max_expected_time_per_block).into(),
+                max_tx_expiration: None,
                 vp_whitelist: vec![],
                 tx_whitelist: vec![],
                 implicit_vp_code_hash: Hash::zero(),
@@ -1295,6 +1359,9 @@ mod tests {
                 fee_unshielding_gas_limit: 20_000,
                 fee_unshielding_descriptions_limit: 15,
                 minimum_gas_price: BTreeMap::default(),
+                gas_costs: Default::default(),
+                fee_market: Default::default(),
+                shielded_rewards: BTreeMap::default(),
             };
             parameters.init_storage(&mut wl_storage).unwrap();
 
@@ -3,12 +3,14 @@
 use std::iter::Peekable;
 
 use super::EPOCH_SWITCH_BLOCKS_DELAY;
+use crate::ledger::gas::GasCosts;
 use crate::ledger::parameters::EpochDuration;
 use crate::ledger::storage::write_log::{self, WriteLog};
 use crate::ledger::storage::{DBIter, Storage, StorageHasher, DB};
 use crate::ledger::storage_api::{ResultExt, StorageRead, StorageWrite};
-use crate::ledger::{gas, parameters, storage_api};
+use crate::ledger::{parameters, storage_api};
 use crate::types::address::Address;
+use crate::types::event::TxEvent;
 use crate::types::storage::{self, BlockHeight};
 use crate::types::time::DateTimeUtc;
 
@@ -50,10 +52,9 @@ where
     /// Create a temp storage that can mutated in memory, but never committed to
     /// DB.
     pub fn new(storage: &'a Storage<D, H>) -> Self {
-        Self {
-            write_log: WriteLog::default(),
-            storage,
-        }
+        let mut write_log = WriteLog::default();
+        write_log.set_gas_costs(storage.gas_costs);
+        Self { write_log, storage }
     }
 }
 
@@ -136,10 +137,19 @@ where
     H: 'static + StorageHasher,
 {
     /// Combine storage with write-log
-    pub fn new(write_log: WriteLog, storage: Storage<D, H>) -> Self {
+    pub fn new(mut write_log: WriteLog, storage: Storage<D, H>) -> Self {
+        write_log.set_gas_costs(storage.gas_costs);
         Self { write_log, storage }
     }
 
+    /// Set the gas costs in effect in both the storage and the write log. Must
+    /// be called with the costs from the protocol parameters whenever they
+    /// may have changed.
+    pub fn set_gas_costs(&mut self, gas_costs: GasCosts) {
+        self.storage.gas_costs = gas_costs;
+        self.write_log.set_gas_costs(gas_costs);
+    }
+
     /// Commit the current transaction's write log to the block when it's
     /// accepted by all the triggered validity predicates. Starts a new
     /// transaction write log.
@@ -256,7 +266,7 @@ where
             storage_iter,
             write_log_iter,
         },
-        gas::STORAGE_ACCESS_GAS_PER_BYTE,
+        storage.gas_costs.storage_access_per_byte,
     )
 }
 
@@ -281,7 +291,7 @@ where
             storage_iter,
             write_log_iter,
         },
-        gas::STORAGE_ACCESS_GAS_PER_BYTE,
+        storage.gas_costs.storage_access_per_byte,
     )
 }
 
@@ -498,6 +508,11 @@ where
             .into_storage_result();
         Ok(())
     }
+
+    fn emit_event(&mut self, event: TxEvent) -> storage_api::Result<()> {
+        let _ = self.write_log_mut().emit_event(event);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
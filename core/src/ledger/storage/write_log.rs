@@ -2,21 +2,22 @@
 //! before they are committed to the ledger's storage.
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Mutex;
 
 use itertools::Itertools;
 use thiserror::Error;
 
 use crate::ledger;
-use crate::ledger::gas::{
-    STORAGE_ACCESS_GAS_PER_BYTE, STORAGE_WRITE_GAS_PER_BYTE,
-};
+use crate::ledger::gas::GasCosts;
 use crate::ledger::storage::traits::StorageHasher;
 use crate::ledger::storage::Storage;
 use crate::types::address::{Address, EstablishedAddressGen, InternalAddress};
+use crate::types::event::TxEvent;
 use crate::types::hash::Hash;
 use crate::types::ibc::IbcEvent;
 use crate::types::storage;
 use crate::types::token::{
+    is_any_denom_key, is_any_max_supply_key, is_any_metadata_key,
     is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
 };
 
@@ -42,7 +43,7 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// A storage modification
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum StorageModification {
     /// Write a new value
     Write {
@@ -87,6 +88,57 @@ pub struct WriteLog {
     tx_precommit_write_log: HashMap<storage::Key, StorageModification>,
     /// The IBC events for the current transaction
     ibc_events: BTreeSet<IbcEvent>,
+    /// The events emitted by the current transaction in the emission order
+    events: Vec<TxEvent>,
+    /// The reads of the current transaction and its validity predicates, only
+    /// recorded for a speculative execution
+    read_tracker: Option<ReadTracker>,
+    /// The gas costs in effect, loaded from the protocol parameters
+    gas_costs: GasCosts,
+}
+
+/// The keys read from the write log by a transaction and its validity
+/// predicates. A transaction executed speculatively against an older state
+/// has the same outcome as in the current state if none of these have changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadSet {
+    /// The keys read directly
+    pub keys: BTreeSet<storage::Key>,
+    /// The prefixes iterated over
+    pub prefixes: BTreeSet<storage::Key>,
+    /// Whether the generator of established addresses has been used
+    pub address_gen: bool,
+}
+
+/// Records the reads behind a shared reference, as the write log is shared by
+/// the validity predicates running in parallel
+#[derive(Debug, Default)]
+struct ReadTracker(Mutex<ReadSet>);
+
+impl ReadTracker {
+    fn update(&self, f: impl FnOnce(&mut ReadSet)) {
+        f(&mut self
+            .0
+            .lock()
+            .expect("The read set lock shouldn't be poisoned"))
+    }
+
+    fn into_inner(self) -> ReadSet {
+        self.0
+            .into_inner()
+            .expect("The read set lock shouldn't be poisoned")
+    }
+}
+
+impl Clone for ReadTracker {
+    fn clone(&self) -> Self {
+        let read_set = self
+            .0
+            .lock()
+            .expect("The read set lock shouldn't be poisoned")
+            .clone();
+        Self(Mutex::new(read_set))
+    }
 }
 
 /// Write log prefix iterator
@@ -113,17 +165,28 @@ impl Default for WriteLog {
             tx_write_log: HashMap::with_capacity(100),
             tx_precommit_write_log: HashMap::with_capacity(100),
             ibc_events: BTreeSet::new(),
+            events: Vec::new(),
+            read_tracker: None,
+            gas_costs: GasCosts::default(),
         }
     }
 }
 
 impl WriteLog {
+    /// Set the gas costs charged for the accesses to the write log
+    pub fn set_gas_costs(&mut self, gas_costs: GasCosts) {
+        self.gas_costs = gas_costs;
+    }
+
     /// Read a value at the given key and return the value and the gas cost,
     /// returns [`None`] if the key is not present in the write log
     pub fn read(
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.track_read(|reads| {
+            reads.keys.insert(key.clone());
+        });
         // try to read from tx write log first
         match self
             .tx_write_log
@@ -149,9 +212,12 @@ impl WriteLog {
                         key.len() + value.len()
                     }
                 };
-                (Some(v), gas as u64 * STORAGE_ACCESS_GAS_PER_BYTE)
+                (Some(v), gas as u64 * self.gas_costs.storage_access_per_byte)
             }
-            None => (None, key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE),
+            None => (
+                None,
+                key.len() as u64 * self.gas_costs.storage_access_per_byte,
+            ),
         }
     }
 
@@ -162,6 +228,9 @@ impl WriteLog {
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.track_read(|reads| {
+            reads.keys.insert(key.clone());
+        });
         // try to read from tx write log first
         match self.block_write_log.get(key) {
             Some(v) => {
@@ -177,9 +246,12 @@ impl WriteLog {
                         key.len() + value.len()
                     }
                 };
-                (Some(v), gas as u64 * STORAGE_ACCESS_GAS_PER_BYTE)
+                (Some(v), gas as u64 * self.gas_costs.storage_access_per_byte)
             }
-            None => (None, key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE),
+            None => (
+                None,
+                key.len() as u64 * self.gas_costs.storage_access_per_byte,
+            ),
         }
     }
 
@@ -215,7 +287,10 @@ impl WriteLog {
             // the previous value exists on the storage
             None => len as i64,
         };
-        Ok((gas as u64 * STORAGE_WRITE_GAS_PER_BYTE, size_diff))
+        Ok((
+            gas as u64 * self.gas_costs.storage_write_per_byte,
+            size_diff,
+        ))
     }
 
     /// Write a key and a value.
@@ -282,7 +357,10 @@ impl WriteLog {
         };
         // Temp writes are not propagated to db so just charge the cost of
         // accessing storage
-        Ok((gas as u64 * STORAGE_ACCESS_GAS_PER_BYTE, size_diff))
+        Ok((
+            gas as u64 * self.gas_costs.storage_access_per_byte,
+            size_diff,
+        ))
     }
 
     /// Delete a key and its value, and return the gas cost and the size
@@ -310,7 +388,10 @@ impl WriteLog {
             None => 0,
         };
         let gas = key.len() + size_diff as usize;
-        Ok((gas as u64 * STORAGE_WRITE_GAS_PER_BYTE, -size_diff))
+        Ok((
+            gas as u64 * self.gas_costs.storage_write_per_byte,
+            -size_diff,
+        ))
     }
 
     /// Delete a key and its value.
@@ -342,6 +423,7 @@ impl WriteLog {
         storage_address_gen: &EstablishedAddressGen,
         vp_code_hash: Hash,
     ) -> (Address, u64) {
+        self.track_read(|reads| reads.address_gen = true);
         // If we've previously generated a new account, we use the local copy of
         // the generator. Otherwise, we create a new copy from the storage
         let address_gen =
@@ -350,7 +432,7 @@ impl WriteLog {
             address_gen.generate_address("TODO more randomness".as_bytes());
         let key = storage::Key::validity_predicate(&addr);
         let gas = (key.len() + vp_code_hash.len()) as u64
-            * STORAGE_WRITE_GAS_PER_BYTE;
+            * self.gas_costs.storage_write_per_byte;
         self.tx_write_log
             .insert(key, StorageModification::InitAccount { vp_code_hash });
         (addr, gas)
@@ -363,7 +445,14 @@ impl WriteLog {
             .iter()
             .fold(0, |acc, (k, v)| acc + k.len() + v.len());
         self.ibc_events.insert(event);
-        len as u64 * STORAGE_ACCESS_GAS_PER_BYTE
+        len as u64 * self.gas_costs.storage_access_per_byte
+    }
+
+    /// Set a tx event and return the gas cost.
+    pub fn emit_event(&mut self, event: TxEvent) -> u64 {
+        let len = event.size_in_bytes();
+        self.events.push(event);
+        len as u64 * self.gas_costs.storage_access_per_byte
     }
 
     /// Get the storage keys changed and accounts keys initialized in the
@@ -430,6 +519,16 @@ impl WriteLog {
         &self.ibc_events
     }
 
+    /// Take the events emitted by the current transaction
+    pub fn take_events(&mut self) -> Vec<TxEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Get the events emitted by the current transaction
+    pub fn get_events(&self) -> &[TxEvent] {
+        &self.events
+    }
+
     /// Add the entire content of the tx write log to the precommit one. The tx
     /// log gets reset in the process.
     pub fn precommit_tx(&mut self) {
@@ -459,6 +558,7 @@ impl WriteLog {
 
         self.block_write_log.extend(tx_precommit_write_log);
         self.take_ibc_events();
+        self.take_events();
     }
 
     /// Drop the current transaction's write log and precommit when it's
@@ -467,6 +567,7 @@ impl WriteLog {
     pub fn drop_tx(&mut self) {
         self.tx_precommit_write_log.clear();
         self.tx_write_log.clear();
+        self.events.clear();
     }
 
     /// Drop the current transaction's write log but keep the precommit one.
@@ -547,12 +648,32 @@ impl WriteLog {
                 verifiers
                     .insert(Address::Internal(InternalAddress::Multitoken));
                 verifiers.insert(owner.clone());
-            } else if is_any_minted_balance_key(key).is_some()
-                || is_any_minter_key(key).is_some()
+            } else if is_any_minted_balance_key(key).is_some() {
+                verifiers
+                    .insert(Address::Internal(InternalAddress::Multitoken));
+            } else if let Some(token) = is_any_minter_key(key)
+                .or_else(|| is_any_max_supply_key(key))
+                .or_else(|| is_any_metadata_key(key))
             {
+                // The settings of user-defined tokens must also be
+                // authorized by the token account
+                if matches!(token, Address::Established(_))
+                    && !initialized_accounts.contains(token)
+                {
+                    verifiers.insert(token.clone());
+                }
                 verifiers
                     .insert(Address::Internal(InternalAddress::Multitoken));
             } else {
+                if matches!(
+                    is_any_denom_key(key),
+                    Some(Address::Established(_))
+                ) {
+                    // The denomination of a registered user-defined token
+                    // must not change
+                    verifiers
+                        .insert(Address::Internal(InternalAddress::Multitoken));
+                }
                 for addr in key.iter_addresses() {
                     if verifiers_from_tx.contains(addr)
                         || initialized_accounts.contains(addr)
@@ -577,6 +698,9 @@ impl WriteLog {
     /// Iterate modifications prior to the current transaction, whose storage
     /// key matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_pre(&self, prefix: &storage::Key) -> PrefixIter {
+        self.track_read(|reads| {
+            reads.prefixes.insert(prefix.clone());
+        });
         let mut matches = BTreeMap::new();
 
         for (key, modification) in &self.block_write_log {
@@ -592,6 +716,9 @@ impl WriteLog {
     /// Iterate modifications posterior of the current tx, whose storage key
     /// matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_post(&self, prefix: &storage::Key) -> PrefixIter {
+        self.track_read(|reads| {
+            reads.prefixes.insert(prefix.clone());
+        });
         let mut matches = BTreeMap::new();
 
         for (key, modification) in &self.block_write_log {
@@ -608,6 +735,70 @@ impl WriteLog {
         let iter = matches.into_iter();
         PrefixIter { iter }
     }
+
+    /// Start recording the keys read by the current transaction and its
+    /// validity predicates
+    pub fn track_reads(&mut self) {
+        self.read_tracker = Some(ReadTracker::default());
+    }
+
+    /// Stop recording the reads and return the keys read since
+    /// [`WriteLog::track_reads`]
+    pub fn take_read_set(&mut self) -> ReadSet {
+        self.read_tracker
+            .take()
+            .map(ReadTracker::into_inner)
+            .unwrap_or_default()
+    }
+
+    fn track_read(&self, f: impl FnOnce(&mut ReadSet)) {
+        if let Some(tracker) = &self.read_tracker {
+            tracker.update(f)
+        }
+    }
+
+    /// Check that none of the given reads of a transaction executed on top of
+    /// the `snapshot` would see a different value in this write log. Only the
+    /// block write logs are compared, so this must be called in between
+    /// transactions.
+    pub fn is_read_set_unchanged(
+        &self,
+        snapshot: &WriteLog,
+        read_set: &ReadSet,
+    ) -> bool {
+        let prefix_matches = |write_log: &WriteLog, prefix: &storage::Key| {
+            write_log
+                .block_write_log
+                .iter()
+                .filter(|(key, _)| key.split_prefix(prefix).is_some())
+                .map(|(key, modification)| (key.clone(), modification.clone()))
+                .collect::<BTreeMap<_, _>>()
+        };
+        read_set.keys.iter().all(|key| {
+            self.block_write_log.get(key) == snapshot.block_write_log.get(key)
+        }) && read_set.prefixes.iter().all(|prefix| {
+            prefix_matches(self, prefix) == prefix_matches(snapshot, prefix)
+        }) && (!read_set.address_gen
+            || self.address_gen == snapshot.address_gen)
+    }
+
+    /// Move the modifications of a transaction executed speculatively on a
+    /// copy of this write log into the current transaction's write log, as if
+    /// the transaction has been executed on this write log
+    pub fn merge_speculative_tx(
+        &mut self,
+        speculative: WriteLog,
+        read_set: &ReadSet,
+    ) {
+        self.tx_precommit_write_log
+            .extend(speculative.tx_precommit_write_log);
+        self.tx_write_log.extend(speculative.tx_write_log);
+        self.ibc_events.extend(speculative.ibc_events);
+        self.events.extend(speculative.events);
+        if read_set.address_gen {
+            self.address_gen = speculative.address_gen;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -632,7 +823,10 @@ mod tests {
 
         // delete a non-existing key
         let (gas, diff) = write_log.delete(&key).unwrap();
-        assert_eq!(gas, key.len() as u64 * STORAGE_WRITE_GAS_PER_BYTE);
+        assert_eq!(
+            gas,
+            key.len() as u64 * write_log.gas_costs.storage_write_per_byte
+        );
         assert_eq!(diff, 0);
 
         // insert a value
@@ -640,7 +834,8 @@ mod tests {
         let (gas, diff) = write_log.write(&key, inserted.clone()).unwrap();
         assert_eq!(
             gas,
-            (key.len() + inserted.len()) as u64 * STORAGE_WRITE_GAS_PER_BYTE
+            (key.len() + inserted.len()) as u64
+                * write_log.gas_costs.storage_write_per_byte
         );
         assert_eq!(diff, inserted.len() as i64);
 
@@ -659,7 +854,8 @@ mod tests {
         let (gas, diff) = write_log.write(&key, updated.clone()).unwrap();
         assert_eq!(
             gas,
-            (key.len() + updated.len()) as u64 * STORAGE_WRITE_GAS_PER_BYTE
+            (key.len() + updated.len()) as u64
+                * write_log.gas_costs.storage_write_per_byte
         );
         assert_eq!(diff, updated.len() as i64 - inserted.len() as i64);
 
@@ -667,13 +863,17 @@ mod tests {
         let (gas, diff) = write_log.delete(&key).unwrap();
         assert_eq!(
             gas,
-            (key.len() + updated.len()) as u64 * STORAGE_WRITE_GAS_PER_BYTE
+            (key.len() + updated.len()) as u64
+                * write_log.gas_costs.storage_write_per_byte
         );
         assert_eq!(diff, -(updated.len() as i64));
 
         // delete the deleted key again
         let (gas, diff) = write_log.delete(&key).unwrap();
-        assert_eq!(gas, key.len() as u64 * STORAGE_WRITE_GAS_PER_BYTE);
+        assert_eq!(
+            gas,
+            key.len() as u64 * write_log.gas_costs.storage_write_per_byte
+        );
         assert_eq!(diff, 0);
 
         // read the deleted key
@@ -682,14 +882,18 @@ mod tests {
             StorageModification::Delete => {}
             _ => panic!("unexpected result"),
         }
-        assert_eq!(gas, key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE);
+        assert_eq!(
+            gas,
+            key.len() as u64 * write_log.gas_costs.storage_access_per_byte
+        );
 
         // insert again
         let reinserted = "reinserted".as_bytes().to_vec();
         let (gas, diff) = write_log.write(&key, reinserted.clone()).unwrap();
         assert_eq!(
             gas,
-            (key.len() + reinserted.len()) as u64 * STORAGE_WRITE_GAS_PER_BYTE
+            (key.len() + reinserted.len()) as u64
+                * write_log.gas_costs.storage_write_per_byte
         );
         assert_eq!(diff, reinserted.len() as i64);
     }
@@ -706,7 +910,8 @@ mod tests {
         let vp_key = storage::Key::validity_predicate(&addr);
         assert_eq!(
             gas,
-            (vp_key.len() + vp_hash.len()) as u64 * STORAGE_WRITE_GAS_PER_BYTE
+            (vp_key.len() + vp_hash.len()) as u64
+                * write_log.gas_costs.storage_write_per_byte
         );
 
         // read
@@ -719,7 +924,8 @@ mod tests {
         }
         assert_eq!(
             gas,
-            (vp_key.len() + vp_hash.len()) as u64 * STORAGE_ACCESS_GAS_PER_BYTE
+            (vp_key.len() + vp_hash.len()) as u64
+                * write_log.gas_costs.storage_access_per_byte
         );
 
         // get all
@@ -834,6 +1040,78 @@ mod tests {
         assert_eq!(value, None);
     }
 
+    #[test]
+    fn test_emit_event() {
+        let mut write_log = WriteLog::default();
+        let owner = address::testing::established_address_1();
+
+        let event1 = TxEvent::new("bond").with("source", owner.clone());
+        let event2 = TxEvent::new("bond").with("validator", owner);
+        let gas = write_log.emit_event(event1.clone());
+        assert_eq!(
+            gas,
+            event1.size_in_bytes() as u64
+                * write_log.gas_costs.storage_access_per_byte
+        );
+        write_log.emit_event(event2.clone());
+        // the events of the same type are kept in the emission order
+        assert_eq!(write_log.get_events(), &[event1, event2]);
+
+        write_log.commit_tx();
+        assert!(write_log.get_events().is_empty());
+    }
+
+    #[test]
+    fn test_speculative_tx() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let prefix =
+            storage::Key::parse("prefix").expect("cannot parse the key string");
+        let prefixed = prefix.push(&"key".to_string()).unwrap();
+        let val = "val".as_bytes().to_vec();
+        write_log.write(&key1, val.clone()).unwrap();
+        write_log.commit_tx();
+        let snapshot = write_log.clone();
+
+        // execute a tx on a copy of the write log
+        let mut speculative = snapshot.clone();
+        speculative.track_reads();
+        let _ = speculative.read(&key1);
+        let _ = speculative.iter_prefix_post(&prefix);
+        speculative.write(&key2, val.clone()).unwrap();
+        let read_set = speculative.take_read_set();
+        assert_eq!(read_set.keys, BTreeSet::from([key1.clone()]));
+        assert_eq!(read_set.prefixes, BTreeSet::from([prefix]));
+        assert!(!read_set.address_gen);
+
+        // writing a key that hasn't been read doesn't invalidate the tx
+        write_log.write(&key2, val.clone()).unwrap();
+        write_log.commit_tx();
+        assert!(write_log.is_read_set_unchanged(&snapshot, &read_set));
+
+        // the same value is still valid
+        let mut unchanged = write_log.clone();
+        unchanged.write(&key1, val.clone()).unwrap();
+        unchanged.commit_tx();
+        assert!(unchanged.is_read_set_unchanged(&snapshot, &read_set));
+
+        // a changed value or a new key with the prefix invalidates it
+        let mut changed = write_log.clone();
+        changed.delete(&key1).unwrap();
+        changed.commit_tx();
+        assert!(!changed.is_read_set_unchanged(&snapshot, &read_set));
+        let mut changed = write_log.clone();
+        changed.write(&prefixed, val).unwrap();
+        changed.commit_tx();
+        assert!(!changed.is_read_set_unchanged(&snapshot, &read_set));
+
+        write_log.merge_speculative_tx(speculative, &read_set);
+        assert_eq!(write_log.get_keys(), BTreeSet::from([key2]));
+    }
+
     prop_compose! {
         fn arb_verifiers_changed_key_tx_all_key()
             (verifiers_from_tx in testing::arb_verifiers_from_tx())
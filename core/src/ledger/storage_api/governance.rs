@@ -10,11 +10,13 @@ use crate::ledger::governance::storage::keys as governance_keys;
 use crate::ledger::governance::storage::proposal::{
     ProposalType, StorageProposal,
 };
+use crate::ledger::governance::storage::upgrade::UpgradePlan;
 use crate::ledger::governance::storage::vote::StorageProposalVote;
 use crate::ledger::governance::utils::Vote;
 use crate::ledger::governance::ADDRESS as governance_address;
 use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
 use crate::types::address::Address;
+use crate::types::event::{TxEvent, INIT_PROPOSAL_EVENT, VOTE_PROPOSAL_EVENT};
 use crate::types::storage::Epoch;
 use crate::types::transaction::governance::{
     InitProposalData, VoteProposalData,
@@ -69,6 +71,12 @@ where
     let grace_epoch_key = governance_keys::get_grace_epoch_key(proposal_id);
     storage.write(&grace_epoch_key, data.grace_epoch)?;
 
+    if data.is_expedited {
+        let expedited_key =
+            governance_keys::get_proposal_expedited_key(proposal_id);
+        storage.write(&expedited_key, true)?;
+    }
+
     if let ProposalType::Default(Some(_)) = data.r#type {
         let proposal_code_key =
             governance_keys::get_proposal_code_key(proposal_id);
@@ -85,6 +93,7 @@ where
 
     let funds_key = governance_keys::get_funds_key(proposal_id);
     storage.write(&funds_key, min_proposal_funds)?;
+    lock_deposit(storage, min_proposal_funds)?;
 
     // this key must always be written for each proposal
     let committing_proposals_key =
@@ -100,6 +109,14 @@ where
         &data.author,
         &governance_address,
         min_proposal_funds,
+    )?;
+
+    storage.emit_event(
+        TxEvent::new(INIT_PROPOSAL_EVENT)
+            .with("proposal_id", proposal_id)
+            .with("author", data.author)
+            .with("voting_start_epoch", data.voting_start_epoch.0)
+            .with("voting_end_epoch", data.voting_end_epoch.0),
     )
 }
 
@@ -111,6 +128,10 @@ pub fn vote_proposal<S>(
 where
     S: StorageRead + StorageWrite,
 {
+    if !data.vote.is_valid() {
+        return Err(storage_api::Error::new_const("Invalid split vote"));
+    }
+
     for delegation in data.delegations {
         let vote_key = governance_keys::get_vote_proposal_key(
             data.id,
@@ -119,7 +140,12 @@ where
         );
         storage.write(&vote_key, data.vote.clone())?;
     }
-    Ok(())
+    storage.emit_event(
+        TxEvent::new(VOTE_PROPOSAL_EVENT)
+            .with("proposal_id", data.id)
+            .with("voter", data.voter)
+            .with("vote", data.vote.to_string()),
+    )
 }
 
 /// Read a proposal by id from storage
@@ -144,6 +170,7 @@ where
     let grace_epoch: Option<Epoch> = storage.read(&grace_epoch_key)?;
     let proposal_type: Option<ProposalType> =
         storage.read(&proposal_type_key)?;
+    let is_expedited = is_proposal_expedited(storage, id)?;
 
     let proposal = proposal_type.map(|proposal_type| StorageProposal {
         id,
@@ -153,11 +180,25 @@ where
         voting_start_epoch: voting_start_epoch.unwrap(),
         voting_end_epoch: voting_end_epoch.unwrap(),
         grace_epoch: grace_epoch.unwrap(),
+        is_expedited,
     });
 
     Ok(proposal)
 }
 
+/// Check if a proposal is expedited
+pub fn is_proposal_expedited<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    let expedited_key =
+        governance_keys::get_proposal_expedited_key(proposal_id);
+    Ok(storage.read(&expedited_key)?.unwrap_or_default())
+}
+
 /// Query all the votes for a proposal_id
 pub fn get_proposal_votes<S>(
     storage: &S,
@@ -218,6 +259,136 @@ where
     }
 }
 
+/// Query the software upgrade scheduled by a governance proposal, if any
+pub fn get_scheduled_upgrade<S>(
+    storage: &S,
+) -> storage_api::Result<Option<UpgradePlan>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_upgrade_key())
+}
+
+/// Schedule a software upgrade. Fails if another upgrade is already
+/// scheduled, it must be applied before the next one can be scheduled.
+pub fn schedule_upgrade<S>(
+    storage: &mut S,
+    plan: UpgradePlan,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if get_scheduled_upgrade(storage)?.is_some() {
+        return Err(storage_api::Error::new_const(
+            "Another software upgrade is already scheduled",
+        ));
+    }
+    storage.write(&governance_keys::get_upgrade_key(), plan)
+}
+
+/// Remove the scheduled software upgrade once it has been applied
+pub fn remove_scheduled_upgrade<S>(storage: &mut S) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.delete(&governance_keys::get_upgrade_key())
+}
+
+/// Query the total amount of proposal deposits held by the governance account
+/// that have not been returned nor burned yet
+pub fn get_locked_deposits<S>(storage: &S) -> storage_api::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&governance_keys::get_locked_deposits_key())?
+        .unwrap_or_default())
+}
+
+/// Initialize the locked deposits of a chain that has proposals from before
+/// they were tracked. They are the deposits of the proposals that have not
+/// been settled yet, i.e. with a grace epoch not before the epoch of the last
+/// committed block. Does nothing if the locked deposits are already tracked.
+pub fn init_locked_deposits<S>(
+    storage: &mut S,
+    last_epoch: Epoch,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let locked_deposits_key = governance_keys::get_locked_deposits_key();
+    if storage.has_key(&locked_deposits_key)? {
+        return Ok(());
+    }
+    let counter: u64 = storage
+        .read(&governance_keys::get_counter_key())?
+        .unwrap_or_default();
+    let mut locked = token::Amount::zero();
+    for id in 0..counter {
+        let grace_epoch: Option<Epoch> =
+            storage.read(&governance_keys::get_grace_epoch_key(id))?;
+        if grace_epoch.map_or(true, |grace_epoch| grace_epoch < last_epoch) {
+            continue;
+        }
+        let funds: token::Amount = storage
+            .read(&governance_keys::get_funds_key(id))?
+            .unwrap_or_default();
+        locked = locked.checked_add(funds).ok_or_else(|| {
+            storage_api::Error::new_const("Locked deposits overflow")
+        })?;
+    }
+    storage.write(&locked_deposits_key, locked)
+}
+
+/// Add a new proposal deposit to the locked deposits
+pub fn lock_deposit<S>(
+    storage: &mut S,
+    amount: token::Amount,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let locked = get_locked_deposits(storage)?
+        .checked_add(amount)
+        .ok_or_else(|| {
+            storage_api::Error::new_const("Locked deposits overflow")
+        })?;
+    storage.write(&governance_keys::get_locked_deposits_key(), locked)
+}
+
+/// Remove a settled proposal deposit from the locked deposits
+pub fn unlock_deposit<S>(
+    storage: &mut S,
+    amount: token::Amount,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let locked = get_locked_deposits(storage)?
+        .checked_sub(amount)
+        .ok_or_else(|| {
+            storage_api::Error::new_const("Locked deposits underflow")
+        })?;
+    storage.write(&governance_keys::get_locked_deposits_key(), locked)
+}
+
+/// Query the balance of the governance treasury in the given token. Proposal
+/// deposits are not part of the treasury and can't be spent.
+pub fn get_treasury_balance<S>(
+    storage: &S,
+    token: &Address,
+) -> storage_api::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let balance = token::read_balance(storage, token, &governance_address)?;
+    if *token != storage.get_native_token()? {
+        return Ok(balance);
+    }
+    let locked = get_locked_deposits(storage)?;
+    Ok(balance.checked_sub(locked).unwrap_or_default())
+}
+
 /// Get governance parameters
 pub fn get_parameters<S>(
     storage: &S,
@@ -245,6 +416,12 @@ where
     let min_proposal_voting_period: u64 =
         storage.read(&key)?.expect("Parameter should be definied.");
 
+    // Chains started before expedited proposals existed don't have this
+    // parameter, in which case expedited proposals get the regular period
+    let key = governance_keys::get_min_expedited_proposal_voting_period_key();
+    let min_expedited_proposal_voting_period: u64 =
+        storage.read(&key)?.unwrap_or(min_proposal_voting_period);
+
     let key = governance_keys::get_max_proposal_period_key();
     let max_proposal_period: u64 =
         storage.read(&key)?.expect("Parameter should be definied.");
@@ -253,6 +430,7 @@ where
         min_proposal_fund,
         max_proposal_code_size,
         min_proposal_voting_period,
+        min_expedited_proposal_voting_period,
         max_proposal_period,
         max_proposal_content_size,
         min_proposal_grace_epochs,
@@ -14,6 +14,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 pub use error::{CustomError, Error, OptionExt, Result, ResultExt};
 
 use crate::types::address::Address;
+use crate::types::event::TxEvent;
 use crate::types::storage::{
     self, BlockHash, BlockHeight, Epoch, Header, TxIndex,
 };
@@ -123,6 +124,10 @@ pub trait StorageWrite {
     /// Delete a value at the given key from storage.
     fn delete(&mut self, key: &storage::Key) -> Result<()>;
 
+    /// Emit an event for indexers. The events are kept in the order they are
+    /// emitted.
+    fn emit_event(&mut self, event: TxEvent) -> Result<()>;
+
     /// Delete all key-vals with a matching prefix.
     fn delete_prefix(&mut self, prefix: &storage::Key) -> Result<()>
     where
@@ -1,6 +1,6 @@
 //! Pgf
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::ledger::governance::storage::proposal::StoragePgfFunding;
 use crate::ledger::pgf::parameters::PgfParameters;
@@ -9,6 +9,8 @@ use crate::ledger::pgf::storage::steward::StewardDetail;
 use crate::ledger::storage_api::{self};
 use crate::types::address::Address;
 use crate::types::dec::Dec;
+use crate::types::storage::Epoch;
+use crate::types::token;
 
 /// Query the current pgf steward set
 pub fn get_stewards<S>(storage: &S) -> storage_api::Result<Vec<StewardDetail>>
@@ -79,6 +81,59 @@ where
     Ok(fundings)
 }
 
+/// Remove the pgf funding stream to a target
+pub fn remove_funding<S>(
+    storage: &mut S,
+    target: &Address,
+) -> storage_api::Result<()>
+where
+    S: storage_api::StorageRead + storage_api::StorageWrite,
+{
+    pgf_keys::fundings_handle().remove(storage, target)?;
+
+    Ok(())
+}
+
+/// Query the history of the pgf funding payouts to a target
+pub fn get_payouts<S>(
+    storage: &S,
+    target: &Address,
+) -> storage_api::Result<BTreeMap<Epoch, token::Amount>>
+where
+    S: storage_api::StorageRead,
+{
+    pgf_keys::payouts_handle()
+        .at(target)
+        .iter(storage)?
+        .collect()
+}
+
+/// Record the payout of a pgf funding stream in the given epoch. The stream is
+/// removed once it has nothing left to pay.
+pub fn record_payout<S>(
+    storage: &mut S,
+    mut funding: StoragePgfFunding,
+    epoch: Epoch,
+    amount: token::Amount,
+) -> storage_api::Result<()>
+where
+    S: storage_api::StorageRead + storage_api::StorageWrite,
+{
+    let target = funding.detail.target.clone();
+    pgf_keys::payouts_handle()
+        .at(&target)
+        .insert(storage, epoch, amount)?;
+
+    funding.paid += amount;
+    if funding.is_exhausted(epoch.next()) {
+        remove_funding(storage, &target)?;
+    } else {
+        pgf_keys::fundings_handle().insert(storage, target, funding)?;
+    }
+
+    Ok(())
+}
+
 /// Query the pgf parameters
 pub fn get_parameters<S>(storage: &S) -> storage_api::Result<PgfParameters>
 where
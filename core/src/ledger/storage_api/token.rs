@@ -3,6 +3,7 @@
 use super::{StorageRead, StorageWrite};
 use crate::ledger::storage_api;
 use crate::types::address::{Address, InternalAddress};
+use crate::types::event::{TxEvent, BURN_EVENT, MINT_EVENT, TRANSFER_EVENT};
 use crate::types::token;
 pub use crate::types::token::{
     balance_key, is_any_minted_balance_key, is_balance_key, minted_balance_key,
@@ -36,6 +37,28 @@ where
     Ok(balance)
 }
 
+/// Read the max supply of a given user-defined token, if any.
+pub fn read_max_supply<S>(
+    storage: &S,
+    token: &Address,
+) -> storage_api::Result<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    storage.read(&token::max_supply_key(token))
+}
+
+/// Read the metadata of a given user-defined token, if it's registered.
+pub fn read_metadata<S>(
+    storage: &S,
+    token: &Address,
+) -> storage_api::Result<Option<token::TokenMetadata>>
+where
+    S: StorageRead,
+{
+    storage.read(&token::metadata_key(token))
+}
+
 /// Read the denomination of a given token, if any. Note that native
 /// transparent tokens do not have this set and instead use the constant
 /// [`token::NATIVE_MAX_DECIMAL_PLACES`].
@@ -110,7 +133,14 @@ where
             match dest_balance.checked_add(amount) {
                 Some(new_dest_balance) => {
                     storage.write(&src_key, new_src_balance)?;
-                    storage.write(&dest_key, new_dest_balance)
+                    storage.write(&dest_key, new_dest_balance)?;
+                    storage.emit_event(
+                        TxEvent::new(TRANSFER_EVENT)
+                            .with("token", token.clone())
+                            .with("source", src.clone())
+                            .with("target", dest.clone())
+                            .with("amount", amount),
+                    )
                 }
                 None => Err(storage_api::Error::new_const(
                     "The transfer would overflow destination balance",
@@ -149,7 +179,13 @@ where
     })?;
 
     storage.write(&balance_key, new_balance)?;
-    storage.write(&total_supply_key, new_supply)
+    storage.write(&total_supply_key, new_supply)?;
+    storage.emit_event(
+        TxEvent::new(MINT_EVENT)
+            .with("token", token.clone())
+            .with("target", dest.clone())
+            .with("amount", amount),
+    )
 }
 
 /// Burn an amount of token for a specific address.
@@ -181,5 +217,11 @@ where
         total_supply.checked_sub(amount_to_burn).unwrap_or_default();
 
     let total_supply_key = token::minted_balance_key(token);
-    storage.write(&total_supply_key, new_total_supply)
+    storage.write(&total_supply_key, new_total_supply)?;
+    storage.emit_event(
+        TxEvent::new(BURN_EVENT)
+            .with("token", token.clone())
+            .with("source", source.clone())
+            .with("amount", amount_to_burn),
+    )
 }
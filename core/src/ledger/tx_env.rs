@@ -5,8 +5,11 @@ use borsh::BorshSerialize;
 
 use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
 use crate::types::address::Address;
+use crate::types::hash::{Hash, HashAlgorithm};
 use crate::types::ibc::IbcEvent;
+use crate::types::key::common;
 use crate::types::storage;
+use crate::types::time::DateTimeUtc;
 
 /// Transaction host functions
 pub trait TxEnv: StorageRead + StorageWrite {
@@ -64,4 +67,25 @@ pub trait TxEnv: StorageRead + StorageWrite {
         &self,
         event_type: impl AsRef<str>,
     ) -> Result<Option<IbcEvent>, storage_api::Error>;
+
+    /// Get the time of the block to which the current transaction is being
+    /// applied
+    fn get_block_time(&self) -> Result<DateTimeUtc, storage_api::Error>;
+
+    /// Hash the data with the given algorithm
+    fn hash(
+        &self,
+        algorithm: HashAlgorithm,
+        data: impl AsRef<[u8]>,
+    ) -> Result<Hash, storage_api::Error>;
+
+    /// Verify a signature over the raw data. Ed25519 signatures are over the
+    /// data itself, while Secp256k1 signatures are over a 32 bytes digest,
+    /// e.g. obtained with [`Self::hash`], which the data must be.
+    fn verify_signature(
+        &self,
+        public_key: &common::PublicKey,
+        signature: &common::Signature,
+        data: impl AsRef<[u8]>,
+    ) -> Result<bool, storage_api::Error>;
 }
@@ -6,10 +6,12 @@ use borsh::BorshDeserialize;
 use super::storage_api::{self, StorageRead};
 use crate::proto::Tx;
 use crate::types::address::Address;
-use crate::types::hash::Hash;
+use crate::types::hash::{Hash, HashAlgorithm};
+use crate::types::key::common;
 use crate::types::storage::{
     BlockHash, BlockHeight, Epoch, Header, Key, TxIndex,
 };
+use crate::types::time::DateTimeUtc;
 
 /// Validity predicate's environment is available for native VPs and WASM VPs
 pub trait VpEnv<'view>
@@ -69,6 +71,10 @@ where
     /// current transaction is being applied.
     fn get_block_epoch(&self) -> Result<Epoch, storage_api::Error>;
 
+    /// Getting the block time. The time is that of the block to which the
+    /// current transaction is being applied.
+    fn get_block_time(&self) -> Result<DateTimeUtc, storage_api::Error>;
+
     /// Get the shielded transaction index.
     fn get_tx_index(&self) -> Result<TxIndex, storage_api::Error>;
 
@@ -100,6 +106,23 @@ where
     /// Verify a MASP transaction
     fn verify_masp(&self, tx: Vec<u8>) -> Result<bool, storage_api::Error>;
 
+    /// Hash the data with the given algorithm
+    fn hash(
+        &self,
+        algorithm: HashAlgorithm,
+        data: impl AsRef<[u8]>,
+    ) -> Result<Hash, storage_api::Error>;
+
+    /// Verify a signature over the raw data. Ed25519 signatures are over the
+    /// data itself, while Secp256k1 signatures are over a 32 bytes digest,
+    /// e.g. obtained with [`Self::hash`], which the data must be.
+    fn verify_signature(
+        &self,
+        public_key: &common::PublicKey,
+        signature: &common::Signature,
+        data: impl AsRef<[u8]>,
+    ) -> Result<bool, storage_api::Error>;
+
     /// Charge the provided gas for the current vp
     fn charge_gas(&self, used_gas: u64) -> Result<(), storage_api::Error>;
 
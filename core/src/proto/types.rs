@@ -23,7 +23,7 @@ use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 use super::generated::types;
-use crate::ledger::gas::{GasMetering, VpGasMeter, VERIFY_TX_SIG_GAS_COST};
+use crate::ledger::gas::{GasCosts, GasMetering, VpGasMeter};
 use crate::ledger::storage::{KeccakHasher, Sha256Hasher, StorageHasher};
 #[cfg(any(feature = "tendermint", feature = "tendermint-abcipp"))]
 use crate::tendermint_proto::abci::ResponseDeliverTx;
@@ -1373,7 +1373,8 @@ impl Tx {
     }
 
     /// Verify that the section with the given hash has been signed by the given
-    /// public key
+    /// public key. If a gas meter is given, the signature verifications are
+    /// charged to it at the given gas costs.
     pub fn verify_signatures(
         &self,
         hashes: &[crate::types::hash::Hash],
@@ -1381,7 +1382,7 @@ impl Tx {
         signer: &Option<Address>,
         threshold: u8,
         max_signatures: Option<u8>,
-        mut gas_meter: Option<&mut VpGasMeter>,
+        mut gas_meter: Option<(&mut VpGasMeter, &GasCosts)>,
     ) -> std::result::Result<Vec<&Signature>, Error> {
         let max_signatures = max_signatures.unwrap_or(u8::MAX);
         // Records the public key indices used in successful signatures
@@ -1421,12 +1422,14 @@ impl Tx {
                             )
                         });
                     // Compute the cost of the signature verifications
-                    if let Some(x) = gas_meter.as_mut() {
+                    if let Some((x, gas_costs)) = gas_meter.as_mut() {
                         let amt_verified = usize::from(amt_verifieds.is_err())
                             + verified_pks.len()
                             - prev_verifieds;
-                        x.consume(VERIFY_TX_SIG_GAS_COST * amt_verified as u64)
-                            .map_err(|_| Error::OutOfGas)?;
+                        x.consume(
+                            gas_costs.verify_tx_sig * amt_verified as u64,
+                        )
+                        .map_err(|_| Error::OutOfGas)?;
                     }
                     // Record the section witnessing these signatures
                     if amt_verifieds? > 0 {
@@ -450,7 +450,15 @@ impl TryFrom<Signer> for Address {
     type Error = DecodeError;
 
     fn try_from(signer: Signer) -> Result<Self> {
-        Address::decode(signer.as_ref())
+        // The given address should be an address or a payment address. Tokens
+        // received by a payment address are held by the MASP address.
+        Address::decode(signer.as_ref()).or_else(|err| {
+            match crate::types::masp::PaymentAddress::from_str(signer.as_ref())
+            {
+                Ok(_) => Ok(masp()),
+                Err(_) => Err(err),
+            }
+        })
     }
 }
 
@@ -681,23 +689,6 @@ pub fn tokens() -> HashMap<Address, (&'static str, Denomination)> {
     .collect()
 }
 
-/// Temporary helper for testing, a hash map of tokens addresses with their
-/// MASP XAN incentive schedules. If the reward is (a, b) then a rewarded tokens
-/// are dispensed for every b possessed tokens.
-pub fn masp_rewards() -> HashMap<Address, (u32, u32)> {
-    vec![
-        (nam(), (0, 100)),
-        (btc(), (1, 100)),
-        (eth(), (2, 100)),
-        (dot(), (3, 100)),
-        (schnitzel(), (4, 100)),
-        (apfel(), (5, 100)),
-        (kartoffel(), (6, 100)),
-    ]
-    .into_iter()
-    .collect()
-}
-
 #[cfg(test)]
 pub mod tests {
     use proptest::prelude::*;
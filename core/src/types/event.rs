@@ -0,0 +1,365 @@
+//! Events emitted by transactions
+
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+
+use crate::types::address::Address;
+use crate::types::storage::BlockHeight;
+use crate::types::token::Amount;
+
+/// A typed value of a tx event attribute
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub enum EventValue {
+    /// A string
+    String(String),
+    /// A token amount
+    Amount(Amount),
+    /// An address
+    Address(Address),
+    /// An unsigned integer
+    U64(u64),
+    /// A boolean
+    Bool(bool),
+}
+
+impl std::fmt::Display for EventValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventValue::String(s) => write!(f, "{}", s),
+            // The raw amount since the denomination depends on the token
+            EventValue::Amount(amount) => {
+                write!(f, "{}", amount.raw_amount())
+            }
+            EventValue::Address(addr) => write!(f, "{}", addr),
+            EventValue::U64(n) => write!(f, "{}", n),
+            EventValue::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+impl From<String> for EventValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for EventValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<Amount> for EventValue {
+    fn from(value: Amount) -> Self {
+        Self::Amount(value)
+    }
+}
+
+impl From<Address> for EventValue {
+    fn from(value: Address) -> Self {
+        Self::Address(value)
+    }
+}
+
+impl From<u64> for EventValue {
+    fn from(value: u64) -> Self {
+        Self::U64(value)
+    }
+}
+
+impl From<bool> for EventValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+/// An event emitted by a transaction
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct TxEvent {
+    /// The event type
+    pub event_type: String,
+    /// The attributes of the event
+    pub attributes: BTreeMap<String, EventValue>,
+}
+
+impl TxEvent {
+    /// Make a new event without attributes
+    pub fn new(event_type: impl Into<String>) -> Self {
+        Self {
+            event_type: event_type.into(),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Add an attribute to the event
+    pub fn with(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<EventValue>,
+    ) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// The attributes rendered as strings for ABCI events
+    pub fn string_attributes(
+        &self,
+    ) -> impl Iterator<Item = (String, String)> + '_ {
+        self.attributes
+            .iter()
+            .map(|(key, value)| (key.clone(), value.to_string()))
+    }
+
+    /// The size of the event type and the attributes in bytes, used to charge
+    /// gas for emitting the event
+    pub fn size_in_bytes(&self) -> usize {
+        self.string_attributes()
+            .fold(self.event_type.len(), |acc, (k, v)| acc + k.len() + v.len())
+    }
+
+    /// Namespace the event type as the one of an event emitted by a wasm
+    /// transaction
+    pub fn into_tx_emitted(mut self) -> Self {
+        self.event_type.insert_str(0, TX_EMITTED_EVENT_PREFIX);
+        self
+    }
+
+    /// The event type without its namespace, if the event has been emitted by
+    /// a wasm transaction
+    pub fn tx_emitted_type(&self) -> Option<&str> {
+        self.event_type.strip_prefix(TX_EMITTED_EVENT_PREFIX)
+    }
+}
+
+impl std::fmt::Display for TxEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attributes = self
+            .string_attributes()
+            .map(|(k, v)| format!("{}: {};", k, v))
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(
+            f,
+            "Event type: {}, Attributes: {}",
+            self.event_type, attributes
+        )
+    }
+}
+
+/// The maximum number of events returned by a query of the event index
+pub const MAX_EVENT_INDEX_QUERY_LIMIT: u64 = 100;
+
+/// The maximum length of an attribute value to index an event by. Longer
+/// values, e.g. logs, are stored but cannot be looked up.
+pub const MAX_INDEXED_ATTRIBUTE_LEN: usize = 256;
+
+/// An event stored in the event index of the node's DB
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct IndexedEvent {
+    /// The event type
+    pub event_type: String,
+    /// The attributes of the event
+    pub attributes: BTreeMap<String, String>,
+    /// The event encoded as reported by the ledger
+    pub data: Vec<u8>,
+}
+
+impl IndexedEvent {
+    /// The attributes which the event is looked up by in the index
+    pub fn indexed_attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .filter(|(_, value)| value.len() <= MAX_INDEXED_ATTRIBUTE_LEN)
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+/// A query of the event index. The matching events are ordered by the block
+/// height and the order in which they have been emitted in the block.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct EventIndexQuery {
+    /// Match only the events of this type
+    pub event_type: Option<String>,
+    /// Match only the events with all of these attribute values
+    pub attributes: BTreeMap<String, String>,
+    /// Match only the events emitted at or above this height
+    pub min_height: Option<BlockHeight>,
+    /// Match only the events emitted at or below this height
+    pub max_height: Option<BlockHeight>,
+    /// The number of matching events to skip
+    pub offset: u64,
+    /// The maximum number of events to return, capped at
+    /// [`MAX_EVENT_INDEX_QUERY_LIMIT`]
+    pub limit: u64,
+}
+
+impl EventIndexQuery {
+    /// Query the events of the given type
+    pub fn new(event_type: impl Into<String>) -> Self {
+        Self {
+            event_type: Some(event_type.into()),
+            limit: MAX_EVENT_INDEX_QUERY_LIMIT,
+            ..Default::default()
+        }
+    }
+
+    /// Filter the events by an attribute value
+    pub fn with(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// The number of events to return
+    pub fn limit(&self) -> usize {
+        self.limit.min(MAX_EVENT_INDEX_QUERY_LIMIT) as usize
+    }
+
+    /// Check if the event emitted at the given height matches the query,
+    /// ignoring the pagination
+    pub fn matches(&self, height: BlockHeight, event: &IndexedEvent) -> bool {
+        self.min_height.map_or(true, |min| height >= min)
+            && self.max_height.map_or(true, |max| height <= max)
+            && self
+                .event_type
+                .as_ref()
+                .map_or(true, |event_type| event_type == &event.event_type)
+            && self
+                .attributes
+                .iter()
+                .all(|(key, value)| event.attributes.get(key) == Some(value))
+    }
+}
+
+/// The prefix of the type of the events emitted by the wasm transactions. A
+/// transaction is free to emit any event, so these are kept apart from the
+/// events of the protocol and of the native logic. This includes the events of
+/// the native logic run by a transaction, e.g. the `transfer` event of a
+/// transfer tx is reported as `tx/transfer`.
+pub const TX_EMITTED_EVENT_PREFIX: &str = "tx/";
+
+/// Tokens have been transferred. Attributes: `token`, `source`, `target` and
+/// `amount`.
+pub const TRANSFER_EVENT: &str = "transfer";
+/// Tokens have been minted. Attributes: `token`, `target` and `amount`.
+pub const MINT_EVENT: &str = "mint";
+/// Tokens have been burned. Attributes: `token`, `source` and `amount`.
+pub const BURN_EVENT: &str = "burn";
+/// Tokens have been bonded to a validator. Attributes: `source`,
+/// `validator`, `amount` and `start_epoch` from which the bond contributes to
+/// the validator's stake.
+pub const BOND_EVENT: &str = "bond";
+/// Tokens have been unbonded from a validator. Attributes: `source`,
+/// `validator`, `amount` and `withdrawable_epoch` from which the tokens can be
+/// withdrawn.
+pub const UNBOND_EVENT: &str = "unbond";
+/// A misbehavior of a validator has been recorded to be slashed. Attributes:
+/// `validator`, `slash_type`, `infraction_epoch`, `height` of the evidence and
+/// `processing_epoch` in which the slash rate will be computed.
+pub const SLASH_EVENT: &str = "slash";
+/// An enqueued slash has been processed. Attributes: `validator`,
+/// `infraction_epoch` and the final slash `rate`.
+pub const SLASH_PROCESSED_EVENT: &str = "slash_processed";
+/// A validator has been jailed. Attributes: `validator` and `epoch` from which
+/// it's jailed.
+pub const JAIL_EVENT: &str = "jail";
+/// A validator has been unjailed. Attributes: `validator` and `epoch` from
+/// which it's back in the validator set.
+pub const UNJAIL_EVENT: &str = "unjail";
+/// The block rewards have been logged. Attributes: `epoch`, `proposer`,
+/// `signing_stake` and `total_stake` of the consensus validator set.
+pub const BLOCK_REWARDS_EVENT: &str = "block_rewards";
+/// A governance proposal has been submitted. Attributes: `proposal_id`,
+/// `author`, `voting_start_epoch` and `voting_end_epoch`.
+pub const INIT_PROPOSAL_EVENT: &str = "init_proposal";
+/// A governance proposal has been voted. Attributes: `proposal_id`, `voter`
+/// and `vote`.
+pub const VOTE_PROPOSAL_EVENT: &str = "vote_proposal";
+/// A new epoch has started. Attributes: `epoch` and `height` of its first
+/// block.
+pub const NEW_EPOCH_EVENT: &str = "new_epoch";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::address;
+
+    #[test]
+    fn test_tx_event_attributes() {
+        let owner = address::testing::established_address_1();
+        let event = TxEvent::new("transfer")
+            .with("owner", owner.clone())
+            .with("amount", Amount::native_whole(10))
+            .with("shielded", false);
+        let attributes: BTreeMap<_, _> = event.string_attributes().collect();
+        assert_eq!(attributes["owner"], owner.to_string());
+        assert_eq!(attributes["amount"], "10000000");
+        assert_eq!(attributes["shielded"], "false");
+        assert_eq!(
+            event.size_in_bytes(),
+            "transfer".len()
+                + attributes
+                    .iter()
+                    .map(|(k, v)| k.len() + v.len())
+                    .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_event_index_query_matches() {
+        let event = IndexedEvent {
+            event_type: TRANSFER_EVENT.to_string(),
+            attributes: BTreeMap::from([
+                ("source".to_string(), "alice".to_string()),
+                ("log".to_string(), "x".repeat(MAX_INDEXED_ATTRIBUTE_LEN + 1)),
+            ]),
+            data: vec![],
+        };
+        assert_eq!(
+            event.indexed_attributes().collect::<Vec<_>>(),
+            vec![("source", "alice")]
+        );
+
+        let query =
+            EventIndexQuery::new(TRANSFER_EVENT).with("source", "alice");
+        assert!(query.matches(BlockHeight(1), &event));
+        assert!(
+            !query
+                .clone()
+                .with("target", "bob")
+                .matches(BlockHeight(1), &event)
+        );
+        assert!(
+            !EventIndexQuery::new(BURN_EVENT).matches(BlockHeight(1), &event)
+        );
+        let query = EventIndexQuery {
+            min_height: Some(BlockHeight(2)),
+            ..query
+        };
+        assert!(!query.matches(BlockHeight(1), &event));
+        assert!(query.matches(BlockHeight(2), &event));
+        assert_eq!(query.limit(), MAX_EVENT_INDEX_QUERY_LIMIT as usize);
+    }
+}
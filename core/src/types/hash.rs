@@ -24,6 +24,8 @@ pub enum Error {
     ConversionFailed(std::array::TryFromSliceError),
     #[error("Failed to convert string into a hash: {0}")]
     FromStringError(data_encoding::DecodeError),
+    #[error("Unknown hash algorithm: {0}")]
+    UnknownAlgorithm(u64),
 }
 
 /// Result for functions that may fail
@@ -154,6 +156,58 @@ impl From<Hash> for crate::tendermint::Hash {
     }
 }
 
+/// The hash functions that can be used by the wasm code via the host
+/// environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// SHA-256
+    Sha256,
+    /// Keccak-256, as used by Ethereum
+    Keccak256,
+    /// BLAKE2b with a 256 bits output
+    Blake2b256,
+}
+
+impl HashAlgorithm {
+    /// Hash the data
+    pub fn hash(&self, data: impl AsRef<[u8]>) -> Hash {
+        match self {
+            Self::Sha256 => Hash::sha256(data),
+            Self::Keccak256 => Hash(crate::types::keccak::keccak_hash(data).0),
+            Self::Blake2b256 => {
+                let digest = blake2b_simd::Params::new()
+                    .hash_length(HASH_LENGTH)
+                    .hash(data.as_ref());
+                Hash::try_from(digest.as_bytes())
+                    .expect("The digest length should be the hash length")
+            }
+        }
+    }
+}
+
+impl From<HashAlgorithm> for u64 {
+    fn from(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => 0,
+            HashAlgorithm::Keccak256 => 1,
+            HashAlgorithm::Blake2b256 => 2,
+        }
+    }
+}
+
+impl TryFrom<u64> for HashAlgorithm {
+    type Error = self::Error;
+
+    fn try_from(value: u64) -> HashResult<Self> {
+        match value {
+            0 => Ok(Self::Sha256),
+            1 => Ok(Self::Keccak256),
+            2 => Ok(Self::Blake2b256),
+            _ => Err(Error::UnknownAlgorithm(value)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -172,4 +226,28 @@ mod tests {
             let _: Hash = hex_hash.try_into().unwrap();
         }
     }
+
+    #[test]
+    fn test_hash_algorithms() {
+        let expected = [
+            (
+                HashAlgorithm::Sha256,
+                "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855",
+            ),
+            (
+                HashAlgorithm::Keccak256,
+                "C5D2460186F7233C927E7DB2DCC703C0E500B653CA82273B7BFAD8045D85A470",
+            ),
+            (
+                HashAlgorithm::Blake2b256,
+                "0E5751C026E543B2E8AB2EB06099DAA1D1E5DF47778F7787FAAB45CDF12FE3A8",
+            ),
+        ];
+        for (algorithm, hash) in expected {
+            assert_eq!(algorithm.hash(b"").to_string(), hash);
+            let id = u64::from(algorithm);
+            assert_eq!(HashAlgorithm::try_from(id).unwrap(), algorithm);
+        }
+        assert!(HashAlgorithm::try_from(3).is_err());
+    }
 }
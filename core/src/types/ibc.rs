@@ -4,6 +4,10 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use masp_primitives::transaction::Transaction;
+
+use crate::types::storage::Epoch;
+use crate::types::token::{Amount, Transfer};
 
 /// Wrapped IbcEvent
 #[derive(
@@ -45,13 +49,136 @@ impl std::fmt::Display for IbcEvent {
     }
 }
 
+/// IBC transfer with a shielded part, used to send tokens from the MASP or
+/// to receive them into it
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct IbcShieldedTransfer {
+    /// The transparent transfer which the MASP transaction is bound to
+    pub transfer: Transfer,
+    /// The MASP transaction
+    pub masp_tx: Transaction,
+}
+
+/// The limits of the net flow of a token through an IBC channel, set by
+/// governance
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct RateLimit {
+    /// The maximum net amount that can be received in a period
+    pub max_inflow: Amount,
+    /// The maximum net amount that can be sent in a period
+    pub max_outflow: Amount,
+    /// The length of a period in epochs
+    pub period: u64,
+}
+
+/// The amounts of a token that went through an IBC channel since the start
+/// of the current rate limit period
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct RateLimitFlow {
+    /// The epoch when the period started
+    pub period_start: Epoch,
+    /// The received amount in the period
+    pub inflow: Amount,
+    /// The sent amount in the period
+    pub outflow: Amount,
+}
+
+impl RateLimitFlow {
+    /// Get the flow of the period including the given epoch. The flow is
+    /// reset when the recorded period has already ended.
+    pub fn at(&self, epoch: Epoch, period: u64) -> Self {
+        if epoch.0 < self.period_start.0.saturating_add(period) {
+            *self
+        } else {
+            Self {
+                period_start: epoch,
+                ..Self::default()
+            }
+        }
+    }
+
+    /// The received amount minus the sent amount, if positive
+    pub fn net_inflow(&self) -> Amount {
+        self.inflow.checked_sub(self.outflow).unwrap_or_default()
+    }
+
+    /// The sent amount minus the received amount, if positive
+    pub fn net_outflow(&self) -> Amount {
+        self.outflow.checked_sub(self.inflow).unwrap_or_default()
+    }
+}
+
+/// The rate limit of a token through an IBC channel with the remaining
+/// amounts in the current period
+#[derive(Debug, Clone, Copy, BorshSerialize, BorshDeserialize)]
+pub struct RateLimitQuota {
+    /// The rate limit set by governance
+    pub limit: RateLimit,
+    /// The flow in the current period
+    pub flow: RateLimitFlow,
+    /// The amount which can still be received in the current period
+    pub remaining_inflow: Amount,
+    /// The amount which can still be sent in the current period
+    pub remaining_outflow: Amount,
+}
+
+impl RateLimitQuota {
+    /// Get the quota of the current period from the rate limit and the last
+    /// recorded flow
+    pub fn new(limit: RateLimit, flow: RateLimitFlow, epoch: Epoch) -> Self {
+        let flow = flow.at(epoch, limit.period);
+        // An inflow can also cancel out the current net outflow, and vice
+        // versa
+        let remaining_inflow = limit
+            .max_inflow
+            .checked_sub(flow.net_inflow())
+            .unwrap_or_default()
+            .checked_add(flow.net_outflow())
+            .unwrap_or_else(Amount::max);
+        let remaining_outflow = limit
+            .max_outflow
+            .checked_sub(flow.net_outflow())
+            .unwrap_or_default()
+            .checked_add(flow.net_inflow())
+            .unwrap_or_else(Amount::max);
+        Self {
+            limit,
+            flow,
+            remaining_inflow,
+            remaining_outflow,
+        }
+    }
+}
+
 #[cfg(any(feature = "abciplus", feature = "abcipp"))]
 mod ibc_rs_conversion {
     use std::collections::HashMap;
 
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use data_encoding::HEXUPPER;
     use thiserror::Error;
 
-    use super::IbcEvent;
+    use super::{IbcEvent, IbcShieldedTransfer};
+    use crate::ibc::applications::transfer::Memo;
     use crate::ibc::core::events::{
         Error as IbcEventError, IbcEvent as RawIbcEvent,
     };
@@ -62,6 +189,10 @@ mod ibc_rs_conversion {
     pub enum Error {
         #[error("IBC event error: {0}")]
         IbcEvent(IbcEventError),
+        #[error("IBC transfer memo HEX decoding error: {0}")]
+        DecodingHex(data_encoding::DecodeError),
+        #[error("IBC transfer memo decoding error: {0}")]
+        DecodingShieldedTransfer(std::io::Error),
     }
 
     /// Conversion functions result
@@ -84,7 +215,82 @@ mod ibc_rs_conversion {
             })
         }
     }
+
+    impl From<IbcShieldedTransfer> for Memo {
+        fn from(shielded: IbcShieldedTransfer) -> Self {
+            let bytes = shielded.try_to_vec().expect("Encoding shouldn't fail");
+            HEXUPPER.encode(&bytes).into()
+        }
+    }
+
+    impl TryFrom<Memo> for IbcShieldedTransfer {
+        type Error = Error;
+
+        fn try_from(memo: Memo) -> Result<Self> {
+            let bytes = HEXUPPER
+                .decode(memo.as_ref().as_bytes())
+                .map_err(Error::DecodingHex)?;
+            Self::try_from_slice(&bytes)
+                .map_err(Error::DecodingShieldedTransfer)
+        }
+    }
 }
 
 #[cfg(any(feature = "abciplus", feature = "abcipp"))]
 pub use ibc_rs_conversion::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_flow_reset() {
+        let flow = RateLimitFlow {
+            period_start: Epoch(2),
+            inflow: Amount::from(10),
+            outflow: Amount::from(4),
+        };
+        assert_eq!(flow.at(Epoch(4), 3), flow);
+        assert_eq!(
+            flow.at(Epoch(5), 3),
+            RateLimitFlow {
+                period_start: Epoch(5),
+                ..RateLimitFlow::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_quota() {
+        let limit = RateLimit {
+            max_inflow: Amount::from(100),
+            max_outflow: Amount::from(50),
+            period: 1,
+        };
+        let flow = RateLimitFlow {
+            period_start: Epoch(1),
+            inflow: Amount::from(10),
+            outflow: Amount::from(40),
+        };
+        assert_eq!(flow.net_inflow(), Amount::zero());
+        assert_eq!(flow.net_outflow(), Amount::from(30));
+
+        let quota = RateLimitQuota::new(limit, flow, Epoch(1));
+        assert_eq!(quota.remaining_inflow, Amount::from(130));
+        assert_eq!(quota.remaining_outflow, Amount::from(20));
+
+        // the flow of the previous period isn't counted
+        let quota = RateLimitQuota::new(limit, flow, Epoch(2));
+        assert_eq!(quota.remaining_inflow, Amount::from(100));
+        assert_eq!(quota.remaining_outflow, Amount::from(50));
+
+        // nothing remains once the limit is exceeded
+        let flow = RateLimitFlow {
+            outflow: Amount::from(61),
+            ..flow
+        };
+        let quota = RateLimitQuota::new(limit, flow, Epoch(1));
+        assert_eq!(quota.remaining_inflow, Amount::from(151));
+        assert_eq!(quota.remaining_outflow, Amount::zero());
+    }
+}
@@ -367,4 +367,20 @@ impl super::SigScheme for SigScheme {
             _ => Err(VerifySigError::MismatchedScheme),
         }
     }
+
+    fn verify_raw_signature(
+        pk: &Self::PublicKey,
+        message: &[u8],
+        sig: &Self::Signature,
+    ) -> Result<(), VerifySigError> {
+        match (pk, sig) {
+            (PublicKey::Ed25519(pk), Signature::Ed25519(sig)) => {
+                ed25519::SigScheme::verify_raw_signature(pk, message, sig)
+            }
+            (PublicKey::Secp256k1(pk), Signature::Secp256k1(sig)) => {
+                secp256k1::SigScheme::verify_raw_signature(pk, message, sig)
+            }
+            _ => Err(VerifySigError::MismatchedScheme),
+        }
+    }
 }
@@ -362,4 +362,13 @@ impl super::SigScheme for SigScheme {
         pk.0.verify(&sig.0, &data.signable_hash::<H>())
             .map_err(|err| VerifySigError::SigVerifyError(err.to_string()))
     }
+
+    fn verify_raw_signature(
+        pk: &Self::PublicKey,
+        message: &[u8],
+        sig: &Self::Signature,
+    ) -> Result<(), VerifySigError> {
+        pk.0.verify(&sig.0, message)
+            .map_err(|err| VerifySigError::SigVerifyError(err.to_string()))
+    }
 }
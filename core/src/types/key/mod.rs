@@ -337,6 +337,16 @@ pub trait SigScheme: Eq + Ord + Debug + Serialize + Default {
     where
         H: 'static + StorageHasher;
 
+    /// Check that the public key matches the signature on the given raw
+    /// message, as signed outside of Namada. Ed25519 signatures are over the
+    /// message itself, while Secp256k1 signatures are over a 32 bytes digest,
+    /// which the message must be.
+    fn verify_raw_signature(
+        pk: &Self::PublicKey,
+        message: &[u8],
+        sig: &Self::Signature,
+    ) -> Result<(), VerifySigError>;
+
     /// Sign the data with a key, using a SHA256 hasher.
     #[inline]
     fn sign(
@@ -670,6 +680,8 @@ sigscheme_test! {secp256k1_test, secp256k1::SigScheme}
 
 #[cfg(test)]
 mod more_tests {
+    use data_encoding::HEXLOWER;
+
     use super::*;
 
     #[test]
@@ -705,4 +717,77 @@ mod more_tests {
             core::slice::from_raw_parts(ptr, len)
         });
     }
+
+    /// Test the verification of raw signatures against the Ed25519 test vector
+    /// 2 of RFC 8032
+    #[test]
+    fn verify_raw_signature_ed25519() {
+        let pk_bytes: [u8; 32] = HEXLOWER
+            .decode(
+                b"3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            )
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let sig_bytes: [u8; 64] = HEXLOWER
+            .decode(
+                b"92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                  085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            )
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let pk = common::PublicKey::Ed25519(ed25519::PublicKey(
+            ed25519_consensus::VerificationKey::try_from(pk_bytes).unwrap(),
+        ));
+        let sig = common::Signature::Ed25519(ed25519::Signature(
+            ed25519_consensus::Signature::from(sig_bytes),
+        ));
+        let message = [0x72];
+        assert!(
+            common::SigScheme::verify_raw_signature(&pk, &message, &sig)
+                .is_ok()
+        );
+        assert!(
+            common::SigScheme::verify_raw_signature(&pk, &[0x73], &sig)
+                .is_err()
+        );
+    }
+
+    /// Test the verification of raw signatures against the RFC 6979 test
+    /// vector of the secret key 1 signing "Satoshi Nakamoto" with SHA-256
+    #[test]
+    fn verify_raw_signature_secp256k1() {
+        let pk_bytes = HEXLOWER
+            .decode(
+                b"0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )
+            .unwrap();
+        let sig_bytes = HEXLOWER
+            .decode(
+                b"934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+                  2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+            )
+            .unwrap();
+        let pk = common::PublicKey::Secp256k1(secp256k1::PublicKey(
+            libsecp256k1::PublicKey::parse_slice(&pk_bytes, None).unwrap(),
+        ));
+        let sig = common::Signature::Secp256k1(secp256k1::Signature(
+            libsecp256k1::Signature::parse_standard_slice(&sig_bytes).unwrap(),
+            libsecp256k1::RecoveryId::parse(0).unwrap(),
+        ));
+        let digest = Sha256::digest(b"Satoshi Nakamoto");
+        assert!(
+            common::SigScheme::verify_raw_signature(&pk, &digest, &sig).is_ok()
+        );
+        // The message must be the digest
+        assert!(
+            common::SigScheme::verify_raw_signature(
+                &pk,
+                b"Satoshi Nakamoto",
+                &sig
+            )
+            .is_err()
+        );
+    }
 }
@@ -615,6 +615,29 @@ impl super::SigScheme for SigScheme {
             )))
         }
     }
+
+    fn verify_raw_signature(
+        pk: &Self::PublicKey,
+        message: &[u8],
+        sig: &Self::Signature,
+    ) -> Result<(), VerifySigError> {
+        let digest = <&[u8; 32]>::try_from(message).map_err(|_| {
+            VerifySigError::SigVerifyError(format!(
+                "A secp256k1 signature must be over a 32 bytes digest, got {} \
+                 bytes",
+                message.len()
+            ))
+        })?;
+        let message = libsecp256k1::Message::parse(digest);
+        if libsecp256k1::verify(&message, &sig.0, &pk.0) {
+            Ok(())
+        } else {
+            Err(VerifySigError::SigVerifyError(format!(
+                "Error verifying secp256k1 signature: {}",
+                libsecp256k1::Error::InvalidSignature
+            )))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -8,6 +8,7 @@ pub mod eth_abi;
 pub mod eth_bridge_pool;
 pub mod ethereum_events;
 pub mod ethereum_structs;
+pub mod event;
 pub mod hash;
 pub mod ibc;
 pub mod internal;
@@ -596,6 +596,15 @@ impl Key {
         Key { segments }
     }
 
+    /// Returns the prefix of the keys of the wasm code hashes, keyed by their
+    /// code path
+    pub fn wasm_hash_prefix() -> Self {
+        let mut segments =
+            Self::from(WASM_KEY_PREFIX.to_owned().to_db_key()).segments;
+        segments.push(DbKeySeg::StringSeg(WASM_HASH_PREFIX.to_owned()));
+        Key { segments }
+    }
+
     /// Returns a key of the validity predicate of the given address
     /// Only this function can push "?" segment for validity predicate
     pub fn validity_predicate(addr: &Address) -> Self {
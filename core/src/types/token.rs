@@ -806,6 +806,10 @@ pub const DENOM_STORAGE_KEY: &str = "denomination";
 pub const MINTER_STORAGE_KEY: &str = "minter";
 /// Key segment for minted balance
 pub const MINTED_STORAGE_KEY: &str = "minted";
+/// Key segment for the max supply of a user-defined token
+pub const MAX_SUPPLY_STORAGE_KEY: &str = "max_supply";
+/// Key segment for the metadata of a user-defined token
+pub const METADATA_STORAGE_KEY: &str = "metadata";
 /// Key segment for head shielded transaction pointer keys
 pub const HEAD_TX_KEY: &str = "head-tx";
 /// Key segment prefix for shielded transaction key
@@ -840,6 +844,24 @@ pub fn minter_key(token_addr: &Address) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Obtain a storage key for the max supply of a user-defined token.
+pub fn max_supply_key(token_addr: &Address) -> Key {
+    Key::from(Address::Internal(InternalAddress::Multitoken).to_db_key())
+        .push(&token_addr.to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&MAX_SUPPLY_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Obtain a storage key for the metadata of a user-defined token.
+pub fn metadata_key(token_addr: &Address) -> Key {
+    Key::from(Address::Internal(InternalAddress::Multitoken).to_db_key())
+        .push(&token_addr.to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&METADATA_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Obtain a storage key for the minted multitoken balance.
 pub fn minted_balance_key(token_addr: &Address) -> Key {
     balance_prefix(token_addr)
@@ -904,6 +926,19 @@ pub fn is_denom_key(token_addr: &Address, key: &Key) -> bool {
         ] if key == DENOM_STORAGE_KEY && addr == token_addr)
 }
 
+/// Check if the given storage key is a denomination key of a unspecified
+/// token that's not under a sub-key. If it is, returns the token.
+pub fn is_any_denom_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(token), DbKeySeg::StringSeg(denom)]
+            if denom == DENOM_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is a masp key
 pub fn is_masp_key(key: &Key) -> bool {
     matches!(&key.segments[..],
@@ -931,6 +966,40 @@ pub fn is_any_minter_key(key: &Key) -> Option<&Address> {
     }
 }
 
+/// Check if the given storage key is for the max supply of a unspecified
+/// token. If it is, returns the token.
+pub fn is_any_max_supply_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(max_supply),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && max_supply == MAX_SUPPLY_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
+/// Check if the given storage key is for the metadata of a unspecified token.
+/// If it is, returns the token.
+pub fn is_any_metadata_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(metadata),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && metadata == METADATA_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is for total supply of a unspecified token.
 /// If it is, returns the token.
 pub fn is_any_minted_balance_key(key: &Key) -> Option<&Address> {
@@ -979,6 +1048,90 @@ pub struct Transfer {
     pub shielded: Option<Hash>,
 }
 
+/// The metadata of a user-defined token
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TokenMetadata {
+    /// The name of the token
+    pub name: String,
+    /// The ticker symbol of the token
+    pub symbol: String,
+}
+
+impl TokenMetadata {
+    /// The max length of a token name
+    pub const MAX_NAME_LEN: usize = 64;
+    /// The max length of a token symbol
+    pub const MAX_SYMBOL_LEN: usize = 12;
+
+    /// Check that the name is not empty and that the symbol is made of
+    /// alphanumeric ASCII characters, and that both fit in their max length
+    pub fn is_valid(&self) -> bool {
+        !self.name.trim().is_empty()
+            && self.name.len() <= Self::MAX_NAME_LEN
+            && !self.symbol.is_empty()
+            && self.symbol.len() <= Self::MAX_SYMBOL_LEN
+            && self.symbol.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+}
+
+/// A tx data type to register a user-defined token, or to update the settings
+/// of a registered one. The token is an established account, which must
+/// authorize the tx.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct InitToken {
+    /// The address of the token
+    pub token: Address,
+    /// The only account allowed to mint the token
+    pub minter: Address,
+    /// The max total supply of the token, unlimited if `None`
+    pub max_supply: Option<Amount>,
+    /// The denomination of the token. It cannot be changed once the token is
+    /// registered.
+    pub denom: Denomination,
+    /// The metadata of the token
+    pub metadata: TokenMetadata,
+}
+
+/// A tx data type to mint a user-defined token. The minter of the token must
+/// authorize the tx.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct MintToken {
+    /// The address of the token
+    pub token: Address,
+    /// The account that receives the minted tokens
+    pub target: Address,
+    /// The amount to mint
+    pub amount: DenominatedAmount,
+}
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum TransferError {
@@ -5,11 +5,13 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::ledger::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, TreasuryProposal, UpgradeProposal,
 };
 use crate::ledger::governance::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType,
+    AddRemove, PGFAction, PGFStream, PGFTarget, ProposalType,
 };
+use crate::ledger::governance::storage::upgrade::UpgradePlan;
 use crate::ledger::governance::storage::vote::StorageProposalVote;
 use crate::types::address::Address;
 use crate::types::hash::Hash;
@@ -47,6 +49,8 @@ pub struct InitProposalData {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// Whether the proposal is expedited, with a shorter voting period
+    pub is_expedited: bool,
 }
 
 impl InitProposalData {
@@ -92,6 +96,7 @@ impl TryFrom<DefaultProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            is_expedited: value.proposal.is_expedited,
         })
     }
 }
@@ -111,6 +116,7 @@ impl TryFrom<PgfStewardProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            is_expedited: value.proposal.is_expedited,
         })
     }
 }
@@ -125,15 +131,13 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             .iter()
             .cloned()
             .map(|funding| {
-                let target = PGFTarget {
+                PGFAction::Continuous(PGFStream {
                     target: funding.address,
                     amount: funding.amount,
-                };
-                if funding.amount.is_zero() {
-                    PGFAction::Continuous(AddRemove::Remove(target))
-                } else {
-                    PGFAction::Continuous(AddRemove::Add(target))
-                }
+                    start_epoch: funding.start_epoch,
+                    end_epoch: funding.end_epoch,
+                    budget: funding.budget,
+                })
             })
             .collect::<Vec<PGFAction>>();
 
@@ -151,7 +155,16 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             })
             .collect::<Vec<PGFAction>>();
 
-        let extra_data = [continous_fundings, retro_fundings].concat();
+        let cancelled_fundings = value
+            .data
+            .cancel
+            .iter()
+            .cloned()
+            .map(PGFAction::Cancel)
+            .collect::<Vec<PGFAction>>();
+
+        let extra_data =
+            [continous_fundings, retro_fundings, cancelled_fundings].concat();
 
         Ok(InitProposalData {
             id: value.proposal.id,
@@ -161,6 +174,61 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            is_expedited: value.proposal.is_expedited,
+        })
+    }
+}
+
+impl TryFrom<ParameterChangeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: ParameterChangeProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::ParameterChange(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+            is_expedited: value.proposal.is_expedited,
+        })
+    }
+}
+
+impl TryFrom<UpgradeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: UpgradeProposal) -> Result<Self, Self::Error> {
+        let plan = UpgradePlan::try_from(value.data)
+            .map_err(|_| ProposalError::InvalidProposalData)?;
+
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::Upgrade(plan),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+            is_expedited: value.proposal.is_expedited,
+        })
+    }
+}
+
+impl TryFrom<TreasuryProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: TreasuryProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::Treasury(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+            is_expedited: value.proposal.is_expedited,
         })
     }
 }
@@ -32,6 +32,7 @@ pub use wrapper::*;
 
 use crate::ledger::gas::{Gas, VpsGas};
 use crate::types::address::Address;
+use crate::types::event::TxEvent;
 use crate::types::hash::Hash;
 use crate::types::ibc::IbcEvent;
 use crate::types::storage;
@@ -58,6 +59,8 @@ pub struct TxResult {
     pub initialized_accounts: Vec<Address>,
     /// IBC events emitted by the transaction
     pub ibc_events: BTreeSet<IbcEvent>,
+    /// Events emitted by the transaction
+    pub events: Vec<TxEvent>,
 }
 
 impl TxResult {
@@ -9,6 +9,9 @@ use namada_core::hints;
 use namada_core::ledger::eth_bridge::storage::bridge_pool::{
     get_nonce_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
 };
+use namada_core::ledger::eth_bridge::storage::flow_limits::{
+    self, FlowDirection,
+};
 use namada_core::ledger::eth_bridge::storage::{
     self as bridge_storage, wrapped_erc20s,
 };
@@ -228,7 +231,7 @@ where
 ///
 /// Mints `amount` of a wrapped ERC20 `asset` for `receiver`.
 /// If the given asset is not whitelisted or has exceeded the
-/// token caps, mint NUTs, too.
+/// token caps or its inbound flow limit, mint NUTs, too.
 fn mint_eth_assets<D, H>(
     wl_storage: &mut WlStorage<D, H>,
     asset: &EthAddress,
@@ -294,6 +297,22 @@ where
         _ = changed_keys.insert(supply_key);
     }
 
+    if asset_count.should_mint_erc20s() {
+        flow_limits::record_flow(
+            wl_storage,
+            asset,
+            FlowDirection::Inbound,
+            asset_count.erc20_amount,
+        )?;
+        _ = changed_keys.insert(
+            flow_limits::Key {
+                asset: *asset,
+                suffix: flow_limits::KeyType::Flow(FlowDirection::Inbound),
+            }
+            .into(),
+        );
+    }
+
     Ok((asset_count, changed_keys))
 }
 
@@ -863,6 +882,11 @@ mod tests {
         /// If the token is not whitelisted, this value
         /// is not set.
         dai_token_cap: Option<token::Amount>,
+        /// The inbound flow limit of DAI.
+        ///
+        /// If the limit is not configured, this value
+        /// is not set.
+        dai_inbound_limit: Option<token::Amount>,
         /// The transferred amount of DAI.
         transferred_amount: token::Amount,
     }
@@ -871,12 +895,16 @@ mod tests {
         /// Execute a test with the given parameters.
         fn run_test(self) {
             let dai_token_cap = self.dai_token_cap.unwrap_or_default();
+            let dai_mint_limit =
+                self.dai_inbound_limit.map_or(dai_token_cap, |limit| {
+                    std::cmp::min(limit, dai_token_cap)
+                });
 
             let (erc20_amount, nut_amount) =
-                if dai_token_cap > self.transferred_amount {
+                if dai_mint_limit > self.transferred_amount {
                     (self.transferred_amount, token::Amount::zero())
                 } else {
-                    (dai_token_cap, self.transferred_amount - dai_token_cap)
+                    (dai_mint_limit, self.transferred_amount - dai_mint_limit)
                 };
             assert_eq!(self.transferred_amount, nut_amount + erc20_amount);
 
@@ -894,6 +922,14 @@ mod tests {
                     )],
                 );
             }
+            if let Some(limit) = self.dai_inbound_limit {
+                let key = flow_limits::Key {
+                    asset: DAI_ERC20_ETH_ADDRESS,
+                    suffix: flow_limits::KeyType::Limit(FlowDirection::Inbound),
+                }
+                .into();
+                wl_storage.write(&key, limit).expect("Test failed");
+            }
 
             let receiver = address::testing::established_address_1();
             let transfers = vec![TransferToNamada {
@@ -931,6 +967,14 @@ mod tests {
                     }
                 }
             }
+
+            let inbound_flow = flow_limits::read_flow(
+                &wl_storage,
+                &DAI_ERC20_ETH_ADDRESS,
+                FlowDirection::Inbound,
+            )
+            .expect("Test failed");
+            assert_eq!(inbound_flow.amount, erc20_amount);
         }
     }
 
@@ -939,6 +983,7 @@ mod tests {
     fn test_minting_dai_when_not_whitelisted() {
         TestMintDai {
             dai_token_cap: None,
+            dai_inbound_limit: None,
             transferred_amount: Amount::from(100),
         }
         .run_test();
@@ -950,6 +995,7 @@ mod tests {
     fn test_minting_dai_on_cap_overrun() {
         TestMintDai {
             dai_token_cap: Some(Amount::from(80)),
+            dai_inbound_limit: None,
             transferred_amount: Amount::from(100),
         }
         .run_test();
@@ -961,6 +1007,19 @@ mod tests {
     fn test_minting_dai_wrapped() {
         TestMintDai {
             dai_token_cap: Some(Amount::max()),
+            dai_inbound_limit: None,
+            transferred_amount: Amount::from(100),
+        }
+        .run_test();
+    }
+
+    /// Test that overrunning the inbound flow limits results in
+    /// minting DAI NUTs, along with wDAI.
+    #[test]
+    fn test_minting_dai_on_inbound_flow_limit_overrun() {
+        TestMintDai {
+            dai_token_cap: Some(Amount::max()),
+            dai_inbound_limit: Some(Amount::from(60)),
             transferred_amount: Amount::from(100),
         }
         .run_test();
@@ -1,5 +1,8 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::hints;
+use namada_core::ledger::eth_bridge::storage::flow_limits::{
+    self, FlowDirection,
+};
 use namada_core::ledger::eth_bridge::storage::{
     active_key, bridge_pool, whitelist,
 };
@@ -475,6 +478,20 @@ where
             .expect("Reading from storage should not fail")
     }
 
+    /// Fetch the amount of the asset associated with the given
+    /// [`EthAddress`] that may still cross the Ethereum bridge in
+    /// the given direction, during the current epoch.
+    ///
+    /// If no flow limit has been configured, return [`None`].
+    pub fn get_remaining_flow(
+        self,
+        token: &EthAddress,
+        direction: FlowDirection,
+    ) -> Option<token::Amount> {
+        flow_limits::remaining_flow(self.wl_storage, token, direction)
+            .expect("Reading from storage should not fail")
+    }
+
     /// Return the number of ERC20 and NUT assets to be minted,
     /// after receiving a "transfer to Namada" Ethereum event.
     ///
@@ -483,6 +500,8 @@ where
     /// 1. `token` is not whitelisted.
     /// 2. `token` has exceeded the configured token caps,
     ///    after minting `amount_to_mint`.
+    /// 3. `token` has exceeded its inbound flow limit for the
+    ///    current epoch, after minting `amount_to_mint`.
     pub fn get_eth_assets_to_mint(
         self,
         token: &EthAddress,
@@ -506,19 +525,20 @@ where
             );
         }
 
-        if amount_to_mint + supply > cap {
-            let erc20_amount = cap - supply;
-            let nut_amount = amount_to_mint - erc20_amount;
-
-            return EthAssetMint {
-                nut_amount,
-                erc20_amount,
-            };
-        }
+        let erc20_amount = if amount_to_mint + supply > cap {
+            cap - supply
+        } else {
+            amount_to_mint
+        };
+        let erc20_amount = self
+            .get_remaining_flow(token, FlowDirection::Inbound)
+            .map_or(erc20_amount, |remaining_flow| {
+                std::cmp::min(erc20_amount, remaining_flow)
+            });
 
         EthAssetMint {
-            erc20_amount: amount_to_mint,
-            nut_amount: token::Amount::zero(),
+            nut_amount: amount_to_mint - erc20_amount,
+            erc20_amount,
         }
     }
 
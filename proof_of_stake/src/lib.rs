@@ -40,6 +40,10 @@ use namada_core::ledger::storage_api::{
 };
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::dec::Dec;
+use namada_core::types::event::{
+    TxEvent, BLOCK_REWARDS_EVENT, BOND_EVENT, JAIL_EVENT, SLASH_EVENT,
+    SLASH_PROCESSED_EVENT, UNBOND_EVENT, UNJAIL_EVENT,
+};
 use namada_core::types::key::{
     common, tm_consensus_key_raw_hash, PublicKeyTmRawHash,
 };
@@ -1040,6 +1044,14 @@ where
         &ADDRESS,
     )?;
 
+    storage.emit_event(
+        TxEvent::new(BOND_EVENT)
+            .with("source", source.clone())
+            .with("validator", validator.clone())
+            .with("amount", token::Amount::from_change(amount))
+            .with("start_epoch", pipeline_epoch.0),
+    )?;
+
     Ok(())
 }
 
@@ -2055,6 +2067,14 @@ where
         params.pipeline_len,
     )?;
 
+    storage.emit_event(
+        TxEvent::new(UNBOND_EVENT)
+            .with("source", source.clone())
+            .with("validator", validator.clone())
+            .with("amount", token::Amount::from_change(amount))
+            .with("withdrawable_epoch", withdrawable_epoch.0),
+    )?;
+
     Ok(())
 }
 
@@ -3382,6 +3402,14 @@ where
         rewards_accumulator_handle().insert(storage, address, value)?;
     }
 
+    storage.emit_event(
+        TxEvent::new(BLOCK_REWARDS_EVENT)
+            .with("epoch", epoch.0)
+            .with("proposer", proposer_address.clone())
+            .with("signing_stake", total_signing_stake)
+            .with("total_stake", total_consensus_stake),
+    )?;
+
     Ok(())
 }
 
@@ -3598,6 +3626,20 @@ where
         )?;
     }
 
+    storage.emit_event(
+        TxEvent::new(SLASH_EVENT)
+            .with("validator", validator.clone())
+            .with("slash_type", slash_type.to_string())
+            .with("infraction_epoch", evidence_epoch.0)
+            .with("height", evidence_block_height)
+            .with("processing_epoch", processing_epoch.0),
+    )?;
+    storage.emit_event(
+        TxEvent::new(JAIL_EVENT)
+            .with("validator", validator.clone())
+            .with("epoch", validator_set_update_epoch.0),
+    )?;
+
     // No other actions are performed here until the epoch in which the slash is
     // processed.
 
@@ -3704,6 +3746,12 @@ where
             // Add this slash to the list of validator's slashes in storage
             validator_slashes_handle(&validator)
                 .push(storage, enqueued_slash.clone())?;
+            storage.emit_event(
+                TxEvent::new(SLASH_PROCESSED_EVENT)
+                    .with("validator", validator.clone())
+                    .with("infraction_epoch", enqueued_slash.epoch.0)
+                    .with("rate", enqueued_slash.rate.to_string()),
+            )?;
 
             total_rate += enqueued_slash.rate;
         }
@@ -3974,7 +4022,12 @@ where
         current_epoch,
         params.pipeline_len,
     )?;
-    Ok(())
+
+    storage.emit_event(
+        TxEvent::new(UNJAIL_EVENT)
+            .with("validator", validator.clone())
+            .with("epoch", pipeline_epoch.0),
+    )
 }
 
 /// Check if a validator is frozen. A validator is frozen until after all of its
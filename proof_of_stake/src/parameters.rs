@@ -1,6 +1,7 @@
 //! Proof-of-Stake system parameters
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::ledger::governance::storage::parameter_change::PosParamChange;
 use namada_core::types::dec::Dec;
 use namada_core::types::storage::Epoch;
 use namada_core::types::token;
@@ -173,6 +174,54 @@ impl PosParams {
         let end = infraction_epoch + self.cubic_slashing_window_length;
         (start, end)
     }
+
+    /// Apply a parameter change voted by governance
+    pub fn apply_change(&mut self, change: &PosParamChange) {
+        match change {
+            PosParamChange::BlockProposerReward(value) => {
+                self.block_proposer_reward = *value
+            }
+            PosParamChange::BlockVoteReward(value) => {
+                self.block_vote_reward = *value
+            }
+            PosParamChange::MaxInflationRate(value) => {
+                self.max_inflation_rate = *value
+            }
+            PosParamChange::TargetStakedRatio(value) => {
+                self.target_staked_ratio = *value
+            }
+            PosParamChange::DuplicateVoteMinSlashRate(value) => {
+                self.duplicate_vote_min_slash_rate = *value
+            }
+            PosParamChange::LightClientAttackMinSlashRate(value) => {
+                self.light_client_attack_min_slash_rate = *value
+            }
+        }
+    }
+
+    /// Get the current value of the parameter targeted by a change
+    pub fn current_value(&self, change: &PosParamChange) -> String {
+        match change {
+            PosParamChange::BlockProposerReward(_) => {
+                self.block_proposer_reward.to_string()
+            }
+            PosParamChange::BlockVoteReward(_) => {
+                self.block_vote_reward.to_string()
+            }
+            PosParamChange::MaxInflationRate(_) => {
+                self.max_inflation_rate.to_string()
+            }
+            PosParamChange::TargetStakedRatio(_) => {
+                self.target_staked_ratio.to_string()
+            }
+            PosParamChange::DuplicateVoteMinSlashRate(_) => {
+                self.duplicate_vote_min_slash_rate.to_string()
+            }
+            PosParamChange::LightClientAttackMinSlashRate(_) => {
+                self.light_client_attack_min_slash_rate.to_string()
+            }
+        }
+    }
 }
 
 #[cfg(test)]
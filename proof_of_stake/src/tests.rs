@@ -14,6 +14,7 @@ use namada_core::types::address::testing::{
 };
 use namada_core::types::address::{Address, EstablishedAddressGen};
 use namada_core::types::dec::Dec;
+use namada_core::types::event::BOND_EVENT;
 use namada_core::types::key::common::{PublicKey, SecretKey};
 use namada_core::types::key::testing::{
     arb_common_keypair, common_sk_from_simple_seed,
@@ -301,6 +302,17 @@ fn test_bonds_aux(params: PosParams, validators: Vec<GenesisValidator>) {
     )
     .unwrap();
 
+    // Check the emitted bond event
+    let events = s.write_log.take_events();
+    let bond_event = events.last().unwrap();
+    assert_eq!(bond_event.event_type, BOND_EVENT);
+    assert_eq!(
+        bond_event.attributes["validator"],
+        validator.address.clone().into()
+    );
+    assert_eq!(bond_event.attributes["amount"], amount_self_bond.into());
+    assert_eq!(bond_event.attributes["start_epoch"], pipeline_epoch.0.into());
+
     // Check the bond delta
     let self_bond = bond_handle(&validator.address, &validator.address);
     let delta = self_bond
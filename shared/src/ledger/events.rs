@@ -13,6 +13,7 @@ use serde_json::Value;
 use crate::ledger::governance::utils::ProposalEvent;
 use crate::sdk::error::{EncodingError, Error, EventError};
 use crate::tendermint_proto::abci::EventAttribute;
+use crate::types::event::{self, IndexedEvent, TxEvent};
 use crate::types::ibc::IbcEvent;
 #[cfg(feature = "ferveo-tpke")]
 use crate::types::transaction::TxType;
@@ -53,6 +54,11 @@ pub enum EventType {
     Proposal,
     /// The pgf payment
     PgfPayment,
+    /// An event emitted by the native logic
+    Tx(String),
+    /// An event emitted by a wasm transaction, reported with the
+    /// [`event::TX_EMITTED_EVENT_PREFIX`]
+    TxEmitted(String),
 }
 
 impl Display for EventType {
@@ -63,6 +69,10 @@ impl Display for EventType {
             EventType::Ibc(t) => write!(f, "{}", t),
             EventType::Proposal => write!(f, "proposal"),
             EventType::PgfPayment => write!(f, "pgf_payment"),
+            EventType::Tx(t) => write!(f, "{}", t),
+            EventType::TxEmitted(t) => {
+                write!(f, "{}{}", event::TX_EMITTED_EVENT_PREFIX, t)
+            }
         }?;
         Ok(())
     }
@@ -83,7 +93,24 @@ impl FromStr for EventType {
             "write_acknowledgement" => {
                 Ok(EventType::Ibc("write_acknowledgement".to_string()))
             }
-            _ => Err(EventError::InvalidEventType),
+            // Native events
+            event::TRANSFER_EVENT
+            | event::MINT_EVENT
+            | event::BURN_EVENT
+            | event::BOND_EVENT
+            | event::UNBOND_EVENT
+            | event::SLASH_EVENT
+            | event::SLASH_PROCESSED_EVENT
+            | event::JAIL_EVENT
+            | event::UNJAIL_EVENT
+            | event::BLOCK_REWARDS_EVENT
+            | event::INIT_PROPOSAL_EVENT
+            | event::VOTE_PROPOSAL_EVENT
+            | event::NEW_EPOCH_EVENT => Ok(EventType::Tx(s.to_string())),
+            _ => s
+                .strip_prefix(event::TX_EMITTED_EVENT_PREFIX)
+                .map(|t| EventType::TxEmitted(t.to_string()))
+                .ok_or(EventError::InvalidEventType),
         }
     }
 }
@@ -171,6 +198,44 @@ impl From<IbcEvent> for Event {
     }
 }
 
+impl From<TxEvent> for Event {
+    fn from(tx_event: TxEvent) -> Self {
+        let event_type = match tx_event.tx_emitted_type() {
+            Some(event_type) => EventType::TxEmitted(event_type.to_string()),
+            None => EventType::Tx(tx_event.event_type.clone()),
+        };
+        Self {
+            attributes: tx_event.string_attributes().collect(),
+            event_type,
+            level: EventLevel::Tx,
+        }
+    }
+}
+
+impl From<&Event> for IndexedEvent {
+    fn from(event: &Event) -> Self {
+        Self {
+            event_type: event.event_type.to_string(),
+            attributes: event
+                .attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            data: event
+                .try_to_vec()
+                .expect("Encoding an event shouldn't fail"),
+        }
+    }
+}
+
+impl TryFrom<&IndexedEvent> for Event {
+    type Error = std::io::Error;
+
+    fn try_from(event: &IndexedEvent) -> Result<Self, Self::Error> {
+        Self::try_from_slice(&event.data)
+    }
+}
+
 impl From<ProposalEvent> for Event {
     fn from(proposal_event: ProposalEvent) -> Self {
         Self {
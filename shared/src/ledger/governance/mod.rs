@@ -7,13 +7,13 @@ use std::collections::BTreeSet;
 use borsh::BorshDeserialize;
 use namada_core::ledger::governance::storage::keys as gov_storage;
 use namada_core::ledger::governance::storage::proposal::{
-    AddRemove, ProposalType,
+    AddRemove, PGFAction, ProposalType, TreasuryTransfer,
 };
 use namada_core::ledger::governance::storage::vote::StorageProposalVote;
 use namada_core::ledger::governance::utils::is_valid_validator_voting_period;
 use namada_core::ledger::storage;
-use namada_core::ledger::storage_api::account;
 use namada_core::ledger::storage_api::governance::is_proposal_accepted;
+use namada_core::ledger::storage_api::{account, pgf};
 use namada_core::ledger::vp_env::VpEnv;
 use namada_core::proto::Tx;
 use namada_proof_of_stake::is_validator;
@@ -37,6 +37,12 @@ pub const ADDRESS: Address = Address::Internal(InternalAddress::Governance);
 /// The maximum number of item in a pgf proposal
 pub const MAX_PGF_ACTIONS: usize = 20;
 
+/// The maximum number of changes in a parameter change proposal
+pub const MAX_PARAMETER_CHANGES: usize = 20;
+
+/// The maximum number of transfers in a treasury spending proposal
+pub const MAX_TREASURY_TRANSFERS: usize = 20;
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum Error {
@@ -109,9 +115,15 @@ where
                 (KeyType::END_EPOCH, Some(proposal_id)) => {
                     self.is_valid_end_epoch(proposal_id)
                 }
+                (KeyType::EXPEDITED, Some(proposal_id)) => {
+                    self.is_valid_expedited(proposal_id)
+                }
                 (KeyType::FUNDS, Some(proposal_id)) => {
                     self.is_valid_funds(proposal_id, &native_token)
                 }
+                (KeyType::LOCKED_DEPOSITS, _) => {
+                    self.is_valid_locked_deposits()
+                }
                 (KeyType::AUTHOR, Some(proposal_id)) => {
                     self.is_valid_author(proposal_id, verifiers)
                 }
@@ -121,6 +133,7 @@ where
                 }
                 (KeyType::PARAMETER, _) => self.is_valid_parameter(tx_data),
                 (KeyType::BALANCE, _) => self.is_valid_balance(&native_token),
+                (KeyType::TREASURY, _) => self.is_valid_treasury_balance(key),
                 (KeyType::UNKNOWN_GOVERNANCE, _) => Ok(false),
                 (KeyType::UNKNOWN, _) => Ok(true),
                 _ => Ok(false),
@@ -166,6 +179,7 @@ where
                 gov_storage::get_voting_start_epoch_key(counter),
                 gov_storage::get_voting_end_epoch_key(counter),
                 gov_storage::get_grace_epoch_key(counter),
+                gov_storage::get_locked_deposits_key(),
             ]);
 
             // Check that expected set is a subset of the actual one
@@ -242,6 +256,11 @@ where
             return Err(Error::InvalidVoteType);
         }
 
+        if !vote.is_valid() {
+            tracing::info!("Invalid split vote {vote}.");
+            return Ok(false);
+        }
+
         // first check if validator, then check if delegator
         let is_validator = self
             .is_validator(
@@ -335,11 +354,42 @@ where
             }
             ProposalType::PGFPayment(payments) => {
                 if payments.len() > MAX_PGF_ACTIONS {
-                    Ok(false)
-                } else {
-                    Ok(true)
+                    return Ok(false);
+                }
+                let are_streams_valid =
+                    payments.iter().all(|payment| match payment {
+                        PGFAction::Continuous(stream) => stream.is_valid(),
+                        _ => true,
+                    });
+                if !are_streams_valid {
+                    return Ok(false);
                 }
+                // only stewards can propose to cancel a funding stream
+                if payments.iter().any(|payment| payment.is_cancel()) {
+                    let author_key = gov_storage::get_author_key(proposal_id);
+                    let author: Address =
+                        self.force_read(&author_key, ReadType::Post)?;
+                    let is_steward = pgf::is_steward(&self.ctx.pre(), &author)?;
+                    return Ok(is_steward);
+                }
+                Ok(true)
+            }
+            ProposalType::ParameterChange(changes) => {
+                let changed_parameters = changes
+                    .iter()
+                    .map(|change| change.name())
+                    .collect::<BTreeSet<String>>();
+
+                Ok(!changes.is_empty()
+                    && changes.len() <= MAX_PARAMETER_CHANGES
+                    && changed_parameters.len() == changes.len()
+                    && changes.iter().all(|change| change.is_valid()))
             }
+            ProposalType::Upgrade(plan) => Ok(plan.is_valid()),
+            ProposalType::Treasury(transfers) => Ok(!transfers.is_empty()
+                && transfers.len() <= MAX_TREASURY_TRANSFERS
+                && transfers.iter().all(|transfer| !transfer.amount.is_zero())
+                && TreasuryTransfer::total_per_token(&transfers).is_some()),
             _ => Ok(true), // default proposal
         }
     }
@@ -436,9 +486,6 @@ where
         let start_epoch_key =
             gov_storage::get_voting_start_epoch_key(proposal_id);
         let end_epoch_key = gov_storage::get_voting_end_epoch_key(proposal_id);
-        let min_period_parameter_key =
-            gov_storage::get_min_proposal_voting_period_key();
-
         let current_epoch = self.ctx.get_block_epoch()?;
 
         let has_pre_start_epoch = self.ctx.has_key_pre(&start_epoch_key)?;
@@ -452,8 +499,7 @@ where
             self.force_read(&start_epoch_key, ReadType::Post)?;
         let end_epoch: Epoch =
             self.force_read(&end_epoch_key, ReadType::Post)?;
-        let min_period: u64 =
-            self.force_read(&min_period_parameter_key, ReadType::Pre)?;
+        let min_period = self.min_voting_period(proposal_id)?;
 
         if end_epoch <= start_epoch || start_epoch <= current_epoch {
             return Ok(false);
@@ -468,8 +514,6 @@ where
         let start_epoch_key =
             gov_storage::get_voting_start_epoch_key(proposal_id);
         let end_epoch_key = gov_storage::get_voting_end_epoch_key(proposal_id);
-        let min_period_parameter_key =
-            gov_storage::get_min_proposal_voting_period_key();
         let max_period_parameter_key =
             gov_storage::get_max_proposal_period_key();
 
@@ -486,8 +530,7 @@ where
             self.force_read(&start_epoch_key, ReadType::Post)?;
         let end_epoch: Epoch =
             self.force_read(&end_epoch_key, ReadType::Post)?;
-        let min_period: u64 =
-            self.force_read(&min_period_parameter_key, ReadType::Pre)?;
+        let min_period = self.min_voting_period(proposal_id)?;
         let max_period: u64 =
             self.force_read(&max_period_parameter_key, ReadType::Pre)?;
 
@@ -504,6 +547,59 @@ where
             && (end_epoch - start_epoch).0 <= max_period)
     }
 
+    /// Validate an expedited key
+    fn is_valid_expedited(&self, proposal_id: u64) -> Result<bool> {
+        let expedited_key =
+            gov_storage::get_proposal_expedited_key(proposal_id);
+        let proposal_type_key = gov_storage::get_proposal_type_key(proposal_id);
+
+        // The flag can only be written by the tx creating the proposal
+        let has_pre_expedited = self.ctx.has_key_pre(&expedited_key)?;
+        let has_pre_proposal_type = self.ctx.has_key_pre(&proposal_type_key)?;
+        if has_pre_expedited || has_pre_proposal_type {
+            return Ok(false);
+        }
+
+        let is_expedited: bool =
+            self.force_read(&expedited_key, ReadType::Post)?;
+        let proposal_type: ProposalType =
+            self.force_read(&proposal_type_key, ReadType::Post)?;
+
+        let can_be_expedited = matches!(
+            proposal_type,
+            ProposalType::Default(_) | ProposalType::ParameterChange(_)
+        );
+        if is_expedited && !can_be_expedited {
+            tracing::info!(
+                "Proposal of type {proposal_type} can't be expedited."
+            );
+        }
+        Ok(!is_expedited || can_be_expedited)
+    }
+
+    /// Get the minimum voting period that applies to a new proposal,
+    /// depending on whether the proposal is expedited. Chains without the
+    /// expedited parameter fall back to the regular period.
+    fn min_voting_period(&self, proposal_id: u64) -> Result<u64> {
+        let expedited_key =
+            gov_storage::get_proposal_expedited_key(proposal_id);
+        let is_expedited: bool =
+            self.ctx.post().read(&expedited_key)?.unwrap_or_default();
+        let min_period_key = gov_storage::get_min_proposal_voting_period_key();
+        let min_period: u64 =
+            self.force_read(&min_period_key, ReadType::Pre)?;
+        if !is_expedited {
+            return Ok(min_period);
+        }
+        let min_expedited_period_key =
+            gov_storage::get_min_expedited_proposal_voting_period_key();
+        Ok(self
+            .ctx
+            .pre()
+            .read(&min_expedited_period_key)?
+            .unwrap_or(min_period))
+    }
+
     /// Validate a funds key
     pub fn is_valid_funds(
         &self,
@@ -532,6 +628,35 @@ where
         }
     }
 
+    /// Validate the locked deposits key. It can only be increased by the
+    /// deposits of the proposals created in this transaction.
+    fn is_valid_locked_deposits(&self) -> Result<bool> {
+        let counter_key = gov_storage::get_counter_key();
+        let locked_deposits_key = gov_storage::get_locked_deposits_key();
+
+        let pre_counter: u64 = self.force_read(&counter_key, ReadType::Pre)?;
+        let post_counter: u64 =
+            self.force_read(&counter_key, ReadType::Post)?;
+        let pre_locked: token::Amount = self
+            .ctx
+            .pre()
+            .read(&locked_deposits_key)?
+            .unwrap_or_default();
+        let post_locked: token::Amount =
+            self.force_read(&locked_deposits_key, ReadType::Post)?;
+
+        let mut expected_locked = Some(pre_locked);
+        for proposal_id in pre_counter..post_counter {
+            let funds: token::Amount = self.force_read(
+                &gov_storage::get_funds_key(proposal_id),
+                ReadType::Post,
+            )?;
+            expected_locked =
+                expected_locked.and_then(|locked| locked.checked_add(funds));
+        }
+        Ok(expected_locked == Some(post_locked))
+    }
+
     /// Validate a balance key
     fn is_valid_balance(&self, native_token_address: &Address) -> Result<bool> {
         let balance_key =
@@ -554,6 +679,17 @@ where
         }
     }
 
+    /// Validate a treasury balance key. Tokens can be sent to the treasury by
+    /// any transaction but can only leave it with a treasury spending proposal.
+    fn is_valid_treasury_balance(&self, key: &Key) -> Result<bool> {
+        let pre_balance: token::Amount =
+            self.ctx.pre().read(key)?.unwrap_or_default();
+        let post_balance: token::Amount =
+            self.ctx.post().read(key)?.unwrap_or_default();
+
+        Ok(post_balance >= pre_balance)
+    }
+
     /// Validate a author key
     pub fn is_valid_author(
         &self,
@@ -709,10 +845,16 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     END_EPOCH,
     #[allow(non_camel_case_types)]
+    EXPEDITED,
+    #[allow(non_camel_case_types)]
     FUNDS,
     #[allow(non_camel_case_types)]
+    LOCKED_DEPOSITS,
+    #[allow(non_camel_case_types)]
     BALANCE,
     #[allow(non_camel_case_types)]
+    TREASURY,
+    #[allow(non_camel_case_types)]
     AUTHOR,
     #[allow(non_camel_case_types)]
     PARAMETER,
@@ -740,16 +882,25 @@ impl KeyType {
             KeyType::PROPOSAL_COMMIT
         } else if gov_storage::is_end_epoch_key(key) {
             KeyType::END_EPOCH
+        } else if gov_storage::is_expedited_key(key) {
+            KeyType::EXPEDITED
         } else if gov_storage::is_balance_key(key) {
             KeyType::FUNDS
         } else if gov_storage::is_author_key(key) {
             KeyType::AUTHOR
         } else if gov_storage::is_counter_key(key) {
             KeyType::COUNTER
+        } else if gov_storage::is_locked_deposits_key(key) {
+            KeyType::LOCKED_DEPOSITS
         } else if gov_storage::is_parameter_key(key) {
             KeyType::PARAMETER
         } else if token::is_balance_key(native_token, key).is_some() {
             KeyType::BALANCE
+        } else if matches!(
+            token::is_any_token_balance_key(key),
+            Some([_, owner]) if *owner == ADDRESS
+        ) {
+            KeyType::TREASURY
         } else if gov_storage::is_governance_key(key) {
             KeyType::UNKNOWN_GOVERNANCE
         } else {
@@ -109,6 +109,42 @@ impl ProposalEvent {
         )
     }
 
+    /// Create a new proposal event for parameter change proposal
+    pub fn parameter_change_proposal_event(
+        proposal_id: u64,
+        result: bool,
+    ) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
+    /// Create a new proposal event for upgrade proposal
+    pub fn upgrade_proposal_event(proposal_id: u64, result: bool) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
+    /// Create a new proposal event for treasury spending proposal
+    pub fn treasury_proposal_event(proposal_id: u64, result: bool) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
     /// Create a new proposal event for eth proposal
     pub fn eth_proposal_event(proposal_id: u64, result: bool) -> Self {
         ProposalEvent::new(
@@ -4,7 +4,6 @@ pub mod eth_bridge;
 pub mod events;
 pub mod governance;
 pub mod ibc;
-pub mod inflation;
 pub mod native_vp;
 pub mod pgf;
 pub mod pos;
@@ -15,5 +14,5 @@ pub mod storage;
 pub mod vp_host_fns;
 
 pub use namada_core::ledger::{
-    gas, parameters, replay_protection, storage_api, tx_env, vp_env,
+    gas, inflation, parameters, replay_protection, storage_api, tx_env, vp_env,
 };
@@ -21,8 +21,12 @@ use namada_core::hints;
 use namada_core::ledger::eth_bridge::storage::bridge_pool::{
     get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
 };
+use namada_core::ledger::eth_bridge::storage::flow_limits::{
+    self, EpochedFlow, FlowDirection,
+};
 use namada_core::ledger::eth_bridge::storage::whitelist;
 use namada_core::ledger::eth_bridge::ADDRESS as BRIDGE_ADDRESS;
+use namada_core::ledger::storage_api::StorageRead;
 use namada_ethereum_bridge::parameters::read_native_erc20_address;
 use namada_ethereum_bridge::storage::wrapped_erc20s;
 
@@ -311,6 +315,64 @@ where
         Ok(true)
     }
 
+    /// Check that the outbound flow of the transferred asset was
+    /// updated, and that it does not exceed its per epoch limit.
+    fn check_outbound_flow(
+        &self,
+        transfer: &PendingTransfer,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let asset = &transfer.transfer.asset;
+        let flow_key = flow_limits::Key {
+            asset: *asset,
+            suffix: flow_limits::KeyType::Flow(FlowDirection::Outbound),
+        }
+        .into();
+        if !keys_changed.contains(&flow_key) {
+            tracing::debug!(
+                ?transfer,
+                "The outbound flow of the transferred asset was not updated"
+            );
+            return Ok(false);
+        }
+
+        let pre = self.ctx.pre();
+        let epoch = pre.get_block_epoch().map_err(|e| Error(e.into()))?;
+        let expected_flow = read_outbound_flow(&pre, asset)?
+            .checked_add_at(epoch, transfer.transfer.amount)
+            .ok_or_else(|| {
+                eyre!("Addition overflowed updating the outbound flow.")
+            })?;
+        let flow = read_outbound_flow(&self.ctx.post(), asset)?;
+        if flow != expected_flow {
+            tracing::debug!(
+                ?transfer,
+                ?flow,
+                ?expected_flow,
+                "The outbound flow of the transferred asset was not correctly \
+                 updated"
+            );
+            return Ok(false);
+        }
+
+        let limit =
+            flow_limits::read_flow_limit(&pre, asset, FlowDirection::Outbound)
+                .map_err(|e| Error(e.into()))?;
+        match limit {
+            Some(limit) if flow.amount > limit => {
+                tracing::debug!(
+                    ?transfer,
+                    outbound_flow = %flow.amount.to_string_native(),
+                    outbound_limit = %limit.to_string_native(),
+                    "The transfer exceeds the outbound flow limit of the \
+                     transferred asset for the current epoch"
+                );
+                Ok(false)
+            }
+            _ => Ok(true),
+        }
+    }
+
     /// Deteremine the debit and credit amounts that should be checked.
     fn determine_escrow_checks<'trans, 'this: 'trans>(
         &'this self,
@@ -502,6 +564,19 @@ enum GasCheck {}
 /// Perform a token check.
 enum TokenCheck {}
 
+/// Read the outbound flow of some `asset` from the given storage view.
+#[inline]
+fn read_outbound_flow<S>(
+    storage: &S,
+    asset: &EthAddress,
+) -> Result<EpochedFlow, Error>
+where
+    S: StorageRead,
+{
+    flow_limits::read_flow(storage, asset, FlowDirection::Outbound)
+        .map_err(|e| Error(e.into()))
+}
+
 /// Sum gas and token amounts on a pending transfer, checking for overflows.
 #[inline]
 fn sum_gas_and_token_amounts(
@@ -610,30 +685,32 @@ where
             return Ok(false);
         }
         // check the escrowed assets
-        if transfer.transfer.asset == wnam_address {
+        let assets_escrowed = if transfer.transfer.asset == wnam_address {
             self.check_wnam_escrow(
                 &wnam_address,
                 &transfer,
                 escrow_checks.token_check,
-            )
+            )?
         } else {
-            self.check_escrowed_toks(escrow_checks.token_check)
+            self.check_escrowed_toks(escrow_checks.token_check)?
+        };
+        if !assets_escrowed {
+            tracing::debug!(
+                ?transfer,
+                "The assets of the transfer were not properly escrowed into \
+                 the Ethereum bridge pool."
+            );
+            return Ok(false);
         }
-        .map(|ok| {
-            if ok {
-                tracing::info!(
-                    "The Ethereum bridge pool VP accepted the transfer {:?}.",
-                    transfer
-                );
-            } else {
-                tracing::debug!(
-                    ?transfer,
-                    "The assets of the transfer were not properly escrowed \
-                     into the Ethereum bridge pool."
-                );
-            }
-            ok
-        })
+        // check that the outbound flow limits are respected
+        if !self.check_outbound_flow(&transfer, keys_changed)? {
+            return Ok(false);
+        }
+        tracing::info!(
+            "The Ethereum bridge pool VP accepted the transfer {:?}.",
+            transfer
+        );
+        Ok(true)
     }
 }
 
@@ -660,7 +737,7 @@ mod test_bridge_pool_vp {
     use crate::types::chain::ChainId;
     use crate::types::eth_bridge_pool::{GasFee, TransferToEthereum};
     use crate::types::hash::Hash;
-    use crate::types::storage::TxIndex;
+    use crate::types::storage::{Epoch, TxIndex};
     use crate::types::transaction::TxType;
     use crate::vm::wasm::VpCache;
     use crate::vm::WasmCacheRwAccess;
@@ -961,6 +1038,27 @@ mod test_bridge_pool_vp {
         Error,
     }
 
+    /// Record the outbound flow of a transfer added to the pool,
+    /// and return the key that was changed.
+    fn update_outbound_flow(
+        transfer: &PendingTransfer,
+        write_log: &mut WriteLog,
+    ) -> Key {
+        let key = flow_limits::Key {
+            asset: transfer.transfer.asset,
+            suffix: flow_limits::KeyType::Flow(FlowDirection::Outbound),
+        }
+        .into();
+        let flow = EpochedFlow {
+            epoch: Epoch::default(),
+            amount: transfer.transfer.amount,
+        };
+        write_log
+            .write(&key, flow.try_to_vec().expect("Test failed"))
+            .expect("Test failed");
+        key
+    }
+
     /// Helper function that tests various ways gas can be escrowed,
     /// either correctly or incorrectly, is handled appropriately
     fn assert_bridge_pool<F>(
@@ -973,8 +1071,30 @@ mod test_bridge_pool_vp {
     ) where
         F: FnOnce(&mut PendingTransfer, &mut WriteLog) -> BTreeSet<Key>,
     {
-        // setup
-        let mut wl_storage = setup_storage();
+        assert_bridge_pool_with_storage(
+            setup_storage(),
+            payer_gas_delta,
+            gas_escrow_delta,
+            payer_delta,
+            escrow_delta,
+            insert_transfer,
+            expect,
+        )
+    }
+
+    /// Like [`assert_bridge_pool`], but operating over some
+    /// pre-initialized storage.
+    fn assert_bridge_pool_with_storage<F>(
+        mut wl_storage: WlStorage<MockDB, Sha256Hasher>,
+        payer_gas_delta: SignedAmount,
+        gas_escrow_delta: SignedAmount,
+        payer_delta: SignedAmount,
+        escrow_delta: SignedAmount,
+        insert_transfer: F,
+        expect: Expect,
+    ) where
+        F: FnOnce(&mut PendingTransfer, &mut WriteLog) -> BTreeSet<Key>,
+    {
         let tx = Tx::from_type(TxType::Raw);
 
         // the transfer to be added to the pool
@@ -995,6 +1115,8 @@ mod test_bridge_pool_vp {
         // add transfer to pool
         let mut keys_changed =
             insert_transfer(&mut transfer, &mut wl_storage.write_log);
+        keys_changed
+            .insert(update_outbound_flow(&transfer, &mut wl_storage.write_log));
 
         // change Bertha's balances
         let mut new_keys_changed = update_balances(
@@ -1527,6 +1649,10 @@ mod test_bridge_pool_vp {
             )
             .expect("Test failed");
         assert!(keys_changed.insert(eb_account_key));
+        assert!(keys_changed.insert(update_outbound_flow(
+            &transfer,
+            &mut wl_storage.write_log
+        )));
 
         let verifiers = BTreeSet::default();
         // create the data to be given to the vp
@@ -1817,6 +1943,8 @@ mod test_bridge_pool_vp {
             SignedAmount::Positive(TOKENS.into()),
         );
         keys_changed.append(&mut new_keys_changed);
+        keys_changed
+            .insert(update_outbound_flow(&transfer, &mut wl_storage.write_log));
 
         // create the data to be given to the vp
         let verifiers = BTreeSet::default();
@@ -1899,6 +2027,139 @@ mod test_bridge_pool_vp {
         );
     }
 
+    /// Test that the Bridge pool VP accepts transfers within the
+    /// outbound flow limit of the transferred asset.
+    #[test]
+    fn test_outbound_flow_within_limit() {
+        let mut wl_storage = setup_storage();
+        let limit_key = flow_limits::Key {
+            asset: ASSET,
+            suffix: flow_limits::KeyType::Limit(FlowDirection::Outbound),
+        }
+        .into();
+        wl_storage
+            .write(&limit_key, Amount::from(TOKENS))
+            .expect("Test failed");
+        wl_storage.write_log.commit_tx();
+        assert_bridge_pool_with_storage(
+            wl_storage,
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+            SignedAmount::Positive(TOKENS.into()),
+            |transfer, log| {
+                log.write(
+                    &get_pending_key(transfer),
+                    transfer.try_to_vec().unwrap(),
+                )
+                .unwrap();
+                BTreeSet::from([get_pending_key(transfer)])
+            },
+            Expect::True,
+        );
+    }
+
+    /// Test that the Bridge pool VP rejects transfers exceeding the
+    /// outbound flow limit of the transferred asset.
+    #[test]
+    fn test_outbound_flow_limit_exceeded() {
+        let mut wl_storage = setup_storage();
+        let limit_key = flow_limits::Key {
+            asset: ASSET,
+            suffix: flow_limits::KeyType::Limit(FlowDirection::Outbound),
+        }
+        .into();
+        wl_storage
+            .write(&limit_key, Amount::from(TOKENS - 1))
+            .expect("Test failed");
+        wl_storage.write_log.commit_tx();
+        assert_bridge_pool_with_storage(
+            wl_storage,
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+            SignedAmount::Positive(TOKENS.into()),
+            |transfer, log| {
+                log.write(
+                    &get_pending_key(transfer),
+                    transfer.try_to_vec().unwrap(),
+                )
+                .unwrap();
+                BTreeSet::from([get_pending_key(transfer)])
+            },
+            Expect::False,
+        );
+    }
+
+    /// Test that the Bridge pool VP rejects transfers that do not
+    /// update the outbound flow of the transferred asset.
+    #[test]
+    fn test_outbound_flow_must_be_updated() {
+        let mut wl_storage = setup_storage();
+        let tx = Tx::from_type(TxType::Raw);
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: ASSET,
+                sender: bertha_address(),
+                recipient: EthAddress([1; 20]),
+                amount: TOKENS.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: GAS_FEE.into(),
+                payer: bertha_address(),
+            },
+        };
+        wl_storage
+            .write_log
+            .write(&get_pending_key(&transfer), transfer.try_to_vec().unwrap())
+            .unwrap();
+        let mut keys_changed = BTreeSet::from([get_pending_key(&transfer)]);
+        keys_changed.append(&mut update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: bertha_address(),
+                gas: BERTHA_WEALTH.into(),
+                token: BERTHA_TOKENS.into(),
+            },
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+        ));
+        keys_changed.append(&mut update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: BRIDGE_POOL_ADDRESS,
+                gas: ESCROWED_AMOUNT.into(),
+                token: ESCROWED_TOKENS.into(),
+            },
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Positive(TOKENS.into()),
+        ));
+        let verifiers = BTreeSet::default();
+        let vp = BridgePoolVp {
+            ctx: setup_ctx(
+                &tx,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &keys_changed,
+                &verifiers,
+            ),
+        };
+
+        let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+        tx.add_data(transfer);
+
+        let res = vp
+            .validate_tx(&tx, &keys_changed, &verifiers)
+            .expect("Test failed");
+        assert!(!res);
+    }
+
     /// Test that the Bridge pool native VP validates transfers that
     /// do not contain gas fees and no associated changed keys.
     #[test]
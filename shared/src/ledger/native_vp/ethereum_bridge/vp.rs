@@ -2,8 +2,12 @@
 use std::collections::{BTreeSet, HashSet};
 
 use eyre::{eyre, Result};
+use namada_core::ledger::eth_bridge::storage::flow_limits::{
+    self, FlowDirection,
+};
 use namada_core::ledger::eth_bridge::storage::{self, escrow_key};
 use namada_core::ledger::storage::traits::StorageHasher;
+use namada_core::ledger::storage_api::governance;
 use namada_core::ledger::{eth_bridge, storage as ledger_storage};
 use namada_core::types::address::Address;
 use namada_core::types::storage::Key;
@@ -79,6 +83,45 @@ where
             Ok(false)
         }
     }
+
+    /// Validate changes to the flow limits sub-space of the Ethereum
+    /// bridge.
+    ///
+    /// Flow limits may only be changed by governance. Outbound flows
+    /// may only be updated by transfers to the Bridge pool, whose VP
+    /// checks the updated amounts against the configured limits.
+    /// Inbound flows are only ever updated natively, by the protocol.
+    fn check_flow_limits(
+        &self,
+        tx: &Tx,
+        flow_keys: &[flow_limits::Key],
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool, Error> {
+        for key in flow_keys {
+            let valid = match key.suffix {
+                flow_limits::KeyType::Limit(_) => tx
+                    .data()
+                    .map(|data| {
+                        governance::is_proposal_accepted(&self.ctx.pre(), &data)
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(false),
+                flow_limits::KeyType::Flow(FlowDirection::Outbound) => {
+                    verifiers
+                        .contains(&storage::bridge_pool::BRIDGE_POOL_ADDRESS)
+                }
+                flow_limits::KeyType::Flow(FlowDirection::Inbound) => false,
+            };
+            if !valid {
+                tracing::debug!(
+                    ?key,
+                    "Invalid change to the flow limits of the Ethereum bridge"
+                );
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
 
 impl<'a, DB, H, CA> NativeVp for EthBridge<'a, DB, H, CA>
@@ -94,6 +137,9 @@ where
     ///
     /// We only permit increasing the escrowed balance of NAM under the Ethereum
     /// bridge address, when writing to storage from wasm transactions.
+    /// Additionally, transfers to the Bridge pool may update the outbound
+    /// flows of the Ethereum bridge, and governance may change its flow
+    /// limits.
     ///
     /// Some other changes to the storage subspace of this account are expected
     /// to happen natively i.e. bypassing this validity predicate. For example,
@@ -101,7 +147,7 @@ where
     /// no wasm transactions should be able to modify those keys.
     fn validate_tx(
         &self,
-        _: &Tx,
+        tx: &Tx,
         keys_changed: &BTreeSet<Key>,
         verifiers: &BTreeSet<Address>,
     ) -> Result<bool, Self::Error> {
@@ -111,7 +157,30 @@ where
             "Ethereum Bridge VP triggered",
         );
 
-        if !validate_changed_keys(&self.ctx.storage.native_token, keys_changed)?
+        let mut flow_keys = vec![];
+        let mut other_keys = BTreeSet::new();
+        for key in keys_changed {
+            match flow_limits::is_flow_limits_key(key) {
+                Some(flow_key) => flow_keys.push(flow_key),
+                None => {
+                    other_keys.insert(key.clone());
+                }
+            }
+        }
+
+        if !self.check_flow_limits(tx, &flow_keys, verifiers)? {
+            return Ok(false);
+        }
+        // NB: transfers of ERC20s to the Bridge pool, as well as
+        // governance proposals, may only touch the flow limits
+        // sub-space of this account
+        if !flow_keys.is_empty()
+            && !other_keys.iter().any(storage::has_eth_addr_segment)
+        {
+            return Ok(true);
+        }
+
+        if !validate_changed_keys(&self.ctx.storage.native_token, &other_keys)?
         {
             return Ok(false);
         }
@@ -505,4 +574,91 @@ mod tests {
         let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
         assert!(!res.expect("Test failed"));
     }
+
+    /// Test that transfers to the Bridge pool may update the outbound
+    /// flows of the Ethereum bridge.
+    #[test]
+    fn test_outbound_flow_updated_by_bridge_pool() {
+        let mut wl_storage = setup_storage();
+        let flow_key: Key = flow_limits::Key {
+            asset: ethereum_events::testing::DAI_ERC20_ETH_ADDRESS,
+            suffix: flow_limits::KeyType::Flow(FlowDirection::Outbound),
+        }
+        .into();
+        wl_storage
+            .write_log
+            .write(
+                &flow_key,
+                flow_limits::EpochedFlow {
+                    epoch: Default::default(),
+                    amount: Amount::from(ESCROW_AMOUNT),
+                }
+                .try_to_vec()
+                .expect("Test failed"),
+            )
+            .expect("Test failed");
+
+        let keys_changed = BTreeSet::from([flow_key]);
+        let tx = Tx::from_type(TxType::Raw);
+
+        // the Bridge pool VP must be triggered
+        for (verifiers, expected) in [
+            (BTreeSet::from([BRIDGE_POOL_ADDRESS]), true),
+            (BTreeSet::new(), false),
+        ] {
+            let vp = EthBridge {
+                ctx: setup_ctx(
+                    &tx,
+                    &wl_storage.storage,
+                    &wl_storage.write_log,
+                    &keys_changed,
+                    &verifiers,
+                ),
+            };
+            let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+            assert_eq!(res.expect("Test failed"), expected);
+        }
+    }
+
+    /// Test that wasm txs cannot update the inbound flows, nor the
+    /// flow limits of the Ethereum bridge, outside of governance.
+    #[test]
+    fn test_flow_limits_keys_changed_rejected() {
+        for suffix in [
+            flow_limits::KeyType::Flow(FlowDirection::Inbound),
+            flow_limits::KeyType::Limit(FlowDirection::Inbound),
+            flow_limits::KeyType::Limit(FlowDirection::Outbound),
+        ] {
+            let mut wl_storage = setup_storage();
+            let key: Key = flow_limits::Key {
+                asset: ethereum_events::testing::DAI_ERC20_ETH_ADDRESS,
+                suffix,
+            }
+            .into();
+            wl_storage
+                .write_log
+                .write(
+                    &key,
+                    Amount::from(ESCROW_AMOUNT)
+                        .try_to_vec()
+                        .expect("Test failed"),
+                )
+                .expect("Test failed");
+
+            let keys_changed = BTreeSet::from([key]);
+            let verifiers = BTreeSet::from([BRIDGE_POOL_ADDRESS]);
+            let tx = Tx::from_type(TxType::Raw);
+            let vp = EthBridge {
+                ctx: setup_ctx(
+                    &tx,
+                    &wl_storage.storage,
+                    &wl_storage.write_log,
+                    &keys_changed,
+                    &verifiers,
+                ),
+            };
+            let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+            assert!(!res.expect("Test failed"));
+        }
+    }
 }
@@ -9,8 +9,8 @@ use namada_core::ledger::storage::write_log::StorageModification;
 use namada_core::ledger::storage::{self as ledger_storage, StorageHasher};
 use namada_core::ledger::storage_api::StorageRead;
 use namada_core::types::address::{Address, InternalAddress};
-use namada_core::types::ibc::IbcEvent;
-use namada_core::types::storage::{BlockHeight, Header, Key};
+use namada_core::types::ibc::{IbcEvent, IbcShieldedTransfer};
+use namada_core::types::storage::{BlockHeight, Epoch, Header, Key};
 use namada_core::types::token::{self, Amount, DenominatedAmount};
 
 use super::Error;
@@ -50,6 +50,11 @@ where
         self.store.keys().filter(|k| is_ibc_key(k)).collect()
     }
 
+    /// Get the token keys changed by minting, burning or transferring tokens
+    pub fn get_changed_token_keys(&self) -> HashSet<&Key> {
+        self.store.keys().filter(|k| !is_ibc_key(k)).collect()
+    }
+
     pub fn get_changed_value(&self, key: &Key) -> Option<&StorageModification> {
         self.store.get(key)
     }
@@ -232,11 +237,24 @@ where
         )
     }
 
+    fn handle_masp_tx(
+        &mut self,
+        _shielded: &IbcShieldedTransfer,
+    ) -> Result<(), Self::Error> {
+        // The MASP transaction doesn't update any IBC key. It is validated by
+        // the MASP VP.
+        Ok(())
+    }
+
     /// Get the current height of this chain
     fn get_height(&self) -> Result<BlockHeight, Self::Error> {
         self.ctx.get_block_height().map_err(Error::NativeVpError)
     }
 
+    fn get_epoch(&self) -> Result<Epoch, Self::Error> {
+        self.ctx.get_block_epoch().map_err(Error::NativeVpError)
+    }
+
     /// Get the block header of this chain
     fn get_header(
         &self,
@@ -362,10 +380,21 @@ where
         unimplemented!("Validation doesn't burn")
     }
 
+    fn handle_masp_tx(
+        &mut self,
+        _shielded: &IbcShieldedTransfer,
+    ) -> Result<(), Self::Error> {
+        unimplemented!("Validation doesn't handle a masp tx")
+    }
+
     fn get_height(&self) -> Result<BlockHeight, Self::Error> {
         self.ctx.get_block_height().map_err(Error::NativeVpError)
     }
 
+    fn get_epoch(&self) -> Result<Epoch, Self::Error> {
+        self.ctx.get_block_epoch().map_err(Error::NativeVpError)
+    }
+
     fn get_header(
         &self,
         height: BlockHeight,
@@ -8,18 +8,28 @@ use std::rc::Rc;
 use std::time::Duration;
 
 use context::{PseudoExecutionContext, VpValidationContext};
+use namada_core::ledger::ibc::nft::{nft_transfer_port, NftClass, NftMetadata};
 use namada_core::ledger::ibc::{
-    Error as ActionError, IbcActions, TransferModule, ValidationParams,
+    decode_message, Error as ActionError, IbcActions, IbcMessage,
+    NftTransferModule, TransferModule, ValidationParams,
 };
 use namada_core::ledger::storage::write_log::StorageModification;
 use namada_core::ledger::storage::{self as ledger_storage, StorageHasher};
+use namada_core::ledger::storage_api::governance;
 use namada_core::proto::Tx;
 use namada_core::types::address::Address;
+use namada_core::types::ibc::{RateLimit, RateLimitFlow};
 use namada_core::types::storage::Key;
 use namada_proof_of_stake::read_pos_params;
 use thiserror::Error;
 
-use crate::ledger::ibc::storage::{calc_hash, is_ibc_denom_key, is_ibc_key};
+use crate::ibc::core::ics04_channel::msgs::PacketMsg;
+use crate::ibc::core::MsgEnvelope;
+use crate::ledger::ibc::storage::{
+    calc_hash, is_ibc_denom_key, is_ibc_key, is_nft_class_key,
+    is_nft_metadata_key, is_rate_limit_flow_key, is_rate_limit_key,
+    rate_limit_key,
+};
 use crate::ledger::native_vp::{self, Ctx, NativeVp, VpEnv};
 use crate::ledger::parameters::read_epoch_duration_parameter;
 use crate::vm::WasmCacheAccess;
@@ -41,6 +51,10 @@ pub enum Error {
     Denom(String),
     #[error("IBC event error: {0}")]
     IbcEvent(String),
+    #[error("Rate limit error: {0}")]
+    RateLimit(String),
+    #[error("NFT error: {0}")]
+    Nft(String),
 }
 
 /// IBC functions result
@@ -74,6 +88,11 @@ where
         let signed = tx_data;
         let tx_data = signed.data().ok_or(Error::NoTxData)?;
 
+        // Rate limits are updated by governance without any IBC message
+        if keys_changed.iter().any(|k| is_rate_limit_key(k).is_some()) {
+            return self.validate_rate_limit_update(&tx_data, keys_changed);
+        }
+
         // Pseudo execution and compare them
         self.validate_state(&tx_data, keys_changed)?;
 
@@ -83,6 +102,12 @@ where
         // Validate the denom store if a denom key has been changed
         self.validate_denom(keys_changed)?;
 
+        // Validate the NFT class and metadata if they have been changed
+        self.validate_nft(keys_changed)?;
+
+        // Validate the flows against the rate limits
+        self.validate_rate_limit_flow(keys_changed)?;
+
         Ok(true)
     }
 }
@@ -104,6 +129,8 @@ where
         let mut actions = IbcActions::new(ctx.clone());
         let module = TransferModule::new(ctx.clone());
         actions.add_transfer_route(module.module_id(), module);
+        let nft_module = NftTransferModule::new(ctx.clone());
+        actions.add_nft_transfer_route(nft_module.module_id(), nft_module);
         actions.execute(tx_data)?;
 
         let changed_ibc_keys: HashSet<&Key> =
@@ -124,6 +151,24 @@ where
             match_value(key, actual, ctx.borrow().get_changed_value(key))?;
         }
 
+        // NFTs are minted, burned or escrowed only as the IBC message
+        // specifies
+        if is_nft_message(tx_data) {
+            for key in ctx.borrow().get_changed_token_keys() {
+                if !keys_changed.contains(key) {
+                    return Err(Error::StateChange(format!(
+                        "The NFT wasn't updated: Key {}",
+                        key
+                    )));
+                }
+                let actual = self
+                    .ctx
+                    .read_bytes_post(key)
+                    .map_err(Error::NativeVpError)?;
+                match_value(key, actual, ctx.borrow().get_changed_value(key))?;
+            }
+        }
+
         // check the event
         let actual = self.ctx.write_log.get_ibc_events();
         if *actual != ctx.borrow().event {
@@ -144,8 +189,10 @@ where
         let mut actions = IbcActions::new(ctx.clone());
         actions.set_validation_params(self.validation_params()?);
 
-        let module = TransferModule::new(ctx);
+        let module = TransferModule::new(ctx.clone());
         actions.add_transfer_route(module.module_id(), module);
+        let nft_module = NftTransferModule::new(ctx);
+        actions.add_nft_transfer_route(nft_module.module_id(), nft_module);
         actions.validate(tx_data).map_err(Error::IbcAction)
     }
 
@@ -195,6 +242,162 @@ where
         }
         Ok(())
     }
+
+    fn validate_nft(&self, keys_changed: &BTreeSet<Key>) -> VpResult<()> {
+        for key in keys_changed {
+            if let Some(hash) = is_nft_class_key(key) {
+                let class = self
+                    .ctx
+                    .read_post::<NftClass>(key)
+                    .map_err(|e| {
+                        Error::Nft(format!(
+                            "Getting the NFT class failed: Key {}, Error {}",
+                            key, e
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        Error::Nft(format!(
+                            "The NFT class shouldn't be deleted: Key {}",
+                            key
+                        ))
+                    })?;
+                if calc_hash(&class.id) != hash {
+                    return Err(Error::Nft(format!(
+                        "The NFT class is invalid: Key {}, Class {}",
+                        key, class.id
+                    )));
+                }
+            }
+            if let Some(hash) = is_nft_metadata_key(key) {
+                // The metadata is deleted when the NFT is burned
+                if let Some(metadata) =
+                    self.ctx.read_post::<NftMetadata>(key).map_err(|e| {
+                        Error::Nft(format!(
+                            "Getting the NFT metadata failed: Key {}, Error {}",
+                            key, e
+                        ))
+                    })?
+                {
+                    let nft =
+                        format!("{}/{}", metadata.class_id, metadata.token_id);
+                    if calc_hash(&nft) != hash {
+                        return Err(Error::Nft(format!(
+                            "The NFT metadata is invalid: Key {}, NFT {}",
+                            key, nft
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Only governance can update the rate limits, and the proposal should
+    /// update nothing else in the IBC storage
+    fn validate_rate_limit_update(
+        &self,
+        tx_data: &[u8],
+        keys_changed: &BTreeSet<Key>,
+    ) -> VpResult<bool> {
+        let is_accepted =
+            governance::is_proposal_accepted(&self.ctx.pre(), tx_data)
+                .map_err(Error::NativeVpError)?;
+        if !is_accepted {
+            return Err(Error::RateLimit(
+                "The rate limits can be updated only by governance".to_string(),
+            ));
+        }
+        for key in keys_changed.iter().filter(|k| is_ibc_key(k)) {
+            if is_rate_limit_key(key).is_none() {
+                return Err(Error::RateLimit(format!(
+                    "Only the rate limits can be updated by the proposal: Key \
+                     {}",
+                    key
+                )));
+            }
+            let limit = self
+                .ctx
+                .read_post::<RateLimit>(key)
+                .map_err(Error::NativeVpError)?;
+            if matches!(limit, Some(RateLimit { period: 0, .. })) {
+                return Err(Error::RateLimit(format!(
+                    "The rate limit period should be at least an epoch: Key {}",
+                    key
+                )));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Validate that the increased net flows don't exceed the rate limits
+    fn validate_rate_limit_flow(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+    ) -> VpResult<()> {
+        for key in keys_changed {
+            let Some((channel_id, token)) = is_rate_limit_flow_key(key) else {
+                continue;
+            };
+            let limit = self
+                .ctx
+                .read_pre::<RateLimit>(&rate_limit_key(&channel_id, &token))
+                .map_err(Error::NativeVpError)?
+                .ok_or_else(|| {
+                    Error::RateLimit(format!(
+                        "The flow was updated without any rate limit: Key {}",
+                        key
+                    ))
+                })?;
+            let epoch =
+                self.ctx.get_block_epoch().map_err(Error::NativeVpError)?;
+            let pre = self
+                .ctx
+                .read_pre::<RateLimitFlow>(key)
+                .map_err(Error::NativeVpError)?
+                .unwrap_or_default()
+                .at(epoch, limit.period);
+            let post = self
+                .ctx
+                .read_post::<RateLimitFlow>(key)
+                .map_err(Error::NativeVpError)?
+                .unwrap_or_default();
+            if post.period_start != pre.period_start {
+                return Err(Error::RateLimit(format!(
+                    "The rate limit period is invalid: Key {}, Expected {}, \
+                     Actual {}",
+                    key, pre.period_start, post.period_start
+                )));
+            }
+            let inflow_exceeded = post.inflow > pre.inflow
+                && post.net_inflow() > limit.max_inflow;
+            let outflow_exceeded = post.outflow > pre.outflow
+                && post.net_outflow() > limit.max_outflow;
+            if inflow_exceeded || outflow_exceeded {
+                return Err(Error::RateLimit(format!(
+                    "The rate limit is exceeded: Channel {}, Token {}",
+                    channel_id, token
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if the IBC message transfers NFTs
+fn is_nft_message(tx_data: &[u8]) -> bool {
+    let nft_port = nft_transfer_port();
+    match decode_message(tx_data) {
+        Ok(IbcMessage::NftTransfer(_)) => true,
+        Ok(IbcMessage::Envelope(MsgEnvelope::Packet(msg))) => match msg {
+            PacketMsg::Recv(msg) => msg.packet.port_id_on_b == nft_port,
+            PacketMsg::Ack(msg) => msg.packet.port_id_on_a == nft_port,
+            PacketMsg::Timeout(msg) => msg.packet.port_id_on_a == nft_port,
+            PacketMsg::TimeoutOnClose(msg) => {
+                msg.packet.port_id_on_a == nft_port
+            }
+        },
+        _ => false,
+    }
 }
 
 fn match_value(
@@ -303,7 +506,7 @@ mod tests {
         client_update_height_key, client_update_timestamp_key, commitment_key,
         connection_counter_key, connection_key, consensus_state_key,
         ibc_denom_key, next_sequence_ack_key, next_sequence_recv_key,
-        next_sequence_send_key, receipt_key,
+        next_sequence_send_key, rate_limit_flow_key, receipt_key,
     };
     use crate::core::ledger::storage::testing::TestWlStorage;
     use crate::core::types::address::testing::{
@@ -2729,4 +2932,114 @@ mod tests {
                 .expect("validation failed")
         );
     }
+
+    #[test]
+    fn test_update_rate_limit_without_governance() {
+        let mut keys_changed = BTreeSet::new();
+        let mut wl_storage = init_storage();
+
+        let limit_key = rate_limit_key(&get_channel_id(), &nam());
+        let limit = RateLimit {
+            max_inflow: Amount::native_whole(100),
+            max_outflow: Amount::native_whole(100),
+            period: 1,
+        };
+        wl_storage
+            .write_log
+            .write(&limit_key, limit.try_to_vec().unwrap())
+            .expect("write failed");
+        keys_changed.insert(limit_key);
+
+        let tx_index = TxIndex::default();
+        let tx_code = vec![];
+        // the proposal ID which hasn't been accepted
+        let tx_data = 0u64.try_to_vec().unwrap();
+        let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+        tx.add_code(tx_code)
+            .add_serialized_data(tx_data)
+            .sign_wrapper(keypair_1());
+
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) =
+            wasm::compilation_cache::common::testing::cache();
+
+        let verifiers = BTreeSet::new();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let ibc = Ibc { ctx };
+        let result = ibc.validate_tx(&tx, &keys_changed, &verifiers);
+        assert!(matches!(result, Err(Error::RateLimit(_))));
+    }
+
+    #[test]
+    fn test_rate_limit_flow() {
+        let mut keys_changed = BTreeSet::new();
+        let mut wl_storage = init_storage();
+
+        let limit_key = rate_limit_key(&get_channel_id(), &nam());
+        let limit = RateLimit {
+            max_inflow: Amount::native_whole(100),
+            max_outflow: Amount::native_whole(50),
+            period: 1,
+        };
+        wl_storage
+            .write_log
+            .write(&limit_key, limit.try_to_vec().unwrap())
+            .expect("write failed");
+        wl_storage.write_log.commit_tx();
+        wl_storage.commit_block().expect("commit failed");
+
+        let flow_key = rate_limit_flow_key(&get_channel_id(), &nam());
+        keys_changed.insert(flow_key.clone());
+
+        let tx_index = TxIndex::default();
+        let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+        tx.add_code(vec![])
+            .add_serialized_data(vec![])
+            .sign_wrapper(keypair_1());
+
+        for (outflow, is_valid) in [(50, true), (51, false)] {
+            let flow = RateLimitFlow {
+                period_start: Epoch(0),
+                inflow: Amount::zero(),
+                outflow: Amount::native_whole(outflow),
+            };
+            wl_storage
+                .write_log
+                .write(&flow_key, flow.try_to_vec().unwrap())
+                .expect("write failed");
+
+            let gas_meter = VpGasMeter::new_from_tx_meter(
+                &TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into()),
+            );
+            let (vp_wasm_cache, _vp_cache_dir) =
+                wasm::compilation_cache::common::testing::cache();
+            let verifiers = BTreeSet::new();
+            let ctx = Ctx::new(
+                &ADDRESS,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &tx,
+                &tx_index,
+                gas_meter,
+                &keys_changed,
+                &verifiers,
+                vp_wasm_cache,
+            );
+            let ibc = Ibc { ctx };
+            let result = ibc.validate_rate_limit_flow(&keys_changed);
+            assert_eq!(result.is_ok(), is_valid);
+        }
+    }
 }
@@ -22,10 +22,12 @@ use crate::ledger::storage::write_log::WriteLog;
 use crate::ledger::storage::{Storage, StorageHasher};
 use crate::proto::Tx;
 use crate::types::address::Address;
-use crate::types::hash::Hash;
+use crate::types::hash::{Hash, HashAlgorithm};
+use crate::types::key::common;
 use crate::types::storage::{
     BlockHash, BlockHeight, Epoch, Header, Key, TxIndex,
 };
+use crate::types::time::DateTimeUtc;
 use crate::vm::prefix_iter::PrefixIterators;
 use crate::vm::WasmCacheAccess;
 
@@ -433,9 +435,18 @@ where
         .into_storage_result()
     }
 
+    fn get_block_time(&self) -> Result<DateTimeUtc, storage_api::Error> {
+        vp_host_fns::get_block_time(
+            &mut self.gas_meter.borrow_mut(),
+            self.storage,
+        )
+        .into_storage_result()
+    }
+
     fn get_tx_index(&self) -> Result<TxIndex, storage_api::Error> {
         vp_host_fns::get_tx_index(
             &mut self.gas_meter.borrow_mut(),
+            self.storage,
             self.tx_index,
         )
         .into_storage_result()
@@ -526,13 +537,47 @@ where
         unimplemented!("no masp native vp")
     }
 
+    fn hash(
+        &self,
+        algorithm: HashAlgorithm,
+        data: impl AsRef<[u8]>,
+    ) -> Result<Hash, storage_api::Error> {
+        vp_host_fns::hash(
+            &mut self.gas_meter.borrow_mut(),
+            self.storage,
+            algorithm,
+            data.as_ref(),
+        )
+        .into_storage_result()
+    }
+
+    fn verify_signature(
+        &self,
+        public_key: &common::PublicKey,
+        signature: &common::Signature,
+        data: impl AsRef<[u8]>,
+    ) -> Result<bool, storage_api::Error> {
+        vp_host_fns::verify_signature(
+            &mut self.gas_meter.borrow_mut(),
+            self.storage,
+            public_key,
+            signature,
+            data.as_ref(),
+        )
+        .into_storage_result()
+    }
+
     fn charge_gas(&self, _used_gas: u64) -> Result<(), storage_api::Error> {
         unimplemented!("Native vps don't consume whitelisted gas")
     }
 
     fn get_tx_code_hash(&self) -> Result<Option<Hash>, storage_api::Error> {
-        vp_host_fns::get_tx_code_hash(&mut self.gas_meter.borrow_mut(), self.tx)
-            .into_storage_result()
+        vp_host_fns::get_tx_code_hash(
+            &mut self.gas_meter.borrow_mut(),
+            self.storage,
+            self.tx,
+        )
+        .into_storage_result()
     }
 
     fn read_pre<T: borsh::BorshDeserialize>(
@@ -11,8 +11,10 @@ use crate::proto::Tx;
 use crate::types::address::{Address, InternalAddress};
 use crate::types::storage::{Key, KeySeg};
 use crate::types::token::{
+    denom_key, is_any_denom_key, is_any_max_supply_key, is_any_metadata_key,
     is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
-    minter_key, Amount, Change,
+    max_supply_key, metadata_key, minted_balance_key, minter_key, Amount,
+    Change, Denomination, TokenMetadata,
 };
 use crate::vm::WasmCacheAccess;
 
@@ -76,9 +78,32 @@ where
                     return Ok(false);
                 }
             } else if let Some(token) = is_any_minter_key(key) {
-                if !self.is_valid_minter(token, verifiers)? {
+                let is_valid = match token {
+                    Address::Established(_) => {
+                        self.is_valid_token_update(token, verifiers)?
+                    }
+                    _ => self.is_valid_minter(token, verifiers)?,
+                };
+                if !is_valid {
+                    return Ok(false);
+                }
+            } else if let Some(token) =
+                is_any_max_supply_key(key).or_else(|| is_any_metadata_key(key))
+            {
+                if !self.is_valid_token_update(token, verifiers)? {
                     return Ok(false);
                 }
+            } else if let Some(token) = is_any_denom_key(key) {
+                // The denomination of a registered token is immutable
+                let metadata: Option<TokenMetadata> =
+                    self.ctx.read_pre(&metadata_key(token))?;
+                if metadata.is_some() {
+                    let pre: Option<Denomination> = self.ctx.read_pre(key)?;
+                    let post: Option<Denomination> = self.ctx.read_post(key)?;
+                    if pre != post {
+                        return Ok(false);
+                    }
+                }
             } else if key.segments.get(0)
                 == Some(
                     &Address::Internal(InternalAddress::Multitoken).to_db_key(),
@@ -126,6 +151,21 @@ where
                     _ => Ok(false),
                 }
             }
+            Address::Established(_) => {
+                // A user-defined token can only be minted by its minter, once
+                // it's registered, up to its max supply
+                let minter_key = minter_key(token);
+                match self.ctx.read_post::<Address>(&minter_key)? {
+                    Some(minter) if verifiers.contains(&minter) => {}
+                    _ => return Ok(false),
+                }
+                let metadata: Option<TokenMetadata> =
+                    self.ctx.read_post(&metadata_key(token))?;
+                if metadata.is_none() {
+                    return Ok(false);
+                }
+                self.is_within_max_supply(token)
+            }
             _ => {
                 // ERC20 and other tokens should not be minted by a wasm
                 // transaction
@@ -133,6 +173,58 @@ where
             }
         }
     }
+
+    /// Check a change of the settings of a user-defined token. It must be
+    /// authorized by the token account and leave the token with a minter,
+    /// valid metadata, a denomination and a max supply that covers the
+    /// minted amount.
+    pub fn is_valid_token_update(
+        &self,
+        token: &Address,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        if !matches!(token, Address::Established(_)) {
+            return Ok(false);
+        }
+        // A token account initialized in the same tx has no VP to authorize
+        // it yet
+        let is_authorized = verifiers.contains(token)
+            || self
+                .ctx
+                .write_log
+                .get_initialized_accounts()
+                .contains(token);
+        if !is_authorized {
+            return Ok(false);
+        }
+        let minter: Option<Address> = self.ctx.read_post(&minter_key(token))?;
+        if minter.is_none() {
+            return Ok(false);
+        }
+        let metadata: Option<TokenMetadata> =
+            self.ctx.read_post(&metadata_key(token))?;
+        if !matches!(metadata, Some(metadata) if metadata.is_valid()) {
+            return Ok(false);
+        }
+        let denom: Option<Denomination> =
+            self.ctx.read_post(&denom_key(token))?;
+        if denom.is_none() {
+            return Ok(false);
+        }
+        self.is_within_max_supply(token)
+    }
+
+    /// Check that the minted amount of a token doesn't exceed its max supply,
+    /// if any
+    fn is_within_max_supply(&self, token: &Address) -> Result<bool> {
+        let max_supply: Option<Amount> =
+            self.ctx.read_post(&max_supply_key(token))?;
+        let minted: Amount = self
+            .ctx
+            .read_post(&minted_balance_key(token))?
+            .unwrap_or_default();
+        Ok(max_supply.map_or(true, |max_supply| minted <= max_supply))
+    }
 }
 
 #[cfg(test)]
@@ -610,4 +702,164 @@ mod tests {
                 .expect("validation failed")
         );
     }
+
+    fn validate(
+        wl_storage: &TestWlStorage,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> bool {
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            keys_changed,
+            verifiers,
+            vp_wasm_cache,
+        );
+        let vp = MultitokenVp { ctx };
+        vp.validate_tx(&tx, keys_changed, verifiers)
+            .expect("validation failed")
+    }
+
+    fn token_metadata() -> TokenMetadata {
+        TokenMetadata {
+            name: "Test token".to_string(),
+            symbol: "TEST".to_string(),
+        }
+    }
+
+    /// Write the settings of a registered user-defined token in storage
+    fn register_token(
+        wl_storage: &mut TestWlStorage,
+        token: &Address,
+        minter: &Address,
+        max_supply: Amount,
+    ) {
+        let writes = [
+            (minter_key(token), minter.try_to_vec().unwrap()),
+            (max_supply_key(token), max_supply.try_to_vec().unwrap()),
+            (metadata_key(token), token_metadata().try_to_vec().unwrap()),
+            (denom_key(token), Denomination(6).try_to_vec().unwrap()),
+        ];
+        for (key, value) in writes {
+            wl_storage.storage.write(&key, value).expect("write failed");
+        }
+    }
+
+    #[test]
+    fn test_token_registration() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let token = established_address_1();
+        let minter = established_address_2();
+        let writes = [
+            (minter_key(&token), minter.try_to_vec().unwrap()),
+            (
+                max_supply_key(&token),
+                Amount::native_whole(1000).try_to_vec().unwrap(),
+            ),
+            (metadata_key(&token), token_metadata().try_to_vec().unwrap()),
+            (denom_key(&token), Denomination(6).try_to_vec().unwrap()),
+        ];
+        for (key, value) in writes {
+            wl_storage
+                .write_log
+                .write(&key, value)
+                .expect("write failed");
+            keys_changed.insert(key);
+        }
+
+        // The token account must authorize its registration
+        let mut verifiers = BTreeSet::new();
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+        verifiers.insert(token.clone());
+        assert!(validate(&wl_storage, &keys_changed, &verifiers));
+
+        // Invalid metadata
+        let metadata_key = metadata_key(&token);
+        let metadata = TokenMetadata {
+            name: "Test token".to_string(),
+            symbol: "TE ST".to_string(),
+        };
+        wl_storage
+            .write_log
+            .write(&metadata_key, metadata.try_to_vec().unwrap())
+            .expect("write failed");
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+    }
+
+    #[test]
+    fn test_user_token_mint() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let token = established_address_1();
+        let minter = established_address_2();
+        register_token(
+            &mut wl_storage,
+            &token,
+            &minter,
+            Amount::native_whole(100),
+        );
+
+        let target_key = balance_key(&token, &minter);
+        let minted_key = minted_balance_key(&token);
+        for key in [&target_key, &minted_key] {
+            wl_storage
+                .write_log
+                .write(key, Amount::native_whole(100).try_to_vec().unwrap())
+                .expect("write failed");
+            keys_changed.insert(key.clone());
+        }
+
+        // The minter must authorize the mint
+        let mut verifiers = BTreeSet::new();
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+        verifiers.insert(minter.clone());
+        assert!(validate(&wl_storage, &keys_changed, &verifiers));
+
+        // The max supply cannot be exceeded
+        for key in [&target_key, &minted_key] {
+            wl_storage
+                .write_log
+                .write(key, Amount::native_whole(101).try_to_vec().unwrap())
+                .expect("write failed");
+        }
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+    }
+
+    #[test]
+    fn test_user_token_denom_change() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let token = established_address_1();
+        let minter = established_address_2();
+        register_token(
+            &mut wl_storage,
+            &token,
+            &minter,
+            Amount::native_whole(100),
+        );
+
+        let denom_key = denom_key(&token);
+        wl_storage
+            .write_log
+            .write(&denom_key, Denomination(8).try_to_vec().unwrap())
+            .expect("write failed");
+        keys_changed.insert(denom_key);
+
+        let verifiers = [token].into_iter().collect();
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+    }
 }
@@ -1,11 +1,14 @@
 //! The ledger's protocol
+pub mod parallel;
+
 use std::collections::BTreeSet;
-use std::panic;
+use std::{cmp, panic};
 
 use borsh::BorshSerialize;
 use eyre::{eyre, WrapErr};
 use masp_primitives::transaction::Transaction;
 use namada_core::ledger::gas::TxGasMeter;
+use namada_core::ledger::parameters::read_base_fee;
 use namada_core::ledger::storage::wl_storage::WriteLogAndStorage;
 use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
 use namada_core::proto::Section;
@@ -187,6 +190,7 @@ where
                 vps_result: VpsResult::default(),
                 initialized_accounts: vec![],
                 ibc_events: BTreeSet::default(),
+                events: vec![],
             })
         }
         TxType::Decrypted(DecryptedTx::Undecryptable) => {
@@ -236,6 +240,7 @@ where
     // Writes wrapper tx hash to block write log (changes must be persisted even
     // in case of failure)
     let wrapper_hash_key = replay_protection::get_replay_protection_key(
+        tx.header.expiration,
         &hash::Hash(tx.header_hash().0),
     );
     shell_params
@@ -254,10 +259,14 @@ where
     )?;
 
     // Account for gas
-    shell_params.tx_gas_meter.add_tx_size_gas(tx_bytes)?;
+    let gas_costs = shell_params.wl_storage.storage().gas_costs;
+    shell_params
+        .tx_gas_meter
+        .add_tx_size_gas(tx_bytes, &gas_costs)?;
 
     // If wrapper was succesful, write inner tx hash to storage
     let inner_hash_key = replay_protection::get_replay_protection_key(
+        tx.header.expiration,
         &hash::Hash(tx.update_header(TxType::Raw).header_hash().0),
     );
     shell_params
@@ -390,8 +399,8 @@ where
     Ok(())
 }
 
-/// Perform the actual transfer of fess from the fee payer to the block
-/// proposer.
+/// Perform the actual payment of fees by the fee payer. The base fee is burned
+/// and the rest of the fee is transferred to the block proposer as a tip.
 pub fn transfer_fee<WLS>(
     wl_storage: &mut WLS,
     block_proposer: &Address,
@@ -410,12 +419,21 @@ where
     match wrapper.get_tx_fee() {
         Ok(fees) => {
             if balance.checked_sub(fees).is_some() {
+                let burned = burned_fee(wl_storage, wrapper)?;
+                token_burn(
+                    wl_storage,
+                    &wrapper.fee.token,
+                    &wrapper.fee_payer(),
+                    burned,
+                )?;
+                // The burned part can't exceed the fee
+                let tip = fees.checked_sub(burned).unwrap_or_default();
                 token_transfer(
                     wl_storage,
                     &wrapper.fee.token,
                     &wrapper.fee_payer(),
                     block_proposer,
-                    fees,
+                    tip,
                 )
                 .map_err(|e| Error::FeeError(e.to_string()))
             } else {
@@ -475,6 +493,59 @@ where
     }
 }
 
+/// The part of the wrapper's fee that is burned: the base fee for every unit of
+/// gas, or the whole fee if the gas price is below the base fee
+fn burned_fee<WLS>(wl_storage: &WLS, wrapper: &WrapperTx) -> Result<Amount>
+where
+    WLS: WriteLogAndStorage + StorageRead,
+{
+    let base_fee = read_base_fee(wl_storage, &wrapper.fee.token)
+        .map_err(|e| Error::FeeError(e.to_string()))?
+        .unwrap_or_default();
+    cmp::min(base_fee, wrapper.fee.amount_per_gas_unit)
+        .checked_mul(wrapper.gas_limit.into())
+        .ok_or_else(|| {
+            Error::FeeError("The burned fee amount overflows".to_string())
+        })
+}
+
+/// Burn `amount` of `token` from `src`, decreasing the token's total supply.
+/// Like [`token_transfer`], this function updates the tx write log.
+fn token_burn<WLS>(
+    wl_storage: &mut WLS,
+    token: &Address,
+    src: &Address,
+    amount: Amount,
+) -> Result<()>
+where
+    WLS: WriteLogAndStorage + StorageRead,
+{
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let src_key = namada_core::types::token::balance_key(token, src);
+    let src_balance = storage_api::token::read_balance(wl_storage, token, src)
+        .expect("Token balance read in protocol must not fail");
+    let new_src_balance = src_balance
+        .checked_sub(amount)
+        .ok_or_else(|| Error::FeeError("Insufficient source balance".into()))?;
+    let supply_key = namada_core::types::token::minted_balance_key(token);
+    // The supply can't be lower than the balance of the source
+    let new_supply = storage_api::token::read_total_supply(wl_storage, token)
+        .expect("Token supply read in protocol must not fail")
+        .checked_sub(amount)
+        .unwrap_or_default();
+    wl_storage
+        .write_log_mut()
+        .write(&src_key, new_src_balance.try_to_vec().unwrap())
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+    wl_storage
+        .write_log_mut()
+        .write(&supply_key, new_supply.try_to_vec().unwrap())
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+    Ok(())
+}
+
 /// Transfer `token` from `src` to `dest`. Returns an `Err` if `src` has
 /// insufficient balance or if the transfer the `dest` would overflow (This can
 /// only happen if the total supply does't fit in `token::Amount`). Contrary to
@@ -530,11 +601,27 @@ where
     }
 }
 
-/// Check if the fee payer has enough transparent balance to pay fees
+/// Check if the wrapper's gas price covers the current base fee and if the fee
+/// payer has enough transparent balance to pay fees
 pub fn check_fees<WLS>(wl_storage: &WLS, wrapper: &WrapperTx) -> Result<()>
 where
     WLS: WriteLogAndStorage + StorageRead,
 {
+    let base_fee = read_base_fee(wl_storage, &wrapper.fee.token)
+        .map_err(|e| Error::FeeError(e.to_string()))?
+        .ok_or_else(|| {
+            Error::FeeError(format!(
+                "The provided {} token is not allowed for fee payment",
+                wrapper.fee.token
+            ))
+        })?;
+    if wrapper.fee.amount_per_gas_unit < base_fee {
+        return Err(Error::FeeError(format!(
+            "Gas price {:?} is below the current base fee {:?} for token {}",
+            wrapper.fee.amount_per_gas_unit, base_fee, wrapper.fee.token
+        )));
+    }
+
     let balance = storage_api::token::read_balance(
         wl_storage,
         &wrapper.fee.token,
@@ -610,6 +697,7 @@ where
     let initialized_accounts = write_log.get_initialized_accounts();
     let changed_keys = write_log.get_keys();
     let ibc_events = write_log.take_ibc_events();
+    let events = write_log.take_events();
 
     Ok(TxResult {
         gas_used,
@@ -617,6 +705,7 @@ where
         vps_result,
         initialized_accounts,
         ibc_events,
+        events,
     })
 }
 
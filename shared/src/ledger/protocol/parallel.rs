@@ -0,0 +1,165 @@
+//! Optimistic parallel execution of decrypted transactions
+//!
+//! The decrypted transactions of a block are first executed concurrently, each
+//! on its own copy of the write log taken before the block's transactions are
+//! applied, while recording the keys read by the transaction and its VPs.
+//! Then the transactions are applied in the block order. A speculative result
+//! is used only if none of the keys that it has read have been changed by the
+//! preceding transactions, in which case the execution is identical to the
+//! sequential one. Otherwise, the transaction is re-executed on the current
+//! state. Either way, the resulting state is the same as with sequential
+//! execution.
+
+use std::collections::BTreeMap;
+
+use namada_core::ledger::gas::TxGasMeter;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::{apply_wasm_tx, Result, ShellParams};
+use crate::ledger::storage::write_log::{ReadSet, WriteLog};
+use crate::ledger::storage::{
+    DBIter, StorageHasher, TempWlStorage, WlStorage, DB,
+};
+use crate::proto::Tx;
+use crate::types::storage::TxIndex;
+use crate::types::transaction::TxResult;
+use crate::vm::wasm::{TxCache, VpCache};
+use crate::vm::WasmCacheAccess;
+
+/// A decrypted transaction to be executed speculatively
+pub struct SpeculativeTxInput {
+    /// The index of the tx in the block
+    pub tx_index: TxIndex,
+    /// The decrypted tx
+    pub tx: Tx,
+    /// The gas meter with the remaining gas of the wrapper tx
+    pub tx_gas_meter: TxGasMeter,
+}
+
+/// The outcome of a speculative execution
+struct SpeculativeTx {
+    write_log: WriteLog,
+    read_set: ReadSet,
+    tx_gas_meter: TxGasMeter,
+    result: Result<TxResult>,
+}
+
+/// The decrypted transactions of a block executed in parallel, to be applied
+/// in order with [`SpeculativeTxs::apply_tx`]
+pub struct SpeculativeTxs {
+    /// The write log which the txs have been executed on
+    snapshot: WriteLog,
+    txs: BTreeMap<TxIndex, SpeculativeTx>,
+    /// The number of txs that had to be re-executed because of a conflict
+    reexecuted: usize,
+}
+
+impl SpeculativeTxs {
+    /// Execute the given txs concurrently on top of the current state
+    pub fn execute<D, H, CA>(
+        txs: Vec<SpeculativeTxInput>,
+        wl_storage: &WlStorage<D, H>,
+        vp_wasm_cache: &VpCache<CA>,
+        tx_wasm_cache: &TxCache<CA>,
+    ) -> Self
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        let snapshot = wl_storage.write_log.clone();
+        let txs = txs
+            .into_par_iter()
+            .map(|input| {
+                let SpeculativeTxInput {
+                    tx_index,
+                    tx,
+                    mut tx_gas_meter,
+                } = input;
+                let mut temp_wl_storage = TempWlStorage {
+                    write_log: snapshot.clone(),
+                    storage: &wl_storage.storage,
+                };
+                temp_wl_storage.write_log.track_reads();
+                let result = apply_wasm_tx(
+                    tx,
+                    &tx_index,
+                    ShellParams::new(
+                        &mut tx_gas_meter,
+                        &mut temp_wl_storage,
+                        &mut vp_wasm_cache.clone(),
+                        &mut tx_wasm_cache.clone(),
+                    ),
+                );
+                let mut write_log = temp_wl_storage.write_log;
+                let read_set = write_log.take_read_set();
+                (
+                    tx_index,
+                    SpeculativeTx {
+                        write_log,
+                        read_set,
+                        tx_gas_meter,
+                        result,
+                    },
+                )
+            })
+            .collect();
+        Self {
+            snapshot,
+            txs,
+            reexecuted: 0,
+        }
+    }
+
+    /// Apply a decrypted tx to the current transaction's write log in the
+    /// same way as [`apply_wasm_tx`]. The result of the speculative execution
+    /// is used if it's still valid, otherwise the tx is executed again.
+    pub fn apply_tx<D, H, CA>(
+        &mut self,
+        tx: Tx,
+        tx_index: TxIndex,
+        tx_gas_meter: &mut TxGasMeter,
+        wl_storage: &mut WlStorage<D, H>,
+        vp_wasm_cache: &mut VpCache<CA>,
+        tx_wasm_cache: &mut TxCache<CA>,
+    ) -> Result<TxResult>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        if let Some(speculative) = self.txs.remove(&tx_index) {
+            if speculative.tx_gas_meter.tx_gas_limit
+                == tx_gas_meter.tx_gas_limit
+                && wl_storage.write_log.is_read_set_unchanged(
+                    &self.snapshot,
+                    &speculative.read_set,
+                )
+            {
+                wl_storage.write_log.merge_speculative_tx(
+                    speculative.write_log,
+                    &speculative.read_set,
+                );
+                *tx_gas_meter = speculative.tx_gas_meter;
+                return speculative.result;
+            }
+            tracing::debug!("Re-executing tx {} after a conflict", tx_index.0);
+            self.reexecuted += 1;
+        }
+        apply_wasm_tx(
+            tx,
+            &tx_index,
+            ShellParams::new(
+                tx_gas_meter,
+                wl_storage,
+                vp_wasm_cache,
+                tx_wasm_cache,
+            ),
+        )
+    }
+
+    /// The number of txs that have been re-executed because of a conflict
+    pub fn reexecuted(&self) -> usize {
+        self.reexecuted
+    }
+}
@@ -11,8 +11,8 @@ pub use types::{
 use vp::{Vp, VP};
 
 pub use self::shell::eth_bridge::{
-    Erc20FlowControl, GenBridgePoolProofReq, GenBridgePoolProofRsp,
-    TransferToErcArgs,
+    Erc20FlowControl, Erc20FlowLimits, GenBridgePoolProofReq,
+    GenBridgePoolProofRsp, TransferToErcArgs,
 };
 use super::storage::traits::StorageHasher;
 use super::storage::{DBIter, DB};
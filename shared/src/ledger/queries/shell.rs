@@ -4,17 +4,21 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
+use namada_core::ledger::parameters::Parameters;
 use namada_core::ledger::storage::LastBlock;
 use namada_core::types::account::{Account, AccountPublicKeysMap};
 use namada_core::types::address::Address;
+use namada_core::types::event::EventIndexQuery;
 use namada_core::types::hash::Hash;
+use namada_core::types::ibc::{RateLimit, RateLimitFlow, RateLimitQuota};
 use namada_core::types::storage::{BlockHeight, BlockResults, KeySeg};
-use namada_core::types::token::MaspDenom;
+use namada_core::types::token::{self, MaspDenom};
 
 use self::eth_bridge::{EthBridge, ETH_BRIDGE};
 use crate::ibc::core::ics04_channel::packet::Sequence;
 use crate::ibc::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::ledger::events::log::dumb_queries;
+use crate::ledger::ibc::storage::{rate_limit_flow_key, rate_limit_key};
 use crate::ledger::events::{Event, EventType};
 use crate::ledger::queries::types::{RequestCtx, RequestQuery};
 use crate::ledger::queries::{require_latest_height, EncodedResponseQuery};
@@ -76,17 +80,30 @@ router! {SHELL,
     // was the transaction applied?
     ( "applied" / [tx_hash: Hash] ) -> Option<Event> = applied,
 
+    // Look up the events in the event index with the borsh-encoded
+    // `EventIndexQuery` in the request data
+    ( "events" ) -> Vec<(BlockHeight, Event)> = (with_options events),
+
     // Query account subspace
     ( "account" / [owner: Address] ) -> Option<Account> = account,
 
     // Query public key revealad
     ( "revealed" / [owner: Address] ) -> bool = revealed,
 
+    // Current base fee per unit of gas of a token allowed for fee payment
+    ( "base_fee" / [token: Address] ) -> Option<token::Amount> = base_fee,
+
+    // Protocol parameters
+    ( "parameters" ) -> Parameters = parameters,
+
     // IBC UpdateClient event
     ( "ibc_client_update" / [client_id: ClientId] / [consensus_height: BlockHeight] ) -> Option<Event> = ibc_client_update,
 
     // IBC packet event
     ( "ibc_packet" / [event_type: EventType] / [source_port: PortId] / [source_channel: ChannelId] / [destination_port: PortId] / [destination_channel: ChannelId] / [sequence: Sequence]) -> Option<Event> = ibc_packet,
+
+    // IBC rate limit of a token through a channel, with the remaining quota
+    ( "ibc_rate_limit" / [channel_id: ChannelId] / [token: Address] ) -> Option<RateLimitQuota> = ibc_rate_limit,
 }
 
 // Handlers:
@@ -444,12 +461,19 @@ where
     H: 'static + StorageHasher + Sync,
 {
     let matcher = dumb_queries::QueryMatcher::accepted(tx_hash);
-    Ok(ctx
-        .event_log
-        .iter_with_matcher(matcher)
-        .by_ref()
+    if let Some(event) =
+        ctx.event_log.iter_with_matcher(matcher).by_ref().next()
+    {
+        return Ok(Some(event.clone()));
+    }
+    // Fall back to the event index for the events that are no longer in the
+    // in-memory log
+    let query = EventIndexQuery::new(EventType::Accepted.to_string())
+        .with("hash", tx_hash.to_string());
+    Ok(read_events(&ctx, &query)?
+        .into_iter()
         .next()
-        .cloned())
+        .map(|(_, event)| event))
 }
 
 fn applied<D, H>(
@@ -461,12 +485,58 @@ where
     H: 'static + StorageHasher + Sync,
 {
     let matcher = dumb_queries::QueryMatcher::applied(tx_hash);
-    Ok(ctx
-        .event_log
-        .iter_with_matcher(matcher)
-        .by_ref()
+    if let Some(event) =
+        ctx.event_log.iter_with_matcher(matcher).by_ref().next()
+    {
+        return Ok(Some(event.clone()));
+    }
+    let query = EventIndexQuery::new(EventType::Applied.to_string())
+        .with("hash", tx_hash.to_string());
+    Ok(read_events(&ctx, &query)?
+        .into_iter()
         .next()
-        .cloned())
+        .map(|(_, event)| event))
+}
+
+fn events<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    request: &RequestQuery,
+) -> storage_api::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let query =
+        EventIndexQuery::try_from_slice(&request.data).into_storage_result()?;
+    let data = read_events(&ctx, &query)?
+        .try_to_vec()
+        .into_storage_result()?;
+    Ok(EncodedResponseQuery {
+        data,
+        ..Default::default()
+    })
+}
+
+/// Read the events matching the query from the event index
+fn read_events<D, H>(
+    ctx: &RequestCtx<'_, D, H>,
+    query: &EventIndexQuery,
+) -> storage_api::Result<Vec<(BlockHeight, Event)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ctx.wl_storage
+        .storage
+        .db
+        .read_events(query)
+        .into_storage_result()?
+        .iter()
+        .map(|(height, event)| {
+            let event = Event::try_from(event).into_storage_result()?;
+            Ok((*height, event))
+        })
+        .collect()
 }
 
 fn ibc_client_update<D, H>(
@@ -519,6 +589,28 @@ where
         .cloned())
 }
 
+fn ibc_rate_limit<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    channel_id: ChannelId,
+    token: Address,
+) -> storage_api::Result<Option<RateLimitQuota>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let key = rate_limit_key(&channel_id, &token);
+    let Some(limit) = ctx.wl_storage.read::<RateLimit>(&key)? else {
+        return Ok(None);
+    };
+    let key = rate_limit_flow_key(&channel_id, &token);
+    let flow = ctx
+        .wl_storage
+        .read::<RateLimitFlow>(&key)?
+        .unwrap_or_default();
+    let epoch = ctx.wl_storage.storage.get_current_epoch().0;
+    Ok(Some(RateLimitQuota::new(limit, flow, epoch)))
+}
+
 fn account<D, H>(
     ctx: RequestCtx<'_, D, H>,
     owner: Address,
@@ -559,6 +651,27 @@ where
     Ok(!public_keys.is_empty())
 }
 
+fn base_fee<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    token: Address,
+) -> storage_api::Result<Option<token::Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_core::ledger::parameters::read_base_fee(ctx.wl_storage, &token)
+}
+
+fn parameters<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+) -> storage_api::Result<Parameters>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_core::ledger::parameters::read(ctx.wl_storage)
+}
+
 #[cfg(test)]
 mod test {
     use borsh::{BorshDeserialize, BorshSerialize};
@@ -6,6 +6,9 @@ use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::ledger::eth_bridge::storage::bridge_pool::get_key_from_hash;
+use namada_core::ledger::eth_bridge::storage::flow_limits::{
+    self, FlowDirection,
+};
 use namada_core::ledger::storage::merkle_tree::StoreRef;
 use namada_core::ledger::storage::{DBIter, StorageHasher, StoreType, DB};
 use namada_core::ledger::storage_api::{
@@ -57,6 +60,26 @@ pub struct Erc20FlowControl {
     cap: Amount,
 }
 
+/// Contains information about the per epoch flow limits of some
+/// ERC20 asset crossing the Ethereum bridge.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize,
+)]
+pub struct Erc20FlowLimits {
+    /// The epoch the flows below refer to.
+    pub epoch: Epoch,
+    /// The maximum amount of the asset that may enter Namada
+    /// per epoch, if a limit has been configured.
+    pub inbound_limit: Option<Amount>,
+    /// The amount of the asset that entered Namada in `epoch`.
+    pub inbound_flow: Amount,
+    /// The maximum amount of the asset that may leave Namada
+    /// per epoch, if a limit has been configured.
+    pub outbound_limit: Option<Amount>,
+    /// The amount of the asset that left Namada in `epoch`.
+    pub outbound_flow: Amount,
+}
+
 /// Request data to pass to `generate_bridge_pool_proof`.
 #[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
 pub struct GenBridgePoolProofReq<'transfers, 'relayer> {
@@ -163,6 +186,39 @@ router! {ETH_BRIDGE,
     // ERC20 token in Namada.
     ( "erc20" / "flow_control" / [asset: EthAddress] )
         -> Erc20FlowControl = get_erc20_flow_control,
+
+    // Read the per epoch inbound and outbound flow limits of some
+    // ERC20 token, along with its flows in the current epoch.
+    ( "erc20" / "flow_limits" / [asset: EthAddress] )
+        -> Erc20FlowLimits = get_erc20_flow_limits,
+}
+
+/// Read the per epoch inbound and outbound flow limits of some
+/// ERC20 token, along with its flows in the current epoch.
+fn get_erc20_flow_limits<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    asset: EthAddress,
+) -> storage_api::Result<Erc20FlowLimits>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = ctx.wl_storage.storage.get_current_epoch().0;
+    let read_limit = |direction| {
+        flow_limits::read_flow_limit(ctx.wl_storage, &asset, direction)
+    };
+    let read_flow = |direction| {
+        flow_limits::read_flow(ctx.wl_storage, &asset, direction)
+            .map(|flow| flow.amount_at(epoch))
+    };
+
+    Ok(Erc20FlowLimits {
+        epoch,
+        inbound_limit: read_limit(FlowDirection::Inbound)?,
+        inbound_flow: read_flow(FlowDirection::Inbound)?,
+        outbound_limit: read_limit(FlowDirection::Outbound)?,
+        outbound_flow: read_flow(FlowDirection::Outbound)?,
+    })
 }
 
 /// Read the total supply and respective cap of some wrapped
@@ -1556,6 +1612,66 @@ mod test_ethbridge_router {
             Ok(f) if f.supply == supply_amount && f.cap == cap_amount
         );
     }
+
+    /// Test reading the flow limits of an ERC20 token.
+    #[tokio::test]
+    async fn test_get_erc20_flow_limits() {
+        const ERC20_TOKEN: EthAddress = EthAddress([0; 20]);
+
+        let mut client = TestClient::new(RPC);
+        assert_eq!(client.wl_storage.storage.last_epoch.0, 0);
+
+        // initialize storage
+        test_utils::init_default_storage(&mut client.wl_storage);
+
+        // check limits - should not be set
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .get_erc20_flow_limits(&client, &ERC20_TOKEN)
+            .await;
+        assert_matches!(
+            result,
+            Ok(f) if f.inbound_limit.is_none()
+                && f.outbound_limit.is_none()
+                && f.inbound_flow.is_zero()
+                && f.outbound_flow.is_zero()
+        );
+
+        // write limits and flows to storage
+        let limit_amount = Amount::native_whole(12345);
+        let key = flow_limits::Key {
+            asset: ERC20_TOKEN,
+            suffix: flow_limits::KeyType::Limit(FlowDirection::Outbound),
+        }
+        .into();
+        client
+            .wl_storage
+            .write(&key, limit_amount)
+            .expect("Test failed");
+        let flow_amount = Amount::native_whole(123);
+        flow_limits::record_flow(
+            &mut client.wl_storage,
+            &ERC20_TOKEN,
+            FlowDirection::Outbound,
+            flow_amount,
+        )
+        .expect("Test failed");
+
+        // check that the limits and flows were updated
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .get_erc20_flow_limits(&client, &ERC20_TOKEN)
+            .await;
+        assert_matches!(
+            result,
+            Ok(f) if f.inbound_limit.is_none()
+                && f.outbound_limit == Some(limit_amount)
+                && f.inbound_flow.is_zero()
+                && f.outbound_flow == flow_amount
+        );
+    }
 }
 
 #[cfg(any(feature = "testing", test))]
@@ -2,6 +2,8 @@
 
 use namada_core::ledger::governance::storage::proposal::StorageProposal;
 use namada_core::ledger::governance::utils::Vote;
+use namada_core::types::address::Address;
+use namada_core::types::token;
 
 use crate::core::ledger::governance::parameters::GovernanceParameters;
 use crate::ledger::queries::types::RequestCtx;
@@ -13,6 +15,7 @@ router! {GOV,
     ( "proposal" / [id: u64 ] ) -> Option<StorageProposal> = proposal_id,
     ( "proposal" / [id: u64 ] / "votes" ) -> Vec<Vote> = proposal_id_votes,
     ( "parameters" ) -> GovernanceParameters = parameters,
+    ( "treasury" / [token: Address] ) -> token::Amount = treasury_balance,
 }
 
 /// Find if the given address belongs to a validator account.
@@ -49,3 +52,15 @@ where
 {
     storage_api::governance::get_parameters(ctx.wl_storage)
 }
+
+/// Get the balance of the governance treasury in the given token
+fn treasury_balance<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    token: Address,
+) -> storage_api::Result<token::Amount>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_treasury_balance(ctx.wl_storage, &token)
+}
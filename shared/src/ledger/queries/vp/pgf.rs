@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+
 use namada_core::ledger::governance::storage::proposal::StoragePgfFunding;
 use namada_core::ledger::pgf::storage::steward::StewardDetail;
 use namada_core::types::address::Address;
+use namada_core::types::storage::Epoch;
+use namada_core::types::token;
 
 use crate::core::ledger::pgf::parameters::PgfParameters;
 use crate::ledger::queries::types::RequestCtx;
@@ -12,6 +16,8 @@ router! {PGF,
     ( "stewards" / [ address: Address ] ) -> bool = is_steward,
     ( "stewards" ) -> Vec<StewardDetail> = stewards,
     ( "fundings" ) -> Vec<StoragePgfFunding> = funding,
+    ( "payouts" / [ target: Address ] )
+        -> BTreeMap<Epoch, token::Amount> = payouts,
     ( "parameters" ) -> PgfParameters = parameters,
 }
 
@@ -49,6 +55,18 @@ where
     storage_api::pgf::get_payments(ctx.wl_storage)
 }
 
+/// Query the history of the pgf funding payouts to a target
+fn payouts<D, H>(
+    ctx: RequestCtx<'_, D, H>,
+    target: Address,
+) -> storage_api::Result<BTreeMap<Epoch, token::Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::pgf::get_payouts(ctx.wl_storage, &target)
+}
+
 /// Query the PGF parameters
 fn parameters<D, H>(
     ctx: RequestCtx<'_, D, H>,
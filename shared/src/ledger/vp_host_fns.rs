@@ -3,13 +3,14 @@
 use std::num::TryFromIntError;
 
 use namada_core::types::address::Address;
-use namada_core::types::hash::Hash;
+use namada_core::types::hash::{Hash, HashAlgorithm};
+use namada_core::types::key::{common, SigScheme};
 use namada_core::types::storage::{
     BlockHash, BlockHeight, Epoch, Header, Key, TxIndex,
 };
+use namada_core::types::time::DateTimeUtc;
 use thiserror::Error;
 
-use super::gas::STORAGE_ACCESS_GAS_PER_BYTE;
 use crate::ledger::gas;
 use crate::ledger::gas::{GasMetering, VpGasMeter};
 use crate::ledger::storage::write_log::WriteLog;
@@ -38,6 +39,8 @@ pub enum RuntimeError {
     ReadPermanentValueError,
     #[error("Invalid transaction code hash")]
     InvalidCodeHash,
+    #[error("Hash error: {0}")]
+    HashError(namada_core::types::hash::Error),
 }
 
 /// VP environment function result
@@ -283,15 +286,20 @@ where
 
 /// Getting the block hash. The height is that of the block to which the
 /// current transaction is being applied.
-pub fn get_tx_code_hash(
+pub fn get_tx_code_hash<DB, H>(
     gas_meter: &mut VpGasMeter,
+    storage: &Storage<DB, H>,
     tx: &Tx,
-) -> EnvResult<Option<Hash>> {
+) -> EnvResult<Option<Hash>>
+where
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+{
     let hash = tx
         .get_section(tx.code_sechash())
         .and_then(|x| Section::code_sec(x.as_ref()))
         .map(|x| x.code.hash());
-    add_gas(gas_meter, STORAGE_ACCESS_GAS_PER_BYTE)?;
+    add_gas(gas_meter, storage.gas_costs.storage_access_per_byte)?;
     Ok(hash)
 }
 
@@ -310,13 +318,35 @@ where
     Ok(epoch)
 }
 
+/// Getting the block time. The time is that of the block to which the
+/// current transaction is being applied.
+pub fn get_block_time<DB, H>(
+    gas_meter: &mut VpGasMeter,
+    storage: &Storage<DB, H>,
+) -> EnvResult<DateTimeUtc>
+where
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+{
+    let (time, gas) = storage
+        .get_block_time()
+        .map_err(RuntimeError::StorageError)?;
+    add_gas(gas_meter, gas)?;
+    Ok(time)
+}
+
 /// Getting the block epoch. The epoch is that of the block to which the
 /// current transaction is being applied.
-pub fn get_tx_index(
+pub fn get_tx_index<DB, H>(
     gas_meter: &mut VpGasMeter,
+    storage: &Storage<DB, H>,
     tx_index: &TxIndex,
-) -> EnvResult<TxIndex> {
-    add_gas(gas_meter, STORAGE_ACCESS_GAS_PER_BYTE)?;
+) -> EnvResult<TxIndex>
+where
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+{
+    add_gas(gas_meter, storage.gas_costs.storage_access_per_byte)?;
     Ok(*tx_index)
 }
 
@@ -329,10 +359,52 @@ where
     DB: storage::DB + for<'iter> storage::DBIter<'iter>,
     H: StorageHasher,
 {
-    add_gas(gas_meter, STORAGE_ACCESS_GAS_PER_BYTE)?;
+    add_gas(gas_meter, storage.gas_costs.storage_access_per_byte)?;
     Ok(storage.native_token.clone())
 }
 
+/// Hash the data with the given algorithm.
+pub fn hash<DB, H>(
+    gas_meter: &mut VpGasMeter,
+    storage: &Storage<DB, H>,
+    algorithm: HashAlgorithm,
+    data: &[u8],
+) -> EnvResult<Hash>
+where
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+{
+    add_gas(
+        gas_meter,
+        data.len() as u64 * storage.gas_costs.hash_per_byte,
+    )?;
+    Ok(algorithm.hash(data))
+}
+
+/// Verify a signature over the raw data, see
+/// [`SigScheme::verify_raw_signature`].
+pub fn verify_signature<DB, H>(
+    gas_meter: &mut VpGasMeter,
+    storage: &Storage<DB, H>,
+    public_key: &common::PublicKey,
+    signature: &common::Signature,
+    data: &[u8],
+) -> EnvResult<bool>
+where
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+{
+    let gas_costs = &storage.gas_costs;
+    add_gas(
+        gas_meter,
+        gas_costs.verify_sig + data.len() as u64 * gas_costs.hash_per_byte,
+    )?;
+    Ok(
+        common::SigScheme::verify_raw_signature(public_key, data, signature)
+            .is_ok(),
+    )
+}
+
 /// Storage prefix iterator for prior state (before tx execution), ordered by
 /// storage keys. It will try to get an iterator from the storage.
 pub fn iter_prefix_pre<'a, DB, H>(
@@ -160,7 +160,7 @@ pub struct TxIbcTransfer<C: NamadaTypes = SdkTypes> {
     /// Common tx arguments
     pub tx: Tx<C>,
     /// Transfer source address
-    pub source: C::Address,
+    pub source: C::TransferSource,
     /// Transfer target address
     pub receiver: String,
     /// Transferred token addres    s
@@ -181,6 +181,27 @@ pub struct TxIbcTransfer<C: NamadaTypes = SdkTypes> {
     pub tx_code_path: PathBuf,
 }
 
+/// Generate the shielded transfer for an IBC transfer to a payment address
+#[derive(Clone, Debug)]
+pub struct GenIbcShieldedTransfer<C: NamadaTypes = SdkTypes> {
+    /// The query parameters
+    pub query: Query<C>,
+    /// The output directory path to where serialize the data
+    pub output_folder: Option<PathBuf>,
+    /// The payment address receiving the tokens
+    pub target: C::TransferTarget,
+    /// The token address or the IBC denom of the received token
+    pub token: String,
+    /// Transferred token amount
+    pub amount: InputAmount,
+    /// Port ID of this chain's channel end
+    pub port_id: PortId,
+    /// Channel ID of this chain's channel end
+    pub channel_id: ChannelId,
+    /// Generate the transfer even if the token denomination is unknown
+    pub force: bool,
+}
+
 /// Transaction to initialize create a new proposal
 #[derive(Clone, Debug)]
 pub struct InitProposal<C: NamadaTypes = SdkTypes> {
@@ -196,6 +217,12 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type parameter change
+    pub is_parameter_change: bool,
+    /// Flag if proposal is of type upgrade
+    pub is_upgrade: bool,
+    /// Flag if proposal is of type treasury
+    pub is_treasury: bool,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -339,6 +366,17 @@ pub struct QueryProposal<C: NamadaTypes = SdkTypes> {
 pub struct QueryProtocolParameters<C: NamadaTypes = SdkTypes> {
     /// Common query args
     pub query: Query<C>,
+    /// Native token address
+    pub native_token: C::NativeAddress,
+}
+
+/// Query the governance treasury
+#[derive(Clone, Debug)]
+pub struct QueryTreasury<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Token address, all the tokens of the wallet if not given
+    pub token: Option<C::Address>,
 }
 
 /// Query pgf data
@@ -346,6 +384,17 @@ pub struct QueryProtocolParameters<C: NamadaTypes = SdkTypes> {
 pub struct QueryPgf<C: NamadaTypes = SdkTypes> {
     /// Common query args
     pub query: Query<C>,
+    /// Show the funding streams payouts
+    pub payments: bool,
+}
+
+/// Query the shielded rewards of tokens
+#[derive(Clone, Debug)]
+pub struct QueryShieldedRewards<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Token address, all the rewarded tokens if not given
+    pub token: Option<C::Address>,
 }
 
 /// Withdraw arguments
@@ -476,6 +525,42 @@ pub struct ResignSteward<C: NamadaTypes = SdkTypes> {
     pub tx_code_path: PathBuf,
 }
 
+#[derive(Clone, Debug)]
+/// Register a user-defined token args
+pub struct InitToken<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The established account of the token
+    pub token: C::Address,
+    /// The only address allowed to mint the token
+    pub minter: C::Address,
+    /// The maximum amount of the token that may be minted, if any
+    pub max_supply: Option<token::DenominatedAmount>,
+    /// The number of decimal places of the token
+    pub denom: u8,
+    /// The name of the token
+    pub name: String,
+    /// The ticker symbol of the token
+    pub symbol: String,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+#[derive(Clone, Debug)]
+/// Mint a user-defined token args
+pub struct MintToken<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The token to mint
+    pub token: C::Address,
+    /// The receiver of the minted tokens
+    pub target: C::Address,
+    /// The amount to mint
+    pub amount: InputAmount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
 #[derive(Clone, Debug)]
 /// Re-activate a jailed validator args
 pub struct TxUnjailValidator<C: NamadaTypes = SdkTypes> {
@@ -568,7 +653,8 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     /// Whether to force overwrite the above alias, if it is provided, in the
     /// wallet.
     pub wallet_alias_force: bool,
-    /// The amount being payed (for gas unit) to include the transaction
+    /// The amount being payed (for gas unit) to include the transaction. The
+    /// current base fee is used if not given.
     pub fee_amount: Option<InputAmount>,
     /// The fee payer signing key
     pub wrapper_fee_payer: Option<C::Keypair>,
@@ -156,6 +156,9 @@ pub enum TxError {
     /// The address is not a valid steward
     #[error("The address {0} is not a valid steward.")]
     InvalidSteward(Address),
+    /// The settings of a user-defined token are not valid
+    #[error("Invalid token settings: {0}.")]
+    InvalidTokenSettings(String),
     /// Rate of epoch change too large for current epoch
     #[error(
         "New rate, {0}, is too large of a change with respect to the \
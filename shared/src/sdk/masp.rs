@@ -69,7 +69,10 @@ use crate::tendermint_rpc::query::Query;
 use crate::tendermint_rpc::Order;
 use crate::types::address::{masp, Address};
 use crate::types::io::Io;
-use crate::types::masp::{BalanceOwner, ExtendedViewingKey, PaymentAddress};
+use crate::types::masp::{
+    BalanceOwner, ExtendedViewingKey, PaymentAddress, TransferSource,
+    TransferTarget,
+};
 use crate::types::storage::{BlockHeight, Epoch, Key, KeySeg, TxIndex};
 use crate::types::token;
 use crate::types::token::{
@@ -1487,6 +1490,31 @@ impl<U: ShieldedUtils> ShieldedContext<U> {
         &mut self,
         client: &C,
         args: args::TxTransfer,
+    ) -> Result<Option<ShieldedTransfer>, TransferErr> {
+        let InputAmount::Validated(amt) = args.amount else {
+            unreachable!("The function `gen_shielded_transfer` is only called by `submit_tx` which validates amounts.")
+        };
+        self.gen_shielded_transfer_for::<_, IO>(
+            client,
+            &args.source,
+            &args.target,
+            &args.token,
+            amt,
+        )
+        .await
+    }
+
+    /// Make shielded components for a transfer of the given amount of the
+    /// token from the source to the target. Refer to
+    /// [`Self::gen_shielded_transfer`] for the produced components.
+    #[cfg(feature = "masp-tx-gen")]
+    pub async fn gen_shielded_transfer_for<C: Client + Sync, IO: Io>(
+        &mut self,
+        client: &C,
+        source: &TransferSource,
+        target: &TransferTarget,
+        token: &Address,
+        amt: token::DenominatedAmount,
     ) -> Result<Option<ShieldedTransfer>, TransferErr> {
         // No shielded components are needed when neither source nor destination
         // are shielded
@@ -1496,8 +1524,8 @@ impl<U: ShieldedUtils> ShieldedContext<U> {
         use rand::rngs::StdRng;
         use rand_core::SeedableRng;
 
-        let spending_key = args.source.spending_key();
-        let payment_address = args.target.payment_address();
+        let spending_key = source.spending_key();
+        let payment_address = target.payment_address();
         // No shielded components are needed when neither source nor
         // destination are shielded
         if spending_key.is_none() && payment_address.is_none() {
@@ -1542,12 +1570,8 @@ impl<U: ShieldedUtils> ShieldedContext<U> {
 
         // break up a transfer into a number of transfers with suitable
         // denominations
-        let InputAmount::Validated(amt) = args.amount else {
-            unreachable!("The function `gen_shielded_transfer` is only called by `submit_tx` which validates amounts.")
-        };
         // Convert transaction amount into MASP types
-        let (asset_types, amount) =
-            convert_amount(epoch, &args.token, amt.amount)?;
+        let (asset_types, amount) = convert_amount(epoch, token, amt.amount)?;
 
         // If there are shielded inputs
         if let Some(sk) = spending_key {
@@ -1582,8 +1606,7 @@ impl<U: ShieldedUtils> ShieldedContext<U> {
             // We add a dummy UTXO to our transaction, but only the source of
             // the parent Transfer object is used to validate fund
             // availability
-            let source_enc = args
-                .source
+            let source_enc = source
                 .address()
                 .ok_or_else(|| {
                     Error::Other(
@@ -1631,8 +1654,7 @@ impl<U: ShieldedUtils> ShieldedContext<U> {
         } else {
             // Embed the transparent target address into the shielded
             // transaction so that it can be signed
-            let target_enc = args
-                .target
+            let target_enc = target
                 .address()
                 .ok_or_else(|| {
                     Error::Other(
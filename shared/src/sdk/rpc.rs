@@ -4,16 +4,19 @@ use std::cell::Cell;
 use std::collections::{HashMap, HashSet};
 use std::ops::ControlFlow;
 
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_core::ledger::governance::parameters::GovernanceParameters;
 use namada_core::ledger::governance::storage::proposal::StorageProposal;
 use namada_core::ledger::governance::utils::Vote;
+use namada_core::ledger::parameters::Parameters;
 use namada_core::ledger::storage::LastBlock;
 use namada_core::types::account::Account;
 use namada_core::types::address::Address;
+use namada_core::types::event::EventIndexQuery;
+use namada_core::types::ibc::RateLimitQuota;
 use namada_core::types::storage::Key;
 use namada_core::types::token::{
     Amount, DenominatedAmount, Denomination, MaspDenom,
@@ -24,6 +27,7 @@ use namada_proof_of_stake::types::{
 };
 use serde::Serialize;
 
+use crate::ibc::core::ics24_host::identifier::ChannelId;
 use crate::ledger::events::Event;
 use crate::ledger::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
 use crate::ledger::queries::RPC;
@@ -113,6 +117,18 @@ pub async fn query_epoch_at_height<C: crate::ledger::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().epoch_at_height(client, &height).await)
 }
 
+/// Query the rate limit of the token through the IBC channel with the
+/// remaining quota of the current period, if a limit has been set.
+pub async fn query_ibc_rate_limit<C: crate::ledger::queries::Client + Sync>(
+    client: &C,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> Result<Option<RateLimitQuota>, error::Error> {
+    convert_response::<C, _>(
+        RPC.shell().ibc_rate_limit(client, channel_id, token).await,
+    )
+}
+
 /// Query the last committed block, if any.
 pub async fn query_block<C: crate::ledger::queries::Client + Sync>(
     client: &C,
@@ -436,6 +452,21 @@ pub async fn query_tx_events<C: crate::ledger::queries::Client + Sync>(
     }
 }
 
+/// Look up the events matching the query in the node's event index, which
+/// keeps the events of past blocks across restarts
+pub async fn query_events<C: crate::ledger::queries::Client + Sync>(
+    client: &C,
+    query: &EventIndexQuery,
+) -> Result<Vec<(BlockHeight, Event)>, Error> {
+    let data = query
+        .try_to_vec()
+        .map_err(|e| Error::from(EncodingError::Encode(e.to_string())))?;
+    let response = convert_response::<C, _>(
+        RPC.shell().events(client, Some(data), None, false).await,
+    )?;
+    Ok(response.data)
+}
+
 /// Dry run a transaction
 pub async fn dry_run_tx<C: crate::ledger::queries::Client + Sync, IO: Io>(
     client: &C,
@@ -770,6 +801,26 @@ pub async fn is_public_key_revealed<
     convert_response::<C, bool>(RPC.shell().revealed(client, owner).await)
 }
 
+/// Query the current base fee per unit of gas of a token. Returns `None` if
+/// the token is not allowed for fee payment.
+pub async fn query_base_fee<C: crate::ledger::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+) -> Result<Option<token::Amount>, error::Error> {
+    convert_response::<C, Option<token::Amount>>(
+        RPC.shell().base_fee(client, token).await,
+    )
+}
+
+/// Query the protocol parameters
+pub async fn get_protocol_parameters<
+    C: crate::ledger::queries::Client + Sync,
+>(
+    client: &C,
+) -> Result<Parameters, error::Error> {
+    convert_response::<C, Parameters>(RPC.shell().parameters(client).await)
+}
+
 /// Query an account substorage at a specific index
 pub async fn get_public_key_at<C: crate::ledger::queries::Client + Sync>(
     client: &C,
@@ -871,6 +922,18 @@ pub async fn query_governance_parameters<
     unwrap_client_response::<C, _>(RPC.vp().gov().parameters(client).await)
 }
 
+/// Get the balance of the governance treasury in the given token
+pub async fn query_treasury_balance<
+    C: crate::ledger::queries::Client + Sync,
+>(
+    client: &C,
+    token: &Address,
+) -> Result<token::Amount, error::Error> {
+    convert_response::<C, token::Amount>(
+        RPC.vp().gov().treasury_balance(client, token).await,
+    )
+}
+
 /// Get the givernance parameters
 pub async fn query_proposal_votes<C: crate::ledger::queries::Client + Sync>(
     client: &C,
@@ -1,5 +1,5 @@
 //! Functions to sign transactions
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -336,23 +336,18 @@ pub async fn wrap_tx<
     fee_payer: common::PublicKey,
 ) -> Result<Option<Epoch>, Error> {
     let fee_payer_address = Address::from(&fee_payer);
-    // Validate fee amount and token
-    let gas_cost_key = parameter_storage::get_gas_cost_key();
-    let minimum_fee = match rpc::query_storage_value::<
-        C,
-        BTreeMap<Address, Amount>,
-    >(client, &gas_cost_key)
-    .await
-    .and_then(|map| {
-        map.get(&args.fee_token)
-            .map(ToOwned::to_owned)
-            .ok_or_else(|| {
+    // Validate fee amount and token against the current base fee
+    let minimum_fee = match rpc::query_base_fee(client, &args.fee_token)
+        .await
+        .and_then(|base_fee| {
+            base_fee.ok_or_else(|| {
                 Error::Other(format!(
-                    "Could not retrieve from storage the gas cost for token {}",
+                    "Could not retrieve the base fee for token {}, it may not \
+                     be allowed for fee payment",
                     args.fee_token
                 ))
             })
-    }) {
+        }) {
         Ok(amount) => amount,
         Err(e) => {
             if !args.force {
@@ -382,8 +377,8 @@ pub async fn wrap_tx<
                 // Update the fee amount if it's not enough
                 display_line!(
                     IO,
-                    "The provided gas price {} is less than the minimum \
-                     amount required {}, changing it to match the minimum",
+                    "The provided gas price {} is less than the current base \
+                     fee {}, changing it to match the base fee",
                     amount.to_string_native(),
                     minimum_fee.to_string_native()
                 );
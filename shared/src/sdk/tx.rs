@@ -17,11 +17,13 @@ use masp_primitives::transaction::components::transparent::fees::{
 };
 use masp_primitives::transaction::components::I32Sum;
 use namada_core::ledger::governance::cli::onchain::{
-    DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
-    ProposalVote,
+    DefaultProposal, OnChainProposal, ParameterChangeProposal,
+    PgfFundingProposal, PgfStewardProposal, ProposalVote, SplitProposalVote,
+    TreasuryProposal, UpgradeProposal,
 };
 use namada_core::ledger::governance::storage::proposal::ProposalType;
 use namada_core::ledger::governance::storage::vote::StorageProposalVote;
+use namada_core::ledger::ibc::MsgShieldedTransfer;
 use namada_core::ledger::pgf::cli::steward::Commission;
 use namada_core::types::address::{masp, Address, InternalAddress};
 use namada_core::types::dec::Dec;
@@ -41,7 +43,7 @@ use crate::ibc::core::ics04_channel::timeout::TimeoutHeight;
 use crate::ibc::core::timestamp::Timestamp as IbcTimestamp;
 use crate::ibc::core::Msg;
 use crate::ibc::Height as IbcHeight;
-use crate::ledger::ibc::storage::ibc_denom_key;
+use crate::ledger::ibc::storage::{ibc_denom_key, ibc_token};
 use crate::proto::{MaspBuilder, Tx};
 use crate::sdk::args::{self, InputAmount};
 use crate::sdk::error::{EncodingError, Error, QueryError, Result, TxError};
@@ -56,9 +58,10 @@ use crate::sdk::wallet::{Wallet, WalletUtils};
 use crate::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use crate::tendermint_rpc::error::Error as RpcError;
 use crate::types::control_flow::{time, ProceedOrElse};
+use crate::types::ibc::IbcShieldedTransfer;
 use crate::types::io::Io;
 use crate::types::key::*;
-use crate::types::masp::TransferTarget;
+use crate::types::masp::{TransferSource, TransferTarget};
 use crate::types::storage::Epoch;
 use crate::types::time::DateTimeUtc;
 use crate::types::transaction::account::{InitAccount, UpdateAccount};
@@ -702,6 +705,153 @@ pub async fn build_resign_steward<
     .await
 }
 
+/// Craft transaction to register a user-defined token
+pub async fn build_init_token<
+    C: crate::sdk::queries::Client + Sync,
+    U: WalletUtils,
+    V: ShieldedUtils,
+    IO: Io,
+>(
+    client: &C,
+    wallet: &mut Wallet<U>,
+    shielded: &mut ShieldedContext<V>,
+    args::InitToken {
+        tx: tx_args,
+        token,
+        minter,
+        max_supply,
+        denom,
+        name,
+        symbol,
+        tx_code_path,
+    }: args::InitToken,
+    gas_payer: &common::PublicKey,
+) -> Result<(Tx, Option<Epoch>)> {
+    if !matches!(token, Address::Established(_)) {
+        edisplay_line!(
+            IO,
+            "The token {} must be an established account.",
+            &token
+        );
+        return Err(Error::from(TxError::InvalidTokenSettings(format!(
+            "{token} is not an established account"
+        ))));
+    }
+
+    let metadata = token::TokenMetadata { name, symbol };
+    if !metadata.is_valid() && !tx_args.force {
+        edisplay_line!(
+            IO,
+            "The token name must not be empty and the symbol must be made of \
+             at most {} alphanumeric ASCII characters.",
+            token::TokenMetadata::MAX_SYMBOL_LEN
+        );
+        return Err(Error::from(TxError::InvalidTokenSettings(
+            "invalid name or symbol".to_string(),
+        )));
+    }
+
+    let denom = token::Denomination(denom);
+    let max_supply = max_supply
+        .map(|max_supply| {
+            max_supply.increase_precision(denom).map_err(|e| {
+                edisplay_line!(
+                    IO,
+                    "The max supply {} can't be represented with {} decimal \
+                     places: {}",
+                    max_supply,
+                    denom.0,
+                    e
+                );
+                Error::from(TxError::InvalidTokenSettings(e.to_string()))
+            })
+        })
+        .transpose()?
+        .map(|max_supply| max_supply.amount);
+
+    let data = token::InitToken {
+        token,
+        minter,
+        max_supply,
+        denom,
+        metadata,
+    };
+
+    build::<_, _, _, _, _, IO>(
+        client,
+        wallet,
+        shielded,
+        &tx_args,
+        tx_code_path,
+        data,
+        do_nothing,
+        gas_payer,
+        None,
+    )
+    .await
+}
+
+/// Craft transaction to mint a user-defined token
+pub async fn build_mint_token<
+    C: crate::sdk::queries::Client + Sync,
+    U: WalletUtils,
+    V: ShieldedUtils,
+    IO: Io,
+>(
+    client: &C,
+    wallet: &mut Wallet<U>,
+    shielded: &mut ShieldedContext<V>,
+    args::MintToken {
+        tx: tx_args,
+        token,
+        target,
+        amount,
+        tx_code_path,
+    }: args::MintToken,
+    gas_payer: &common::PublicKey,
+) -> Result<(Tx, Option<Epoch>)> {
+    let metadata = rpc::query_storage_value::<C, token::TokenMetadata>(
+        client,
+        &token::metadata_key(&token),
+    )
+    .await;
+    if metadata.is_err() && !tx_args.force {
+        edisplay_line!(
+            IO,
+            "The token {} is not a registered user-defined token.",
+            &token
+        );
+        return Err(Error::from(TxError::InvalidTokenSettings(format!(
+            "{token} is not registered"
+        ))));
+    }
+    // Check that the target address exists on chain
+    target_exists_or_err::<_, IO>(target.clone(), tx_args.force, client)
+        .await?;
+
+    let amount =
+        validate_amount::<_, IO>(client, amount, &token, tx_args.force).await?;
+
+    let data = token::MintToken {
+        token,
+        target,
+        amount,
+    };
+
+    build::<_, _, _, _, _, IO>(
+        client,
+        wallet,
+        shielded,
+        &tx_args,
+        tx_code_path,
+        data,
+        do_nothing,
+        gas_payer,
+        None,
+    )
+    .await
+}
+
 /// Submit transaction to unjail a jailed validator
 pub async fn build_unjail_validator<
     C: crate::sdk::queries::Client + Sync,
@@ -1138,6 +1288,9 @@ pub async fn build_default_proposal<
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        is_treasury: _,
         tx_code_path,
     }: args::InitProposal,
     proposal: DefaultProposal,
@@ -1196,9 +1349,6 @@ pub async fn build_vote_proposal<
     epoch: Epoch,
     fee_payer: common::PublicKey,
 ) -> Result<(Tx, Option<Epoch>)> {
-    let proposal_vote = ProposalVote::try_from(vote)
-        .map_err(|_| TxError::InvalidProposalVote)?;
-
     let proposal_id = proposal_id.ok_or_else(|| {
         Error::Other("Proposal id must be defined.".to_string())
     })?;
@@ -1210,13 +1360,24 @@ pub async fn build_vote_proposal<
         return Err(Error::from(TxError::ProposalDoesNotExist(proposal_id)));
     };
 
-    let storage_vote =
+    // A vote split by weight is given as a list of `vote=weight` options
+    let storage_vote = if vote.contains('=') {
+        let split_vote = SplitProposalVote::try_from(vote)
+            .map_err(|_| TxError::InvalidProposalVote)?;
+        StorageProposalVote::build_split(&split_vote, &proposal.r#type)
+    } else {
+        let proposal_vote = ProposalVote::try_from(vote)
+            .map_err(|_| TxError::InvalidProposalVote)?;
         StorageProposalVote::build(&proposal_vote, &proposal.r#type)
-            .ok_or_else(|| {
-                Error::from(TxError::Other(
-                    "Should be able to build the proposal vote".to_string(),
-                ))
-            })?;
+    }
+    .ok_or_else(|| {
+        Error::from(TxError::Other(
+            "Should be able to build the proposal vote".to_string(),
+        ))
+    })?;
+    if !storage_vote.is_valid() {
+        return Err(Error::from(TxError::InvalidProposalVote));
+    }
 
     let is_validator = rpc::is_validator(client, &voter).await?;
 
@@ -1240,6 +1401,23 @@ pub async fn build_vote_proposal<
     .cloned()
     .collect::<Vec<Address>>();
 
+    // Votes can be changed until the end of the voting period, in which case
+    // the new vote replaces the previous one
+    let previous_vote = rpc::query_proposal_votes(client, proposal_id)
+        .await?
+        .into_iter()
+        .find(|vote| vote.delegator == voter);
+    if let Some(previous_vote) = previous_vote {
+        display_line!(
+            IO,
+            "Changing the previous vote ({}) of {} on proposal {} to {}.",
+            previous_vote.data,
+            voter,
+            proposal_id,
+            storage_vote
+        );
+    }
+
     let data = VoteProposalData {
         id: proposal_id,
         vote: storage_vote,
@@ -1278,11 +1456,28 @@ pub async fn build_pgf_funding_proposal<
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        is_treasury: _,
         tx_code_path,
     }: args::InitProposal,
     proposal: PgfFundingProposal,
     fee_payer: &common::PublicKey,
 ) -> Result<(Tx, Option<Epoch>)> {
+    let author = &proposal.proposal.author;
+    if !proposal.data.cancel.is_empty()
+        && !rpc::is_steward(client, author).await
+        && !tx.force
+    {
+        edisplay_line!(
+            IO,
+            "Only a steward can propose to cancel a funding stream, but {} is \
+             not a steward.",
+            author
+        );
+        return Err(Error::from(TxError::InvalidSteward(author.clone())));
+    }
+
     let init_proposal_data = InitProposalData::try_from(proposal.clone())
         .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
 
@@ -1323,6 +1518,9 @@ pub async fn build_pgf_stewards_proposal<
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        is_treasury: _,
         tx_code_path,
     }: args::InitProposal,
     proposal: PgfStewardProposal,
@@ -1352,6 +1550,192 @@ pub async fn build_pgf_stewards_proposal<
     .await
 }
 
+/// Build a parameter change proposal governance
+pub async fn build_parameter_change_proposal<
+    C: crate::sdk::queries::Client + Sync,
+    U: WalletUtils,
+    V: ShieldedUtils,
+    IO: Io,
+>(
+    client: &C,
+    wallet: &mut Wallet<U>,
+    shielded: &mut ShieldedContext<V>,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        native_token: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        is_treasury: _,
+        tx_code_path,
+    }: args::InitProposal,
+    proposal: ParameterChangeProposal,
+    fee_payer: common::PublicKey,
+) -> Result<(Tx, Option<Epoch>)> {
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build::<_, _, _, _, _, IO>(
+        client,
+        wallet,
+        shielded,
+        &tx,
+        tx_code_path,
+        init_proposal_data,
+        add_section,
+        &fee_payer,
+        None, // TODO: need to pay the fee to submit a proposal
+    )
+    .await
+}
+
+/// Build a software upgrade proposal governance
+pub async fn build_upgrade_proposal<
+    C: crate::sdk::queries::Client + Sync,
+    U: WalletUtils,
+    V: ShieldedUtils,
+    IO: Io,
+>(
+    client: &C,
+    wallet: &mut Wallet<U>,
+    shielded: &mut ShieldedContext<V>,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        native_token: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        is_treasury: _,
+        tx_code_path,
+    }: args::InitProposal,
+    proposal: UpgradeProposal,
+    fee_payer: common::PublicKey,
+) -> Result<(Tx, Option<Epoch>)> {
+    let last_block = rpc::query_block(client).await?;
+    let last_height =
+        last_block.map(|block| block.height.0).unwrap_or_default();
+    if proposal.data.height <= last_height && !tx.force {
+        edisplay_line!(
+            IO,
+            "The upgrade height {} must be greater than the last block height \
+             {}.",
+            proposal.data.height,
+            last_height
+        );
+        return Err(Error::from(TxError::InvalidProposal(format!(
+            "the upgrade height {} has already been reached",
+            proposal.data.height
+        ))));
+    }
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build::<_, _, _, _, _, IO>(
+        client,
+        wallet,
+        shielded,
+        &tx,
+        tx_code_path,
+        init_proposal_data,
+        add_section,
+        &fee_payer,
+        None, // TODO: need to pay the fee to submit a proposal
+    )
+    .await
+}
+
+/// Build a treasury spending proposal governance
+pub async fn build_treasury_proposal<
+    C: crate::sdk::queries::Client + Sync,
+    U: WalletUtils,
+    V: ShieldedUtils,
+    IO: Io,
+>(
+    client: &C,
+    wallet: &mut Wallet<U>,
+    shielded: &mut ShieldedContext<V>,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        native_token: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        is_treasury: _,
+        tx_code_path,
+    }: args::InitProposal,
+    proposal: TreasuryProposal,
+    fee_payer: common::PublicKey,
+) -> Result<(Tx, Option<Epoch>)> {
+    let mut spendings: BTreeMap<&Address, token::Amount> = BTreeMap::new();
+    for transfer in &proposal.data {
+        *spendings.entry(&transfer.token).or_default() += transfer.amount;
+    }
+    for (token, amount) in spendings {
+        let balance = rpc::query_treasury_balance(client, token).await?;
+        if balance < amount && !tx.force {
+            edisplay_line!(
+                IO,
+                "The treasury holds {} of token {}, which is not enough to \
+                 cover the requested {}.",
+                balance.to_string_native(),
+                token,
+                amount.to_string_native()
+            );
+            return Err(Error::from(TxError::InvalidProposal(format!(
+                "the treasury balance of token {} is too low",
+                token
+            ))));
+        }
+    }
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build::<_, _, _, _, _, IO>(
+        client,
+        wallet,
+        shielded,
+        &tx,
+        tx_code_path,
+        init_proposal_data,
+        add_section,
+        &fee_payer,
+        None, // TODO: need to pay the fee to submit a proposal
+    )
+    .await
+}
+
 /// Submit an IBC transfer
 pub async fn build_ibc_transfer<
     C: crate::sdk::queries::Client + Sync,
@@ -1365,13 +1749,10 @@ pub async fn build_ibc_transfer<
     args: args::TxIbcTransfer,
     fee_payer: common::PublicKey,
 ) -> Result<(Tx, Option<Epoch>)> {
+    let source = args.source.effective_address();
     // Check that the source address exists on chain
-    let source = source_exists_or_err::<_, IO>(
-        args.source.clone(),
-        args.tx.force,
-        client,
-    )
-    .await?;
+    let source =
+        source_exists_or_err::<_, IO>(source, args.tx.force, client).await?;
     // We cannot check the receiver
 
     // validate the amount given
@@ -1407,6 +1788,30 @@ pub async fn build_ibc_transfer<
         token: args.token.clone(),
     });
 
+    // For a transfer from a spending key, the tokens are unshielded to the
+    // MASP address before being sent by the IBC transfer
+    let shielded_parts = match shielded
+        .gen_shielded_transfer_for::<_, IO>(
+            client,
+            &args.source,
+            &TransferTarget::Address(Address::Internal(InternalAddress::Ibc)),
+            &args.token,
+            validated_amount,
+        )
+        .await
+    {
+        Ok(stx) => Ok(stx),
+        Err(Build(builder::Error::InsufficientFunds(_))) => {
+            Err(TxError::NegativeBalanceAfterTransfer(
+                Box::new(source.clone()),
+                validated_amount.amount.to_string_native(),
+                Box::new(args.token.clone()),
+            ))
+        }
+        Err(err) => Err(TxError::MaspError(err.to_string())),
+    }?;
+    let shielded_tx_epoch = shielded_parts.as_ref().map(|stx| stx.epoch);
+
     let tx_code_hash = query_wasm_code_hash::<_, IO>(
         client,
         args.tx_code_path.to_str().unwrap(),
@@ -1470,17 +1875,43 @@ pub async fn build_ibc_transfer<
         timeout_timestamp_on_b: timeout_timestamp,
     };
 
-    let any_msg = msg.to_any();
-    let mut data = vec![];
-    prost::Message::encode(&any_msg, &mut data)
-        .map_err(TxError::EncodeFailure)?;
+    let data = match shielded_parts {
+        Some(shielded_transfer) => {
+            let transfer = token::Transfer {
+                source: source.clone(),
+                // The unshielded tokens are escrowed or burned by the IBC
+                // transfer
+                target: Address::Internal(InternalAddress::Ibc),
+                token: args.token.clone(),
+                amount: validated_amount,
+                key: None,
+                shielded: None,
+            };
+            let msg = MsgShieldedTransfer {
+                message: msg,
+                shielded_transfer: IbcShieldedTransfer {
+                    transfer,
+                    masp_tx: shielded_transfer.masp_tx,
+                },
+            };
+            msg.try_to_vec()
+                .map_err(|e| TxError::EncodeTxFailure(e.to_string()))?
+        }
+        None => {
+            let any_msg = msg.to_any();
+            let mut data = vec![];
+            prost::Message::encode(&any_msg, &mut data)
+                .map_err(TxError::EncodeFailure)?;
+            data
+        }
+    };
 
     let chain_id = args.tx.chain_id.clone().unwrap();
     let mut tx = Tx::new(chain_id, args.tx.expiration);
     tx.add_code_from_hash(tx_code_hash)
         .add_serialized_data(data);
 
-    let epoch = prepare_tx::<C, U, V, IO>(
+    let fee_unshield_epoch = prepare_tx::<C, U, V, IO>(
         client,
         wallet,
         shielded,
@@ -1490,8 +1921,80 @@ pub async fn build_ibc_transfer<
         tx_source_balance,
     )
     .await?;
+    // Manage the two masp epochs
+    let masp_epoch = match (fee_unshield_epoch, shielded_tx_epoch) {
+        (Some(fee_epoch), Some(transfer_epoch)) => {
+            if fee_epoch != transfer_epoch && !args.tx.force {
+                return Err(Error::Other(
+                    "Fee unshielding masp tx and the IBC transfer masp \
+                     transaction were crafted on an epoch boundary"
+                        .to_string(),
+                ));
+            }
+            Some(fee_epoch.min(transfer_epoch))
+        }
+        (fee_epoch, transfer_epoch) => fee_epoch.or(transfer_epoch),
+    };
 
-    Ok((tx, epoch))
+    Ok((tx, masp_epoch))
+}
+
+/// Generate the shielded transfer to be set in the memo of an IBC transfer
+/// whose receiver is a payment address of this chain. The given port ID and
+/// channel ID are those of the destination of the IBC transfer, i.e. this
+/// chain.
+pub async fn gen_ibc_shielded_transfer<
+    C: crate::sdk::queries::Client + Sync,
+    V: ShieldedUtils,
+    IO: Io,
+>(
+    client: &C,
+    shielded: &mut ShieldedContext<V>,
+    args: args::GenIbcShieldedTransfer,
+) -> Result<Option<IbcShieldedTransfer>> {
+    let key = match args.target.payment_address() {
+        Some(pa) if pa.is_pinned() => Some(pa.hash()),
+        Some(_) => None,
+        None => return Ok(None),
+    };
+    // The token sent from the counterparty chain is received as a token
+    // having the IBC denom prefixed with the port ID and the channel ID,
+    // unless this chain is the source of the token
+    let token = match Address::decode(&args.token) {
+        Ok(token) => token,
+        Err(_) => ibc_token(format!(
+            "{}/{}/{}",
+            args.port_id, args.channel_id, args.token
+        )),
+    };
+    let validated_amount =
+        validate_amount::<_, IO>(client, args.amount, &token, args.force)
+            .await?;
+
+    let source = Address::Internal(InternalAddress::Ibc);
+    let shielded_transfer = shielded
+        .gen_shielded_transfer_for::<_, IO>(
+            client,
+            &TransferSource::Address(source.clone()),
+            &args.target,
+            &token,
+            validated_amount,
+        )
+        .await
+        .map_err(|err| TxError::MaspError(err.to_string()))?;
+
+    let transfer = token::Transfer {
+        source,
+        target: masp(),
+        token,
+        amount: validated_amount,
+        key,
+        shielded: None,
+    };
+    Ok(shielded_transfer.map(|stx| IbcShieldedTransfer {
+        transfer,
+        masp_tx: stx.masp_tx,
+    }))
 }
 
 /// Abstraction for helping build transactions
@@ -14,16 +14,18 @@ use super::wasm::TxCache;
 #[cfg(feature = "wasm-runtime")]
 use super::wasm::VpCache;
 use super::WasmCacheAccess;
-use crate::ledger::gas::{self, VpGasMeter, STORAGE_ACCESS_GAS_PER_BYTE};
+use crate::ledger::gas::{self, VpGasMeter};
 use crate::ledger::storage::write_log::{self, WriteLog};
 use crate::ledger::storage::{self, Storage, StorageHasher};
 use crate::ledger::vp_host_fns;
 use crate::proto::Tx;
 use crate::types::address::{self, Address};
-use crate::types::hash::Hash;
+use crate::types::event::TxEvent;
+use crate::types::hash::{Hash, HashAlgorithm};
 use crate::types::ibc::IbcEvent;
 use crate::types::internal::HostEnvResult;
-use crate::types::storage::{BlockHeight, Key, TxIndex};
+use crate::types::key::{common, SigScheme};
+use crate::types::storage::{BlockHeight, Epoch, Key, TxIndex};
 use crate::types::token::{
     is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
 };
@@ -63,6 +65,8 @@ pub enum TxRuntimeError {
     MissingTxData,
     #[error("IBC: {0}")]
     Ibc(#[from] namada_core::ledger::ibc::Error),
+    #[error("Hash error: {0}")]
+    HashError(crate::types::hash::Error),
 }
 
 type TxResult<T> = std::result::Result<T, TxRuntimeError>;
@@ -974,6 +978,32 @@ where
     tx_charge_gas(env, gas)
 }
 
+/// Emitting a tx event function exposed to the wasm VM Tx environment.
+/// The given event will be set to the write log, namespaced as an event
+/// emitted by the tx.
+pub fn tx_emit_event<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    event_ptr: u64,
+    event_len: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (event, gas) = env
+        .memory
+        .read_bytes(event_ptr, event_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let event: TxEvent = BorshDeserialize::try_from_slice(&event)
+        .map_err(TxRuntimeError::EncodingError)?;
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let gas = write_log.emit_event(event.into_tx_emitted());
+    tx_charge_gas(env, gas)
+}
+
 /// Getting an IBC event function exposed to the wasm VM Tx environment.
 pub fn tx_get_ibc_event<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -1008,6 +1038,92 @@ where
     Ok(HostEnvResult::Fail.to_i64())
 }
 
+/// Hashing function exposed to the wasm VM Tx environment. The hash of the data
+/// with the given algorithm is written to the `result_ptr`.
+pub fn tx_hash<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    algorithm: u64,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> TxResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let algorithm = HashAlgorithm::try_from(algorithm)
+        .map_err(TxRuntimeError::HashError)?;
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let storage = unsafe { env.ctx.storage.get() };
+    tx_charge_gas(env, data.len() as u64 * storage.gas_costs.hash_per_byte)?;
+    let hash = algorithm.hash(&data);
+    let gas = env
+        .memory
+        .write_bytes(result_ptr, hash.0)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)
+}
+
+/// Signature verification function exposed to the wasm VM Tx environment. The
+/// public key and the signature are borsh-encoded [`common::PublicKey`] and
+/// [`common::Signature`] of the raw data, see
+/// [`SigScheme::verify_raw_signature`].
+#[allow(clippy::too_many_arguments)]
+pub fn tx_verify_signature<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    public_key_ptr: u64,
+    public_key_len: u64,
+    signature_ptr: u64,
+    signature_len: u64,
+    data_ptr: u64,
+    data_len: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (public_key, gas) = env
+        .memory
+        .read_bytes(public_key_ptr, public_key_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let public_key = common::PublicKey::try_from_slice(&public_key)
+        .map_err(TxRuntimeError::EncodingError)?;
+
+    let (signature, gas) = env
+        .memory
+        .read_bytes(signature_ptr, signature_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let signature = common::Signature::try_from_slice(&signature)
+        .map_err(TxRuntimeError::EncodingError)?;
+
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+    let gas_costs = unsafe { env.ctx.storage.get() }.gas_costs;
+    tx_charge_gas(
+        env,
+        gas_costs.verify_sig + data.len() as u64 * gas_costs.hash_per_byte,
+    )?;
+
+    Ok(HostEnvResult::from(
+        common::SigScheme::verify_raw_signature(&public_key, &data, &signature)
+            .is_ok(),
+    )
+    .to_i64())
+}
+
 /// Storage read prior state (before tx execution) function exposed to the wasm
 /// VM VP environment. It will try to read from the storage.
 ///
@@ -1382,7 +1498,8 @@ where
     verifiers.insert(addr);
     // This is not a storage write, use the same multiplier used for a storage
     // read
-    tx_charge_gas(env, addr_len * STORAGE_ACCESS_GAS_PER_BYTE)
+    let storage = unsafe { env.ctx.storage.get() };
+    tx_charge_gas(env, addr_len * storage.gas_costs.storage_access_per_byte)
 }
 
 /// Update a validity predicate function exposed to the wasm VM Tx environment
@@ -1514,7 +1631,8 @@ where
     CA: WasmCacheAccess,
 {
     let tx_index = unsafe { env.ctx.tx_index.get() };
-    tx_charge_gas(env, crate::vm::host_env::gas::STORAGE_ACCESS_GAS_PER_BYTE)?;
+    let storage = unsafe { env.ctx.storage.get() };
+    tx_charge_gas(env, storage.gas_costs.storage_access_per_byte)?;
     Ok(tx_index.0)
 }
 
@@ -1532,8 +1650,9 @@ where
     CA: WasmCacheAccess,
 {
     let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let storage = unsafe { env.ctx.storage.get() };
     let tx_index = unsafe { env.ctx.tx_index.get() };
-    let tx_idx = vp_host_fns::get_tx_index(gas_meter, tx_index)?;
+    let tx_idx = vp_host_fns::get_tx_index(gas_meter, storage, tx_index)?;
     Ok(tx_idx.0)
 }
 
@@ -1577,6 +1696,33 @@ where
     Ok(epoch.0)
 }
 
+/// Getting the block time function exposed to the wasm VM Tx environment.
+/// The time is that of the block to which the current transaction is being
+/// applied.
+pub fn tx_get_block_time<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let storage = unsafe { env.ctx.storage.get() };
+    let (time, gas) = storage
+        .get_block_time()
+        .map_err(TxRuntimeError::StorageError)?;
+    tx_charge_gas(env, gas)?;
+    let value = time.try_to_vec().map_err(TxRuntimeError::EncodingError)?;
+    let len: i64 = value
+        .len()
+        .try_into()
+        .map_err(TxRuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
 /// Get the native token's address
 pub fn tx_get_native_token<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -1589,7 +1735,7 @@ where
     CA: WasmCacheAccess,
 {
     let storage = unsafe { env.ctx.storage.get() };
-    tx_charge_gas(env, STORAGE_ACCESS_GAS_PER_BYTE)?;
+    tx_charge_gas(env, storage.gas_costs.storage_access_per_byte)?;
     let native_token = storage.native_token.clone();
     let native_token_string = native_token.encode();
     let gas = env
@@ -1743,8 +1889,9 @@ where
     CA: WasmCacheAccess,
 {
     let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let storage = unsafe { env.ctx.storage.get() };
     let tx = unsafe { env.ctx.tx.get() };
-    let hash = vp_host_fns::get_tx_code_hash(gas_meter, tx)?;
+    let hash = vp_host_fns::get_tx_code_hash(gas_meter, storage, tx)?;
     let mut result_bytes = vec![];
     if let Some(hash) = hash {
         result_bytes.push(1);
@@ -1778,6 +1925,34 @@ where
     Ok(epoch.0)
 }
 
+/// Getting the block time function exposed to the wasm VM VP environment. The
+/// time is that of the block to which the current transaction is being
+/// applied.
+pub fn vp_get_block_time<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let storage = unsafe { env.ctx.storage.get() };
+    let time = vp_host_fns::get_block_time(gas_meter, storage)?;
+    let value = time
+        .try_to_vec()
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+    let len: i64 = value
+        .len()
+        .try_into()
+        .map_err(vp_host_fns::RuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
 /// Verify a transaction signature
 /// TODO: this is just a warkaround to track gas for multiple singature
 /// verifications. When the runtime gas meter is implemented, this funcion can
@@ -1839,6 +2014,7 @@ where
     let max_signatures = Option::<u8>::try_from_slice(&max_signatures)
         .map_err(vp_host_fns::RuntimeError::EncodingError)?;
 
+    let storage = unsafe { env.ctx.storage.get() };
     let tx = unsafe { env.ctx.tx.get() };
 
     Ok(HostEnvResult::from(
@@ -1848,7 +2024,7 @@ where
             &Some(signer),
             threshold,
             max_signatures,
-            Some(gas_meter),
+            Some((gas_meter, &storage.gas_costs)),
         )
         .is_ok(),
     )
@@ -1890,6 +2066,94 @@ where
     )
 }
 
+/// Hashing function exposed to the wasm VM VP environment. The hash of the data
+/// with the given algorithm is written to the `result_ptr`.
+pub fn vp_hash<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    algorithm: u64,
+    data_ptr: u64,
+    data_len: u64,
+    result_ptr: u64,
+) -> vp_host_fns::EnvResult<()>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let algorithm = HashAlgorithm::try_from(algorithm)
+        .map_err(vp_host_fns::RuntimeError::HashError)?;
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas)?;
+    let storage = unsafe { env.ctx.storage.get() };
+    let hash = vp_host_fns::hash(gas_meter, storage, algorithm, &data)?;
+    let gas = env
+        .memory
+        .write_bytes(result_ptr, hash.0)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas)
+}
+
+/// Signature verification function exposed to the wasm VM VP environment. The
+/// public key and the signature are borsh-encoded [`common::PublicKey`] and
+/// [`common::Signature`] of the raw data, see
+/// [`SigScheme::verify_raw_signature`].
+#[allow(clippy::too_many_arguments)]
+pub fn vp_verify_signature<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    public_key_ptr: u64,
+    public_key_len: u64,
+    signature_ptr: u64,
+    signature_len: u64,
+    data_ptr: u64,
+    data_len: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: storage::DB + for<'iter> storage::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let (public_key, gas) = env
+        .memory
+        .read_bytes(public_key_ptr, public_key_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas)?;
+    let public_key = common::PublicKey::try_from_slice(&public_key)
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+
+    let (signature, gas) = env
+        .memory
+        .read_bytes(signature_ptr, signature_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas)?;
+    let signature = common::Signature::try_from_slice(&signature)
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    vp_host_fns::add_gas(gas_meter, gas)?;
+
+    let storage = unsafe { env.ctx.storage.get() };
+    let valid = vp_host_fns::verify_signature(
+        gas_meter,
+        storage,
+        &public_key,
+        &signature,
+        &data,
+    )?;
+    Ok(HostEnvResult::from(valid).to_i64())
+}
+
 /// Log a string from exposed to the wasm VM Tx environment. The message will be
 /// printed at the [`tracing::Level::INFO`]. This function is for development
 /// only.
@@ -1927,14 +2191,18 @@ where
     use std::cell::RefCell;
     use std::rc::Rc;
 
-    use namada_core::ledger::ibc::{IbcActions, TransferModule};
+    use namada_core::ledger::ibc::{
+        IbcActions, NftTransferModule, TransferModule,
+    };
 
     let tx_data = unsafe { env.ctx.tx.get().data() }
         .ok_or(TxRuntimeError::MissingTxData)?;
     let ctx = Rc::new(RefCell::new(env.ctx.clone()));
     let mut actions = IbcActions::new(ctx.clone());
-    let module = TransferModule::new(ctx);
+    let module = TransferModule::new(ctx.clone());
     actions.add_transfer_route(module.module_id(), module);
+    let nft_module = NftTransferModule::new(ctx);
+    actions.add_nft_transfer_route(nft_module.module_id(), nft_module);
     actions.execute(&tx_data)?;
 
     Ok(())
@@ -2303,6 +2571,54 @@ where
         Ok(())
     }
 
+    fn handle_masp_tx(
+        &mut self,
+        shielded: &namada_core::types::ibc::IbcShieldedTransfer,
+    ) -> Result<(), Self::Error> {
+        use masp_primitives::transaction::Transaction;
+        use namada_core::types::storage::KeySeg;
+        use namada_core::types::token::{
+            Transfer, HEAD_TX_KEY, PIN_KEY_PREFIX, TX_KEY_PREFIX,
+        };
+
+        let masp_addr = address::masp();
+        let verifiers = unsafe { self.verifiers.get() };
+        verifiers.insert(masp_addr.clone());
+        let head_tx_key = Key::from(masp_addr.to_db_key())
+            .push(&HEAD_TX_KEY.to_owned())
+            .expect("Cannot obtain a storage key");
+        let current_tx_idx: u64 =
+            ibc_read_borsh(self, &head_tx_key)?.unwrap_or(0);
+        let current_tx_key = Key::from(masp_addr.to_db_key())
+            .push(&(TX_KEY_PREFIX.to_owned() + &current_tx_idx.to_string()))
+            .expect("Cannot obtain a storage key");
+        // Save the Transfer object and its location within the blockchain
+        // so that clients do not have to separately look these
+        // up
+        let storage = unsafe { self.storage.get() };
+        let (epoch, gas) = storage.get_current_epoch();
+        ibc_tx_charge_gas(self, gas)?;
+        let height = self.get_height()?;
+        let tx_index = unsafe { self.tx_index.get() };
+        let record: (Epoch, BlockHeight, TxIndex, Transfer, Transaction) = (
+            epoch,
+            height,
+            *tx_index,
+            shielded.transfer.clone(),
+            shielded.masp_tx.clone(),
+        );
+        ibc_write_borsh(self, &current_tx_key, &record)?;
+        ibc_write_borsh(self, &head_tx_key, &(current_tx_idx + 1))?;
+        // If storage key has been supplied, then pin this transaction to it
+        if let Some(key) = &shielded.transfer.key {
+            let pin_key = Key::from(masp_addr.to_db_key())
+                .push(&(PIN_KEY_PREFIX.to_owned() + key))
+                .expect("Cannot obtain a storage key");
+            ibc_write_borsh(self, &pin_key, &current_tx_idx)?;
+        }
+        Ok(())
+    }
+
     fn get_height(&self) -> std::result::Result<BlockHeight, Self::Error> {
         let storage = unsafe { self.storage.get() };
         let (height, gas) = storage.get_block_height();
@@ -2310,6 +2626,13 @@ where
         Ok(height)
     }
 
+    fn get_epoch(&self) -> std::result::Result<Epoch, Self::Error> {
+        let storage = unsafe { self.storage.get() };
+        let (epoch, gas) = storage.get_current_epoch();
+        ibc_tx_charge_gas(self, gas)?;
+        Ok(epoch)
+    }
+
     fn get_header(
         &self,
         height: BlockHeight,
@@ -9,7 +9,8 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
 use std::thread::sleep;
 use std::time::Duration;
 use std::{cmp, fs};
@@ -33,6 +34,8 @@ pub struct Cache<N: CacheName, A: WasmCacheAccess> {
     progress: Arc<RwLock<HashMap<Hash, Compilation>>>,
     /// In-memory LRU cache of compiled modules
     in_memory: Arc<RwLock<MemoryCache>>,
+    /// Hit and miss counters, shared by all the handles of the cache
+    stats: Arc<StatsCounters>,
     /// The cache's name
     name: PhantomData<N>,
     /// Cache access level
@@ -45,6 +48,38 @@ pub trait CacheName: Clone + std::fmt::Debug {
     fn name() -> &'static str;
 }
 
+/// Cache hit and miss statistics
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of modules found in the in-memory cache
+    pub memory_hits: u64,
+    /// Number of modules loaded from the file system cache
+    pub file_hits: u64,
+    /// Number of modules that had to be compiled on demand
+    pub misses: u64,
+}
+
+#[derive(Debug, Default)]
+struct StatsCounters {
+    memory_hits: AtomicU64,
+    file_hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl StatsCounters {
+    fn memory_hit(&self) {
+        self.memory_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn file_hit(&self) {
+        self.file_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
 /// In-memory LRU cache of compiled modules
 type MemoryCache = CLruCache<Hash, Module, RandomState, ModuleCacheScale>;
 
@@ -95,6 +130,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             dir,
             progress: Default::default(),
             in_memory,
+            stats: Default::default(),
             name: Default::default(),
             access: Default::default(),
         }
@@ -125,6 +161,21 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         self.in_memory.read().unwrap().weight()
     }
 
+    /// Get the hit and miss statistics of the cache since it was created
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            memory_hits: self.stats.memory_hits.load(Ordering::Relaxed),
+            file_hits: self.stats.file_hits.load(Ordering::Relaxed),
+            misses: self.stats.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Check if the module with the given hash is already compiled or being
+    /// compiled
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.progress.read().unwrap().contains_key(hash)
+    }
+
     /// Get a WASM module from LRU cache, from a file or compile it and cache
     /// it. Updates the position in the LRU cache.
     fn get(
@@ -133,6 +184,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
     ) -> Result<Option<(Module, Store)>, wasm::run::Error> {
         let mut in_memory = self.in_memory.write().unwrap();
         if let Some(module) = in_memory.get(hash) {
+            self.stats.memory_hit();
             tracing::trace!(
                 "{} found {} in cache.",
                 N::name(),
@@ -156,6 +208,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     drop(progress);
                     let mut in_memory = self.in_memory.write().unwrap();
                     if let Some(module) = in_memory.get(hash) {
+                        self.stats.memory_hit();
                         tracing::info!(
                             "{} found {} in memory cache.",
                             N::name(),
@@ -165,6 +218,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     }
 
                     let (module, store) = file_load_module(&self.dir, hash);
+                    self.stats.file_hit();
                     tracing::info!(
                         "{} found {} in file cache.",
                         N::name(),
@@ -195,6 +249,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                             N::name(),
                             hash.to_string()
                         );
+                        self.stats.file_hit();
                         file_load_module(&self.dir, hash)
                     } else {
                         return Ok(None);
@@ -223,6 +278,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
     ) -> Result<Option<(Module, Store)>, wasm::run::Error> {
         let in_memory = self.in_memory.read().unwrap();
         if let Some(module) = in_memory.peek(hash) {
+            self.stats.memory_hit();
             tracing::info!(
                 "{} found {} in cache.",
                 N::name(),
@@ -246,6 +302,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     drop(progress);
                     let in_memory = self.in_memory.read().unwrap();
                     if let Some(module) = in_memory.peek(hash) {
+                        self.stats.memory_hit();
                         tracing::info!(
                             "{} found {} in memory cache.",
                             N::name(),
@@ -255,6 +312,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     }
 
                     let (module, store) = file_load_module(&self.dir, hash);
+                    self.stats.file_hit();
                     tracing::info!(
                         "{} found {} in file cache.",
                         N::name(),
@@ -282,6 +340,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                             N::name(),
                             hash.to_string()
                         );
+                        self.stats.file_hit();
                         Ok(Some(file_load_module(&self.dir, hash)))
                     } else {
                         Ok(None)
@@ -304,6 +363,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             match progress.get(&hash) {
                 Some(_) => return self.peek(&hash),
                 None => {
+                    self.stats.miss();
                     let code = wasm::run::prepare_wasm_code(code)?;
                     return Ok(Some(compile(code)?));
                 }
@@ -318,6 +378,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         progress.insert(hash, Compilation::Compiling);
         drop(progress);
 
+        self.stats.miss();
         tracing::info!("Compiling {} {}.", N::name(), hash.to_string());
 
         match wasm::run::prepare_wasm_code(code) {
@@ -360,8 +421,9 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         }
     }
 
-    /// Pre-compile a WASM module to a file. The compilation runs in a new OS
-    /// thread and the function returns immediately.
+    /// Pre-compile a WASM module to a file. The compilation runs in the
+    /// background compilation thread pool and the function returns
+    /// immediately.
     pub fn pre_compile(&mut self, code: impl AsRef<[u8]>) {
         if A::is_read_write() {
             let hash = hash_of_code(&code);
@@ -380,63 +442,49 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
                     let progress = self.progress.clone();
                     let code = code.as_ref().to_vec();
                     let dir = self.dir.clone();
-                    std::thread::spawn(move || {
+                    compilation_pool().spawn(move || {
                         tracing::info!("Compiling WASM {}.", hash.to_string());
 
-                        let (_module, _store) =
-                            match wasm::run::prepare_wasm_code(code) {
-                                Ok(code) => match compile(code) {
-                                    Ok((module, store)) => {
-                                        let mut progress =
-                                            progress.write().unwrap();
-                                        progress
-                                            .insert(hash, Compilation::Done);
-                                        tracing::info!(
-                                            "Finished compiling WASM {hash}."
-                                        );
-                                        if progress.values().all(
-                                            |compilation| {
-                                                matches!(
-                                                    compilation,
-                                                    Compilation::Done
-                                                )
-                                            },
-                                        ) {
-                                            tracing::info!(
-                                                "Finished compiling all {}.",
-                                                N::name()
-                                            )
-                                        }
-                                        file_write_module(&dir, &module, &hash);
-                                        (module, store)
-                                    }
-                                    Err(err) => {
-                                        let mut progress =
-                                            progress.write().unwrap();
-                                        tracing::info!(
-                                            "Failed to compile WASM {} with {}",
-                                            hash.to_string(),
-                                            err
-                                        );
-                                        progress.remove(&hash);
-                                        return Err(err);
-                                    }
-                                },
-                                Err(err) => {
-                                    let mut progress =
-                                        progress.write().unwrap();
+                        let code = match wasm::run::prepare_wasm_code(code) {
+                            Ok(code) => code,
+                            Err(err) => {
+                                let mut progress = progress.write().unwrap();
+                                tracing::info!(
+                                    "Failed to prepare WASM {} with {}",
+                                    hash.to_string(),
+                                    err
+                                );
+                                progress.remove(&hash);
+                                return;
+                            }
+                        };
+                        match compile(code) {
+                            Ok((module, _store)) => {
+                                let mut progress = progress.write().unwrap();
+                                progress.insert(hash, Compilation::Done);
+                                tracing::info!(
+                                    "Finished compiling WASM {hash}."
+                                );
+                                if progress.values().all(|compilation| {
+                                    matches!(compilation, Compilation::Done)
+                                }) {
                                     tracing::info!(
-                                        "Failed to prepare WASM {} with {}",
-                                        hash.to_string(),
-                                        err
-                                    );
-                                    progress.remove(&hash);
-                                    return Err(err);
+                                        "Finished compiling all {}.",
+                                        N::name()
+                                    )
                                 }
-                            };
-
-                        let res: Result<(), wasm::run::Error> = Ok(());
-                        res
+                                file_write_module(&dir, &module, &hash);
+                            }
+                            Err(err) => {
+                                let mut progress = progress.write().unwrap();
+                                tracing::info!(
+                                    "Failed to compile WASM {} with {}",
+                                    hash.to_string(),
+                                    err
+                                );
+                                progress.remove(&hash);
+                            }
+                        }
                     });
                 }
             }
@@ -449,12 +497,30 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             dir: self.dir.clone(),
             progress: self.progress.clone(),
             in_memory: self.in_memory.clone(),
+            stats: self.stats.clone(),
             name: Default::default(),
             access: Default::default(),
         }
     }
 }
 
+/// The thread pool used to pre-compile WASM modules in the background. It's
+/// separate from the global pool, so that a batch of compilations doesn't
+/// hold up the parallel execution of txs.
+fn compilation_pool() -> &'static rayon::ThreadPool {
+    static POOL: OnceLock<rayon::ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let num_threads = std::thread::available_parallelism()
+            .map(|num| cmp::max(1, num.get() / 2))
+            .unwrap_or(1);
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("wasm-compile-{index}"))
+            .build()
+            .expect("Couldn't create the WASM compilation thread pool")
+    })
+}
+
 fn hash_of_code(code: impl AsRef<[u8]>) -> Hash {
     Hash::sha256(code.as_ref())
 }
@@ -1018,6 +1084,52 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_cache_stats() {
+        let tx_no_op = load_wasm(TestWasms::TxNoOp.path());
+        let (mut cache, tmp_dir) = testing::cache::<TestCache>();
+
+        // The first compilation is a miss
+        cache.compile_or_fetch(&tx_no_op.code).unwrap().unwrap();
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                memory_hits: 0,
+                file_hits: 0,
+                misses: 1,
+            }
+        );
+
+        // Fetching it again hits the in-memory cache, which is shared with
+        // the read-only handle
+        cache.fetch(&tx_no_op.hash).unwrap().unwrap();
+        cache.read_only().fetch(&tx_no_op.hash).unwrap().unwrap();
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                memory_hits: 2,
+                file_hits: 0,
+                misses: 1,
+            }
+        );
+
+        // A new cache on the same dir, as after a restart, loads it from file
+        let mut cache = Cache::<TestCache, WasmCacheRwAccess>::new(
+            tmp_dir.path(),
+            50 * 1024 * 1024,
+        );
+        cache.fetch(&tx_no_op.hash).unwrap().unwrap();
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                memory_hits: 0,
+                file_hits: 1,
+                misses: 0,
+            }
+        );
+        assert!(cache.contains(&tx_no_op.hash));
+    }
+
     /// Get the WASM code bytes, its hash and find the compiled module's size
     fn load_wasm(file: impl AsRef<Path>) -> WasmWithMeta {
         // When `WeightScale` calls `loupe::size_of_val` in the cache, for some
@@ -6,7 +6,7 @@ use std::str::Utf8Error;
 use std::sync::Arc;
 
 use borsh::BorshSerialize;
-use namada_core::ledger::gas::VM_MEMORY_ACCESS_GAS_PER_BYTE;
+use namada_core::ledger::gas::GasCosts;
 use thiserror::Error;
 use wasmer::{
     vm, BaseTunables, HostEnvInitError, LazyInit, Memory, MemoryError,
@@ -222,12 +222,22 @@ fn write_memory_bytes(
 }
 
 /// The wasm memory
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct WasmMemory {
     inner: LazyInit<wasmer::Memory>,
+    gas_costs: GasCosts,
 }
 
 impl WasmMemory {
+    /// Create a memory that charges the given gas costs for its accesses. It
+    /// has to be initialized with [`WasmMemory::init_env_memory`] before use.
+    pub fn new(gas_costs: GasCosts) -> Self {
+        Self {
+            inner: LazyInit::default(),
+            gas_costs,
+        }
+    }
+
     /// Initialize the memory from the given exports, used to implement
     /// [`wasmer::WasmerEnv`].
     pub fn init_env_memory(
@@ -258,13 +268,14 @@ impl VmMemory for WasmMemory {
     fn read_bytes(&self, offset: u64, len: usize) -> Result<(Vec<u8>, u64)> {
         let memory = self.inner.get_ref().ok_or(Error::UninitializedMemory)?;
         let bytes = read_memory_bytes(memory, offset, len)?;
-        let gas = bytes.len() as u64 * VM_MEMORY_ACCESS_GAS_PER_BYTE;
+        let gas = bytes.len() as u64 * self.gas_costs.vm_memory_access_per_byte;
         Ok((bytes, gas))
     }
 
     /// Write bytes into memory at the given offset and return the gas cost
     fn write_bytes(&self, offset: u64, bytes: impl AsRef<[u8]>) -> Result<u64> {
-        let gas = bytes.as_ref().len() as u64 * VM_MEMORY_ACCESS_GAS_PER_BYTE;
+        let gas = bytes.as_ref().len() as u64
+            * self.gas_costs.vm_memory_access_per_byte;
         let memory = self.inner.get_ref().ok_or(Error::UninitializedMemory)?;
         write_memory_bytes(memory, offset, bytes)?;
         Ok(gas)
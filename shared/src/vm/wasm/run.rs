@@ -121,7 +121,7 @@ where
     let mut result_buffer: Option<Vec<u8>> = None;
 
     let env = TxVmEnv::new(
-        WasmMemory::default(),
+        WasmMemory::new(storage.gas_costs),
         storage,
         write_log,
         &mut iterators,
@@ -215,7 +215,7 @@ where
     };
 
     let env = VpVmEnv::new(
-        WasmMemory::default(),
+        WasmMemory::new(storage.gas_costs),
         address,
         storage,
         write_log,
@@ -375,7 +375,7 @@ where
         let storage = unsafe { ctx.storage.get() };
         let gas_meter = unsafe { ctx.gas_meter.get() };
         let env = VpVmEnv {
-            memory: WasmMemory::default(),
+            memory: WasmMemory::new(storage.gas_costs),
             ctx,
         };
 
@@ -520,14 +520,16 @@ where
                 }
             };
 
-            gas_meter.add_wasm_load_from_storage_gas(tx_len)?;
-            gas_meter.add_compiling_gas(tx_len)?;
+            gas_meter
+                .add_wasm_load_from_storage_gas(tx_len, &storage.gas_costs)?;
+            gas_meter.add_compiling_gas(tx_len, &storage.gas_costs)?;
             Ok((module, store))
         }
         Commitment::Id(code) => {
             gas_meter.add_compiling_gas(
                 u64::try_from(code.len())
                     .map_err(|e| Error::ConversionError(e.to_string()))?,
+                &storage.gas_costs,
             )?;
             validate_untrusted_wasm(code).map_err(Error::ValidationError)?;
             match wasm_cache.compile_or_fetch(code)? {
@@ -31,7 +31,7 @@ use namada_apps::config::genesis::genesis_config::{
 use namada_apps::config::utils::convert_tm_addr_to_socket_addr;
 use namada_apps::facade::tendermint_config::net::Address as TendermintAddress;
 use namada_core::ledger::governance::cli::onchain::{
-    PgfFunding, PgfFundingTarget, StewardsUpdate,
+    PgfFunding, PgfFundingStream, PgfFundingTarget, StewardsUpdate,
 };
 use namada_test_utils::TestWasms;
 use namada_vp_prelude::BTreeSet;
@@ -2409,14 +2409,18 @@ fn pgf_governance_proposal() -> Result<()> {
     let christel = find_address(&test, CHRISTEL)?;
 
     let pgf_funding = PgfFunding {
-        continous: vec![PgfFundingTarget {
+        continous: vec![PgfFundingStream {
             amount: token::Amount::from_u64(10),
             address: bertha.clone(),
+            start_epoch: Epoch(0),
+            end_epoch: Epoch(200),
+            budget: token::Amount::from_u64(1000),
         }],
         retro: vec![PgfFundingTarget {
             amount: token::Amount::from_u64(5),
             address: christel,
         }],
+        cancel: vec![],
     };
 
     let valid_proposal_json_path =
@@ -2466,6 +2470,18 @@ fn pgf_governance_proposal() -> Result<()> {
     ))?;
     client.assert_success();
 
+    // 15. Query pgf funding streams payouts
+    let query_pgf_payments =
+        vec!["query-pgf", "--payments", "--node", &validator_one_rpc];
+    let mut client = run!(test, Bin::Client, query_pgf_payments, Some(30))?;
+    client.exp_string(&format!("- {}", bertha))?;
+    client.exp_string(&format!(
+        "Budget: {}",
+        token::Amount::from_u64(1000).to_string_native()
+    ))?;
+    client.exp_string("Payouts:")?;
+    client.assert_success();
+
     Ok(())
 }
 
@@ -5,8 +5,11 @@ use color_eyre::owo_colors::OwoColorize;
 use namada::types::io::DefaultIo;
 use namada_apps::client::tx::CLIShieldedUtils;
 use namada_apps::node::ledger::shell::testing::client::run;
+use namada_apps::node::ledger::shell::testing::node::MockNode;
 use namada_apps::node::ledger::shell::testing::utils::{Bin, CapturedOutput};
-use namada_core::types::address::{btc, eth, masp_rewards};
+use namada_core::ledger::parameters::{self, ShieldedRewardParams};
+use namada_core::types::address::{btc, eth, Address};
+use namada_core::types::dec::Dec;
 use namada_core::types::token;
 use namada_core::types::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use test_log::test;
@@ -30,10 +33,37 @@ fn masp_incentives() -> Result<()> {
     let validator_one_rpc = "127.0.0.1:26567";
     // Download the shielded pool parameters before starting node
     let _ = CLIShieldedUtils::new::<DefaultIo>(PathBuf::new());
-    // Lengthen epoch to ensure that a transaction can be constructed and
-    // submitted within the same block. Necessary to ensure that conversion is
-    // not invalidated.
-    let mut node = setup::setup()?;
+    // Pin the rewards of BTC and ETH at their maximum rate, which is constant
+    // while the locked amounts don't change, and don't reward NAM
+    let mut node = setup::initialize_genesis(|mut genesis| {
+        let pinned = ShieldedRewardParams {
+            max_reward_rate: Dec::one(),
+            kp_gain_nom: Dec::two(),
+            kd_gain_nom: Dec::zero(),
+            locked_ratio_target: Dec::one(),
+        };
+        for (alias, params) in [
+            ("BTC", pinned.clone()),
+            ("ETH", pinned.clone()),
+            (
+                "NAM",
+                ShieldedRewardParams {
+                    max_reward_rate: Dec::zero(),
+                    ..pinned
+                },
+            ),
+        ] {
+            genesis.token.get_mut(alias).unwrap().parameters = Some(params);
+        }
+        genesis
+    })?;
+    // The reward rate that the node last applied to the given token
+    let reward_rate = |node: &MockNode, token: &Address| {
+        let locked = node.shell.lock().unwrap();
+        parameters::read_shielded_rewards_state(&locked.wl_storage, token)
+            .unwrap()
+            .last_reward_rate
+    };
     // Wait till epoch boundary
     let ep0 = node.next_epoch();
     // Send 20 BTC from Albert to PA
@@ -94,10 +124,9 @@ fn masp_incentives() -> Result<()> {
     assert!(captured.result.is_ok());
     assert!(captured.contains("No shielded nam balance found"));
 
-    let masp_rewards = masp_rewards();
-
     // Wait till epoch boundary
     let ep1 = node.next_epoch();
+    let btc_reward = reward_rate(&node, &btc());
 
     // Assert BTC balance at VK(A) is 20
     let captured = CapturedOutput::of(|| {
@@ -138,7 +167,7 @@ fn masp_incentives() -> Result<()> {
         )
     });
 
-    let amt = (amt20 * masp_rewards[&btc()]).0 * (ep1.0 - ep0.0);
+    let amt = (amt20 * btc_reward).0 * (ep1.0 - ep0.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -162,7 +191,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt20 * masp_rewards[&btc()]).0 * (ep1.0 - ep0.0);
+    let amt = (amt20 * btc_reward).0 * (ep1.0 - ep0.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -208,7 +237,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt20 * masp_rewards[&btc()]).0 * (ep2.0 - ep0.0);
+    let amt = (amt20 * btc_reward).0 * (ep2.0 - ep0.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -232,7 +261,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt20 * masp_rewards[&btc()]).0 * (ep2.0 - ep0.0);
+    let amt = (amt20 * btc_reward).0 * (ep2.0 - ep0.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -303,6 +332,7 @@ fn masp_incentives() -> Result<()> {
 
     // Wait till epoch boundary
     let ep4 = node.next_epoch();
+    let eth_reward = reward_rate(&node, &eth());
 
     // Assert ETH balance at VK(B) is 10
     let captured = CapturedOutput::of(|| {
@@ -339,7 +369,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt10 * masp_rewards[&eth()]).0 * (ep4.0 - ep3.0);
+    let amt = (amt10 * eth_reward).0 * (ep4.0 - ep3.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -364,8 +394,8 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = ((amt20 * masp_rewards[&btc()]).0 * (ep4.0 - ep0.0))
-        + ((amt10 * masp_rewards[&eth()]).0 * (ep4.0 - ep3.0));
+    let amt = ((amt20 * btc_reward).0 * (ep4.0 - ep0.0))
+        + ((amt10 * eth_reward).0 * (ep4.0 - ep3.0));
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -435,7 +465,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt10 * masp_rewards[&eth()]).0 * (ep5.0 - ep3.0);
+    let amt = (amt10 * eth_reward).0 * (ep5.0 - ep3.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -461,8 +491,8 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = ((amt20 * masp_rewards[&btc()]).0 * (ep.0 - ep0.0))
-        + ((amt10 * masp_rewards[&eth()]).0 * (ep5.0 - ep3.0));
+    let amt = ((amt20 * btc_reward).0 * (ep.0 - ep0.0))
+        + ((amt10 * eth_reward).0 * (ep5.0 - ep3.0));
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -530,7 +560,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt20 * masp_rewards[&btc()]).0 * (ep6.0 - ep0.0);
+    let amt = (amt20 * btc_reward).0 * (ep6.0 - ep0.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -555,8 +585,8 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = ((amt20 * masp_rewards[&btc()]).0 * (ep6.0 - ep0.0))
-        + ((amt10 * masp_rewards[&eth()]).0 * (ep5.0 - ep3.0));
+    let amt = ((amt20 * btc_reward).0 * (ep6.0 - ep0.0))
+        + ((amt10 * eth_reward).0 * (ep5.0 - ep3.0));
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -583,7 +613,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt20 * masp_rewards[&btc()]).0 * (ep6.0 - ep0.0);
+    let amt = (amt20 * btc_reward).0 * (ep6.0 - ep0.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -607,7 +637,7 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = (amt10 * masp_rewards[&eth()]).0 * (ep5.0 - ep3.0);
+    let amt = (amt10 * eth_reward).0 * (ep5.0 - ep3.0);
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -632,8 +662,8 @@ fn masp_incentives() -> Result<()> {
             ],
         )
     });
-    let amt = ((amt20 * masp_rewards[&btc()]).0 * (ep6.0 - ep0.0))
-        + ((amt10 * masp_rewards[&eth()]).0 * (ep5.0 - ep3.0));
+    let amt = ((amt20 * btc_reward).0 * (ep6.0 - ep0.0))
+        + ((amt10 * eth_reward).0 * (ep5.0 - ep3.0));
     let denominated = DenominatedAmount {
         amount: amt,
         denom: NATIVE_MAX_DECIMAL_PLACES.into(),
@@ -658,8 +688,7 @@ fn masp_incentives() -> Result<()> {
             "--token",
             NAM,
             "--amount",
-            &((amt10 * masp_rewards[&eth()]).0 * (ep5.0 - ep3.0))
-                .to_string_native(),
+            &((amt10 * eth_reward).0 * (ep5.0 - ep3.0)).to_string_native(),
             "--signing-keys",
             BERTHA,
             "--node",
@@ -684,8 +713,7 @@ fn masp_incentives() -> Result<()> {
             "--token",
             NAM,
             "--amount",
-            &((amt20 * masp_rewards[&btc()]).0 * (ep6.0 - ep0.0))
-                .to_string_native(),
+            &((amt20 * btc_reward).0 * (ep6.0 - ep0.0)).to_string_native(),
             "--signing-keys",
             ALBERT,
             "--node",
@@ -53,7 +53,7 @@ pub use namada::ledger::ibc::storage::{
     ack_key, channel_counter_key, channel_key, client_counter_key,
     client_state_key, client_update_height_key, client_update_timestamp_key,
     commitment_key, connection_counter_key, connection_key,
-    consensus_state_key, ibc_token, next_sequence_ack_key,
+    consensus_state_key, forwarded_packet_key, ibc_token, next_sequence_ack_key,
     next_sequence_recv_key, next_sequence_send_key, port_key, receipt_key,
 };
 use namada::ledger::native_vp::ibc::{
@@ -729,6 +729,59 @@ pub fn received_packet(
     }
 }
 
+pub fn forward_memo(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    receiver: &str,
+    timeout: Option<&str>,
+) -> String {
+    let mut forward = serde_json::json!({
+        "receiver": receiver,
+        "port": port_id.to_string(),
+        "channel": channel_id.to_string(),
+    });
+    if let Some(timeout) = timeout {
+        forward["timeout"] = timeout.into();
+    }
+    serde_json::json!({ "forward": forward }).to_string()
+}
+
+pub fn set_packet_memo(packet: &mut Packet, memo: String) {
+    let mut data: PacketData =
+        serde_json::from_slice(&packet.data).expect("invalid packet data");
+    data.memo = memo.into();
+    packet.data = serde_json::to_vec(&data).unwrap();
+}
+
+/// Get the packet sent in the transaction from the IBC event
+pub fn sent_packet(env: &TestTxEnv) -> Packet {
+    let event = env
+        .wl_storage
+        .write_log
+        .get_ibc_events()
+        .iter()
+        .find(|event| event.event_type == "send_packet")
+        .expect("no packet was sent");
+    let attr = |key: &str| {
+        event
+            .attributes
+            .get(key)
+            .cloned()
+            .expect("the attribute should exist")
+    };
+    let timestamp = attr("packet_timeout_timestamp").parse().unwrap();
+    Packet {
+        seq_on_a: attr("packet_sequence").parse::<u64>().unwrap().into(),
+        port_id_on_a: attr("packet_src_port").parse().unwrap(),
+        chan_id_on_a: attr("packet_src_channel").parse().unwrap(),
+        port_id_on_b: attr("packet_dst_port").parse().unwrap(),
+        chan_id_on_b: attr("packet_dst_channel").parse().unwrap(),
+        data: attr("packet_data").into_bytes(),
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: Timestamp::from_nanoseconds(timestamp).unwrap(),
+    }
+}
+
 pub fn msg_timeout(packet: Packet, next_sequence_recv: Sequence) -> MsgTimeout {
     MsgTimeout {
         packet,
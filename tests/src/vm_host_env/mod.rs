@@ -22,20 +22,23 @@ mod tests {
     use std::panic;
 
     use itertools::Itertools;
+    use namada::ibc::applications::transfer::packet::PacketData;
     use namada::ibc::core::Msg;
     use namada::ledger::ibc::storage as ibc_storage;
     use namada::ledger::native_vp::ibc::{
         get_dummy_header as tm_dummy_header, Error as IbcError,
     };
+    use namada::ledger::events::{Event, EventType};
     use namada::ledger::tx_env::TxEnv;
     use namada::proto::Tx;
-    use namada::types::hash::Hash;
+    use namada::types::event::TxEvent;
+    use namada::types::hash::{Hash, HashAlgorithm};
     use namada::types::key::*;
     use namada::types::storage::{self, BlockHash, BlockHeight, Key, KeySeg};
     use namada::types::time::DateTimeUtc;
     use namada::types::token::{self, Amount};
     use namada::types::{address, key};
-    use namada_core::ledger::gas::{TxGasMeter, VpGasMeter};
+    use namada_core::ledger::gas::{GasCosts, TxGasMeter, VpGasMeter};
     use namada_core::ledger::ibc::context::transfer_mod::testing::DummyTransferModule;
     use namada_core::ledger::ibc::Error as IbcActionError;
     use namada_test_utils::TestWasms;
@@ -233,6 +236,73 @@ mod tests {
         tx::ctx().init_account(code_hash).unwrap();
     }
 
+    #[test]
+    fn test_tx_emit_event() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let owner = address::testing::established_address_1();
+        let event = TxEvent::new("custom")
+            .with("owner", owner)
+            .with("amount", Amount::native_whole(1))
+            .with("count", 2_u64);
+        tx::ctx().emit_event(event.clone()).unwrap();
+        tx::ctx().emit_event(TxEvent::new("empty")).unwrap();
+
+        // An event can't pass for one of the protocol
+        tx::ctx().emit_event(TxEvent::new("applied")).unwrap();
+
+        let events =
+            tx_host_env::with(|env| env.wl_storage.write_log.take_events());
+        assert_eq!(
+            events,
+            vec![
+                event.into_tx_emitted(),
+                TxEvent::new("empty").into_tx_emitted(),
+                TxEvent::new("applied").into_tx_emitted(),
+            ]
+        );
+        let applied = Event::from(events[2].clone());
+        assert_eq!(
+            applied.event_type,
+            EventType::TxEmitted("applied".to_string())
+        );
+        assert_eq!(applied.event_type.to_string(), "tx/applied");
+        assert_ne!(applied.event_type, EventType::Applied);
+    }
+
+    #[test]
+    fn test_tx_hash_and_verify_signature() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let data = b"arbitrary data".to_vec();
+        for algorithm in [
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Keccak256,
+            HashAlgorithm::Blake2b256,
+        ] {
+            assert_eq!(
+                tx::ctx().hash(algorithm, &data).unwrap(),
+                algorithm.hash(&data)
+            );
+        }
+
+        // Ed25519 and Secp256k1 keys. Signing hashes the data with SHA-256,
+        // so the signatures are over the raw digest.
+        let digest = HashAlgorithm::Sha256.hash(&data);
+        for keypair in [key::testing::keypair_1(), key::testing::keypair_3()] {
+            let pk = keypair.ref_to();
+            let sig = common::SigScheme::sign(&keypair, &data);
+            assert!(tx::ctx().verify_signature(&pk, &sig, digest).unwrap());
+            assert!(!tx::ctx().verify_signature(&pk, &sig, &data).unwrap());
+            let other_pk = key::testing::keypair_2().ref_to();
+            assert!(
+                !tx::ctx().verify_signature(&other_pk, &sig, digest).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_tx_get_metadata() {
         // The environment must be initialized first
@@ -272,6 +342,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tx_get_block_time() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let time: DateTimeUtc = "2023-06-01T12:00:00Z".parse().unwrap();
+        tx_host_env::with(|env| {
+            env.wl_storage
+                .storage
+                .set_header(storage::Header {
+                    hash: Hash::default(),
+                    time,
+                    next_validators_hash: Hash::default(),
+                })
+                .unwrap()
+        });
+        assert_eq!(tx::ctx().get_block_time().unwrap(), time);
+    }
+
     /// An example how to write a VP host environment integration test
     #[test]
     fn test_vp_host_env() {
@@ -483,8 +572,13 @@ mod tests {
                         &None,
                         1,
                         None,
-                        Some(&mut VpGasMeter::new_from_tx_meter(
-                            &TxGasMeter::new_from_sub_limit(u64::MAX.into())
+                        Some((
+                            &mut VpGasMeter::new_from_tx_meter(
+                                &TxGasMeter::new_from_sub_limit(
+                                    u64::MAX.into()
+                                )
+                            ),
+                            &GasCosts::default()
                         ))
                     )
                     .is_ok()
@@ -504,8 +598,13 @@ mod tests {
                         &None,
                         1,
                         None,
-                        Some(&mut VpGasMeter::new_from_tx_meter(
-                            &TxGasMeter::new_from_sub_limit(u64::MAX.into())
+                        Some((
+                            &mut VpGasMeter::new_from_tx_meter(
+                                &TxGasMeter::new_from_sub_limit(
+                                    u64::MAX.into()
+                                )
+                            ),
+                            &GasCosts::default()
                         ))
                     )
                     .is_err()
@@ -513,6 +612,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_vp_hash_and_verify_signature() {
+        // The environment must be initialized first
+        vp_host_env::init();
+
+        let data = b"arbitrary data".to_vec();
+        for algorithm in [
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Keccak256,
+            HashAlgorithm::Blake2b256,
+        ] {
+            assert_eq!(
+                vp::CTX.hash(algorithm, &data).unwrap(),
+                algorithm.hash(&data)
+            );
+        }
+
+        // Signing hashes the data with SHA-256, so the signature is over the
+        // raw digest
+        let digest = HashAlgorithm::Sha256.hash(&data);
+        let keypair = key::testing::keypair_3();
+        let pk = keypair.ref_to();
+        let sig = common::SigScheme::sign(&keypair, &data);
+        assert!(vp::CTX.verify_signature(&pk, &sig, digest).unwrap());
+        assert!(!vp::CTX.verify_signature(&pk, &sig, &data).unwrap());
+    }
+
     #[test]
     fn test_vp_get_metadata() {
         // The environment must be initialized first
@@ -552,6 +678,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vp_get_block_time() {
+        // The environment must be initialized first
+        vp_host_env::init();
+
+        let time: DateTimeUtc = "2023-06-01T12:00:00Z".parse().unwrap();
+        vp_host_env::with(|env| {
+            env.wl_storage
+                .storage
+                .set_header(storage::Header {
+                    hash: Hash::default(),
+                    time,
+                    next_validators_hash: Hash::default(),
+                })
+                .unwrap()
+        });
+        assert_eq!(vp::CTX.get_block_time().unwrap(), time);
+    }
+
     #[test]
     fn test_vp_eval() {
         // The environment must be initialized first
@@ -1852,4 +1997,230 @@ mod tests {
         let result = ibc::validate_multitoken_vp_from_tx(&env, &tx, &escrow);
         assert!(result.expect("token validation failed unexpectedly"));
     }
+
+    #[test]
+    fn test_ibc_forward_received_token() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        // packet to be forwarded to the next chain through the same channel
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        let memo =
+            ibc::forward_memo(&port_id, &channel_id, "next_receiver", None);
+        ibc::set_packet_memo(&mut packet, memo);
+
+        // Start a transaction to receive a packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![])
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // receive a packet and forward the token
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("forwarding the token failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // Check if the token was forwarded
+        let sequence = ibc::Sequence::from(1);
+        let forwarded = ibc::sent_packet(&env);
+        assert_eq!(forwarded.seq_on_a, sequence);
+        let data: PacketData =
+            serde_json::from_slice(&forwarded.data).expect("invalid data");
+        assert_eq!(data.receiver.to_string(), "next_receiver");
+        assert_eq!(
+            data.sender.to_string(),
+            address::Address::Internal(address::InternalAddress::Ibc)
+                .to_string()
+        );
+        // The received packet isn't acknowledged yet
+        assert!(
+            !env.wl_storage
+                .write_log
+                .get_ibc_events()
+                .iter()
+                .any(|event| event.event_type == "write_acknowledgement")
+        );
+        tx_host_env::set(env);
+        let commitment_key =
+            ibc::commitment_key(&port_id, &channel_id, sequence);
+        let ack_key = ibc::ack_key(&port_id, &channel_id, sequence);
+        let forwarded_key =
+            ibc::forwarded_packet_key(&port_id, &channel_id, sequence);
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let key = ibc::balance_key_with_ibc_prefix(denom, &receiver);
+        tx_host_env::with(|env| {
+            assert!(env.wl_storage.has_key(&commitment_key).unwrap());
+            assert!(!env.wl_storage.has_key(&ack_key).unwrap());
+            assert!(env.wl_storage.has_key(&forwarded_key).unwrap());
+            let balance: Option<Amount> =
+                env.wl_storage.read(&key).expect("read error");
+            assert_eq!(balance, None);
+        });
+    }
+
+    #[test]
+    fn test_ibc_forward_timeout_refund() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        // packet to be forwarded with the timeout which will expire soon
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        let memo = ibc::forward_memo(
+            &port_id,
+            &channel_id,
+            "next_receiver",
+            Some("1ns"),
+        );
+        ibc::set_packet_memo(&mut packet, memo);
+
+        // Start a transaction to receive a packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        // receive a packet and forward the token
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("forwarding the token failed");
+
+        // Commit
+        let mut env = tx_host_env::take();
+        let forwarded = ibc::sent_packet(&env);
+        env.commit_tx_and_block();
+        // for the next block
+        env.wl_storage
+            .storage
+            .begin_block(BlockHash::default(), BlockHeight(2))
+            .unwrap();
+        env.wl_storage
+            .storage
+            .set_header(tm_dummy_header())
+            .unwrap();
+        tx_host_env::set(env);
+
+        // Start a transaction to notify the timeout of the forwarded packet
+        let msg = ibc::msg_timeout(forwarded.clone(), ibc::Sequence::from(1));
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![])
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+
+        // timeout the forwarded packet
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("timeout failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // Check if the received packet got an error acknowledgement for the
+        // source chain to refund the original sender, instead of sending a
+        // new packet
+        let events = env.wl_storage.write_log.get_ibc_events();
+        assert!(!events.iter().any(|event| event.event_type == "send_packet"));
+        let ack_event = events
+            .iter()
+            .find(|event| event.event_type == "write_acknowledgement")
+            .expect("the received packet wasn't acknowledged");
+        assert_eq!(ack_event.attributes.get("packet_sequence").unwrap(), "1");
+        assert!(
+            ack_event
+                .attributes
+                .get("packet_ack")
+                .unwrap()
+                .contains("error")
+        );
+        tx_host_env::set(env);
+        let ack_key =
+            ibc::ack_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        let forwarded_key = ibc::forwarded_packet_key(
+            &forwarded.port_id_on_a,
+            &forwarded.chan_id_on_a,
+            forwarded.seq_on_a,
+        );
+        // The refunded vouchers have been burned
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let key = ibc::balance_key_with_ibc_prefix(
+            denom,
+            &address::Address::Internal(address::InternalAddress::Ibc),
+        );
+        tx_host_env::with(|env| {
+            assert!(env.wl_storage.has_key(&ack_key).unwrap());
+            assert!(!env.wl_storage.has_key(&forwarded_key).unwrap());
+            let balance: Option<Amount> =
+                env.wl_storage.read(&key).expect("read error");
+            assert_eq!(balance.unwrap_or_default(), Amount::zero());
+        });
+    }
 }
@@ -447,6 +447,7 @@ mod native_tx_host_env {
         result_ptr: u64
     ));
     native_host_fn!(tx_emit_ibc_event(event_ptr: u64, event_len: u64));
+    native_host_fn!(tx_emit_event(event_ptr: u64, event_len: u64));
     native_host_fn!(tx_get_ibc_event(event_type_ptr: u64, event_type_len: u64) -> i64);
     native_host_fn!(tx_get_chain_id(result_ptr: u64));
     native_host_fn!(tx_get_block_height() -> u64);
@@ -454,7 +455,22 @@ mod native_tx_host_env {
     native_host_fn!(tx_get_block_header(height: u64) -> i64);
     native_host_fn!(tx_get_block_hash(result_ptr: u64));
     native_host_fn!(tx_get_block_epoch() -> u64);
+    native_host_fn!(tx_get_block_time() -> i64);
     native_host_fn!(tx_get_native_token(result_ptr: u64));
+    native_host_fn!(tx_hash(
+        algorithm: u64,
+        data_ptr: u64,
+        data_len: u64,
+        result_ptr: u64
+    ));
+    native_host_fn!(tx_verify_signature(
+        public_key_ptr: u64,
+        public_key_len: u64,
+        signature_ptr: u64,
+        signature_len: u64,
+        data_ptr: u64,
+        data_len: u64
+    ) -> i64);
     native_host_fn!(tx_log_string(str_ptr: u64, str_len: u64));
     native_host_fn!(tx_charge_gas(used_gas: u64));
 }
@@ -353,6 +353,7 @@ mod native_vp_host_env {
     native_host_fn!(vp_get_block_hash(result_ptr: u64));
     native_host_fn!(vp_get_tx_code_hash(result_ptr: u64));
     native_host_fn!(vp_get_block_epoch() -> u64);
+    native_host_fn!(vp_get_block_time() -> i64);
     native_host_fn!(vp_get_native_token(result_ptr: u64));
     native_host_fn!(vp_eval(
             vp_code_ptr: u64,
@@ -361,6 +362,20 @@ mod native_vp_host_env {
             input_data_len: u64,
         ) -> i64);
     native_host_fn!(vp_log_string(str_ptr: u64, str_len: u64));
+    native_host_fn!(vp_hash(
+        algorithm: u64,
+        data_ptr: u64,
+        data_len: u64,
+        result_ptr: u64
+    ));
+    native_host_fn!(vp_verify_signature(
+        public_key_ptr: u64,
+        public_key_len: u64,
+        signature_ptr: u64,
+        signature_len: u64,
+        data_ptr: u64,
+        data_len: u64
+    ) -> i64);
     native_host_fn!(vp_verify_tx_section_signature(
             hash_list_ptr: u64,
             hash_list_len: u64,
@@ -4,25 +4,27 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 pub use namada_core::ledger::ibc::{
-    Error, IbcActions, IbcCommonContext, IbcStorageContext, ProofSpec,
-    TransferModule,
+    Error, IbcActions, IbcCommonContext, IbcStorageContext,
+    NftTransferModule, ProofSpec, TransferModule,
 };
 use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
 use namada_core::ledger::tx_env::TxEnv;
 use namada_core::types::address::{Address, InternalAddress};
-pub use namada_core::types::ibc::IbcEvent;
-use namada_core::types::storage::{BlockHeight, Header, Key};
+pub use namada_core::types::ibc::{IbcEvent, IbcShieldedTransfer};
+use namada_core::types::storage::{BlockHeight, Epoch, Header, Key};
 use namada_core::types::token::DenominatedAmount;
 
-use crate::token::{burn, mint, transfer};
+use crate::token::{burn, handle_masp_tx, mint, transfer};
 use crate::{Ctx, KeyValIterator};
 
 /// IBC actions to handle an IBC message
 pub fn ibc_actions(ctx: &mut Ctx) -> IbcActions<Ctx> {
     let ctx = Rc::new(RefCell::new(ctx.clone()));
     let mut actions = IbcActions::new(ctx.clone());
-    let module = TransferModule::new(ctx);
+    let module = TransferModule::new(ctx.clone());
     actions.add_transfer_route(module.module_id(), module);
+    let nft_module = NftTransferModule::new(ctx);
+    actions.add_nft_transfer_route(nft_module.module_id(), nft_module);
     actions
 }
 
@@ -116,10 +118,21 @@ impl IbcStorageContext for Ctx {
         burn(self, target, token, amount.amount)
     }
 
+    fn handle_masp_tx(
+        &mut self,
+        shielded: &IbcShieldedTransfer,
+    ) -> Result<(), Self::Error> {
+        handle_masp_tx(self, &shielded.transfer, &shielded.masp_tx)
+    }
+
     fn get_height(&self) -> std::result::Result<BlockHeight, Self::Error> {
         self.get_block_height()
     }
 
+    fn get_epoch(&self) -> std::result::Result<Epoch, Self::Error> {
+        self.get_block_epoch()
+    }
+
     fn get_header(
         &self,
         height: BlockHeight,
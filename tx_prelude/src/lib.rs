@@ -240,6 +240,12 @@ impl StorageWrite for Ctx {
         unsafe { namada_tx_delete(key.as_ptr() as _, key.len() as _) };
         Ok(())
     }
+
+    fn emit_event(&mut self, event: event::TxEvent) -> storage_api::Result<()> {
+        let event = BorshSerialize::try_to_vec(&event).unwrap();
+        unsafe { namada_tx_emit_event(event.as_ptr() as _, event.len() as _) };
+        Ok(())
+    }
 }
 
 impl TxEnv for Ctx {
@@ -350,6 +356,57 @@ impl TxEnv for Ctx {
             None => Ok(None),
         }
     }
+
+    fn get_block_time(&self) -> Result<time::DateTimeUtc, Error> {
+        let read_result = unsafe { namada_tx_get_block_time() };
+        let value = read_from_buffer(read_result, namada_tx_result_buffer)
+            .expect("The block time should be always available");
+        Ok(time::DateTimeUtc::try_from_slice(&value[..])
+            .expect("The conversion shouldn't fail"))
+    }
+
+    fn hash(
+        &self,
+        algorithm: hash::HashAlgorithm,
+        data: impl AsRef<[u8]>,
+    ) -> Result<hash::Hash, Error> {
+        let data = data.as_ref();
+        let result = Vec::with_capacity(hash::HASH_LENGTH);
+        unsafe {
+            namada_tx_hash(
+                algorithm.into(),
+                data.as_ptr() as _,
+                data.len() as _,
+                result.as_ptr() as _,
+            );
+        }
+        let slice = unsafe {
+            slice::from_raw_parts(result.as_ptr(), hash::HASH_LENGTH)
+        };
+        Ok(hash::Hash::try_from(slice).expect("Cannot convert the hash"))
+    }
+
+    fn verify_signature(
+        &self,
+        public_key: &key::common::PublicKey,
+        signature: &key::common::Signature,
+        data: impl AsRef<[u8]>,
+    ) -> Result<bool, Error> {
+        let public_key = public_key.try_to_vec().unwrap();
+        let signature = signature.try_to_vec().unwrap();
+        let data = data.as_ref();
+        let valid = unsafe {
+            namada_tx_verify_signature(
+                public_key.as_ptr() as _,
+                public_key.len() as _,
+                signature.as_ptr() as _,
+                signature.len() as _,
+                data.as_ptr() as _,
+                data.len() as _,
+            )
+        };
+        Ok(HostEnvResult::is_success(valid))
+    }
 }
 
 /// Execute IBC tx.
@@ -1,5 +1,8 @@
 use masp_primitives::transaction::Transaction;
 use namada_core::types::address::Address;
+use namada_core::types::event::{
+    TxEvent, BURN_EVENT, MINT_EVENT, TRANSFER_EVENT,
+};
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::KeySeg;
 use namada_core::types::token;
@@ -32,24 +35,18 @@ pub fn transfer(
         dest_bal.receive(&amount.amount);
         ctx.write(&src_key, src_bal)?;
         ctx.write(&dest_key, dest_bal)?;
+        ctx.emit_event(
+            TxEvent::new(TRANSFER_EVENT)
+                .with("token", token.clone())
+                .with("source", src.clone())
+                .with("target", dest.clone())
+                .with("amount", amount.amount),
+        )?;
     }
 
     // If this transaction has a shielded component, then handle it
     // separately
     if let Some(shielded) = shielded {
-        let masp_addr = address::masp();
-        ctx.insert_verifier(&masp_addr)?;
-        let head_tx_key = storage::Key::from(masp_addr.to_db_key())
-            .push(&HEAD_TX_KEY.to_owned())
-            .expect("Cannot obtain a storage key");
-        let current_tx_idx: u64 =
-            ctx.read(&head_tx_key).unwrap_or(None).unwrap_or(0);
-        let current_tx_key = storage::Key::from(masp_addr.to_db_key())
-            .push(&(TX_KEY_PREFIX.to_owned() + &current_tx_idx.to_string()))
-            .expect("Cannot obtain a storage key");
-        // Save the Transfer object and its location within the blockchain
-        // so that clients do not have to separately look these
-        // up
         let transfer = Transfer {
             source: src.clone(),
             target: dest.clone(),
@@ -58,22 +55,45 @@ pub fn transfer(
             key: key.clone(),
             shielded: *shielded_hash,
         };
-        let record: (Epoch, BlockHeight, TxIndex, Transfer, Transaction) = (
-            ctx.get_block_epoch()?,
-            ctx.get_block_height()?,
-            ctx.get_tx_index()?,
-            transfer,
-            shielded.clone(),
-        );
-        ctx.write(&current_tx_key, record)?;
-        ctx.write(&head_tx_key, current_tx_idx + 1)?;
-        // If storage key has been supplied, then pin this transaction to it
-        if let Some(key) = key {
-            let pin_key = storage::Key::from(masp_addr.to_db_key())
-                .push(&(PIN_KEY_PREFIX.to_owned() + key))
-                .expect("Cannot obtain a storage key");
-            ctx.write(&pin_key, current_tx_idx)?;
-        }
+        handle_masp_tx(ctx, &transfer, shielded)?;
+    }
+    Ok(())
+}
+
+/// Handle a MASP transaction.
+pub fn handle_masp_tx(
+    ctx: &mut Ctx,
+    transfer: &Transfer,
+    shielded: &Transaction,
+) -> TxResult {
+    let masp_addr = address::masp();
+    ctx.insert_verifier(&masp_addr)?;
+    let head_tx_key = storage::Key::from(masp_addr.to_db_key())
+        .push(&HEAD_TX_KEY.to_owned())
+        .expect("Cannot obtain a storage key");
+    let current_tx_idx: u64 =
+        ctx.read(&head_tx_key).unwrap_or(None).unwrap_or(0);
+    let current_tx_key = storage::Key::from(masp_addr.to_db_key())
+        .push(&(TX_KEY_PREFIX.to_owned() + &current_tx_idx.to_string()))
+        .expect("Cannot obtain a storage key");
+    // Save the Transfer object and its location within the blockchain
+    // so that clients do not have to separately look these
+    // up
+    let record: (Epoch, BlockHeight, TxIndex, Transfer, Transaction) = (
+        ctx.get_block_epoch()?,
+        ctx.get_block_height()?,
+        ctx.get_tx_index()?,
+        transfer.clone(),
+        shielded.clone(),
+    );
+    ctx.write(&current_tx_key, record)?;
+    ctx.write(&head_tx_key, current_tx_idx + 1)?;
+    // If storage key has been supplied, then pin this transaction to it
+    if let Some(key) = &transfer.key {
+        let pin_key = storage::Key::from(masp_addr.to_db_key())
+            .push(&(PIN_KEY_PREFIX.to_owned() + key))
+            .expect("Cannot obtain a storage key");
+        ctx.write(&pin_key, current_tx_idx)?;
     }
     Ok(())
 }
@@ -98,8 +118,39 @@ pub fn mint(
     ctx.write(&minted_key, minted_bal)?;
 
     let minter_key = token::minter_key(token);
-    ctx.write(&minter_key, minter)?;
+    if ctx.read::<Address>(&minter_key)?.as_ref() != Some(minter) {
+        ctx.write(&minter_key, minter)?;
+    }
+
+    ctx.emit_event(
+        TxEvent::new(MINT_EVENT)
+            .with("token", token.clone())
+            .with("target", target.clone())
+            .with("amount", amount),
+    )?;
+
+    Ok(())
+}
+
+/// Register a user-defined token, or update the settings of a registered one.
+pub fn init_token(ctx: &mut Ctx, data: InitToken) -> TxResult {
+    let InitToken {
+        token,
+        minter,
+        max_supply,
+        denom,
+        metadata,
+    } = data;
+    ctx.insert_verifier(&token)?;
 
+    ctx.write(&token::minter_key(&token), minter)?;
+    let max_supply_key = token::max_supply_key(&token);
+    match max_supply {
+        Some(max_supply) => ctx.write(&max_supply_key, max_supply)?,
+        None => ctx.delete(&max_supply_key)?,
+    }
+    ctx.write(&token::denom_key(&token), denom)?;
+    ctx.write(&token::metadata_key(&token), metadata)?;
     Ok(())
 }
 
@@ -122,5 +173,12 @@ pub fn burn(
     ctx.write(&target_key, target_bal)?;
     ctx.write(&minted_key, minted_bal)?;
 
+    ctx.emit_event(
+        TxEvent::new(BURN_EVENT)
+            .with("token", token.clone())
+            .with("source", target.clone())
+            .with("amount", amount),
+    )?;
+
     Ok(())
 }
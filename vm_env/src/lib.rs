@@ -78,6 +78,9 @@ pub mod tx {
         // Emit an IBC event
         pub fn namada_tx_emit_ibc_event(event_ptr: u64, event_len: u64);
 
+        // Emit a tx event
+        pub fn namada_tx_emit_event(event_ptr: u64, event_len: u64);
+
         // Get an IBC event
         pub fn namada_tx_get_ibc_event(
             event_type_ptr: u64,
@@ -99,12 +102,34 @@ pub mod tx {
         // Get the current block epoch
         pub fn namada_tx_get_block_epoch() -> u64;
 
+        // Get the current block time
+        pub fn namada_tx_get_block_time() -> i64;
+
         // Get the current tx index
         pub fn namada_tx_get_tx_index() -> u32;
 
         // Get the native token address
         pub fn namada_tx_get_native_token(result_ptr: u64);
 
+        // Hash the data with the algorithm, writing the 32 bytes hash to the
+        // result
+        pub fn namada_tx_hash(
+            algorithm: u64,
+            data_ptr: u64,
+            data_len: u64,
+            result_ptr: u64,
+        );
+
+        // Verify a signature over the data
+        pub fn namada_tx_verify_signature(
+            public_key_ptr: u64,
+            public_key_len: u64,
+            signature_ptr: u64,
+            signature_len: u64,
+            data_ptr: u64,
+            data_len: u64,
+        ) -> i64;
+
         // Requires a node running with "Info" log level
         pub fn namada_tx_log_string(str_ptr: u64, str_len: u64);
 
@@ -191,6 +216,9 @@ pub mod vp {
         // Get the current block epoch
         pub fn namada_vp_get_block_epoch() -> u64;
 
+        // Get the current block time
+        pub fn namada_vp_get_block_time() -> i64;
+
         // Get the current tx index
         pub fn namada_vp_get_tx_index() -> u32;
 
@@ -222,6 +250,25 @@ pub mod vp {
 
         pub fn namada_vp_verify_masp(tx_ptr: u64, tx_len: u64) -> i64;
 
+        // Hash the data with the algorithm, writing the 32 bytes hash to the
+        // result
+        pub fn namada_vp_hash(
+            algorithm: u64,
+            data_ptr: u64,
+            data_len: u64,
+            result_ptr: u64,
+        );
+
+        // Verify a signature over the data
+        pub fn namada_vp_verify_signature(
+            public_key_ptr: u64,
+            public_key_len: u64,
+            signature_ptr: u64,
+            signature_len: u64,
+            data_ptr: u64,
+            data_len: u64,
+        ) -> i64;
+
         /// Charge the provided amount of gas for the current vp
         pub fn namada_vp_charge_gas(used_gas: u64);
     }
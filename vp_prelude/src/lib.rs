@@ -25,11 +25,12 @@ pub use namada_core::ledger::vp_env::VpEnv;
 pub use namada_core::proto::{Section, Tx};
 pub use namada_core::types::address::Address;
 use namada_core::types::chain::CHAIN_ID_LENGTH;
-use namada_core::types::hash::{Hash, HASH_LENGTH};
+use namada_core::types::hash::{Hash, HashAlgorithm, HASH_LENGTH};
 use namada_core::types::internal::HostEnvResult;
 use namada_core::types::storage::{
     BlockHash, BlockHeight, Epoch, Header, TxIndex, BLOCK_HASH_LENGTH,
 };
+use namada_core::types::time::DateTimeUtc;
 pub use namada_core::types::*;
 pub use namada_macros::validity_predicate;
 pub use namada_proof_of_stake::storage as proof_of_stake;
@@ -78,6 +79,14 @@ pub fn is_proposal_accepted(ctx: &Ctx, proposal_id: u64) -> VpResult {
     ctx.has_key_pre(&proposal_execution_key)
 }
 
+/// Get the shielded part of an IBC transaction, if it transfers tokens from
+/// or to the MASP
+pub fn get_ibc_shielded_transfer(
+    tx_data: &[u8],
+) -> Result<Option<ibc::IbcShieldedTransfer>, Error> {
+    Ok(namada_core::ledger::ibc::get_shielded_transfer(tx_data)?)
+}
+
 /// Verify section signatures
 pub fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
     let max_signatures_per_transaction =
@@ -288,6 +297,14 @@ impl<'view> VpEnv<'view> for Ctx {
         get_block_epoch()
     }
 
+    fn get_block_time(&self) -> Result<DateTimeUtc, Error> {
+        let read_result = unsafe { namada_vp_get_block_time() };
+        let value = read_from_buffer(read_result, namada_vp_result_buffer)
+            .expect("The block time should be always available");
+        Ok(DateTimeUtc::try_from_slice(&value[..])
+            .expect("The conversion shouldn't fail"))
+    }
+
     fn get_tx_index(&self) -> Result<TxIndex, Error> {
         get_tx_index()
     }
@@ -341,6 +358,48 @@ impl<'view> VpEnv<'view> for Ctx {
         Ok(HostEnvResult::is_success(valid))
     }
 
+    fn hash(
+        &self,
+        algorithm: HashAlgorithm,
+        data: impl AsRef<[u8]>,
+    ) -> Result<Hash, Error> {
+        let data = data.as_ref();
+        let result = Vec::with_capacity(HASH_LENGTH);
+        unsafe {
+            namada_vp_hash(
+                algorithm.into(),
+                data.as_ptr() as _,
+                data.len() as _,
+                result.as_ptr() as _,
+            );
+        }
+        let slice =
+            unsafe { slice::from_raw_parts(result.as_ptr(), HASH_LENGTH) };
+        Ok(Hash::try_from(slice).expect("Cannot convert the hash"))
+    }
+
+    fn verify_signature(
+        &self,
+        public_key: &key::common::PublicKey,
+        signature: &key::common::Signature,
+        data: impl AsRef<[u8]>,
+    ) -> Result<bool, Error> {
+        let public_key = public_key.try_to_vec().unwrap();
+        let signature = signature.try_to_vec().unwrap();
+        let data = data.as_ref();
+        let valid = unsafe {
+            namada_vp_verify_signature(
+                public_key.as_ptr() as _,
+                public_key.len() as _,
+                signature.as_ptr() as _,
+                signature.len() as _,
+                data.as_ptr() as _,
+                data.len() as _,
+            )
+        };
+        Ok(HostEnvResult::is_success(valid))
+    }
+
     fn charge_gas(&self, used_gas: u64) -> Result<(), Error> {
         unsafe { namada_vp_charge_gas(used_gas) };
         Ok(())
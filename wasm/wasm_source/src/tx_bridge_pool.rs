@@ -1,6 +1,7 @@
 //! A tx for adding a transfer request across the Ethereum bridge
 //! into the bridge pool.
 use borsh::{BorshDeserialize, BorshSerialize};
+use eth_bridge::storage::flow_limits::{self, FlowDirection};
 use eth_bridge::storage::{bridge_pool, native_erc20_key};
 use eth_bridge_pool::{GasFee, PendingTransfer, TransferToEthereum};
 use namada_tx_prelude::*;
@@ -62,6 +63,9 @@ fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
         )?;
     }
     log_string("Escrow succeeded");
+    // account for the outbound flow of the transferred asset
+    flow_limits::record_flow(ctx, &asset, FlowDirection::Outbound, amount)
+        .wrap_err("Exceeded the outbound flow limit of the asset")?;
     // add transfer into the pool
     let pending_key = bridge_pool::get_pending_key(&transfer);
     ctx.write_bytes(&pending_key, transfer.try_to_vec().unwrap())
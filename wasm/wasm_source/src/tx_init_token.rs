@@ -0,0 +1,15 @@
+//! A tx to register a user-defined token, or to update the settings of a
+//! registered one.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 40000)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let init_token = token::InitToken::try_from_slice(&data[..])
+        .wrap_err("failed to decode an InitToken")?;
+    debug_log!("apply_tx called to init the token {}", init_token.token);
+
+    token::init_token(ctx, init_token)
+}
@@ -0,0 +1,22 @@
+//! A tx for the minter of a user-defined token to mint it.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 40000)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let token::MintToken {
+        token,
+        target,
+        amount,
+    } = token::MintToken::try_from_slice(&data[..])
+        .wrap_err("failed to decode a MintToken")?;
+    debug_log!("apply_tx called to mint {} of {}", amount, token);
+
+    let minter: Address = ctx
+        .read(&token::minter_key(&token))?
+        .ok_or_err_msg("The token has no minter")?;
+    ctx.insert_verifier(&minter)?;
+    token::mint(ctx, &minter, &target, &token, amount.amount)
+}
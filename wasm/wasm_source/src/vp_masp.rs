@@ -91,19 +91,34 @@ fn validate_tx(
     );
 
     let signed = tx_data;
-    let transfer =
-        token::Transfer::try_from_slice(&signed.data().unwrap()[..]).unwrap();
-
-    let shielded = transfer
-        .shielded
-        .as_ref()
-        .map(|hash| {
-            signed
-                .get_section(hash)
-                .and_then(|x| x.as_ref().masp_tx())
-                .ok_or_err_msg("unable to find shielded section")
-        })
-        .transpose()?;
+    let data = signed.data().ok_or_err_msg("No transaction data")?;
+    let (transfer, shielded) = match token::Transfer::try_from_slice(&data[..])
+    {
+        Ok(transfer) => {
+            let shielded = transfer
+                .shielded
+                .as_ref()
+                .map(|hash| {
+                    signed
+                        .get_section(hash)
+                        .and_then(|x| x.as_ref().masp_tx())
+                        .ok_or_err_msg("unable to find shielded section")
+                })
+                .transpose()?;
+            (transfer, shielded)
+        }
+        // An IBC transfer from or to the MASP carries the shielded part in
+        // the IBC message
+        Err(_) => match get_ibc_shielded_transfer(&data[..])? {
+            Some(ibc_shielded) => {
+                (ibc_shielded.transfer, Some(ibc_shielded.masp_tx))
+            }
+            None => {
+                debug_log!("The transaction data has no shielded transfer");
+                return reject();
+            }
+        },
+    };
     if let Some(shielded_tx) = shielded {
         let mut transparent_tx_pool = I128Sum::zero();
         // The Sapling value balance adds to the transparent tx pool